@@ -0,0 +1,152 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cosmwasm_std::{
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, HexBinary, MessageInfo, Response,
+    StdError, StdResult,
+};
+use risc0_groth16::{split_digest, verifier::prepared_verifying_key, Seal, Verifier};
+use risc0_zkp::core::digest::Digest;
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    control_id::ALLOWED_IDS_ROOT,
+    msg::{ControlIdResponse, InstantiateMsg, QueryMsg, VerifyResponse},
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::new().add_attribute("allowed_ids_root", ALLOWED_IDS_ROOT))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Verify {
+            seal,
+            image_id,
+            post_state_digest,
+            journal_digest,
+        } => {
+            let claim_digest = claim_digest(
+                &to_digest(&image_id)?,
+                &to_digest(&post_state_digest)?,
+                &to_digest(&journal_digest)?,
+            );
+            to_json_binary(&VerifyResponse {
+                verified: verify_integrity(&seal, claim_digest)?,
+            })
+        }
+        QueryMsg::VerifyIntegrity { seal, claim_digest } => to_json_binary(&VerifyResponse {
+            verified: verify_integrity(&seal, Digest::from(to_digest(&claim_digest)?))?,
+        }),
+        QueryMsg::ControlId {} => to_json_binary(&ControlIdResponse {
+            allowed_ids_root: HexBinary::from_hex(ALLOWED_IDS_ROOT)?,
+        }),
+    }
+}
+
+fn to_digest(bytes: &HexBinary) -> StdResult<[u8; 32]> {
+    bytes
+        .to_array()
+        .map_err(|_| StdError::generic_err("digests must be 32 bytes long"))
+}
+
+/// Verify that `seal` is a valid Groth16 proof of the claim with the given
+/// digest, for the release of the zkVM identified by [ALLOWED_IDS_ROOT].
+pub fn verify_integrity(seal: &[u8], claim_digest: Digest) -> StdResult<bool> {
+    let seal = Seal::from_vec(seal).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let to_std_err = |err: anyhow::Error| StdError::generic_err(err.to_string());
+
+    let allowed_ids_root = Digest::from(to_digest(&HexBinary::from_hex(ALLOWED_IDS_ROOT)?)?);
+    let (a0, a1) = split_digest(allowed_ids_root).map_err(to_std_err)?;
+    let (c0, c1) = split_digest(claim_digest).map_err(to_std_err)?;
+    let verifier = Verifier::new(
+        &seal,
+        vec![a0, a1, c0, c1],
+        prepared_verifying_key().map_err(to_std_err)?,
+    )
+    .map_err(to_std_err)?;
+    Ok(verifier.verify().is_ok())
+}
+
+/// Compute the digest of the claim of a halted execution with exit code 0, no
+/// input and no assumptions, matching `ReceiptClaimLib.digest` in Solidity.
+pub fn claim_digest(
+    image_id: &[u8; 32],
+    post_state_digest: &[u8; 32],
+    journal_digest: &[u8; 32],
+) -> Digest {
+    let output_digest = Sha256::new()
+        .chain_update(Sha256::digest(b"risc0.Output"))
+        .chain_update(journal_digest)
+        .chain_update([0u8; 32])
+        .chain_update([2, 0])
+        .finalize();
+    let claim_digest: [u8; 32] = Sha256::new()
+        .chain_update(Sha256::digest(b"risc0.ReceiptClaim"))
+        .chain_update([0u8; 32])
+        .chain_update(image_id)
+        .chain_update(post_state_digest)
+        .chain_update(output_digest)
+        .chain_update([0u8; 4])
+        .chain_update([0u8; 4])
+        .chain_update([4, 0])
+        .finalize()
+        .into();
+    Digest::from(claim_digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        from_json,
+        testing::{mock_dependencies, mock_env},
+    };
+
+    use super::*;
+
+    // Receipt from bonsai/ethereum/test/TestReceipt.sol.
+    const SEAL: &str = "102866064c0a93f24ab85854cbb1f2904f3e345bdd567e1f0ef36e819c5441aa24cd64818e919f0cb611b69f717421dc0b859d748325506b76bd9f3b351869be1b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae0460d37ee6b3eefb8cd9985612c81fbbef3e6e25aa2f2ebc35c23f882de07fa51090e96423125defa12ccf193e6bf2ed33ce3ea2085a40ed1c88af7dd1f71d7fec90c5905ea06c54c2f8f27be48dcd055836066f9706f8ab1c3984a321fc2e4f11d16c7e632d68f81dca000df105273ad45a22b03a69752ccb9d8ee2878166ff41a1de23df9c89ef27a77980f84d8d780890cb89fe983f812a4fb6222797c0e529f";
+    const POST_DIGEST: &str = "0c832dac05ecf2eaf58d3cfff95e3b51e18314d377483703fd1f08296365ea71";
+    const IMAGE_ID: &str = "59eeb99a627ecaf527fa51262e797fc39ba69ad9ff36e9b2f5a634edc9fc622d";
+
+    fn verify(journal_digest: [u8; 32]) -> bool {
+        let deps = mock_dependencies();
+        let msg = QueryMsg::Verify {
+            seal: hex::decode(SEAL).unwrap().into(),
+            image_id: HexBinary::from_hex(IMAGE_ID).unwrap(),
+            post_state_digest: HexBinary::from_hex(POST_DIGEST).unwrap(),
+            journal_digest: journal_digest.into(),
+        };
+        let response: VerifyResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        response.verified
+    }
+
+    #[test]
+    fn verify_test_receipt() {
+        assert!(verify(Sha256::digest(b"").into()));
+    }
+
+    #[test]
+    fn reject_wrong_journal() {
+        assert!(!verify([1u8; 32]));
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+/// Control ID root of the recursion circuit, identifying the release of the
+/// zkVM whose receipts are accepted by this contract.
+pub const ALLOWED_IDS_ROOT: &str =
+    "290188641142c54bdb641329127e7d44f9816c5c61137e14328ad247d29ac412";
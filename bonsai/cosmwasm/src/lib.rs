@@ -0,0 +1,25 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CosmWasm contract verifying RISC Zero Groth16 receipts.
+//!
+//! The contract accepts the receipts of the zkVM release identified by
+//! [control_id::ALLOWED_IDS_ROOT], which is generated by
+//! `cargo xtask bootstrap-groth16`, and verifies them against the verifying
+//! key embedded in `risc0-groth16`. Verification is exposed through queries,
+//! see [msg::QueryMsg].
+
+pub mod contract;
+pub mod control_id;
+pub mod msg;
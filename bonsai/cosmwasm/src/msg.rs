@@ -0,0 +1,55 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, HexBinary};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Verify that `seal` is a valid proof of an execution of `image_id` with
+    /// the given post state and journal digests.
+    ///
+    /// Like the `verify` method of the Solidity verifier, this checks that the
+    /// execution halted with exit code 0, with no input and no assumptions.
+    #[returns(VerifyResponse)]
+    Verify {
+        seal: Binary,
+        image_id: HexBinary,
+        post_state_digest: HexBinary,
+        journal_digest: HexBinary,
+    },
+    /// Verify that `seal` is a valid proof of the claim with the given digest.
+    #[returns(VerifyResponse)]
+    VerifyIntegrity {
+        seal: Binary,
+        claim_digest: HexBinary,
+    },
+    /// Returns the control ID root of the zkVM release accepted by the contract.
+    #[returns(ControlIdResponse)]
+    ControlId {},
+}
+
+#[cw_serde]
+pub struct VerifyResponse {
+    pub verified: bool,
+}
+
+#[cw_serde]
+pub struct ControlIdResponse {
+    pub allowed_ids_root: HexBinary,
+}
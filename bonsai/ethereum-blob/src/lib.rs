@@ -0,0 +1,267 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Journals carried in EIP-4844 blobs, verified with the `BlobJournal`
+//! Solidity library.
+//!
+//! Large journals are expensive to pass as calldata. Instead, the data is
+//! encoded in a blob with [encode_blob], and the guest commits a
+//! [BlobJournal] binding the receipt to the blob: its versioned hash, along
+//! with the evaluation `y` of the blob polynomial at the point `z` returned by
+//! [evaluation_point]. On chain, the KZG proof of that evaluation is checked
+//! against the blob of the transaction by the point evaluation precompile.
+//!
+//! This crate is `no_std`, so that guests can build their [BlobJournal] with
+//! [BlobJournal::new]:
+//!
+//! ```ignore
+//! let commitment: [u8; 48] = env::read();
+//! let blob: Vec<u8> = env::read();
+//! // ... check the content of the blob ...
+//! let journal = BlobJournal::new(&commitment, &blob).unwrap();
+//! env::commit_slice(&journal.to_bytes());
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use ark_bls12_381::Fr;
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use sha2::{Digest as _, Sha256};
+
+/// Number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+/// Size of a blob, in bytes.
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * 32;
+
+// Each field element holds 31 bytes of data, the first byte is left empty so
+// that the element is below the BLS12-381 scalar field modulus.
+const DATA_BYTES_PER_FIELD_ELEMENT: usize = 31;
+
+// The data is prefixed with its length, as a big endian u32.
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// Maximum length of the data carried in a single blob.
+pub const MAX_DATA_LEN: usize =
+    FIELD_ELEMENTS_PER_BLOB * DATA_BYTES_PER_FIELD_ELEMENT - LENGTH_PREFIX_SIZE;
+
+/// Version byte of the versioned hash of a KZG commitment.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 1;
+
+/// Encode `data` into a blob.
+///
+/// Returns `None` if `data` is longer than [MAX_DATA_LEN].
+pub fn encode_blob(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() > MAX_DATA_LEN {
+        return None;
+    }
+    let prefixed = [&(data.len() as u32).to_be_bytes()[..], data].concat();
+    let mut blob = alloc::vec![0u8; BYTES_PER_BLOB];
+    for (element, chunk) in blob
+        .chunks_mut(32)
+        .zip(prefixed.chunks(DATA_BYTES_PER_FIELD_ELEMENT))
+    {
+        element[1..1 + chunk.len()].copy_from_slice(chunk);
+    }
+    Some(blob)
+}
+
+/// Decode the data of a blob produced by [encode_blob].
+///
+/// Returns `None` if the blob is malformed.
+pub fn decode_blob(blob: &[u8]) -> Option<Vec<u8>> {
+    if blob.len() != BYTES_PER_BLOB {
+        return None;
+    }
+    let prefixed: Vec<u8> = blob
+        .chunks(32)
+        .flat_map(|element| element[1..].iter().copied())
+        .collect();
+    let len = u32::from_be_bytes(prefixed[..LENGTH_PREFIX_SIZE].try_into().unwrap()) as usize;
+    prefixed
+        .get(LENGTH_PREFIX_SIZE..LENGTH_PREFIX_SIZE + len)
+        .map(<[u8]>::to_vec)
+}
+
+/// Compute the versioned hash of a KZG commitment, as returned by the
+/// `blobhash` opcode.
+pub fn versioned_hash(commitment: &[u8; 48]) -> [u8; 32] {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// Derive the point at which the guest evaluates the blob polynomial.
+///
+/// The point depends on both the commitment and the content of the blob, so
+/// that it can not be chosen before the blob is fixed.
+pub fn evaluation_point(commitment: &[u8; 48], blob: &[u8]) -> [u8; 32] {
+    let mut z: [u8; 32] = Sha256::new()
+        .chain_update(commitment)
+        .chain_update(blob)
+        .finalize()
+        .into();
+    // Clear the most significant byte so the point is below the scalar field modulus.
+    z[0] = 0;
+    z
+}
+
+/// Evaluate the polynomial of `blob` at `z`, as the point evaluation
+/// precompile does.
+///
+/// The blob holds the evaluations of the polynomial over the roots of unity
+/// of order [FIELD_ELEMENTS_PER_BLOB], in bit-reversed order. Both `z` and the
+/// result are big endian elements of the BLS12-381 scalar field.
+///
+/// Returns `None` if the blob is not [BYTES_PER_BLOB] long, or if `z` or an
+/// element of the blob is not below the scalar field modulus.
+pub fn evaluate_blob(blob: &[u8], z: &[u8; 32]) -> Option<[u8; 32]> {
+    if blob.len() != BYTES_PER_BLOB {
+        return None;
+    }
+    let z = field_element(z)?;
+    let domain = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_BLOB)?;
+    let lagrange = domain.evaluate_all_lagrange_coefficients(z);
+    let shift = usize::BITS - FIELD_ELEMENTS_PER_BLOB.trailing_zeros();
+    let mut y = Fr::from(0u64);
+    for (i, element) in blob.chunks(32).enumerate() {
+        let element = field_element(element.try_into().unwrap())?;
+        y += element * lagrange[i.reverse_bits() >> shift];
+    }
+    y.into_bigint().to_bytes_be().try_into().ok()
+}
+
+// Parse a big endian field element, rejecting values above the modulus.
+fn field_element(bytes: &[u8; 32]) -> Option<Fr> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    Fr::from_bigint(BigInt::new(limbs))
+}
+
+/// Journal committed by a guest whose output is carried in a blob.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobJournal {
+    /// Versioned hash of the blob.
+    pub versioned_hash: [u8; 32],
+    /// Evaluation point, see [evaluation_point].
+    pub z: [u8; 32],
+    /// Evaluation of the blob polynomial at `z`, in big endian.
+    pub y: [u8; 32],
+}
+
+impl BlobJournal {
+    /// Build the journal binding a receipt to `blob`, whose KZG commitment is
+    /// `commitment`.
+    ///
+    /// This is meant to be run by the guest. The commitment is not checked
+    /// against the blob here: a commitment that does not match the blob fails
+    /// the evaluation proof checked on chain.
+    ///
+    /// Returns `None` if the blob is malformed, see [evaluate_blob].
+    pub fn new(commitment: &[u8; 48], blob: &[u8]) -> Option<Self> {
+        let z = evaluation_point(commitment, blob);
+        Some(Self {
+            versioned_hash: versioned_hash(commitment),
+            z,
+            y: evaluate_blob(blob, &z)?,
+        })
+    }
+
+    /// Encode the journal as committed by the guest.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.versioned_hash, self.z, self.y].concat()
+    }
+
+    /// Digest of the journal, matching `BlobJournal.digest` in Solidity.
+    pub fn digest(&self) -> [u8; 32] {
+        Sha256::digest(self.to_bytes()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::Field;
+
+    use super::*;
+
+    fn to_bytes(x: Fr) -> [u8; 32] {
+        x.into_bigint().to_bytes_be().try_into().unwrap()
+    }
+
+    #[test]
+    fn blob_roundtrip() {
+        for len in [0, 1, 31, 32, 1000, MAX_DATA_LEN] {
+            let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let blob = encode_blob(&data).unwrap();
+            assert_eq!(blob.len(), BYTES_PER_BLOB);
+            assert!(blob.chunks(32).all(|element| element[0] == 0));
+            assert_eq!(decode_blob(&blob).unwrap(), data);
+        }
+        assert!(encode_blob(&vec![0; MAX_DATA_LEN + 1]).is_none());
+    }
+
+    #[test]
+    fn versioned_hash_has_kzg_version() {
+        assert_eq!(versioned_hash(&[0; 48])[0], VERSIONED_HASH_VERSION_KZG);
+    }
+
+    #[test]
+    fn domain_matches_eip4844() {
+        // EIP-4844 takes 7 as the primitive root of unity.
+        let mut exponent = Fr::MODULUS;
+        exponent.sub_with_borrow(&BigInt::from(1u64));
+        exponent.divn(FIELD_ELEMENTS_PER_BLOB.trailing_zeros());
+        let omega = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_BLOB)
+            .unwrap()
+            .group_gen;
+        assert_eq!(omega, Fr::from(7u64).pow(exponent));
+    }
+
+    #[test]
+    fn evaluate_constant_blob() {
+        let c = to_bytes(Fr::from(42u64));
+        let blob = c.repeat(FIELD_ELEMENTS_PER_BLOB);
+        let z = evaluation_point(&[0; 48], &blob);
+        assert_eq!(evaluate_blob(&blob, &z).unwrap(), c);
+    }
+
+    #[test]
+    fn evaluate_at_roots_of_unity() {
+        let blob = encode_blob(b"hello blob").unwrap();
+        let omega = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_BLOB)
+            .unwrap()
+            .group_gen;
+        // The element at index 1 is the evaluation at the root of unity of
+        // index 2048 in bit-reversed order.
+        for (index, power) in [(0, 0), (1, 2048), (2, 1024)] {
+            let z = to_bytes(omega.pow([power]));
+            assert_eq!(evaluate_blob(&blob, &z).unwrap(), blob[index * 32..][..32]);
+        }
+    }
+
+    #[test]
+    fn evaluate_rejects_non_canonical_elements() {
+        let mut blob = encode_blob(b"").unwrap();
+        assert!(evaluate_blob(&blob, &[0xff; 32]).is_none());
+        blob[..32].copy_from_slice(&[0xff; 32]);
+        assert!(evaluate_blob(&blob, &[0; 32]).is_none());
+    }
+}
@@ -12,8 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use anyhow::anyhow;
 use axum::{extract::State, Extension, Json};
-use bonsai_ethereum_contracts::i_bonsai_relay::CallbackRequestFilter;
+use bonsai_ethereum_contracts::{
+    i_bonsai_relay::CallbackRequestFilter, relay::callback_is_pending, IBonsaiRelay,
+};
 use bonsai_sdk::{alpha::responses::CreateSessRes, alpha_async::get_client_from_parts};
 
 use super::{request_extractor::RequestExtractor, state::ApiState, Error, Result};
@@ -28,6 +33,10 @@ use crate::{
 
 /// Publish a CallbackRequest to the Relayer.
 ///
+/// The request must have been made to the relay contract first, and carry the
+/// nonce it was assigned there. The relayer does not send any transaction for
+/// it, and only checks that the request is still pending.
+///
 /// Return status 200 with a body `CreateSessRes`on success.
 #[utoipa::path(
     post,
@@ -44,6 +53,15 @@ pub(crate) async fn post_callback_request<S: Storage + Sync + Send + Clone>(
     State(state): State<ApiState<S>>,
     RequestExtractor(request): RequestExtractor<CallbackRequest>,
 ) -> Result<Json<CreateSessRes>, Error> {
+    let ethers_client = Arc::new(state.client_config.get_client().await?);
+    let relay = IBonsaiRelay::new(state.relay_contract_address, ethers_client);
+    let pending = callback_is_pending(&relay, request.nonce)
+        .await
+        .map_err(|err| anyhow!("failed to query the relay contract: {err}"))?;
+    if !pending {
+        return Err(Error::CallbackNotPending(request.nonce));
+    }
+
     let client = get_client_from_parts(state.bonsai_url, api_key, risc0_zkvm::VERSION).await?;
     let proxy =
         ProxyCallbackProofRequestProcessor::new(client, state.storage, Some(state.notifier));
@@ -62,6 +80,7 @@ impl From<CallbackRequest> for CallbackRequestFilter {
             callback_contract: val.callback_contract,
             function_selector: val.function_selector,
             gas_limit: val.gas_limit,
+            nonce: val.nonce,
         }
     }
 }
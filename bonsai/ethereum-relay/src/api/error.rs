@@ -23,6 +23,7 @@ use bonsai_sdk::alpha::SdkErr;
 use ethers::{
     prelude::signer::SignerMiddlewareError,
     providers::{Provider, Ws},
+    types::U256,
 };
 use ethers_signers::LocalWallet;
 use tokio::task::JoinError;
@@ -50,6 +51,8 @@ pub(crate) enum Error {
     Storage(#[from] crate::storage::Error),
     #[error("Ethers parse error")]
     EthersParse(#[from] ethers::abi::Error),
+    #[error("Callback request {0} is not pending on the relay contract")]
+    CallbackNotPending(U256),
     #[error("Signer middleware error")]
     SignerMiddleware(#[from] SignerMiddlewareError<Provider<Ws>, LocalWallet>),
     #[error("Unspecified error")]
@@ -59,9 +62,10 @@ pub(crate) enum Error {
 impl Error {
     pub(crate) fn status_code(&self) -> StatusCode {
         match self {
-            Error::Validation { .. } | Error::Bonsai { .. } | Error::Client { .. } => {
-                StatusCode::BAD_REQUEST
-            }
+            Error::Validation { .. }
+            | Error::Bonsai { .. }
+            | Error::Client { .. }
+            | Error::CallbackNotPending { .. } => StatusCode::BAD_REQUEST,
             Error::Bincode { .. }
             | Error::Storage { .. }
             | Error::SignerMiddleware { .. }
@@ -147,6 +147,7 @@ mod tests {
                 .unwrap(),
             function_selector: [0; 4],
             gas_limit: 21000,
+            nonce: 1.into(),
         };
         let extractor = mock_request(original.clone(), "application/octet-stream").await;
 
@@ -155,6 +156,7 @@ mod tests {
         assert_eq!(extractor.0.callback_contract, original.callback_contract);
         assert_eq!(extractor.0.function_selector, original.function_selector);
         assert_eq!(extractor.0.gas_limit, original.gas_limit);
+        assert_eq!(extractor.0.nonce, original.nonce);
     }
 
     #[tokio::test]
@@ -167,6 +169,7 @@ mod tests {
                 .unwrap(),
             function_selector: [0; 4],
             gas_limit: 21000,
+            nonce: 1.into(),
         };
         let extractor = mock_request(original.clone(), "application/json").await;
 
@@ -175,5 +178,6 @@ mod tests {
         assert_eq!(extractor.0.callback_contract, original.callback_contract);
         assert_eq!(extractor.0.function_selector, original.function_selector);
         assert_eq!(extractor.0.gas_limit, original.gas_limit);
+        assert_eq!(extractor.0.nonce, original.nonce);
     }
 }
@@ -14,9 +14,10 @@
 
 use std::sync::Arc;
 
+use ethers::types::Address;
 use tokio::sync::Notify;
 
-use crate::storage::Storage;
+use crate::{storage::Storage, EthersClientConfig};
 
 #[derive(Clone)]
 pub(crate) struct ApiState<S>
@@ -26,4 +27,6 @@ where
     pub(crate) bonsai_url: String,
     pub(crate) storage: S,
     pub(crate) notifier: Arc<Notify>,
+    pub(crate) client_config: EthersClientConfig,
+    pub(crate) relay_contract_address: Address,
 }
@@ -52,6 +52,14 @@ impl<S: Storage + Sync + Send> EventProcessor for ProxyCallbackProofRequestProce
         &self,
         event: CallbackRequestFilter,
     ) -> Result<SessionId, crate::api::error::Error> {
+        // A request may be received both through the REST API and from the
+        // events of the relay contract, and events may be received again after a
+        // restart. Each request is only proven once.
+        if let Some(session_id) = self.storage.find_callback_request(&event).await? {
+            info!(nonce = ?event.nonce, ?session_id, "callback request already received");
+            return Ok(session_id);
+        }
+
         let input_id =
             upload_input(self.bonsai_client.clone(), event.input.clone().to_vec()).await?;
         let bonsai_session_id = create_session(
@@ -52,7 +52,8 @@ impl<EP: EventProcessor<Event = CallbackRequestFilter> + Sync + Send>
     }
 
     pub(crate) async fn run(self) -> Result<(), Error> {
-        const EVENT_NAME: &str = "CallbackRequest(address,bytes32,bytes,address,bytes4,uint64)";
+        const EVENT_NAME: &str =
+            "CallbackRequest(address,bytes32,bytes,address,bytes4,uint64,uint256)";
 
         let filter = ethers::types::Filter::new()
             .address(self.proxy_contract_address)
@@ -23,7 +23,7 @@ mod storage;
 mod tests;
 mod uploader;
 
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use anyhow::{Context, Result};
 use bonsai_sdk::alpha_async::get_client_from_parts;
@@ -33,7 +33,7 @@ use downloader::{
 };
 use ethers::core::types::Address;
 use reqwest::Url;
-use storage::{in_memory::InMemoryStorage, Storage};
+use storage::{file::FileStorage, in_memory::InMemoryStorage, Storage};
 use tokio::{net::TcpListener, sync::Notify};
 use tracing::info;
 use uploader::{
@@ -49,6 +49,17 @@ pub use uploader::completed_proofs::snark::tokenize_snark_receipt;
 
 static DEFAULT_FILTER: &str = "info";
 
+fn init_tracing() {
+    // try to load filter from `RUST_LOG` or use reasonably verbose defaults
+    let filter = ::tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| DEFAULT_FILTER.into());
+    let subscriber = ::tracing_subscriber::FmtSubscriber::builder()
+        .with_env_filter(filter)
+        .with_test_writer()
+        .finish();
+    let _ = ::tracing::subscriber::set_global_default(subscriber);
+}
+
 #[derive(Clone)]
 /// A relayer to integrate Ethereum with Bonsai.
 pub struct Relayer {
@@ -68,16 +79,35 @@ pub struct Relayer {
 
 impl Relayer {
     /// Run a [Relayer] with an Ethereum Client.
+    ///
+    /// Proof requests are kept in memory, and are lost when the relayer stops.
     pub async fn run(self, client_config: EthersClientConfig) -> Result<()> {
-        // try to load filter from `RUST_LOG` or use reasonably verbose defaults
-        let filter = ::tracing_subscriber::EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| DEFAULT_FILTER.into());
-        let subscriber = ::tracing_subscriber::FmtSubscriber::builder()
-            .with_env_filter(filter)
-            .with_test_writer()
-            .finish();
-        let _ = ::tracing::subscriber::set_global_default(subscriber);
+        init_tracing();
+        self.run_with_storage(client_config, InMemoryStorage::new())
+            .await
+    }
 
+    /// Run a [Relayer] with an Ethereum Client, persisting proof requests to
+    /// the file at `storage_path` so that the relayer resumes pending requests
+    /// after a restart.
+    pub async fn run_with_storage_path(
+        self,
+        client_config: EthersClientConfig,
+        storage_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        init_tracing();
+        let storage_path = storage_path.as_ref();
+        let storage = FileStorage::open(storage_path).await.with_context(|| {
+            format!("Failed to open relay storage at {}", storage_path.display())
+        })?;
+        self.run_with_storage(client_config, storage).await
+    }
+
+    async fn run_with_storage<S: Storage + Sync + Send + Clone + 'static>(
+        self,
+        client_config: EthersClientConfig,
+        storage: S,
+    ) -> Result<()> {
         let bonsai_client = get_client_from_parts(
             self.bonsai_api_url.clone(),
             self.bonsai_api_key.clone(),
@@ -86,8 +116,6 @@ impl Relayer {
         .await
         .context("Failed to create Bonsai client.")?;
 
-        let storage = InMemoryStorage::new();
-
         // Setup Downloader
         let new_pending_proof_request_notifier = Arc::new(Notify::new());
         let proxy_callback_proof_request_processor = ProxyCallbackProofRequestProcessor::new(
@@ -133,6 +161,8 @@ impl Relayer {
             bonsai_url: self.bonsai_api_url.clone(),
             storage: storage.clone(),
             notifier: new_pending_proof_request_notifier.clone(),
+            client_config: client_config.clone(),
+            relay_contract_address: self.relay_contract_address,
         };
 
         // Start everything
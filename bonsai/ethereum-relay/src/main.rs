@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use anyhow::Result;
 use bonsai_ethereum_relay::{EthersClientConfig, Relayer};
@@ -63,6 +63,12 @@ struct Args {
     /// zkVM program and no proof is generated.
     #[arg(long, env, default_value_t = false)]
     risc0_dev_mode: bool,
+
+    /// Path of the file persisting proof requests, allowing the relay to
+    /// resume pending requests after a restart. Requests are only kept in
+    /// memory when not set.
+    #[arg(long, env)]
+    storage_path: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -88,5 +94,8 @@ async fn main() -> Result<()> {
         WAIT_DURATION,
     );
 
-    relayer.run(client_config).await
+    match args.storage_path {
+        Some(path) => relayer.run_with_storage_path(client_config, path).await,
+        None => relayer.run(client_config).await,
+    }
 }
@@ -38,6 +38,13 @@ pub struct CallbackRequest {
     pub function_selector: ethers::types::Selector,
     /// The gas limit.
     pub gas_limit: u64,
+    /// The nonce assigned to the request when it was made to the relay
+    /// contract, as emitted in its `CallbackRequest` event.
+    ///
+    /// The relay contract only accepts callbacks answering a request made on
+    /// chain, so the request must be made there first, e.g. by the callback
+    /// contract.
+    pub nonce: ethers::types::U256,
 }
 
 /// The Errors that may occur when processing a [Client] request.
@@ -0,0 +1,298 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bonsai_ethereum_contracts::i_bonsai_relay::CallbackRequestFilter;
+use ethers::types::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+use tokio::{fs, sync::Mutex};
+
+use crate::storage::{
+    in_memory::InMemoryStorage, Error, ProofID, ProofRequestInformation, ProofRequestState, Storage,
+};
+
+/// [Storage] keeping proof requests in memory and persisting them to a JSON
+/// file after every change, so that the relayer can resume after a restart.
+#[derive(Debug, Clone)]
+pub(crate) struct FileStorage {
+    inner: InMemoryStorage,
+    path: Arc<PathBuf>,
+    // Serializes writes to the file.
+    write_lock: Arc<Mutex<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredProofRequest {
+    proof_request_id: String,
+    account: Address,
+    image_id: [u8; 32],
+    input: Bytes,
+    callback_contract: Address,
+    function_selector: [u8; 4],
+    gas_limit: u64,
+    // Absent from files written before callbacks carried a nonce.
+    #[serde(default)]
+    nonce: U256,
+    state: StoredProofRequestState,
+    retries: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+enum StoredProofRequestState {
+    New,
+    Completed,
+    PreparingOnchain,
+}
+
+impl FileStorage {
+    /// Opens the storage persisted at `path`, or creates an empty one if the
+    /// file does not exist yet.
+    ///
+    /// Requests that were pending on Bonsai are restored as new requests, since
+    /// the sessions tracking them were lost with the previous process.
+    pub(crate) async fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let inner = InMemoryStorage::new();
+        let contents = match fs::read(&path).await {
+            Ok(contents) => Some(contents),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(Error::Persistence(err)),
+        };
+        if let Some(contents) = contents {
+            let requests: Vec<StoredProofRequest> =
+                serde_json::from_slice(&contents).map_err(|err| Error::Persistence(err.into()))?;
+            for request in requests {
+                let state = match request.state {
+                    StoredProofRequestState::New => ProofRequestState::New,
+                    StoredProofRequestState::Completed => ProofRequestState::Completed,
+                    StoredProofRequestState::PreparingOnchain => {
+                        ProofRequestState::PreparingOnchain
+                    }
+                };
+                let proof = ProofRequestInformation {
+                    proof_request_id: ProofID::new(request.proof_request_id),
+                    callback_proof_request_event: CallbackRequestFilter {
+                        account: request.account,
+                        image_id: request.image_id,
+                        input: request.input,
+                        callback_contract: request.callback_contract,
+                        function_selector: request.function_selector,
+                        gas_limit: request.gas_limit,
+                        nonce: request.nonce,
+                    },
+                };
+                inner.restore(proof, state, request.retries)?;
+            }
+        }
+        Ok(Self {
+            inner,
+            path: Arc::new(path),
+            write_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    async fn persist(&self) -> Result<(), Error> {
+        let _guard = self.write_lock.lock().await;
+        let requests: Vec<StoredProofRequest> = self
+            .inner
+            .entries()?
+            .into_iter()
+            .filter_map(|(proof, state, retries)| {
+                let state = match state {
+                    ProofRequestState::New | ProofRequestState::Pending => {
+                        StoredProofRequestState::New
+                    }
+                    ProofRequestState::Completed => StoredProofRequestState::Completed,
+                    ProofRequestState::PreparingOnchain => {
+                        StoredProofRequestState::PreparingOnchain
+                    }
+                    ProofRequestState::Failed | ProofRequestState::CompletedOnchain(_) => {
+                        return None
+                    }
+                };
+                let event = proof.callback_proof_request_event;
+                Some(StoredProofRequest {
+                    proof_request_id: proof.proof_request_id.uuid,
+                    account: event.account,
+                    image_id: event.image_id,
+                    input: event.input,
+                    callback_contract: event.callback_contract,
+                    function_selector: event.function_selector,
+                    gas_limit: event.gas_limit,
+                    nonce: event.nonce,
+                    state,
+                    retries,
+                })
+            })
+            .collect();
+        let contents =
+            serde_json::to_vec(&requests).map_err(|err| Error::Persistence(err.into()))?;
+
+        // Write to a temporary file next to the storage first, so that a crash never leaves a
+        // truncated file behind.
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || {
+            let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+            let mut file = NamedTempFile::new_in(dir.unwrap_or(Path::new(".")))?;
+            file.write_all(&contents)?;
+            file.persist(&*path)?;
+            Ok::<_, io::Error>(())
+        })
+        .await
+        .map_err(|err| Error::Persistence(io::Error::new(io::ErrorKind::Other, err)))?
+        .map_err(Error::Persistence)
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for FileStorage {
+    async fn add_new_bonsai_proof_request(
+        &self,
+        proof: ProofRequestInformation,
+    ) -> Result<(), Error> {
+        self.inner.add_new_bonsai_proof_request(proof).await?;
+        self.persist().await
+    }
+
+    async fn fetch_new_bonsai_requests(
+        &self,
+        limit: Option<u64>,
+    ) -> Result<Vec<ProofRequestInformation>, Error> {
+        self.inner.fetch_new_bonsai_requests(limit).await
+    }
+
+    async fn fetch_completed_bonsai_requests(
+        &self,
+        limit: Option<u64>,
+    ) -> Result<Vec<ProofRequestInformation>, Error> {
+        self.inner.fetch_completed_bonsai_requests(limit).await
+    }
+
+    async fn fetch_preparing_onchain_proof_requests(
+        &self,
+        limit: Option<u64>,
+    ) -> Result<Vec<ProofRequestInformation>, Error> {
+        self.inner
+            .fetch_preparing_onchain_proof_requests(limit)
+            .await
+    }
+
+    async fn get_proof_request_state(&self, proof_id: ProofID) -> Result<ProofRequestState, Error> {
+        self.inner.get_proof_request_state(proof_id).await
+    }
+
+    async fn find_callback_request(
+        &self,
+        event: &CallbackRequestFilter,
+    ) -> Result<Option<ProofID>, Error> {
+        self.inner.find_callback_request(event).await
+    }
+
+    async fn transition_proof_request(
+        &self,
+        proof_id: ProofID,
+        new_state: ProofRequestState,
+    ) -> Result<(), Error> {
+        self.inner
+            .transition_proof_request(proof_id, new_state)
+            .await?;
+        self.persist().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof_request_information(id: &str) -> ProofRequestInformation {
+        ProofRequestInformation {
+            proof_request_id: ProofID::new(id.into()),
+            callback_proof_request_event: CallbackRequestFilter {
+                account: Address::default(),
+                image_id: H256::default().into(),
+                input: Bytes::from(vec![1, 2, 3]),
+                callback_contract: Address::default(),
+                function_selector: [0xab, 0xcd, 0xef, 0xab],
+                gas_limit: 3000000,
+                nonce: U256::from(1),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn restores_proof_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("storage.json");
+
+        let storage = FileStorage::open(&path).await.unwrap();
+        for id in ["new", "pending", "completed", "onchain"] {
+            storage
+                .add_new_bonsai_proof_request(proof_request_information(id))
+                .await
+                .unwrap();
+        }
+        for id in ["pending", "completed", "onchain"] {
+            storage
+                .transition_proof_request(ProofID::new(id.into()), ProofRequestState::Pending)
+                .await
+                .unwrap();
+        }
+        for id in ["completed", "onchain"] {
+            storage
+                .transition_proof_request(ProofID::new(id.into()), ProofRequestState::Completed)
+                .await
+                .unwrap();
+        }
+        storage
+            .transition_proof_request(
+                ProofID::new("onchain".into()),
+                ProofRequestState::PreparingOnchain,
+            )
+            .await
+            .unwrap();
+        storage
+            .transition_proof_request(
+                ProofID::new("onchain".into()),
+                ProofRequestState::CompletedOnchain(H256::default()),
+            )
+            .await
+            .unwrap();
+
+        let restored = FileStorage::open(&path).await.unwrap();
+
+        let state = |id: &str| restored.get_proof_request_state(ProofID::new(id.into()));
+        assert_eq!(state("new").await.unwrap(), ProofRequestState::New);
+        // Pending requests are resubmitted to Bonsai after a restart.
+        assert_eq!(state("pending").await.unwrap(), ProofRequestState::New);
+        assert_eq!(
+            state("completed").await.unwrap(),
+            ProofRequestState::Completed
+        );
+        assert!(state("onchain").await.is_err());
+
+        let new_requests = restored.fetch_new_bonsai_requests(None).await.unwrap();
+        assert_eq!(new_requests.len(), 2);
+        assert!(new_requests.iter().all(|request| request
+            .callback_proof_request_event
+            .input
+            .as_ref()
+            == [1, 2, 3]));
+    }
+}
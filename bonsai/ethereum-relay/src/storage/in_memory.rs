@@ -18,8 +18,12 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use bonsai_ethereum_contracts::i_bonsai_relay::CallbackRequestFilter;
+use ethers::types::H256;
+
 use crate::storage::{
-    Error, ProofID, ProofRequestInformation, ProofRequestState, Storage, MAX_PROOF_RETRIES,
+    callback_request_key, Error, ProofID, ProofRequestInformation, ProofRequestState, Storage,
+    MAX_PROOF_RETRIES,
 };
 
 #[derive(Debug, Clone)]
@@ -30,6 +34,10 @@ pub(crate) struct InMemoryStorage {
     pending_proofs: Arc<RwLock<HashMap<String, ProofRequestInformation>>>,
     completed_proofs: Arc<RwLock<HashMap<String, ProofRequestInformation>>>,
     preparing_onchain_proofs: Arc<RwLock<HashMap<String, ProofRequestInformation>>>,
+    // Proof request made for each callback request, by callback request key.
+    // Entries are kept once the proof is delivered, so that a request is not
+    // proven twice.
+    callback_requests: Arc<RwLock<HashMap<H256, ProofID>>>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -58,6 +66,7 @@ impl InMemoryStorage {
             pending_proofs: Arc::new(RwLock::new(HashMap::new())),
             completed_proofs: Arc::new(RwLock::new(HashMap::new())),
             preparing_onchain_proofs: Arc::new(RwLock::new(HashMap::new())),
+            callback_requests: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -113,6 +122,46 @@ impl InMemoryStorage {
                 id: proof_id.clone(),
             })
     }
+
+    /// Returns every tracked proof request along with its state and retry count.
+    pub(crate) fn entries(
+        &self,
+    ) -> Result<Vec<(ProofRequestInformation, ProofRequestState, u64)>, Error> {
+        // Copy the states first, so that no lock is held while reading the request sets.
+        let proof_states = self.proof_states.read()?.clone();
+        let proof_retries = self.proof_retries.read()?.clone();
+        let mut entries = Vec::with_capacity(proof_states.len());
+        for (id, state) in proof_states.iter() {
+            let set = self.get_proof_request_set_for_state(*state);
+            // Failed requests are not retained, so there is nothing to return for them.
+            let Some(proof) = set.read()?.get(id).cloned() else {
+                continue;
+            };
+            let retries = proof_retries.get(id).copied().unwrap_or_default();
+            entries.push((proof, *state, retries));
+        }
+        Ok(entries)
+    }
+
+    /// Inserts a proof request in the given state, bypassing state transition checks.
+    pub(crate) fn restore(
+        &self,
+        proof: ProofRequestInformation,
+        state: ProofRequestState,
+        retries: u64,
+    ) -> Result<(), Error> {
+        let id = proof.proof_request_id.uuid.clone();
+        self.callback_requests.write()?.insert(
+            callback_request_key(&proof.callback_proof_request_event),
+            proof.proof_request_id.clone(),
+        );
+        self.proof_retries.write()?.insert(id.clone(), retries);
+        self.proof_states.write()?.insert(id.clone(), state);
+        self.get_proof_request_set_for_state(state)
+            .write()?
+            .insert(id, proof);
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -131,6 +180,10 @@ impl Storage for InMemoryStorage {
             });
         }
 
+        self.callback_requests.write()?.insert(
+            callback_request_key(&proof.callback_proof_request_event),
+            proof.proof_request_id.clone(),
+        );
         self.proof_retries
             .write()?
             .insert(proof.proof_request_id.uuid.clone(), 0);
@@ -178,6 +231,17 @@ impl Storage for InMemoryStorage {
         }
     }
 
+    async fn find_callback_request(
+        &self,
+        event: &CallbackRequestFilter,
+    ) -> Result<Option<ProofID>, Error> {
+        Ok(self
+            .callback_requests
+            .read()?
+            .get(&callback_request_key(event))
+            .cloned())
+    }
+
     async fn transition_proof_request(
         &self,
         proof_id: ProofID,
@@ -233,7 +297,7 @@ impl Storage for InMemoryStorage {
 #[cfg(test)]
 mod tests {
     use bonsai_ethereum_contracts::i_bonsai_relay::CallbackRequestFilter;
-    use ethers::types::{Address, Bytes, H256};
+    use ethers::types::{Address, Bytes, H256, U256};
     use rstest::*;
 
     use super::*;
@@ -259,6 +323,7 @@ mod tests {
                 callback_contract: Address::default(),
                 function_selector: [0xab, 0xcd, 0xef, 0xab],
                 gas_limit: 3000000,
+                nonce: U256::from(1),
             },
         }
     }
@@ -328,6 +393,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn find_callback_requests(
+        storage: InMemoryStorage,
+        proof_request_information: ProofRequestInformation,
+    ) {
+        let mut event = proof_request_information
+            .callback_proof_request_event
+            .clone();
+        assert_eq!(storage.find_callback_request(&event).await.unwrap(), None);
+
+        storage
+            .add_new_bonsai_proof_request(proof_request_information.clone())
+            .await
+            .unwrap();
+
+        // The same request received from another account, e.g. through the API.
+        event.account = Address::random();
+        assert_eq!(
+            storage.find_callback_request(&event).await.unwrap(),
+            Some(proof_request_information.proof_request_id)
+        );
+
+        event.nonce = U256::from(2);
+        assert_eq!(storage.find_callback_request(&event).await.unwrap(), None);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn failed_proofs_retry_only_max_retries(
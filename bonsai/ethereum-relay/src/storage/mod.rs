@@ -15,8 +15,13 @@
 use std::{cmp::Ordering, sync::PoisonError};
 
 use bonsai_ethereum_contracts::i_bonsai_relay::CallbackRequestFilter;
-use ethers::types::H256;
+use ethers::{
+    abi::{self, Token},
+    types::H256,
+    utils::keccak256,
+};
 
+pub(crate) mod file;
 pub(crate) mod in_memory;
 
 use bonsai_sdk::alpha::SessionId;
@@ -43,6 +48,8 @@ pub(crate) enum Error {
     MaxRetriesExceeded { id: ProofID },
     #[error("Proof already exists")]
     ProofAlreadyExists { id: ProofID },
+    #[error("Failed to persist proof requests")]
+    Persistence(#[source] std::io::Error),
 }
 
 impl<T> From<PoisonError<T>> for Error {
@@ -57,6 +64,25 @@ pub(crate) struct ProofRequestInformation {
     pub callback_proof_request_event: CallbackRequestFilter,
 }
 
+/// Returns the key identifying a callback request, made of its nonce and
+/// parameters.
+///
+/// The account that made the request is left out, since requests received
+/// through the REST API are not attributed to the account that made them on
+/// chain. A request received both through the API and from the relay contract
+/// events has the same key.
+pub(crate) fn callback_request_key(event: &CallbackRequestFilter) -> H256 {
+    keccak256(abi::encode(&[
+        Token::FixedBytes(event.image_id.to_vec()),
+        Token::Bytes(event.input.to_vec()),
+        Token::Address(event.callback_contract),
+        Token::FixedBytes(event.function_selector.to_vec()),
+        Token::Uint(event.gas_limit.into()),
+        Token::Uint(event.nonce),
+    ]))
+    .into()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum ProofRequestState {
     New,
@@ -127,4 +153,8 @@ pub(crate) trait Storage {
         new_state: ProofRequestState,
     ) -> Result<()>;
     async fn get_proof_request_state(&self, proof_id: ProofID) -> Result<ProofRequestState>;
+    /// Returns the ID of the proof request made for the given callback request,
+    /// if it was already received.
+    async fn find_callback_request(&self, event: &CallbackRequestFilter)
+        -> Result<Option<ProofID>>;
 }
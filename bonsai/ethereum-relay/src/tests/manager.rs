@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use bonsai_ethereum_contracts::i_bonsai_relay::CallbackRequestFilter;
 use bonsai_sdk::alpha_async::get_client_from_parts;
-use ethers::types::{Address, Bytes, H256};
+use ethers::types::{Address, Bytes, H256, U256};
 use tokio::sync::Notify;
 
 use super::utils::get_test_bonsai_server;
@@ -62,6 +62,7 @@ async fn integration_test_pending_proof_manager() {
                 callback_contract: Address::default(),
                 function_selector: [0xab, 0xcd, 0xef, 0xab],
                 gas_limit: 3000000,
+                nonce: U256::from(1),
             },
         })
         .await
@@ -150,6 +151,7 @@ async fn integration_test_completed_proof_manager() {
                 callback_contract: Address::default(),
                 function_selector: [0xab, 0xcd, 0xef, 0xab],
                 gas_limit: 3000000,
+                nonce: U256::from(1),
             },
         })
         .await
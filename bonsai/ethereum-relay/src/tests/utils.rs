@@ -18,9 +18,7 @@ use bonsai_sdk::alpha::{
     SessionId,
 };
 use ethers::types::{Address, Bytes, H256};
-use risc0_zkvm::{
-    sha::Digest, ExitCode, InnerReceipt, Journal, MaybePruned, Receipt, ReceiptClaim,
-};
+use risc0_zkvm::{sha::Digest, ExitCode, InnerReceipt, MaybePruned, Receipt, ReceiptClaim};
 use uuid::Uuid;
 use wiremock::{
     matchers::{method, path},
@@ -43,9 +41,8 @@ pub(crate) async fn get_test_bonsai_server() -> (SessionId, MockServer) {
         state: None,
     };
 
-    let receipt_data_response = Receipt {
-        journal: Journal::new(vec![]),
-        inner: InnerReceipt::Fake {
+    let receipt_data_response = Receipt::new(
+        InnerReceipt::Fake {
             claim: ReceiptClaim {
                 pre: MaybePruned::Pruned(Digest::ZERO),
                 post: MaybePruned::Pruned(Digest::ZERO),
@@ -54,7 +51,8 @@ pub(crate) async fn get_test_bonsai_server() -> (SessionId, MockServer) {
                 output: None.into(),
             },
         },
-    };
+        vec![],
+    );
 
     let create_snark_res = CreateSessRes {
         uuid: receipt_id.to_string(),
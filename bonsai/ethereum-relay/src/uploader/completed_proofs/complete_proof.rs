@@ -20,6 +20,7 @@ use bonsai_sdk::{
     alpha_async::session_status,
 };
 use ethers::abi;
+use risc0_zkvm::sha::{Impl, Sha256};
 
 use super::snark::tokenize_snark_receipt;
 use crate::{api, uploader::completed_proofs::error::CompleteProofError};
@@ -83,6 +84,8 @@ pub(crate) async fn get_complete_proof(
         payload: payload.into(),
         gas_limit,
         callback_contract: callback_request.callback_contract,
+        nonce: callback_request.nonce,
+        input_digest: (*Impl::hash_bytes(&callback_request.input)).into(),
     };
 
     Ok(CompleteProof {
@@ -16,7 +16,8 @@ use std::{sync::Arc, time::SystemTime};
 
 use anyhow::anyhow;
 use bonsai_ethereum_contracts::{
-    testutils::Counter, BonsaiRelay, BonsaiTestRelay, RiscZeroGroth16Verifier,
+    relay::callback_request_nonce, testutils::Counter, BonsaiRelay, BonsaiTestRelay,
+    RiscZeroGroth16Verifier,
 };
 use bonsai_ethereum_relay::{
     sdk::{
@@ -309,14 +310,34 @@ async fn e2e_test_counter_publish_mode() {
     input[0] = 32;
     input[35] = 100;
 
-    // Invoke the Counter contract which should request a callback through bonsai
+    // Invoke the Counter contract which should request a callback on chain, and
+    // publish the request to the relay along with the nonce it was assigned.
     let gas_limit: u64 = 3000000;
+    let receipt = counter
+        .method::<_, ()>(
+            "request_callback",
+            (
+                ethers_H256::from(image_id_bytes),
+                Bytes::from(input.clone()),
+                gas_limit,
+                bonsai_relay_contract,
+            ),
+        )
+        .expect("request_callback should be a function")
+        .send()
+        .await
+        .expect("request_callback should succeed")
+        .await
+        .expect("request_callback should be confirmed")
+        .expect("request_callback should not be dropped");
+    let nonce = callback_request_nonce(&receipt).expect("a callback should be requested");
     let request = CallbackRequest {
         callback_contract: counter.address(),
         function_selector: [0xff, 0x58, 0x5c, 0xaf],
         gas_limit,
         image_id: image_id_bytes,
         input,
+        nonce,
     };
 
     let relay_client =
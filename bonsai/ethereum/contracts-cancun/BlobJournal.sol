@@ -0,0 +1,84 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.24;
+
+import {IRiscZeroVerifier} from "../contracts/IRiscZeroVerifier.sol";
+
+/// @notice Verification of receipts whose journal is carried in an EIP-4844 blob.
+/// @dev Instead of committing the data itself, the guest commits a `BlobJournal`: the versioned
+/// hash of the blob holding the data, and the evaluation `y` of the blob polynomial at a point `z`
+/// derived from the blob by the guest. Checking the KZG proof of that evaluation against the blob
+/// attached to the transaction binds the receipt to the content of the blob, while the full data
+/// never has to be sent as calldata.
+library BlobJournal {
+    /// @notice Address of the EIP-4844 point evaluation precompile.
+    address internal constant POINT_EVALUATION = address(0x0a);
+
+    /// @notice Digest of the journal committing to the given blob evaluation.
+    function digest(bytes32 versionedHash, bytes32 z, bytes32 y) internal pure returns (bytes32) {
+        return sha256(abi.encodePacked(versionedHash, z, y));
+    }
+
+    /// @notice Check that `commitment` is the KZG commitment of the blob with the given versioned
+    /// hash, and that `proof` opens it to `y` at `z`.
+    function verifyEvaluation(
+        bytes32 versionedHash,
+        bytes32 z,
+        bytes32 y,
+        bytes calldata commitment,
+        bytes calldata proof
+    ) internal view returns (bool) {
+        if (commitment.length != 48 || proof.length != 48) {
+            return false;
+        }
+        (bool success,) = POINT_EVALUATION.staticcall(abi.encodePacked(versionedHash, z, y, commitment, proof));
+        return success;
+    }
+
+    /// @notice Verify a receipt whose journal commits to the blob at `blobIndex` of the current
+    /// transaction.
+    /// @param verifier The verifier checking the seal.
+    /// @param seal The encoded cryptographic proof (i.e. SNARK).
+    /// @param imageId The identifier for the guest program.
+    /// @param postStateDigest A hash of the final memory state.
+    /// @param blobIndex Index of the blob in the current transaction.
+    /// @param z Evaluation point committed by the guest.
+    /// @param y Evaluation of the blob polynomial at `z` committed by the guest.
+    /// @param commitment KZG commitment of the blob.
+    /// @param proof KZG proof of the evaluation.
+    /// @return true if the receipt and the evaluation proof are valid.
+    function verify(
+        IRiscZeroVerifier verifier,
+        bytes calldata seal,
+        bytes32 imageId,
+        bytes32 postStateDigest,
+        uint256 blobIndex,
+        bytes32 z,
+        bytes32 y,
+        bytes calldata commitment,
+        bytes calldata proof
+    ) internal view returns (bool) {
+        bytes32 versionedHash = blobhash(blobIndex);
+        if (versionedHash == bytes32(0)) {
+            return false;
+        }
+        if (!verifyEvaluation(versionedHash, z, y, commitment, proof)) {
+            return false;
+        }
+        return verifier.verify(seal, imageId, postStateDigest, digest(versionedHash, z, y));
+    }
+}
@@ -72,15 +72,23 @@ abstract contract BonsaiTest is Test, BonsaiCheats {
         // read logs, parse event, get image output, invoke proper callback
         bytes memory logEntry = bonsaiRelay.dequeueCbrEventData();
 
-        (bytes32 imageId, bytes memory input, address callbackContract, bytes4 functionSelector, uint64 gasLimit) =
-            abi.decode(logEntry, (bytes32, bytes, address, bytes4, uint64));
+        (
+            bytes32 imageId,
+            bytes memory input,
+            address callbackContract,
+            bytes4 functionSelector,
+            uint64 gasLimit,
+            uint256 nonce
+        ) = abi.decode(logEntry, (bytes32, bytes, address, bytes4, uint64, uint256));
         vm.resumeGasMetering();
 
-        runCallbackRequest(imageId, input, callbackContract, functionSelector, gasLimit);
+        runCallbackRequest(imageId, input, callbackContract, functionSelector, gasLimit, nonce);
     }
 
     /// @notice Process the given callback request, executing the guest and invoking the indicated
     ///   callback function with the resulting journal and image ID.
+    /// @dev The request is first made to the relay, as if by the callback contract, to be assigned
+    ///   a nonce.
     function runCallbackRequest(
         bytes32 imageId,
         bytes memory input,
@@ -89,6 +97,24 @@ abstract contract BonsaiTest is Test, BonsaiCheats {
         uint64 gasLimit
     ) public {
         vm.pauseGasMetering();
+        uint256 nonce = bonsaiRelay.requestCallback(imageId, input, callbackContract, functionSelector, gasLimit);
+        bonsaiRelay.discardLastCbrEventData();
+        vm.resumeGasMetering();
+
+        runCallbackRequest(imageId, input, callbackContract, functionSelector, gasLimit, nonce);
+    }
+
+    /// @notice Process the given callback request, executing the guest and invoking the indicated
+    ///   callback function with the resulting journal and image ID.
+    function runCallbackRequest(
+        bytes32 imageId,
+        bytes memory input,
+        address callbackContract,
+        bytes4 functionSelector,
+        uint64 gasLimit,
+        uint256 nonce
+    ) public {
+        vm.pauseGasMetering();
 
         CallbackAuthorization memory auth;
         bytes memory payload;
@@ -102,7 +128,7 @@ abstract contract BonsaiTest is Test, BonsaiCheats {
             payload = abi.encodePacked(functionSelector, journal, imageId);
             auth = CallbackAuthorization(seal, postStateDigest);
         }
-        Callback memory callback = Callback(auth, callbackContract, payload, gasLimit);
+        Callback memory callback = Callback(auth, callbackContract, payload, gasLimit, nonce, sha256(input));
         vm.resumeGasMetering();
 
         bonsaiRelay.invokeCallback(callback);
@@ -0,0 +1,113 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.9;
+
+/// @notice Registry of the image IDs allowed for each application.
+/// @dev Applications are identified by `keccak256(bytes(name))`. The first account to register a
+/// name becomes its admin, and is the only one allowed to publish and revoke its image IDs.
+/// Consumers check `isAllowed(appId, imageId)` before accepting a receipt, instead of hardcoding
+/// the image ID of their guest, so that guests can be upgraded without redeploying the consumer.
+contract ImageIdRegistry {
+    /// @notice Admin and allowed image IDs of an application.
+    struct Application {
+        address admin;
+        bytes32[] imageIds;
+    }
+
+    mapping(bytes32 => Application) internal applications;
+    /// @notice Position plus one of each allowed image ID in the list of its application.
+    mapping(bytes32 => mapping(bytes32 => uint256)) internal positions;
+
+    /// @notice Emitted when the application `appId` is registered.
+    event ApplicationRegistered(bytes32 indexed appId, string name, address admin);
+    /// @notice Emitted when the admin of `appId` changes.
+    event AdminTransferred(bytes32 indexed appId, address indexed previousAdmin, address indexed newAdmin);
+    /// @notice Emitted when `imageId` is allowed for `appId`.
+    event ImageIdAdded(bytes32 indexed appId, bytes32 imageId);
+    /// @notice Emitted when `imageId` is no longer allowed for `appId`.
+    event ImageIdRemoved(bytes32 indexed appId, bytes32 imageId);
+
+    modifier onlyAdmin(bytes32 appId) {
+        require(msg.sender == applications[appId].admin, "ImageIdRegistry: caller is not the admin");
+        _;
+    }
+
+    /// @notice Returns the identifier of the application registered under `name`.
+    function appId(string calldata name) public pure returns (bytes32) {
+        return keccak256(bytes(name));
+    }
+
+    /// @notice Register `name`, with the caller as its admin.
+    /// @dev Reverts if the name is already registered.
+    function registerApplication(string calldata name) external returns (bytes32 id) {
+        id = appId(name);
+        require(applications[id].admin == address(0), "ImageIdRegistry: application already registered");
+        applications[id].admin = msg.sender;
+        emit ApplicationRegistered(id, name, msg.sender);
+    }
+
+    /// @notice Transfer the administration of `id` to `newAdmin`.
+    function transferAdmin(bytes32 id, address newAdmin) external onlyAdmin(id) {
+        require(newAdmin != address(0), "ImageIdRegistry: new admin is the zero address");
+        emit AdminTransferred(id, applications[id].admin, newAdmin);
+        applications[id].admin = newAdmin;
+    }
+
+    /// @notice Allow `imageId` for `id`.
+    function addImageId(bytes32 id, bytes32 imageId) public onlyAdmin(id) {
+        require(positions[id][imageId] == 0, "ImageIdRegistry: image ID already allowed");
+        applications[id].imageIds.push(imageId);
+        positions[id][imageId] = applications[id].imageIds.length;
+        emit ImageIdAdded(id, imageId);
+    }
+
+    /// @notice Revoke `imageId` for `id`.
+    function removeImageId(bytes32 id, bytes32 imageId) public onlyAdmin(id) {
+        uint256 position = positions[id][imageId];
+        require(position != 0, "ImageIdRegistry: image ID not allowed");
+
+        // Move the last image ID into the freed slot.
+        bytes32[] storage imageIds = applications[id].imageIds;
+        bytes32 last = imageIds[imageIds.length - 1];
+        imageIds[position - 1] = last;
+        positions[id][last] = position;
+        imageIds.pop();
+        delete positions[id][imageId];
+        emit ImageIdRemoved(id, imageId);
+    }
+
+    /// @notice Replace `oldImageId` with `newImageId` for `id`, in a single transaction.
+    function rotateImageId(bytes32 id, bytes32 oldImageId, bytes32 newImageId) external {
+        addImageId(id, newImageId);
+        removeImageId(id, oldImageId);
+    }
+
+    /// @notice Returns true if `imageId` is allowed for `id`.
+    function isAllowed(bytes32 id, bytes32 imageId) external view returns (bool) {
+        return positions[id][imageId] != 0;
+    }
+
+    /// @notice Returns the admin of `id`, or the zero address if it is not registered.
+    function getAdmin(bytes32 id) external view returns (address) {
+        return applications[id].admin;
+    }
+
+    /// @notice Returns the image IDs allowed for `id`, in no particular order.
+    function getImageIds(bytes32 id) external view returns (bytes32[] memory) {
+        return applications[id].imageIds;
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.9;
+
+import {
+    ExitCode,
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    SystemExitCode
+} from "./IRiscZeroVerifier.sol";
+
+/// @notice Verifier for aggregated RISC Zero receipts, covering many claims with a single proof.
+/// @dev An aggregated receipt is a receipt for an aggregation guest that verifies a list of
+/// receipts and commits the root of a Merkle tree over their claim digests as its journal, such as
+/// the `aggregate` guest of `risc0-zkvm-methods`. The aggregated receipt is verified once, with a
+/// single pairing check, after which each covered claim can be verified with a Merkle inclusion
+/// proof against the recorded root.
+///
+/// Inner nodes of the tree are computed as `sha256(min(a, b) || max(a, b))`, so inclusion proofs
+/// only need to list the sibling digests from the leaf up to the root.
+contract RiscZeroAggregateVerifier {
+    using ReceiptClaimLib for ReceiptClaim;
+    using OutputLib for Output;
+
+    /// @notice Verifier used to check the aggregated receipts.
+    IRiscZeroVerifier public immutable verifier;
+
+    /// @notice Image ID of the aggregation guest.
+    bytes32 public immutable aggregatorImageId;
+
+    /// @notice Merkle roots committed to by verified aggregated receipts.
+    mapping(bytes32 => bool) public verifiedRoots;
+
+    /// @notice Emitted when an aggregated receipt committing to `root` is verified.
+    event RootVerified(bytes32 indexed root);
+
+    constructor(IRiscZeroVerifier _verifier, bytes32 _aggregatorImageId) {
+        verifier = _verifier;
+        aggregatorImageId = _aggregatorImageId;
+    }
+
+    /// @notice Verify an aggregated receipt and record its Merkle root of claims.
+    /// @param seal The encoded cryptographic proof (i.e. SNARK) of the aggregation guest.
+    /// @param postStateDigest A hash of the final memory state of the aggregation guest.
+    /// @param root The Merkle root of claim digests committed as the journal.
+    function submitAggregate(bytes calldata seal, bytes32 postStateDigest, bytes32 root) external {
+        require(
+            verifier.verify(seal, aggregatorImageId, postStateDigest, sha256(abi.encodePacked(root))),
+            "RiscZeroAggregateVerifier: invalid aggregated receipt"
+        );
+        verifiedRoots[root] = true;
+        emit RootVerified(root);
+    }
+
+    /// @notice Verify that the given claim is covered by a verified aggregated receipt.
+    /// @param claim The claim to be verified.
+    /// @param root The Merkle root of a previously verified aggregated receipt.
+    /// @param proof Sibling digests on the path from the claim digest to the root.
+    /// @return true if the claim is included. The return code must be checked.
+    function verify_integrity(ReceiptClaim memory claim, bytes32 root, bytes32[] calldata proof)
+        public
+        view
+        returns (bool)
+    {
+        return verifiedRoots[root] && processProof(claim.digest(), proof) == root;
+    }
+
+    /// @notice Verify that an execution with the given image ID, post-state digest, and journal
+    ///     digest is covered by a verified aggregated receipt.
+    /// @dev As with `IRiscZeroVerifier.verify`, the claim is required to have no committed input,
+    /// an exit code of (Halted, 0), and no assumptions.
+    /// @return true if the claim is included. The return code must be checked.
+    function verify(
+        bytes32 imageId,
+        bytes32 postStateDigest,
+        bytes32 journalDigest,
+        bytes32 root,
+        bytes32[] calldata proof
+    ) external view returns (bool) {
+        ReceiptClaim memory claim = ReceiptClaim(
+            imageId,
+            postStateDigest,
+            ExitCode(SystemExitCode.Halted, 0),
+            bytes32(0),
+            Output(journalDigest, bytes32(0)).digest()
+        );
+        return verify_integrity(claim, root, proof);
+    }
+
+    /// @notice Compute the Merkle root from a leaf and its inclusion proof.
+    function processProof(bytes32 leaf, bytes32[] calldata proof) internal pure returns (bytes32) {
+        bytes32 node = leaf;
+        for (uint256 i = 0; i < proof.length; i++) {
+            node = node < proof[i]
+                ? sha256(abi.encodePacked(node, proof[i]))
+                : sha256(abi.encodePacked(proof[i], node));
+        }
+        return node;
+    }
+}
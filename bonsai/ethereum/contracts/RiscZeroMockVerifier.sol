@@ -0,0 +1,100 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.9;
+
+import {
+    ExitCode,
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    SystemExitCode
+} from "./IRiscZeroVerifier.sol";
+
+/// @notice Seals of dev-mode receipts, accepted by the `RiscZeroMockVerifier`.
+/// @dev A dev-mode seal is the concatenation of a fixed tag and of the digest of the claim. It
+/// carries no cryptographic proof, and can be computed by anyone for any claim. The same encoding
+/// is produced on the host by `bonsai_ethereum_contracts::mock::encode_dev_mode_seal`.
+library DevModeSeal {
+    using OutputLib for Output;
+    using ReceiptClaimLib for ReceiptClaim;
+
+    /// @notice Tag prefixing every dev-mode seal.
+    bytes32 constant TAG = sha256("risc0.DevModeSeal");
+
+    /// @notice Length of a dev-mode seal, in bytes.
+    uint256 constant LENGTH = 64;
+
+    /// @notice Dev-mode seal for the given claim.
+    function encode(ReceiptClaim memory claim) internal pure returns (bytes memory) {
+        return abi.encodePacked(TAG, claim.digest());
+    }
+
+    /// @notice Dev-mode seal for a successful execution of `imageId` committing the given journal,
+    /// as checked by `IRiscZeroVerifier.verify`.
+    function encode(bytes32 imageId, bytes32 postStateDigest, bytes32 journalDigest)
+        internal
+        pure
+        returns (bytes memory)
+    {
+        return encode(
+            ReceiptClaim(
+                imageId,
+                postStateDigest,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(journalDigest, bytes32(0)).digest()
+            )
+        );
+    }
+}
+
+/// @notice Mock verifier contract for RISC Zero receipts of execution, accepting dev-mode seals.
+/// @dev This contract lets application contracts be tested end to end with receipts produced in
+/// dev mode, without any proving. It provides no security whatsoever, and must never be deployed
+/// to a production network.
+contract RiscZeroMockVerifier is IRiscZeroVerifier {
+    using OutputLib for Output;
+    using ReceiptClaimLib for ReceiptClaim;
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify(bytes calldata seal, bytes32 imageId, bytes32 postStateDigest, bytes32 journalDigest)
+        public
+        pure
+        returns (bool)
+    {
+        Receipt memory receipt = Receipt(
+            seal,
+            ReceiptClaim(
+                imageId,
+                postStateDigest,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(journalDigest, bytes32(0)).digest()
+            )
+        );
+        return verify_integrity(receipt);
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify_integrity(Receipt memory receipt) public pure returns (bool) {
+        return receipt.seal.length == DevModeSeal.LENGTH
+            && keccak256(receipt.seal) == keccak256(DevModeSeal.encode(receipt.claim));
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.9;
+
+import {IRiscZeroVerifier, Receipt} from "./IRiscZeroVerifier.sol";
+
+/// @notice Router dispatching RISC Zero receipts to the verifier for the zkVM version they target.
+/// @dev Each verifier is registered under a 4-byte selector, computed as the first four bytes of
+/// `sha256(abi.encodePacked(CONTROL_ID_0, CONTROL_ID_1))` for the control IDs it accepts. Seals
+/// passed to the router are prefixed with the selector of the verifier they are meant for.
+/// Applications pointing at the router keep working across zkVM upgrades, as new verifiers can be
+/// registered next to the existing ones, and versions with known issues can be deprecated.
+/// A deprecated selector can never be registered again.
+contract RiscZeroVerifierRouter is IRiscZeroVerifier {
+    /// @notice Status of a selector in the router.
+    enum VerifierStatus {
+        Unregistered,
+        Active,
+        Deprecated
+    }
+
+    /// @notice Registered verifier for a selector, along with its status.
+    struct VerifierEntry {
+        IRiscZeroVerifier verifier;
+        VerifierStatus status;
+    }
+
+    /// @notice Account allowed to register and deprecate verifiers.
+    address public owner;
+
+    mapping(bytes4 => VerifierEntry) internal verifiers;
+
+    /// @notice Emitted when a verifier is registered under `selector`.
+    event VerifierAdded(bytes4 indexed selector, IRiscZeroVerifier verifier);
+    /// @notice Emitted when the verifier under `selector` is deprecated.
+    event VerifierDeprecated(bytes4 indexed selector);
+    /// @notice Emitted when ownership of the router is transferred.
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "RiscZeroVerifierRouter: caller is not the owner");
+        _;
+    }
+
+    constructor(address _owner) {
+        owner = _owner;
+        emit OwnershipTransferred(address(0), _owner);
+    }
+
+    /// @notice Transfer ownership of the router to `newOwner`.
+    function transferOwnership(address newOwner) external onlyOwner {
+        emit OwnershipTransferred(owner, newOwner);
+        owner = newOwner;
+    }
+
+    /// @notice Register `verifier` under `selector`.
+    /// @dev Reverts if the selector is already in use or has been deprecated.
+    function addVerifier(bytes4 selector, IRiscZeroVerifier verifier) external onlyOwner {
+        require(
+            verifiers[selector].status == VerifierStatus.Unregistered,
+            "RiscZeroVerifierRouter: selector already used"
+        );
+        verifiers[selector] = VerifierEntry(verifier, VerifierStatus.Active);
+        emit VerifierAdded(selector, verifier);
+    }
+
+    /// @notice Deprecate the verifier registered under `selector`.
+    /// @dev Receipts for a deprecated selector are rejected from then on.
+    function deprecateVerifier(bytes4 selector) external onlyOwner {
+        require(
+            verifiers[selector].status == VerifierStatus.Active, "RiscZeroVerifierRouter: selector not active"
+        );
+        verifiers[selector].status = VerifierStatus.Deprecated;
+        emit VerifierDeprecated(selector);
+    }
+
+    /// @notice Returns the verifier registered under `selector` and its status.
+    function getVerifier(bytes4 selector) external view returns (IRiscZeroVerifier, VerifierStatus) {
+        VerifierEntry memory entry = verifiers[selector];
+        return (entry.verifier, entry.status);
+    }
+
+    /// @notice Returns the active verifier for the selector prefixing `seal`.
+    function activeVerifier(bytes calldata seal) internal view returns (IRiscZeroVerifier) {
+        require(seal.length >= 4, "RiscZeroVerifierRouter: seal is missing the selector");
+        VerifierEntry memory entry = verifiers[bytes4(seal[:4])];
+        require(entry.status == VerifierStatus.Active, "RiscZeroVerifierRouter: selector not active");
+        return entry.verifier;
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify(bytes calldata seal, bytes32 imageId, bytes32 postStateDigest, bytes32 journalDigest)
+        external
+        view
+        returns (bool)
+    {
+        return activeVerifier(seal).verify(seal[4:], imageId, postStateDigest, journalDigest);
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify_integrity(Receipt calldata receipt) external view returns (bool) {
+        return activeVerifier(receipt.seal).verify_integrity(Receipt(receipt.seal[4:], receipt.claim));
+    }
+}
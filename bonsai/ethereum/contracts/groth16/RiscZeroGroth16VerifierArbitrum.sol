@@ -0,0 +1,324 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+pragma solidity ^0.8.9;
+
+import {
+    ExitCode,
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    SystemExitCode
+} from "../IRiscZeroVerifier.sol";
+import {reverseByteOrderUint256} from "./RiscZeroGroth16Verifier.sol";
+
+/// @notice Groth16 verifier contract for RISC Zero receipts of execution, tuned for Arbitrum.
+/// @dev On L2 chains, the calldata posted to L1 dominates the cost of a verification, while
+/// computation is cheap. This verifier takes seals with compressed points, which it decompresses
+/// using the modexp precompile:
+/// - G1 points are encoded as `x << 1 | negate` in 32 bytes.
+/// - G2 points are encoded as `(x.c0 << 2 | hint << 1 | negate, x.c1)` in 64 bytes, where `hint`
+///   selects the square root of the norm used to compute the square root in Fp2.
+/// B is left uncompressed, in the precompile order, trading 64 bytes of calldata
+/// for the square root in Fp2. Seals are 192 bytes long.
+/// The control IDs are fixed when this contract is generated, and their contribution to the
+/// public input is precomputed, saving two elliptic curve multiplications per verification.
+/// Seals are encoded with `bonsai_ethereum_contracts::l2::encode_seal`.
+contract RiscZeroGroth16VerifierArbitrum is IRiscZeroVerifier {
+    using ReceiptClaimLib for ReceiptClaim;
+    using OutputLib for Output;
+
+    /// @notice Whether B is compressed in the seal.
+    bool public constant COMPRESS_G2 = false;
+    /// @notice Length of the seals accepted by this verifier.
+    uint256 public constant SEAL_LENGTH = 192;
+
+    /// @notice Control ID hash for the identity_p254 predicate decomposed by `splitDigest`.
+    uint256 public constant CONTROL_ID_0 = 0x447d7e12291364db4bc5421164880129;
+    uint256 public constant CONTROL_ID_1 = 0x12c49ad247d28a32147e13615c6c81f9;
+
+    // Base field size
+    uint256 internal constant P = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    // (P + 1) / 4, exponent of the square root
+    uint256 internal constant EXP_SQRT = 5472060717959818805561601436314318772174077789324455915672259473661306552146;
+    // 1 / 2, 27 / 82 and 3 / 82 in the base field, used to decompress G2 points
+    uint256 internal constant FRACTION_1_2 = 10944121435919637611123202872628637544348155578648911831344518947322613104292;
+    uint256 internal constant FRACTION_27_82 = 19485874751759354771024239261021720505790618469301721065564631296452457478373;
+    uint256 internal constant FRACTION_3_82 = 21621313080719284060999498358119991246151234191964923374119659383734918571893;
+
+    // Verification Key data
+    uint256 internal constant ALPHA_X = 20491192805390485299153009773594534940189261866228447918068658471970481763042;
+    uint256 internal constant ALPHA_Y = 9383485363053290200918347156157836566562967994039712273449902621266178545958;
+    uint256 internal constant BETA_X1 = 4252822878758300859123897981450591353533073413197771768651442665752259397132;
+    uint256 internal constant BETA_X2 = 6375614351688725206403948262868962793625744043794305715222011528459656738731;
+    uint256 internal constant BETA_Y1 = 21847035105528745403288232691147584728191162732299865338377159692350059136679;
+    uint256 internal constant BETA_Y2 = 10505242626370262277552901082094356697409835680220590971873171140371331206856;
+    uint256 internal constant GAMMA_X1 = 11559732032986387107991004021392285783925812861821192530917403151452391805634;
+    uint256 internal constant GAMMA_X2 = 10857046999023057135944570762232829481370756359578518086990519993285655852781;
+    uint256 internal constant GAMMA_Y1 = 4082367875863433681332203403145435568316851327593401208105741076214120093531;
+    uint256 internal constant GAMMA_Y2 = 8495653923123431417604973247489272438418190587263600148770280649306958101930;
+    uint256 internal constant DELTA_X1 = 7693487429026351977103582346623702458502994930724273067160489676068253241122;
+    uint256 internal constant DELTA_X2 = 18083587067730303898088465773541035809657139089748028791075809901844080381583;
+    uint256 internal constant DELTA_Y1 = 7359384703862385660061748803901931167856917172356103761995576336394013820206;
+    uint256 internal constant DELTA_Y2 = 19155327851717349610711498610928624259731705665803906701543914342469719520424;
+    uint256 internal constant IC3_X = 18597303695576860451572687600097059285572952378680993135977532372976749998241;
+    uint256 internal constant IC3_Y = 21723251549997210319914312194189948104957781222830842952199420842095495134729;
+    uint256 internal constant IC4_X = 12164939688015960624286304530289921602116255917351297336606146584068755512927;
+    uint256 internal constant IC4_Y = 17036398532634379807038707418819848170237695473144751623976431755966389161373;
+
+    // IC0 + CONTROL_ID_0 * IC1 + CONTROL_ID_1 * IC2
+    uint256 internal constant CONTROL_IC_X = 535876487216626131636701443797624636363814376183689572645747171087745555276;
+    uint256 internal constant CONTROL_IC_Y = 5751540675543669341115227499756112351395616526904718841216479381726835609415;
+
+    /// @notice splits a digest into two 128-bit words to use as public signal inputs.
+    /// @dev See `RiscZeroGroth16Verifier.splitDigest`.
+    function splitDigest(bytes32 digest) internal pure returns (uint256, uint256) {
+        uint256 reversed = reverseByteOrderUint256(uint256(digest));
+        return (uint256(uint128(uint256(reversed))), uint256(reversed >> 128));
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify(bytes calldata seal, bytes32 imageId, bytes32 postStateDigest, bytes32 journalDigest)
+        public
+        view
+        returns (bool)
+    {
+        Receipt memory receipt = Receipt(
+            seal,
+            ReceiptClaim(
+                imageId,
+                postStateDigest,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(journalDigest, bytes32(0)).digest()
+            )
+        );
+        return verify_integrity(receipt);
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify_integrity(Receipt memory receipt) public view returns (bool) {
+        if (receipt.seal.length != SEAL_LENGTH) {
+            return false;
+        }
+        (uint256[2] memory a, uint256[4] memory b, uint256[2] memory c, bool ok) = decodeSeal(receipt.seal);
+        if (!ok) {
+            return false;
+        }
+        (uint256 claim0, uint256 claim1) = splitDigest(receipt.claim.digest());
+        return verifyProof(a, b, c, claim0, claim1);
+    }
+
+    /// @notice Decodes the seal into uncompressed points, with B in the precompile order
+    /// `(x.c1, x.c0, y.c1, y.c0)`.
+    function decodeSeal(bytes memory seal)
+        internal
+        view
+        returns (uint256[2] memory a, uint256[4] memory b, uint256[2] memory c, bool ok)
+    {
+        uint256[] memory words = new uint256[](seal.length / 32);
+        for (uint256 i = 0; i < words.length; i++) {
+            uint256 word;
+            assembly {
+                word := mload(add(seal, add(32, mul(i, 32))))
+            }
+            words[i] = word;
+        }
+
+        (a, ok) = decompressG1(words[0]);
+        if (!ok) {
+            return (a, b, c, false);
+        }
+        if (COMPRESS_G2) {
+            (b, ok) = decompressG2(words[1], words[2]);
+            if (!ok) {
+                return (a, b, c, false);
+            }
+            (c, ok) = decompressG1(words[3]);
+        } else {
+            b = [words[1], words[2], words[3], words[4]];
+            (c, ok) = decompressG1(words[5]);
+        }
+    }
+
+    function negate(uint256 a) internal pure returns (uint256) {
+        return (P - a) % P;
+    }
+
+    function expmod(uint256 base, uint256 exponent) internal view returns (uint256 result) {
+        bool success;
+        assembly {
+            let f := mload(0x40)
+            mstore(f, 0x20)
+            mstore(add(f, 0x20), 0x20)
+            mstore(add(f, 0x40), 0x20)
+            mstore(add(f, 0x60), base)
+            mstore(add(f, 0x80), exponent)
+            mstore(add(f, 0xa0), P)
+            success := staticcall(gas(), 0x05, f, 0xc0, f, 0x20)
+            result := mload(f)
+        }
+        require(success, "RiscZeroGroth16Verifier: modexp failed");
+    }
+
+    /// @notice Square root in the base field, returning whether `a` is a square.
+    function sqrtFp(uint256 a) internal view returns (uint256 x, bool ok) {
+        x = expmod(a, EXP_SQRT);
+        ok = mulmod(x, x, P) == a;
+    }
+
+    /// @notice Square root in Fp2, using the square root of the norm selected by `hint`.
+    function sqrtFp2(uint256 a0, uint256 a1, bool hint) internal view returns (uint256 x0, uint256 x1, bool ok) {
+        uint256 d;
+        (d, ok) = sqrtFp(addmod(mulmod(a0, a0, P), mulmod(a1, a1, P), P));
+        if (!ok) {
+            return (0, 0, false);
+        }
+        if (hint) {
+            d = negate(d);
+        }
+        (x0, ok) = sqrtFp(mulmod(addmod(a0, d, P), FRACTION_1_2, P));
+        if (!ok) {
+            return (0, 0, false);
+        }
+        x1 = mulmod(a1, expmod(mulmod(x0, 2, P), P - 2), P);
+        ok = a0 == addmod(mulmod(x0, x0, P), negate(mulmod(x1, x1, P)), P) && a1 == mulmod(2, mulmod(x0, x1, P), P);
+    }
+
+    function decompressG1(uint256 compressed) internal view returns (uint256[2] memory point, bool ok) {
+        uint256 x = compressed >> 1;
+        if (x >= P) {
+            return (point, false);
+        }
+        uint256 y;
+        (y, ok) = sqrtFp(addmod(mulmod(mulmod(x, x, P), x, P), 3, P));
+        if (compressed & 1 == 1) {
+            y = negate(y);
+        }
+        point = [x, y];
+    }
+
+    function decompressG2(uint256 compressed0, uint256 compressed1)
+        internal
+        view
+        returns (uint256[4] memory point, bool ok)
+    {
+        uint256 x0 = compressed0 >> 2;
+        uint256 x1 = compressed1;
+        if (x0 >= P || x1 >= P) {
+            return (point, false);
+        }
+
+        // y^2 = x^3 + 3 / (9 + i)
+        uint256 n3ab = mulmod(mulmod(x0, x1, P), P - 3, P);
+        uint256 a3 = mulmod(mulmod(x0, x0, P), x0, P);
+        uint256 b3 = mulmod(mulmod(x1, x1, P), x1, P);
+        uint256 y0 = addmod(FRACTION_27_82, addmod(a3, mulmod(n3ab, x1, P), P), P);
+        uint256 y1 = negate(addmod(FRACTION_3_82, addmod(b3, mulmod(n3ab, x0, P), P), P));
+
+        (y0, y1, ok) = sqrtFp2(y0, y1, compressed0 & 2 == 2);
+        if (compressed0 & 1 == 1) {
+            y0 = negate(y0);
+            y1 = negate(y1);
+        }
+        point = [x1, x0, y1, y0];
+    }
+
+    /// @notice Adds `s * (x, y)` to `acc`.
+    function mulAccumulate(uint256[2] memory acc, uint256 x, uint256 y, uint256 s)
+        internal
+        view
+        returns (bool success)
+    {
+        uint256[4] memory buf = [x, y, s, 0];
+        assembly {
+            success := staticcall(gas(), 0x07, buf, 0x60, buf, 0x40)
+        }
+        if (!success) {
+            return false;
+        }
+        buf[2] = acc[0];
+        buf[3] = acc[1];
+        assembly {
+            success := staticcall(gas(), 0x06, buf, 0x80, acc, 0x40)
+        }
+    }
+
+    function verifyProof(uint256[2] memory a, uint256[4] memory b, uint256[2] memory c, uint256 claim0, uint256 claim1)
+        internal
+        view
+        returns (bool)
+    {
+        uint256[2] memory vk = [CONTROL_IC_X, CONTROL_IC_Y];
+        if (!mulAccumulate(vk, IC3_X, IC3_Y, claim0) || !mulAccumulate(vk, IC4_X, IC4_Y, claim1)) {
+            return false;
+        }
+
+        uint256[24] memory input;
+
+        // -A
+        input[0] = a[0];
+        input[1] = negate(a[1]);
+
+        // B
+        input[2] = b[0];
+        input[3] = b[1];
+        input[4] = b[2];
+        input[5] = b[3];
+
+        // alpha
+        input[6] = ALPHA_X;
+        input[7] = ALPHA_Y;
+
+        // beta
+        input[8] = BETA_X1;
+        input[9] = BETA_X2;
+        input[10] = BETA_Y1;
+        input[11] = BETA_Y2;
+
+        // vk_x
+        input[12] = vk[0];
+        input[13] = vk[1];
+
+        // gamma
+        input[14] = GAMMA_X1;
+        input[15] = GAMMA_X2;
+        input[16] = GAMMA_Y1;
+        input[17] = GAMMA_Y2;
+
+        // C
+        input[18] = c[0];
+        input[19] = c[1];
+
+        // delta
+        input[20] = DELTA_X1;
+        input[21] = DELTA_X2;
+        input[22] = DELTA_Y1;
+        input[23] = DELTA_Y2;
+        uint256[1] memory out;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x08, input, 0x300, out, 0x20)
+        }
+        return success && out[0] == 1;
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+pragma solidity ^0.8.9;
+
+import {
+    ExitCode,
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    SystemExitCode
+} from "../IRiscZeroVerifier.sol";
+import {reverseByteOrderUint256, Seal} from "./RiscZeroGroth16Verifier.sol";
+
+/// @notice Groth16 verifier contract for RISC Zero receipts of execution, compatible with zkSync Era.
+/// @dev The snarkjs verifier makes assumptions about the precompiles that do not hold on EraVM:
+/// - Calls to an address without code succeed and return no data, so a missing precompile would
+///   leave the input buffer in place of the output. Every precompile call checks the size of the
+///   returned data.
+/// - Gas is metered differently, so all the remaining gas is forwarded to the precompiles instead
+///   of a fixed margin being kept.
+/// - The modexp precompile is not available, so seals are not compressed: they are the same 256
+///   bytes as for `RiscZeroGroth16Verifier`, and only ecAdd, ecMul and ecPairing are used.
+/// The control IDs are fixed when this contract is generated, as immutables are not stored in the
+/// bytecode on EraVM, and their contribution to the public input is precomputed.
+contract RiscZeroGroth16VerifierZkSync is IRiscZeroVerifier {
+    using ReceiptClaimLib for ReceiptClaim;
+    using OutputLib for Output;
+
+    /// @notice Length of the seals accepted by this verifier.
+    uint256 public constant SEAL_LENGTH = 256;
+
+    /// @notice Control ID hash for the identity_p254 predicate decomposed by `splitDigest`.
+    uint256 public constant CONTROL_ID_0 = 0x447d7e12291364db4bc5421164880129;
+    uint256 public constant CONTROL_ID_1 = 0x12c49ad247d28a32147e13615c6c81f9;
+
+    // Base field size
+    uint256 internal constant P = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+
+    // Verification Key data
+    uint256 internal constant ALPHA_X = 20491192805390485299153009773594534940189261866228447918068658471970481763042;
+    uint256 internal constant ALPHA_Y = 9383485363053290200918347156157836566562967994039712273449902621266178545958;
+    uint256 internal constant BETA_X1 = 4252822878758300859123897981450591353533073413197771768651442665752259397132;
+    uint256 internal constant BETA_X2 = 6375614351688725206403948262868962793625744043794305715222011528459656738731;
+    uint256 internal constant BETA_Y1 = 21847035105528745403288232691147584728191162732299865338377159692350059136679;
+    uint256 internal constant BETA_Y2 = 10505242626370262277552901082094356697409835680220590971873171140371331206856;
+    uint256 internal constant GAMMA_X1 = 11559732032986387107991004021392285783925812861821192530917403151452391805634;
+    uint256 internal constant GAMMA_X2 = 10857046999023057135944570762232829481370756359578518086990519993285655852781;
+    uint256 internal constant GAMMA_Y1 = 4082367875863433681332203403145435568316851327593401208105741076214120093531;
+    uint256 internal constant GAMMA_Y2 = 8495653923123431417604973247489272438418190587263600148770280649306958101930;
+    uint256 internal constant DELTA_X1 = 7693487429026351977103582346623702458502994930724273067160489676068253241122;
+    uint256 internal constant DELTA_X2 = 18083587067730303898088465773541035809657139089748028791075809901844080381583;
+    uint256 internal constant DELTA_Y1 = 7359384703862385660061748803901931167856917172356103761995576336394013820206;
+    uint256 internal constant DELTA_Y2 = 19155327851717349610711498610928624259731705665803906701543914342469719520424;
+    uint256 internal constant IC3_X = 18597303695576860451572687600097059285572952378680993135977532372976749998241;
+    uint256 internal constant IC3_Y = 21723251549997210319914312194189948104957781222830842952199420842095495134729;
+    uint256 internal constant IC4_X = 12164939688015960624286304530289921602116255917351297336606146584068755512927;
+    uint256 internal constant IC4_Y = 17036398532634379807038707418819848170237695473144751623976431755966389161373;
+
+    // IC0 + CONTROL_ID_0 * IC1 + CONTROL_ID_1 * IC2
+    uint256 internal constant CONTROL_IC_X = 535876487216626131636701443797624636363814376183689572645747171087745555276;
+    uint256 internal constant CONTROL_IC_Y = 5751540675543669341115227499756112351395616526904718841216479381726835609415;
+
+    /// @notice splits a digest into two 128-bit words to use as public signal inputs.
+    /// @dev See `RiscZeroGroth16Verifier.splitDigest`.
+    function splitDigest(bytes32 digest) internal pure returns (uint256, uint256) {
+        uint256 reversed = reverseByteOrderUint256(uint256(digest));
+        return (uint256(uint128(uint256(reversed))), uint256(reversed >> 128));
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify(bytes calldata seal, bytes32 imageId, bytes32 postStateDigest, bytes32 journalDigest)
+        public
+        view
+        returns (bool)
+    {
+        Receipt memory receipt = Receipt(
+            seal,
+            ReceiptClaim(
+                imageId,
+                postStateDigest,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(journalDigest, bytes32(0)).digest()
+            )
+        );
+        return verify_integrity(receipt);
+    }
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify_integrity(Receipt memory receipt) public view returns (bool) {
+        if (receipt.seal.length != SEAL_LENGTH) {
+            return false;
+        }
+        Seal memory seal = abi.decode(receipt.seal, (Seal));
+        if (!checkFields(seal)) {
+            return false;
+        }
+        (uint256 claim0, uint256 claim1) = splitDigest(receipt.claim.digest());
+        return verifyProof(seal, claim0, claim1);
+    }
+
+    /// @notice Checks that all the coordinates of the seal are in the base field.
+    function checkFields(Seal memory seal) internal pure returns (bool) {
+        return seal.a[0] < P && seal.a[1] < P && seal.b[0][0] < P && seal.b[0][1] < P && seal.b[1][0] < P
+            && seal.b[1][1] < P && seal.c[0] < P && seal.c[1] < P;
+    }
+
+    function negate(uint256 a) internal pure returns (uint256) {
+        return (P - a) % P;
+    }
+
+    /// @notice Calls a precompile, checking that it exists by the size of the returned data.
+    function callPrecompile(address precompile, uint256[] memory input, uint256 outputLength)
+        internal
+        view
+        returns (uint256[] memory output, bool success)
+    {
+        output = new uint256[](outputLength);
+        uint256 inputSize = input.length * 0x20;
+        uint256 outputSize = outputLength * 0x20;
+        uint256 returned;
+        assembly {
+            success := staticcall(gas(), precompile, add(input, 0x20), inputSize, add(output, 0x20), outputSize)
+            returned := returndatasize()
+        }
+        success = success && returned == outputSize;
+    }
+
+    /// @notice Adds `s * (x, y)` to `acc`.
+    function mulAccumulate(uint256[2] memory acc, uint256 x, uint256 y, uint256 s) internal view returns (bool) {
+        uint256[] memory input = new uint256[](3);
+        input[0] = x;
+        input[1] = y;
+        input[2] = s;
+        (uint256[] memory product, bool success) = callPrecompile(address(0x07), input, 2);
+        if (!success) {
+            return false;
+        }
+
+        input = new uint256[](4);
+        input[0] = product[0];
+        input[1] = product[1];
+        input[2] = acc[0];
+        input[3] = acc[1];
+        uint256[] memory sum;
+        (sum, success) = callPrecompile(address(0x06), input, 2);
+        if (!success) {
+            return false;
+        }
+        acc[0] = sum[0];
+        acc[1] = sum[1];
+        return true;
+    }
+
+    function verifyProof(Seal memory seal, uint256 claim0, uint256 claim1) internal view returns (bool) {
+        uint256[2] memory vk = [CONTROL_IC_X, CONTROL_IC_Y];
+        if (!mulAccumulate(vk, IC3_X, IC3_Y, claim0) || !mulAccumulate(vk, IC4_X, IC4_Y, claim1)) {
+            return false;
+        }
+
+        uint256[] memory input = new uint256[](24);
+
+        // -A
+        input[0] = seal.a[0];
+        input[1] = negate(seal.a[1]);
+
+        // B
+        input[2] = seal.b[0][0];
+        input[3] = seal.b[0][1];
+        input[4] = seal.b[1][0];
+        input[5] = seal.b[1][1];
+
+        // alpha
+        input[6] = ALPHA_X;
+        input[7] = ALPHA_Y;
+
+        // beta
+        input[8] = BETA_X1;
+        input[9] = BETA_X2;
+        input[10] = BETA_Y1;
+        input[11] = BETA_Y2;
+
+        // vk_x
+        input[12] = vk[0];
+        input[13] = vk[1];
+
+        // gamma
+        input[14] = GAMMA_X1;
+        input[15] = GAMMA_X2;
+        input[16] = GAMMA_Y1;
+        input[17] = GAMMA_Y2;
+
+        // C
+        input[18] = seal.c[0];
+        input[19] = seal.c[1];
+
+        // delta
+        input[20] = DELTA_X1;
+        input[21] = DELTA_X2;
+        input[22] = DELTA_Y1;
+        input[23] = DELTA_Y2;
+
+        (uint256[] memory out, bool success) = callPrecompile(address(0x08), input, 1);
+        return success && out[0] == 1;
+    }
+}
@@ -17,6 +17,7 @@
 pragma solidity ^0.8.17;
 
 import {IBonsaiRelay, Callback, CallbackAuthorization} from "./IBonsaiRelay.sol";
+import {CallbackNonces} from "./CallbackNonces.sol";
 import {IRiscZeroVerifier} from "../IRiscZeroVerifier.sol";
 
 /// @notice Bonsai Relay contract supporting authenticated communication from zkVM guest programs.
@@ -34,9 +35,10 @@ contract BonsaiRelay is IBonsaiRelay {
         address callbackContract,
         bytes4 functionSelector,
         uint64 gasLimit
-    ) external {
+    ) external returns (uint256 nonce) {
+        nonce = CallbackNonces.request(imageId, input, callbackContract, functionSelector, gasLimit);
         // Emit event
-        emit CallbackRequest(msg.sender, imageId, input, callbackContract, functionSelector, gasLimit);
+        emit CallbackRequest(msg.sender, imageId, input, callbackContract, functionSelector, gasLimit, nonce);
     }
 
     function parsePayload(bytes calldata payload) public pure returns (bytes32, bytes calldata) {
@@ -54,6 +56,11 @@ contract BonsaiRelay is IBonsaiRelay {
         return verifier.verify(auth.seal, imageId, auth.postStateDigest, sha256(journal));
     }
 
+    /// @inheritdoc IBonsaiRelay
+    function callbackIsPending(uint256 nonce) external view returns (bool) {
+        return CallbackNonces.isPending(nonce);
+    }
+
     /// @inheritdoc IBonsaiRelay
     function invokeCallbacks(Callback[] calldata callbacks) external returns (bool[] memory invocationResults) {
         invocationResults = new bool[](callbacks.length);
@@ -66,9 +73,16 @@ contract BonsaiRelay is IBonsaiRelay {
                 callbackIsAuthorized(imageId, journal, callback.auth),
                 "BonsaiRelay: callback authorization check failed"
             );
+            require(
+                CallbackNonces.consume(callback, imageId, sha256(journal)),
+                "BonsaiRelay: callback does not answer a pending request"
+            );
 
             // invoke callback
             (invocationResults[i],) = callback.callbackContract.call{gas: callback.gasLimit}(callback.payload);
+            if (!invocationResults[i]) {
+                CallbackNonces.restore(callback, imageId);
+            }
         }
     }
 
@@ -80,6 +94,11 @@ contract BonsaiRelay is IBonsaiRelay {
             callbackIsAuthorized(imageId, journal, callback.auth), "BonsaiRelay: callback authorization check failed"
         );
 
+        require(
+            CallbackNonces.consume(callback, imageId, sha256(journal)),
+            "BonsaiRelay: callback does not answer a pending request"
+        );
+
         // invoke callback
         (bool success, bytes memory data) = callback.callbackContract.call{gas: callback.gasLimit}(callback.payload);
         if (!success) {
@@ -20,13 +20,14 @@ import {Test} from "forge-std/Test.sol";
 import {Proxy} from "openzeppelin/contracts/proxy/Proxy.sol";
 
 import {IBonsaiRelay, Callback, CallbackAuthorization} from "./IBonsaiRelay.sol";
+import {CallbackNonces} from "./CallbackNonces.sol";
 
 /// @notice A wrapper for the Bonsai Relay to use in testing for access to the requested callbacks
 ///     without the need to parse logs.
 contract BonsaiRelayQueueWrapper is IBonsaiRelay, Proxy, Test {
     // An array of byte arrays storing the queue of callback requests received.
-    // NOTE: Both test and fully-verifying versions of the BonsaiRelay and stateless. If they are
-    // ever modified to store state, then these storage variables may be an issue.
+    // NOTE: The state of the wrapped relay is kept in the dedicated storage slot of the
+    // CallbackNonces library, which does not overlap with these storage variables.
     bytes[] private cbrQueue;
     uint256 private cbrIdx;
 
@@ -45,21 +46,35 @@ contract BonsaiRelayQueueWrapper is IBonsaiRelay, Proxy, Test {
         return cbrQueue[cbrIdx++];
     }
 
+    /// @notice Remove the most recent callback request from the queue, for a request that is
+    ///     answered directly rather than through `dequeueCbrEventData`.
+    function discardLastCbrEventData() public {
+        require(cbrIdx < cbrQueue.length);
+        cbrQueue.pop();
+    }
+
     function requestCallback(
         bytes32 imageId,
         bytes calldata input,
         address callbackContract,
         bytes4 functionSelector,
         uint64 gasLimit
-    ) external {
+    ) external returns (uint256 nonce) {
+        // Call the wrapped contract, using a delegate call so that any events come from this, and
+        // the nonce is assigned in the storage of this contract.
+        (bool success, bytes memory data) = address(wrapped).delegatecall(msg.data);
+        if (!success) {
+            assembly {
+                revert(add(data, 32), mload(data))
+            }
+        }
+        nonce = abi.decode(data, (uint256));
+
         // Permanently store the callback request in storage to avoid interference from vm.getRecordedLogs calls
         vm.pauseGasMetering();
-        bytes memory cbrData = abi.encode(imageId, input, callbackContract, functionSelector, gasLimit);
+        bytes memory cbrData = abi.encode(imageId, input, callbackContract, functionSelector, gasLimit, nonce);
         cbrQueue.push(cbrData);
         vm.resumeGasMetering();
-
-        // Call the wrapped contract, using a delegate call so that any events come from this.
-        _delegate(address(wrapped));
     }
 
     // IBonsaiRelay method implementations to satisfy interface.
@@ -73,6 +88,11 @@ contract BonsaiRelayQueueWrapper is IBonsaiRelay, Proxy, Test {
         return wrapped.callbackIsAuthorized(imageId, journal, auth);
     }
 
+    /// @inheritdoc IBonsaiRelay
+    function callbackIsPending(uint256 nonce) external view returns (bool) {
+        return CallbackNonces.isPending(nonce);
+    }
+
     /// @inheritdoc IBonsaiRelay
     function invokeCallbacks(Callback[] calldata) external returns (bool[] memory) {
         _delegate(address(wrapped));
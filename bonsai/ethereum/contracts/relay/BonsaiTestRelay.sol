@@ -17,6 +17,7 @@
 pragma solidity ^0.8.17;
 
 import {IBonsaiRelay, Callback, CallbackAuthorization} from "./IBonsaiRelay.sol";
+import {CallbackNonces} from "./CallbackNonces.sol";
 
 /// @notice A mock Bonsai relay for local testing
 contract BonsaiTestRelay is IBonsaiRelay {
@@ -38,8 +39,9 @@ contract BonsaiTestRelay is IBonsaiRelay {
         address callbackContract,
         bytes4 functionSelector,
         uint64 gasLimit
-    ) external {
-        emit CallbackRequest(msg.sender, imageId, input, callbackContract, functionSelector, gasLimit);
+    ) external returns (uint256 nonce) {
+        nonce = CallbackNonces.request(imageId, input, callbackContract, functionSelector, gasLimit);
+        emit CallbackRequest(msg.sender, imageId, input, callbackContract, functionSelector, gasLimit, nonce);
     }
 
     /// @inheritdoc IBonsaiRelay
@@ -59,6 +61,11 @@ contract BonsaiTestRelay is IBonsaiRelay {
         return (imageId, journal);
     }
 
+    /// @inheritdoc IBonsaiRelay
+    function callbackIsPending(uint256 nonce) external view returns (bool) {
+        return CallbackNonces.isPending(nonce);
+    }
+
     /// @inheritdoc IBonsaiRelay
     function invokeCallbacks(Callback[] calldata callbacks) external returns (bool[] memory invocationResults) {
         invocationResults = new bool[](callbacks.length);
@@ -71,9 +78,16 @@ contract BonsaiTestRelay is IBonsaiRelay {
                 callbackIsAuthorized(imageId, journal, callback.auth),
                 "BonsaiTestRelay: callback authorization check failed"
             );
+            require(
+                CallbackNonces.consume(callback, imageId, sha256(journal)),
+                "BonsaiTestRelay: callback does not answer a pending request"
+            );
 
             // invoke callback
             (invocationResults[i],) = callback.callbackContract.call{gas: callback.gasLimit}(callback.payload);
+            if (!invocationResults[i]) {
+                CallbackNonces.restore(callback, imageId);
+            }
         }
     }
 
@@ -86,6 +100,11 @@ contract BonsaiTestRelay is IBonsaiRelay {
             "BonsaiTestRelay: callback authorization check failed"
         );
 
+        require(
+            CallbackNonces.consume(callback, imageId, sha256(journal)),
+            "BonsaiTestRelay: callback does not answer a pending request"
+        );
+
         // invoke callback
         (bool success, bytes memory data) = callback.callbackContract.call{gas: callback.gasLimit}(callback.payload);
         if (!success) {
@@ -0,0 +1,110 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.17;
+
+import {Callback} from "./IBonsaiRelay.sol";
+
+/// @notice Replay protection for the callbacks delivered through a relay.
+/// @dev Each callback request is assigned a nonce on chain, starting from one, and the relay records
+/// a digest of the image ID, input digest, callback contract, function selector and gas limit of the
+/// request until a callback carrying that nonce is delivered. A callback is only delivered if it
+/// answers a pending request, so a delivered proof cannot be replayed into a second invocation, and
+/// a callback with a nonce of zero is always rejected.
+///
+/// Once delivered, the request digest is recorded along with the digest of the journal that
+/// answered it.
+///
+/// The state is kept in a dedicated storage slot, so that the relay can be called through a
+/// delegating proxy, such as the `BonsaiRelayQueueWrapper`, without clashing with its storage.
+library CallbackNonces {
+    /// @notice Storage slot of the `Layout`.
+    bytes32 internal constant STORAGE_SLOT = keccak256("risc0.BonsaiRelay.CallbackNonces");
+
+    struct Layout {
+        /// @notice Last nonce assigned to a callback request.
+        uint256 lastNonce;
+        /// @notice Digest of each pending callback request, by nonce.
+        mapping(uint256 => bytes32) pending;
+        /// @notice Digest of each delivered callback request and of its journal, by nonce.
+        mapping(uint256 => bytes32) delivered;
+    }
+
+    function layout() internal pure returns (Layout storage l) {
+        bytes32 slot = STORAGE_SLOT;
+        assembly {
+            l.slot := slot
+        }
+    }
+
+    function requestDigest(
+        bytes32 imageId,
+        bytes32 inputDigest,
+        address callbackContract,
+        bytes4 functionSelector,
+        uint64 gasLimit
+    ) internal pure returns (bytes32) {
+        return keccak256(abi.encode(imageId, inputDigest, callbackContract, functionSelector, gasLimit));
+    }
+
+    function requestDigest(Callback calldata callback, bytes32 imageId) internal pure returns (bytes32) {
+        return requestDigest(
+            imageId, callback.inputDigest, callback.callbackContract, bytes4(callback.payload[:4]), callback.gasLimit
+        );
+    }
+
+    /// @notice Assign a nonce to a new callback request, and record it as pending.
+    function request(
+        bytes32 imageId,
+        bytes calldata input,
+        address callbackContract,
+        bytes4 functionSelector,
+        uint64 gasLimit
+    ) internal returns (uint256 nonce) {
+        Layout storage l = layout();
+        nonce = ++l.lastNonce;
+        l.pending[nonce] = requestDigest(imageId, sha256(input), callbackContract, functionSelector, gasLimit);
+    }
+
+    /// @notice Returns true if the callback request with the given nonce is waiting for delivery.
+    function isPending(uint256 nonce) internal view returns (bool) {
+        return layout().pending[nonce] != bytes32(0);
+    }
+
+    /// @notice Mark the callback as delivered.
+    /// @return false if the callback does not answer a pending request.
+    function consume(Callback calldata callback, bytes32 imageId, bytes32 journalDigest) internal returns (bool) {
+        if (callback.nonce == 0) {
+            return false;
+        }
+        Layout storage l = layout();
+        bytes32 digest = requestDigest(callback, imageId);
+        if (l.pending[callback.nonce] != digest) {
+            return false;
+        }
+        delete l.pending[callback.nonce];
+        l.delivered[callback.nonce] = keccak256(abi.encode(digest, journalDigest));
+        return true;
+    }
+
+    /// @notice Revert the effects of `consume`, allowing the callback to be delivered again.
+    /// @dev Used when a callback of a batch fails without reverting the batch.
+    function restore(Callback calldata callback, bytes32 imageId) internal {
+        Layout storage l = layout();
+        l.pending[callback.nonce] = requestDigest(callback, imageId);
+        delete l.delivered[callback.nonce];
+    }
+}
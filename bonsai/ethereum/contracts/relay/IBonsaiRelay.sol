@@ -38,6 +38,11 @@ struct Callback {
     bytes payload;
     /// @notice maximum amount of gas the callback function may use.
     uint64 gasLimit;
+    /// @notice nonce assigned on chain to the callback request being answered, as emitted in
+    ///     `CallbackRequest`. Nonces start from one, and a callback with a nonce of zero is rejected.
+    uint256 nonce;
+    /// @notice SHA-256 digest of the input of the callback request being answered.
+    bytes32 inputDigest;
 }
 
 /// @notice The interface for the Bonsai relay contract
@@ -49,19 +54,22 @@ interface IBonsaiRelay {
         bytes input,
         address callbackContract,
         bytes4 functionSelector,
-        uint64 gasLimit
+        uint64 gasLimit,
+        uint256 nonce
     );
 
     /// @notice Submit request to receive a callback.
     /// @dev This function will usually be called be the Bonsai user's application contract, and
-    ///     will log an event that the Bonsai Relay will detect and respond to.
+    ///     will log an event that the Bonsai Relay will detect and respond to. The request is
+    ///     assigned a nonce, which must be carried by the callback answering it.
+    /// @return nonce the nonce assigned to the request.
     function requestCallback(
         bytes32 imageId,
         bytes calldata input,
         address callbackContract,
         bytes4 functionSelector,
         uint64 gasLimit
-    ) external;
+    ) external returns (uint256 nonce);
 
     /// @notice Determines if the given authorization is valid for the image ID and journal.
     /// @dev A (imageId, journal) pair should be valid, and the respective callback authorized, if
@@ -71,13 +79,19 @@ interface IBonsaiRelay {
         view
         returns (bool);
 
+    /// @notice Returns true if the callback request with the given nonce has not been answered yet.
+    function callbackIsPending(uint256 nonce) external view returns (bool);
+
     /// @notice Submit a batch of callbacks, authorized by an attached SNARK proof.
     /// @dev This function is usually called by the Bonsai Relay. Note that this function does not
-    ///     revert when one of the inner callbacks reverts.
+    ///     revert when one of the inner callbacks reverts. Each callback must answer a pending
+    ///     request, and can be delivered at most once, a callback whose invocation failed can be
+    ///     submitted again.
     /// @return invocationResults a list of booleans indicated if the calldata succeeded or failed.
     function invokeCallbacks(Callback[] calldata callbacks) external returns (bool[] memory invocationResults);
 
     /// @notice Submit a single callback, authorized by an attached SNARK proof.
-    /// @dev This function is usually called by the Bonsai Relay. This function reverts if the callback fails.
+    /// @dev This function is usually called by the Bonsai Relay. This function reverts if the callback fails,
+    ///     or if it does not answer a pending request.
     function invokeCallback(Callback calldata callback) external;
 }
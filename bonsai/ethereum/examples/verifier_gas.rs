@@ -0,0 +1,105 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reports the gas spent by `RiscZeroGroth16Verifier` to verify the archived
+//! Groth16 test vectors.
+//!
+//! Each vector is verified by a fresh verifier deployed to a local Anvil node
+//! with the control IDs of the vector. Run with:
+//!
+//! ```bash
+//! cargo run --example verifier_gas -- [VECTORS_DIR]
+//! ```
+//!
+//! `VECTORS_DIR` defaults to the vectors generated by
+//! `cargo xtask gen-groth16-vectors`.
+
+use std::{fs, path::PathBuf, sync::Arc};
+
+use bonsai_ethereum_contracts::RiscZeroGroth16Verifier;
+use ethers::{prelude::*, utils::Anvil};
+use sha2::{Digest as _, Sha256};
+
+const DEFAULT_VECTORS_DIR: &str = "../../risc0/groth16/tests/vectors";
+
+fn decode_hex(vector: &serde_json::Value, field: &str) -> Vec<u8> {
+    let value = vector[field]
+        .as_str()
+        .unwrap_or_else(|| panic!("missing field `{field}`"));
+    // Control IDs are written as `0x`-prefixed field elements.
+    hex::decode(value.trim_start_matches("0x")).unwrap()
+}
+
+fn decode_u256(vector: &serde_json::Value, field: &str) -> U256 {
+    U256::from_big_endian(&decode_hex(vector, field))
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let vectors_dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_VECTORS_DIR));
+    let mut paths: Vec<_> = fs::read_dir(&vectors_dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", vectors_dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        panic!(
+            "no test vectors found in {}, generate one with `cargo xtask gen-groth16-vectors`",
+            vectors_dir.display()
+        );
+    }
+
+    let anvil = Anvil::new().spawn();
+    let wallet: LocalWallet = anvil.keys()[0].clone().into();
+    let provider = Provider::<Http>::try_from(anvil.endpoint()).unwrap();
+    let client = Arc::new(SignerMiddleware::new(
+        provider,
+        wallet.with_chain_id(anvil.chain_id()),
+    ));
+
+    println!("| vector | deployment gas | verify gas | verified |");
+    println!("|--------|----------------|------------|----------|");
+    for path in paths {
+        let vector: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let control_ids = (
+            decode_u256(&vector, "control_id_0"),
+            decode_u256(&vector, "control_id_1"),
+        );
+
+        let (verifier, deployment) = RiscZeroGroth16Verifier::deploy(client.clone(), control_ids)
+            .unwrap()
+            .send_with_receipt()
+            .await
+            .unwrap();
+        let deployment_gas = deployment.gas_used.unwrap_or_default();
+
+        let seal = Bytes::from(decode_hex(&vector, "seal"));
+        let image_id: [u8; 32] = decode_hex(&vector, "image_id").try_into().unwrap();
+        let post_digest: [u8; 32] = decode_hex(&vector, "post_digest").try_into().unwrap();
+        let journal_digest: [u8; 32] = Sha256::digest(decode_hex(&vector, "journal")).into();
+
+        let call = verifier.verify(seal, image_id, post_digest, journal_digest);
+        let verified = call.call().await.unwrap();
+        let verify_gas = call.estimate_gas().await.unwrap();
+
+        println!(
+            "| {} | {deployment_gas} | {verify_gas} | {verified} |",
+            path.file_stem().unwrap().to_string_lossy()
+        );
+    }
+}
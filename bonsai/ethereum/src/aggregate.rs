@@ -0,0 +1,137 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding helpers for aggregated receipts verified by the
+//! `RiscZeroAggregateVerifier` contract.
+//!
+//! An aggregated receipt commits to the root of a Merkle tree over the digests
+//! of the claims it covers. Inner nodes are computed as
+//! `sha256(min(a, b) || max(a, b))`, and a node without a sibling is promoted
+//! to the next level unchanged.
+//!
+//! The `aggregate` guest of `risc0-zkvm-methods` is an aggregation guest
+//! producing such receipts: it verifies the claims it is given and commits
+//! their Merkle root as its journal.
+
+use sha2::{Digest as _, Sha256};
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a < b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => hash_pair(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Compute the Merkle root over the given claim digests.
+///
+/// Returns `None` if `leaves` is empty.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied()
+}
+
+/// Compute the inclusion proof of the claim digest at `index`, as expected by
+/// `RiscZeroAggregateVerifier.verify_integrity`.
+///
+/// Returns `None` if `index` is out of bounds.
+pub fn inclusion_proof(leaves: &[[u8; 32]], mut index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(proof)
+}
+
+/// Check an inclusion proof of `leaf` against `root`.
+pub fn verify_inclusion(root: &[u8; 32], leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof
+        .iter()
+        .fold(*leaf, |node, sibling| hash_pair(&node, sibling))
+        == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm::{
+        sha::{Digest, Digestible},
+        ExecutorEnv, ExecutorImpl, ExitCode, MaybePruned, Output, ReceiptClaim, SystemState,
+    };
+    use risc0_zkvm_methods::AGGREGATE_ELF;
+
+    use super::*;
+
+    fn claim(image_id: Digest, journal: &[u8]) -> ReceiptClaim {
+        ReceiptClaim {
+            pre: MaybePruned::Pruned(image_id),
+            post: MaybePruned::Value(SystemState {
+                pc: 0,
+                merkle_root: Digest::ZERO,
+            }),
+            exit_code: ExitCode::Halted(0),
+            input: Digest::ZERO,
+            output: Some(Output {
+                journal: MaybePruned::Value(journal.to_vec()),
+                assumptions: MaybePruned::Value(Default::default()),
+            })
+            .into(),
+        }
+    }
+
+    #[test]
+    fn aggregation_guest() {
+        let claims: Vec<_> = (0..3u32)
+            .map(|i| claim(Digest::from([i; 8]), &i.to_le_bytes()))
+            .collect();
+        let mut env = ExecutorEnv::builder();
+        env.write(&claims).unwrap();
+        for claim in &claims {
+            env.add_assumption(claim.clone());
+        }
+        let session = ExecutorImpl::from_elf(env.build().unwrap(), AGGREGATE_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+
+        let leaves: Vec<[u8; 32]> = claims.iter().map(|claim| claim.digest().into()).collect();
+        let root = merkle_root(&leaves).unwrap();
+        assert_eq!(session.journal.unwrap().bytes, root);
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = inclusion_proof(&leaves, index).unwrap();
+            assert!(verify_inclusion(&root, leaf, &proof));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for journals carried in EIP-4844 blobs, verified with the
+//! `BlobJournal` Solidity library.
+//!
+//! The encoding of the blob and the journal committed by the guest are
+//! defined in the `no_std` [bonsai_ethereum_blob] crate, for use in guests,
+//! and re-exported here. With the `kzg` feature, [build_blob] also computes
+//! the KZG commitment and proof needed to verify the journal on chain.
+
+pub use bonsai_ethereum_blob::*;
+
+/// Blob carrying a journal, along with the values needed to verify it on chain.
+#[cfg(feature = "kzg")]
+#[derive(Clone, Debug)]
+pub struct BlobSidecar {
+    /// The blob, as built by [encode_blob].
+    pub blob: Vec<u8>,
+    /// KZG commitment of the blob.
+    pub commitment: [u8; 48],
+    /// KZG proof of the evaluation committed in `journal`.
+    pub proof: [u8; 48],
+    /// Journal the guest is expected to commit for this blob.
+    pub journal: BlobJournal,
+}
+
+/// Encode `data` into a blob and compute the KZG commitment and evaluation
+/// proof expected by the `BlobJournal` Solidity library.
+#[cfg(feature = "kzg")]
+pub fn build_blob(data: &[u8], settings: &c_kzg::KzgSettings) -> Result<BlobSidecar, c_kzg::Error> {
+    use c_kzg::{Blob, Bytes32, KzgCommitment, KzgProof};
+
+    let encoded = encode_blob(data).ok_or_else(|| {
+        c_kzg::Error::InvalidBytesLength(format!("data is longer than {MAX_DATA_LEN} bytes"))
+    })?;
+    let blob = Blob::from_bytes(&encoded)?;
+    let commitment: [u8; 48] = *KzgCommitment::blob_to_kzg_commitment(&blob, settings)?.to_bytes();
+    let z = evaluation_point(&commitment, &encoded);
+    let (proof, y) = KzgProof::compute_kzg_proof(&blob, &Bytes32::new(z), settings)?;
+    Ok(BlobSidecar {
+        blob: encoded,
+        commitment,
+        proof: *proof.to_bytes(),
+        journal: BlobJournal {
+            versioned_hash: versioned_hash(&commitment),
+            z,
+            y: *y,
+        },
+    })
+}
@@ -0,0 +1,248 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding of seals for the L2-optimized Groth16 verifiers.
+//!
+//! On L2 chains, the calldata posted to L1 dominates the cost of verifying a
+//! receipt. The `RiscZeroGroth16VerifierOptimism` and
+//! `RiscZeroGroth16VerifierArbitrum` contracts take seals with compressed
+//! points, which they decompress on chain:
+//! - G1 points are encoded as `x << 1 | negate` in 32 bytes.
+//! - G2 points are encoded as `(x.c0 << 2 | hint << 1 | negate, x.c1)` in 64
+//!   bytes, where `hint` selects the square root of the norm used to compute
+//!   the square root in Fp2.
+
+use ethers::types::{U256, U512};
+
+/// Chain family targeted by an L2-optimized verifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L2Variant {
+    /// `RiscZeroGroth16VerifierOptimism`, compressing all the points.
+    Optimism,
+    /// `RiscZeroGroth16VerifierArbitrum`, leaving B uncompressed.
+    Arbitrum,
+}
+
+impl L2Variant {
+    /// Length of the seals accepted by the verifier.
+    pub fn seal_length(&self) -> usize {
+        match self {
+            L2Variant::Optimism => 128,
+            L2Variant::Arbitrum => 192,
+        }
+    }
+
+    fn compress_g2(&self) -> bool {
+        matches!(self, L2Variant::Optimism)
+    }
+}
+
+/// Length of the seals produced by Bonsai, the ABI encoding of the A, B and C
+/// points.
+pub const SEAL_LENGTH: usize = 256;
+
+// Modulus of the BN254 base field.
+const P: U256 = U256([
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+/// Encode a Groth16 seal, as produced by Bonsai, for the given verifier.
+///
+/// Returns `None` if the seal is malformed, or if its points are not on the
+/// curve.
+pub fn encode_seal(seal: &[u8], variant: L2Variant) -> Option<Vec<u8>> {
+    if seal.len() != SEAL_LENGTH {
+        return None;
+    }
+    let words: Vec<U256> = seal.chunks(32).map(U256::from_big_endian).collect();
+    if words.iter().any(|word| *word >= P) {
+        return None;
+    }
+
+    let a = compress_g1(words[0], words[1])?;
+    // B is encoded in the precompile order, with the imaginary part first.
+    let (bx1, bx0, by1, by0) = (words[2], words[3], words[4], words[5]);
+    let c = compress_g1(words[6], words[7])?;
+
+    let mut encoded = vec![a];
+    if variant.compress_g2() {
+        let (b0, b1) = compress_g2(bx0, bx1, by0, by1)?;
+        encoded.extend([b0, b1]);
+    } else {
+        g2_check(bx0, bx1, by0, by1)?;
+        encoded.extend([bx1, bx0, by1, by0]);
+    }
+    encoded.push(c);
+
+    let mut bytes = vec![0u8; 32 * encoded.len()];
+    for (word, chunk) in encoded.iter().zip(bytes.chunks_mut(32)) {
+        word.to_big_endian(chunk);
+    }
+    Some(bytes)
+}
+
+fn compress_g1(x: U256, y: U256) -> Option<U256> {
+    let y_pos = sqrt(add(mul(mul(x, x), x), U256::from(3)))?;
+    if y == y_pos {
+        Some(x << 1)
+    } else if y == neg(y_pos) {
+        Some(x << 1 | U256::one())
+    } else {
+        None
+    }
+}
+
+fn compress_g2(x0: U256, x1: U256, y0: U256, y1: U256) -> Option<(U256, U256)> {
+    let (a0, a1) = g2_rhs(x0, x1);
+    let d = sqrt(add(mul(a0, a0), mul(a1, a1)))?;
+    let hint = !is_square(mul(add(a0, d), half()));
+    let (y0_pos, y1_pos) = sqrt_fp2(a0, a1, hint)?;
+    let hint_bit = if hint { U256::from(2) } else { U256::zero() };
+    if (y0, y1) == (y0_pos, y1_pos) {
+        Some((x0 << 2 | hint_bit, x1))
+    } else if (y0, y1) == (neg(y0_pos), neg(y1_pos)) {
+        Some((x0 << 2 | hint_bit | U256::one(), x1))
+    } else {
+        None
+    }
+}
+
+// Checks that the point is on the curve, so that the verifier does not reject
+// a seal that could have been detected as invalid off chain.
+fn g2_check(x0: U256, x1: U256, y0: U256, y1: U256) -> Option<()> {
+    let (a0, a1) = g2_rhs(x0, x1);
+    let y_sq0 = sub(mul(y0, y0), mul(y1, y1));
+    let y_sq1 = mul(U256::from(2), mul(y0, y1));
+    ((y_sq0, y_sq1) == (a0, a1)).then_some(())
+}
+
+// Computes x^3 + 3 / (9 + i) in Fp2, the right hand side of the twist curve
+// equation.
+fn g2_rhs(x0: U256, x1: U256) -> (U256, U256) {
+    let x0_sq = mul(x0, x0);
+    let x1_sq = mul(x1, x1);
+    let y0 = sub(mul(x0_sq, x0), mul(U256::from(3), mul(x0, x1_sq)));
+    let y1 = sub(mul(U256::from(3), mul(x0_sq, x1)), mul(x1_sq, x1));
+    let b0 = mul(U256::from(27), inv(U256::from(82)));
+    let b1 = neg(mul(U256::from(3), inv(U256::from(82))));
+    (add(y0, b0), add(y1, b1))
+}
+
+fn sqrt_fp2(a0: U256, a1: U256, hint: bool) -> Option<(U256, U256)> {
+    let mut d = sqrt(add(mul(a0, a0), mul(a1, a1)))?;
+    if hint {
+        d = neg(d);
+    }
+    let x0 = sqrt(mul(add(a0, d), half()))?;
+    let x1 = mul(a1, inv(mul(U256::from(2), x0)));
+    let check0 = sub(mul(x0, x0), mul(x1, x1));
+    let check1 = mul(U256::from(2), mul(x0, x1));
+    ((check0, check1) == (a0, a1)).then_some((x0, x1))
+}
+
+fn add(a: U256, b: U256) -> U256 {
+    // Both operands are below 2^254, so the sum cannot overflow.
+    (a + b) % P
+}
+
+fn sub(a: U256, b: U256) -> U256 {
+    add(a, neg(b))
+}
+
+fn neg(a: U256) -> U256 {
+    (P - a) % P
+}
+
+fn mul(a: U256, b: U256) -> U256 {
+    let product = a.full_mul(b) % U512::from(P);
+    U256::try_from(product).expect("reduced value fits in 256 bits")
+}
+
+fn pow(base: U256, exp: U256) -> U256 {
+    let mut result = U256::one();
+    for i in (0..exp.bits()).rev() {
+        result = mul(result, result);
+        if exp.bit(i) {
+            result = mul(result, base);
+        }
+    }
+    result
+}
+
+fn inv(a: U256) -> U256 {
+    pow(a, P - 2)
+}
+
+fn half() -> U256 {
+    inv(U256::from(2))
+}
+
+fn is_square(a: U256) -> bool {
+    pow(a, (P - 1) >> 1) <= U256::one()
+}
+
+fn sqrt(a: U256) -> Option<U256> {
+    let x = pow(a, (P + 1) >> 2);
+    (mul(x, x) == a).then_some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Seal of the receipt in `test/TestReceipt.sol`.
+    const SEAL: &str = "102866064c0a93f24ab85854cbb1f2904f3e345bdd567e1f0ef36e819c5441aa";
+
+    fn test_seal() -> Vec<u8> {
+        let source = include_str!("../test/TestReceipt.sol");
+        let start = source
+            .find(SEAL)
+            .expect("seal not found in TestReceipt.sol");
+        let end = start + source[start..].find('"').unwrap();
+        hex::decode(&source[start..end]).unwrap()
+    }
+
+    #[test]
+    fn optimism() {
+        let encoded = encode_seal(&test_seal(), L2Variant::Optimism).unwrap();
+        assert_eq!(encoded.len(), L2Variant::Optimism.seal_length());
+        assert_eq!(
+            hex::encode(encoded),
+            "2050cc0c981527e49570b0a99763e5209e7c68b7baacfc3e1de6dd0338a88354\
+             34dfb9acfbbee336661584b207eefbcf9b896a8bcbaf0d708fe20b781fe94426\
+             1b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae046\
+             2d8fcc65ad1f03b94001be20a4e75a8b4456074d2ea59973b1dc50f02cdfe835"
+        );
+    }
+
+    #[test]
+    fn arbitrum() {
+        let seal = test_seal();
+        let encoded = encode_seal(&seal, L2Variant::Arbitrum).unwrap();
+        assert_eq!(encoded.len(), L2Variant::Arbitrum.seal_length());
+        assert_eq!(encoded[32..160], seal[64..192]);
+    }
+
+    #[test]
+    fn rejects_invalid_seals() {
+        let mut seal = test_seal();
+        assert_eq!(encode_seal(&seal[..192], L2Variant::Optimism), None);
+        seal[31] ^= 1;
+        assert_eq!(encode_seal(&seal, L2Variant::Optimism), None);
+        assert_eq!(encode_seal(&seal, L2Variant::Arbitrum), None);
+    }
+}
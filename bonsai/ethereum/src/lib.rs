@@ -18,6 +18,16 @@
 
 use ethers::prelude::*;
 
+pub mod aggregate;
+pub mod blob;
+pub mod l2;
+pub mod mock;
+#[cfg(feature = "receipt")]
+pub mod receipt;
+pub mod registry;
+pub mod relay;
+pub mod router;
+
 abigen!(IBonsaiRelay, "$OUT_DIR/IBonsaiRelay.sol/IBonsaiRelay.json");
 abigen!(BonsaiRelay, "$OUT_DIR/BonsaiRelay.sol/BonsaiRelay.json");
 abigen!(
@@ -29,14 +39,59 @@ abigen!(
     IRiscZeroVerifier,
     "$OUT_DIR/IRiscZeroVerifier.sol/IRiscZeroVerifier.json"
 );
+abigen!(
+    RiscZeroAggregateVerifier,
+    "$OUT_DIR/RiscZeroAggregateVerifier.sol/RiscZeroAggregateVerifier.json"
+);
+abigen!(
+    RiscZeroMockVerifier,
+    "$OUT_DIR/RiscZeroMockVerifier.sol/RiscZeroMockVerifier.json"
+);
+abigen!(
+    ImageIdRegistry,
+    "$OUT_DIR/ImageIdRegistry.sol/ImageIdRegistry.json"
+);
+abigen!(
+    RiscZeroVerifierRouter,
+    "$OUT_DIR/RiscZeroVerifierRouter.sol/RiscZeroVerifierRouter.json"
+);
 abigen!(
     RiscZeroGroth16Verifier,
     "$OUT_DIR/RiscZeroGroth16Verifier.sol/RiscZeroGroth16Verifier.json"
 );
+abigen!(
+    RiscZeroGroth16VerifierOptimism,
+    "$OUT_DIR/RiscZeroGroth16VerifierOptimism.sol/RiscZeroGroth16VerifierOptimism.json"
+);
+abigen!(
+    RiscZeroGroth16VerifierArbitrum,
+    "$OUT_DIR/RiscZeroGroth16VerifierArbitrum.sol/RiscZeroGroth16VerifierArbitrum.json"
+);
+abigen!(
+    RiscZeroGroth16VerifierZkSync,
+    "$OUT_DIR/RiscZeroGroth16VerifierZkSync.sol/RiscZeroGroth16VerifierZkSync.json"
+);
 
 pub mod testutils {
     use ethers::prelude::*;
 
     abigen!(Counter, "$OUT_DIR/Counter.sol/Counter.json");
     abigen!(Proxy, "$OUT_DIR/ProxyTest.sol/Proxy.json");
+
+    /// Reads the constant `name` of a Solidity source, either a `hex"..."`
+    /// literal or a `0x...` number such as `bytes32(0x...)`.
+    ///
+    /// Panics if the constant is not found.
+    pub fn solidity_constant(source: &str, name: &str) -> Vec<u8> {
+        let start = source
+            .find(&format!("{name} ="))
+            .unwrap_or_else(|| panic!("{name} not found"));
+        let value = source[start..].split(';').next().unwrap();
+        let (prefix, suffix) = match value.contains("hex\"") {
+            true => ("hex\"", '"'),
+            false => ("0x", ')'),
+        };
+        let value = &value[value.find(prefix).unwrap() + prefix.len()..];
+        hex::decode(&value[..value.find(suffix).unwrap()]).unwrap()
+    }
 }
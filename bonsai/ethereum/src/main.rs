@@ -16,13 +16,18 @@
 //! structures and procedures for programmatic deployment of and interaction
 //! with a `Proxy` contract instance on Ethereum.
 
+#[cfg(feature = "control-id")]
+use bonsai_ethereum_contracts::router;
+#[cfg(feature = "control-id")]
+use ethers::types::U256;
 #[cfg(feature = "control-id")]
 use risc0_zkvm::sha::Digest;
 #[cfg(feature = "control-id")]
 use risc0_zkvm::ALLOWED_IDS_ROOT;
 
 /// Outputs the Control ID hash for the identity_p254 predicate
-/// decomposed as implemented by splitDigest.
+/// decomposed as implemented by splitDigest, along with the selector of the
+/// matching verifier in the `RiscZeroVerifierRouter`.
 fn main() {
     #[cfg(feature = "control-id")]
     {
@@ -33,6 +38,11 @@ fn main() {
         let (a, b) = split_digest(Digest::from(digest));
         println!("CONTROL_ID_0: 0x{b}");
         println!("CONTROL_ID_1: 0x{a}");
+        let selector = router::selector(
+            U256::from_str_radix(&b, 16).unwrap(),
+            U256::from_str_radix(&a, 16).unwrap(),
+        );
+        println!("ROUTER_SELECTOR: 0x{}", hex::encode(selector));
     }
 }
 
@@ -0,0 +1,81 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dev-mode seals, accepted by the `RiscZeroMockVerifier` contract.
+//!
+//! Receipts produced in dev mode carry no proof, and can not be verified on
+//! chain. To test application contracts end to end without proving, the
+//! `RiscZeroMockVerifier` can be deployed in place of the Groth16 verifier. It
+//! accepts dev-mode seals, made of a fixed tag followed by the digest of the
+//! claim, as produced by [encode_dev_mode_seal]. These seals provide no
+//! security whatsoever.
+
+use sha2::{Digest as _, Sha256};
+
+/// Length of a dev-mode seal, in bytes.
+pub const DEV_MODE_SEAL_LENGTH: usize = 64;
+
+/// Tag prefixing every dev-mode seal, `sha256("risc0.DevModeSeal")`.
+pub fn dev_mode_seal_tag() -> [u8; 32] {
+    Sha256::digest(b"risc0.DevModeSeal").into()
+}
+
+/// Encode the dev-mode seal of the claim with the given digest.
+pub fn encode_dev_mode_seal(claim_digest: [u8; 32]) -> Vec<u8> {
+    [dev_mode_seal_tag(), claim_digest].concat()
+}
+
+/// Decode a dev-mode seal, returning the digest of the claim it attests to.
+///
+/// Returns `None` if `seal` is not a dev-mode seal.
+pub fn decode_dev_mode_seal(seal: &[u8]) -> Option<[u8; 32]> {
+    if seal.len() != DEV_MODE_SEAL_LENGTH || seal[..32] != dev_mode_seal_tag() {
+        return None;
+    }
+    seal[32..].try_into().ok()
+}
+
+/// Encode the dev-mode seal of a receipt produced in dev mode.
+///
+/// Returns `None` if the receipt was not produced in dev mode.
+#[cfg(any(feature = "control-id", feature = "receipt"))]
+pub fn dev_mode_seal(receipt: &risc0_zkvm::Receipt) -> Option<Vec<u8>> {
+    use risc0_zkvm::{sha::Digestible, InnerReceipt};
+
+    match &receipt.inner {
+        InnerReceipt::Fake { claim } => Some(encode_dev_mode_seal(claim.digest().into())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let seal = encode_dev_mode_seal([7u8; 32]);
+        assert_eq!(seal.len(), DEV_MODE_SEAL_LENGTH);
+        assert_eq!(decode_dev_mode_seal(&seal), Some([7u8; 32]));
+    }
+
+    #[test]
+    fn rejects_other_seals() {
+        let mut seal = encode_dev_mode_seal([7u8; 32]);
+        assert_eq!(decode_dev_mode_seal(&seal[..32]), None);
+        seal[0] ^= 1;
+        assert_eq!(decode_dev_mode_seal(&seal), None);
+        assert_eq!(decode_dev_mode_seal(&[0u8; 256]), None);
+    }
+}
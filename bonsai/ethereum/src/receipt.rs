@@ -0,0 +1,405 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ABI encoding of [Receipt]s for the `IRiscZeroVerifier` contracts.
+//!
+//! [VerifyArgs] holds the arguments of `IRiscZeroVerifier.verify`, and can be
+//! built directly from a [Receipt], then encoded as a tuple, as calldata, or
+//! converted into the call of the [IRiscZeroVerifier](crate::IRiscZeroVerifier)
+//! bindings. With the `alloy` feature, the [sol] module provides the typed
+//! alloy counterparts of the Solidity structs.
+//!
+//! The calldata of `verify` for a compact receipt can also be produced without
+//! this crate, by [Receipt::verify_calldata].
+//!
+//! Receipts proven by Bonsai carry a Groth16 seal, which is passed to the
+//! verifier as is. Receipts produced in dev mode are encoded with a dev-mode
+//! seal, accepted by the `RiscZeroMockVerifier`, see [crate::mock].
+
+use ethers::abi::{self, AbiEncode, Token};
+use risc0_zkvm::{
+    sha::{Digest, Digestible},
+    InnerReceipt, Receipt, VerificationError,
+};
+use sha2::{Digest as _, Sha256};
+
+use crate::{i_risc_zero_verifier::VerifyCall, mock::dev_mode_seal};
+
+/// Arguments of `IRiscZeroVerifier.verify` for a [Receipt].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyArgs {
+    /// The seal of the receipt.
+    pub seal: Vec<u8>,
+    /// The image ID of the guest.
+    pub image_id: [u8; 32],
+    /// The digest of the system state after execution.
+    pub post_state_digest: [u8; 32],
+    /// The SHA-256 digest of the journal.
+    pub journal_digest: [u8; 32],
+}
+
+impl VerifyArgs {
+    /// Build the arguments to verify `receipt` as an execution of `image_id`.
+    ///
+    /// Returns [VerificationError::ReceiptFormatError] if the receipt is
+    /// neither a compact receipt nor a dev-mode receipt, since only those can
+    /// be verified on chain.
+    pub fn from_receipt(
+        receipt: &Receipt,
+        image_id: impl Into<Digest>,
+    ) -> Result<Self, VerificationError> {
+        let claim = receipt.get_claim()?;
+        Ok(Self {
+            seal: seal(receipt)?,
+            image_id: image_id.into().into(),
+            post_state_digest: claim.post.digest().into(),
+            journal_digest: Sha256::digest(&receipt.journal.bytes).into(),
+        })
+    }
+
+    /// ABI encoding of the `(seal, imageId, postStateDigest, journalDigest)`
+    /// tuple.
+    pub fn abi_encode(&self) -> Vec<u8> {
+        abi::encode(&[
+            Token::Bytes(self.seal.clone()),
+            Token::FixedBytes(self.image_id.to_vec()),
+            Token::FixedBytes(self.post_state_digest.to_vec()),
+            Token::FixedBytes(self.journal_digest.to_vec()),
+        ])
+    }
+
+    /// Calldata of the call to `IRiscZeroVerifier.verify`.
+    pub fn calldata(&self) -> Vec<u8> {
+        VerifyCall::from(self.clone()).encode()
+    }
+}
+
+impl From<VerifyArgs> for VerifyCall {
+    fn from(args: VerifyArgs) -> Self {
+        VerifyCall {
+            seal: args.seal.into(),
+            image_id: args.image_id,
+            post_state_digest: args.post_state_digest,
+            journal_digest: args.journal_digest,
+        }
+    }
+}
+
+/// Seal of `receipt` as passed to the `IRiscZeroVerifier` contracts.
+///
+/// Returns [VerificationError::ReceiptFormatError] if the receipt is neither a
+/// compact receipt nor a dev-mode receipt.
+pub fn seal(receipt: &Receipt) -> Result<Vec<u8>, VerificationError> {
+    match &receipt.inner {
+        InnerReceipt::Compact(compact) => Ok(compact.seal.clone()),
+        _ => dev_mode_seal(receipt).ok_or(VerificationError::ReceiptFormatError),
+    }
+}
+
+/// Typed alloy counterparts of the structs of `IRiscZeroVerifier.sol`.
+#[cfg(feature = "alloy")]
+pub mod sol {
+    use alloy_primitives::{Bytes, FixedBytes};
+    use risc0_zkvm::{sha::Digestible, ExitCode as ZkvmExitCode, VerificationError};
+
+    use super::VerifyArgs;
+
+    alloy_sol_types::sol! {
+        /// Exit condition indicated by the zkVM at the end of the guest execution.
+        enum SystemExitCode {
+            Halted,
+            Paused,
+            SystemSplit
+        }
+
+        /// Exit code of the guest, made of a system and a user part.
+        struct ExitCode {
+            SystemExitCode system;
+            uint8 user;
+        }
+
+        /// Public claims about a zkVM guest execution.
+        struct ReceiptClaim {
+            bytes32 preStateDigest;
+            bytes32 postStateDigest;
+            ExitCode exitCode;
+            bytes32 input;
+            bytes32 output;
+        }
+
+        /// A receipt attesting to the execution of a guest program.
+        struct Receipt {
+            bytes seal;
+            ReceiptClaim claim;
+        }
+
+        /// Verify that the given seal is a valid proof of execution.
+        function verify(
+            bytes seal,
+            bytes32 imageId,
+            bytes32 postStateDigest,
+            bytes32 journalDigest
+        ) external view returns (bool);
+
+        /// Verify that the given receipt is a valid receipt.
+        function verify_integrity(Receipt receipt) external view returns (bool);
+    }
+
+    impl From<&ZkvmExitCode> for ExitCode {
+        fn from(exit_code: &ZkvmExitCode) -> Self {
+            let (system, user) = match exit_code {
+                ZkvmExitCode::Halted(user) => (SystemExitCode::Halted, *user),
+                ZkvmExitCode::Paused(user) => (SystemExitCode::Paused, *user),
+                ZkvmExitCode::SystemSplit => (SystemExitCode::SystemSplit, 0),
+                ZkvmExitCode::SessionLimit => (SystemExitCode::SystemSplit, 2),
+            };
+            ExitCode {
+                system,
+                user: user as u8,
+            }
+        }
+    }
+
+    impl From<VerifyArgs> for verifyCall {
+        fn from(args: VerifyArgs) -> Self {
+            verifyCall {
+                seal: Bytes::from(args.seal),
+                imageId: FixedBytes(args.image_id),
+                postStateDigest: FixedBytes(args.post_state_digest),
+                journalDigest: FixedBytes(args.journal_digest),
+            }
+        }
+    }
+
+    impl From<&risc0_zkvm::ReceiptClaim> for ReceiptClaim {
+        fn from(claim: &risc0_zkvm::ReceiptClaim) -> Self {
+            ReceiptClaim {
+                preStateDigest: FixedBytes(claim.pre.digest().into()),
+                postStateDigest: FixedBytes(claim.post.digest().into()),
+                exitCode: (&claim.exit_code).into(),
+                input: FixedBytes(claim.input.into()),
+                output: FixedBytes(claim.output.digest().into()),
+            }
+        }
+    }
+
+    impl Receipt {
+        /// Build the Solidity `Receipt` of a compact or dev-mode receipt.
+        pub fn from_receipt(receipt: &super::Receipt) -> Result<Self, VerificationError> {
+            Ok(Receipt {
+                seal: super::seal(receipt)?.into(),
+                claim: (&receipt.get_claim()?).into(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm::{CompactReceipt, ExitCode, MaybePruned, Output, ReceiptClaim, SystemState};
+
+    use super::*;
+    use crate::{mock::encode_dev_mode_seal, testutils::solidity_constant};
+
+    fn claim(image_id: Digest, journal: &[u8]) -> ReceiptClaim {
+        ReceiptClaim {
+            pre: MaybePruned::Pruned(image_id),
+            post: MaybePruned::Value(SystemState {
+                pc: 0,
+                merkle_root: Digest::ZERO,
+            }),
+            exit_code: ExitCode::Halted(0),
+            input: Digest::ZERO,
+            output: Some(Output {
+                journal: MaybePruned::Value(journal.to_vec()),
+                assumptions: MaybePruned::Value(Default::default()),
+            })
+            .into(),
+        }
+    }
+
+    #[test]
+    fn compact_receipt() {
+        let image_id = Digest::from([7u32; 8]);
+        let seal = vec![0xab; 256];
+        let receipt = Receipt::new(
+            InnerReceipt::Compact(CompactReceipt {
+                seal: seal.clone(),
+                claim: claim(image_id, b"journal"),
+            }),
+            b"journal".to_vec(),
+        );
+        let args = VerifyArgs::from_receipt(&receipt, image_id).unwrap();
+        assert_eq!(args.seal, seal);
+        assert_eq!(args.image_id, <[u8; 32]>::from(image_id));
+        assert_eq!(
+            args.journal_digest,
+            <[u8; 32]>::from(Sha256::digest(b"journal"))
+        );
+
+        // The tuple is the calldata of the call, without the function selector.
+        assert_eq!(args.calldata()[4..], args.abi_encode());
+    }
+
+    #[test]
+    fn dev_mode_receipt() {
+        let image_id = Digest::from([7u32; 8]);
+        let claim = claim(image_id, b"journal");
+        let receipt = Receipt::new(
+            InnerReceipt::Fake {
+                claim: claim.clone(),
+            },
+            b"journal".to_vec(),
+        );
+        let args = VerifyArgs::from_receipt(&receipt, image_id).unwrap();
+        assert_eq!(args.seal, encode_dev_mode_seal(claim.digest().into()));
+    }
+
+    // Deploys the `RiscZeroGroth16Verifier` in revm and runs the given calldata against it,
+    // returning whether the receipt was accepted.
+    fn evm_verify(calldata: Vec<u8>) -> bool {
+        use revm::{
+            db::{CacheDB, EmptyDB},
+            primitives::{ExecutionResult, Output as EvmOutput, TransactTo},
+            EVM,
+        };
+
+        let artifact: serde_json::Value = serde_json::from_str(include_str!(concat!(
+            env!("OUT_DIR"),
+            "/RiscZeroGroth16Verifier.sol/RiscZeroGroth16Verifier.json"
+        )))
+        .unwrap();
+        let bytecode = artifact["bytecode"]["object"].as_str().unwrap();
+        let control_ids = include_str!("../contracts/groth16/ControlID.sol");
+        let constructor_args = abi::encode(&[
+            Token::Uint(
+                solidity_constant(control_ids, "CONTROL_ID_0")
+                    .as_slice()
+                    .into(),
+            ),
+            Token::Uint(
+                solidity_constant(control_ids, "CONTROL_ID_1")
+                    .as_slice()
+                    .into(),
+            ),
+        ]);
+
+        let mut evm = EVM::new();
+        evm.database(CacheDB::new(EmptyDB::default()));
+        evm.env.tx.transact_to = TransactTo::create();
+        evm.env.tx.data = [
+            hex::decode(bytecode.trim_start_matches("0x")).unwrap(),
+            constructor_args,
+        ]
+        .concat()
+        .into();
+        let address = match evm.transact_commit().unwrap() {
+            ExecutionResult::Success {
+                output: EvmOutput::Create(_, Some(address)),
+                ..
+            } => address,
+            result => panic!("failed to deploy RiscZeroGroth16Verifier: {result:?}"),
+        };
+
+        evm.env.tx.transact_to = TransactTo::Call(address);
+        evm.env.tx.data = calldata.into();
+        match evm.transact_ref().unwrap().result {
+            ExecutionResult::Success {
+                output: EvmOutput::Call(output),
+                ..
+            } => output.as_ref() == abi::encode(&[Token::Bool(true)]),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn verify_calldata_on_evm() {
+        let source = include_str!("../test/TestReceipt.sol");
+        let image_id = Digest::try_from(&solidity_constant(source, "IMAGE_ID")[..]).unwrap();
+        let journal = solidity_constant(source, "JOURNAL");
+        let claim = ReceiptClaim {
+            pre: MaybePruned::Pruned(image_id),
+            post: MaybePruned::Pruned(
+                Digest::try_from(&solidity_constant(source, "POST_DIGEST")[..]).unwrap(),
+            ),
+            exit_code: ExitCode::Halted(0),
+            input: Digest::ZERO,
+            output: Some(Output {
+                journal: MaybePruned::Value(journal.clone()),
+                assumptions: MaybePruned::Value(Default::default()),
+            })
+            .into(),
+        };
+        let compact = CompactReceipt {
+            seal: solidity_constant(source, "SEAL"),
+            claim,
+        };
+        let receipt = Receipt::new(InnerReceipt::Compact(compact.clone()), journal);
+
+        let calldata = receipt.verify_calldata().unwrap();
+        assert_eq!(calldata, compact.verify_calldata().unwrap());
+        assert_eq!(
+            calldata,
+            VerifyArgs::from_receipt(&receipt, image_id)
+                .unwrap()
+                .calldata()
+        );
+        assert!(evm_verify(calldata.clone()));
+
+        // Mangle the seal, after the selector, head and length of the seal.
+        let mut mangled = calldata;
+        mangled[4 + 5 * 32] ^= 1;
+        assert!(!evm_verify(mangled));
+    }
+
+    #[test]
+    fn verify_calldata_rejects_unsupported_claims() {
+        let image_id = Digest::from([7u32; 8]);
+        let mut compact = CompactReceipt {
+            seal: vec![0xab; 256],
+            claim: claim(image_id, b"journal"),
+        };
+        let receipt = Receipt::new(InnerReceipt::Compact(compact.clone()), b"other".to_vec());
+        assert_eq!(
+            receipt.verify_calldata(),
+            Err(VerificationError::JournalDigestMismatch)
+        );
+
+        compact.claim.exit_code = ExitCode::Halted(1);
+        assert_eq!(
+            compact.verify_calldata(),
+            Err(VerificationError::UnexpectedExitCode)
+        );
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn alloy_matches_ethers() {
+        use alloy_sol_types::SolCall;
+
+        let image_id = Digest::from([7u32; 8]);
+        let receipt = Receipt::new(
+            InnerReceipt::Fake {
+                claim: claim(image_id, b"journal"),
+            },
+            b"journal".to_vec(),
+        );
+        let args = VerifyArgs::from_receipt(&receipt, image_id).unwrap();
+        assert_eq!(
+            sol::verifyCall::from(args.clone()).abi_encode(),
+            args.calldata()
+        );
+        sol::Receipt::from_receipt(&receipt).unwrap();
+    }
+}
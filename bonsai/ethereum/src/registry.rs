@@ -0,0 +1,115 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client for publishing the image IDs of an application in an
+//! `ImageIdRegistry` contract.
+//!
+//! Applications are identified by the keccak256 hash of their name, see
+//! [app_id]. Consumer contracts query `isAllowed(appId, imageId)` rather than
+//! hardcoding the image ID of their guest, so that new guest versions can be
+//! published with [publish_image_id] or [rotate_image_id].
+
+use ethers::{prelude::*, utils::keccak256};
+
+use crate::ImageIdRegistry;
+
+/// Compute the identifier of the application registered under `name`.
+pub fn app_id(name: &str) -> [u8; 32] {
+    keccak256(name.as_bytes())
+}
+
+/// Register the application `name`, with the sender as its admin.
+pub async fn register_application<M: Middleware + 'static>(
+    registry: &ImageIdRegistry<M>,
+    name: &str,
+) -> Result<Option<TransactionReceipt>, ContractError<M>> {
+    registry
+        .register_application(name.to_string())
+        .send()
+        .await?
+        .await
+        .map_err(Into::into)
+}
+
+/// Allow `image_id` for the application `name`.
+///
+/// Must be sent by the admin of the application.
+pub async fn publish_image_id<M: Middleware + 'static>(
+    registry: &ImageIdRegistry<M>,
+    name: &str,
+    image_id: [u8; 32],
+) -> Result<Option<TransactionReceipt>, ContractError<M>> {
+    registry
+        .add_image_id(app_id(name), image_id)
+        .send()
+        .await?
+        .await
+        .map_err(Into::into)
+}
+
+/// Revoke `image_id` for the application `name`.
+///
+/// Must be sent by the admin of the application.
+pub async fn revoke_image_id<M: Middleware + 'static>(
+    registry: &ImageIdRegistry<M>,
+    name: &str,
+    image_id: [u8; 32],
+) -> Result<Option<TransactionReceipt>, ContractError<M>> {
+    registry
+        .remove_image_id(app_id(name), image_id)
+        .send()
+        .await?
+        .await
+        .map_err(Into::into)
+}
+
+/// Replace `old_image_id` with `new_image_id` for the application `name`, in
+/// a single transaction.
+///
+/// Must be sent by the admin of the application.
+pub async fn rotate_image_id<M: Middleware + 'static>(
+    registry: &ImageIdRegistry<M>,
+    name: &str,
+    old_image_id: [u8; 32],
+    new_image_id: [u8; 32],
+) -> Result<Option<TransactionReceipt>, ContractError<M>> {
+    registry
+        .rotate_image_id(app_id(name), old_image_id, new_image_id)
+        .send()
+        .await?
+        .await
+        .map_err(Into::into)
+}
+
+/// Query the image IDs allowed for the application `name`.
+pub async fn allowed_image_ids<M: Middleware + 'static>(
+    registry: &ImageIdRegistry<M>,
+    name: &str,
+) -> Result<Vec<[u8; 32]>, ContractError<M>> {
+    registry.get_image_ids(app_id(name)).call().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_id_is_keccak_of_name() {
+        assert_eq!(
+            hex::encode(app_id("")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_ne!(app_id("app"), app_id("App"));
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for tracking callback requests made through a Bonsai relay.
+//!
+//! Every callback request is assigned a nonce on chain by the relay, starting
+//! from one, and emitted in the `CallbackRequest` event. The callback answering
+//! the request must carry the same nonce and the digest of the request input,
+//! and the relay accepts it only once, so a delivered proof cannot be replayed
+//! into a second callback invocation.
+
+use ethers::{contract::EthLogDecode, prelude::*};
+
+use crate::{i_bonsai_relay::CallbackRequestFilter, IBonsaiRelay};
+
+/// Returns the nonce assigned to the callback request made in the
+/// transaction with the given receipt, if any.
+///
+/// If the transaction made several requests, the nonce of the first one is
+/// returned.
+pub fn callback_request_nonce(receipt: &TransactionReceipt) -> Option<U256> {
+    receipt.logs.iter().find_map(|log| {
+        CallbackRequestFilter::decode_log(&log.clone().into())
+            .ok()
+            .map(|event| event.nonce)
+    })
+}
+
+/// Returns true if the callback request with the given nonce has not been
+/// answered yet.
+pub async fn callback_is_pending<M: Middleware + 'static>(
+    relay: &IBonsaiRelay<M>,
+    nonce: U256,
+) -> Result<bool, ContractError<M>> {
+    relay.callback_is_pending(nonce).call().await
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::abi::{self, Token, Tokenizable};
+
+    use super::*;
+
+    #[test]
+    fn nonce_is_read_from_request_event() {
+        let event = CallbackRequestFilter {
+            account: Address::random(),
+            image_id: [1u8; 32],
+            input: Bytes::from(vec![1, 2, 3]),
+            callback_contract: Address::random(),
+            function_selector: [0xab, 0xcd, 0xef, 0xab],
+            gas_limit: 3000000,
+            nonce: U256::from(42),
+        };
+        let Token::Tuple(tokens) = event.into_token() else {
+            panic!("event should be encoded as a tuple");
+        };
+        let log = Log {
+            topics: vec![CallbackRequestFilter::signature()],
+            data: abi::encode(&tokens).into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![Log::default(), log],
+            ..Default::default()
+        };
+        assert_eq!(callback_request_nonce(&receipt), Some(U256::from(42)));
+        assert_eq!(callback_request_nonce(&TransactionReceipt::default()), None);
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tooling for managing verifier deployments registered in a
+//! `RiscZeroVerifierRouter` contract.
+//!
+//! Each verifier is registered under a 4-byte selector derived from the
+//! control IDs it accepts, see [selector]. Seals submitted to the router must
+//! be prefixed with the selector of the verifier they target, see
+//! [encode_seal].
+
+use ethers::prelude::*;
+use sha2::{Digest as _, Sha256};
+
+use crate::RiscZeroVerifierRouter;
+
+/// Status of a selector in a `RiscZeroVerifierRouter`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifierStatus {
+    /// No verifier has been registered under the selector.
+    Unregistered,
+    /// The verifier is registered and accepts receipts.
+    Active,
+    /// The verifier has been deprecated and rejects all receipts.
+    Deprecated,
+}
+
+impl TryFrom<u8> for VerifierStatus {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Unregistered),
+            1 => Ok(Self::Active),
+            2 => Ok(Self::Deprecated),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Compute the router selector of the verifier accepting the given control
+/// IDs, as output by the `control-id` binary.
+///
+/// The selector is the first four bytes of
+/// `sha256(abi.encodePacked(CONTROL_ID_0, CONTROL_ID_1))`.
+pub fn selector(control_id_0: U256, control_id_1: U256) -> [u8; 4] {
+    let mut buf = [0u8; 32];
+    let mut hasher = Sha256::new();
+    control_id_0.to_big_endian(&mut buf);
+    hasher.update(buf);
+    control_id_1.to_big_endian(&mut buf);
+    hasher.update(buf);
+    hasher.finalize()[..4].try_into().unwrap()
+}
+
+/// Prefix `seal` with `selector` so the router dispatches it to the matching
+/// verifier.
+pub fn encode_seal(selector: [u8; 4], seal: &[u8]) -> Vec<u8> {
+    [&selector[..], seal].concat()
+}
+
+/// Register `verifier` under `selector` in the router.
+///
+/// Must be sent by the owner of the router.
+pub async fn register_verifier<M: Middleware + 'static>(
+    router: &RiscZeroVerifierRouter<M>,
+    selector: [u8; 4],
+    verifier: Address,
+) -> Result<Option<TransactionReceipt>, ContractError<M>> {
+    router
+        .add_verifier(selector, verifier)
+        .send()
+        .await?
+        .await
+        .map_err(Into::into)
+}
+
+/// Deprecate the verifier registered under `selector` in the router.
+///
+/// Must be sent by the owner of the router. Deprecation is permanent: the
+/// selector can not be registered again.
+pub async fn deprecate_verifier<M: Middleware + 'static>(
+    router: &RiscZeroVerifierRouter<M>,
+    selector: [u8; 4],
+) -> Result<Option<TransactionReceipt>, ContractError<M>> {
+    router
+        .deprecate_verifier(selector)
+        .send()
+        .await?
+        .await
+        .map_err(Into::into)
+}
+
+/// Query the verifier registered under `selector` in the router, along with
+/// its status.
+pub async fn query_verifier<M: Middleware + 'static>(
+    router: &RiscZeroVerifierRouter<M>,
+    selector: [u8; 4],
+) -> Result<(Address, VerifierStatus), ContractError<M>> {
+    let (verifier, status) = router.get_verifier(selector).call().await?;
+    let status = VerifierStatus::try_from(status)
+        .map_err(|_| ContractError::DecodingError(ethers::abi::Error::InvalidData))?;
+    Ok((verifier, status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_is_prefixed_with_selector() {
+        let selector = selector(U256::from(1), U256::from(2));
+        let seal = encode_seal(selector, &[0xaa, 0xbb]);
+        assert_eq!(&seal[..4], &selector);
+        assert_eq!(&seal[4..], &[0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn selector_depends_on_both_control_ids() {
+        let a = selector(U256::from(1), U256::from(2));
+        assert_ne!(a, selector(U256::from(2), U256::from(1)));
+        assert_ne!(a, selector(U256::from(1), U256::from(3)));
+    }
+}
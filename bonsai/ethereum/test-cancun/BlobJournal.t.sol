@@ -0,0 +1,80 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.24;
+
+import {Test} from "forge-std/Test.sol";
+
+import {IRiscZeroVerifier, Receipt as RiscZeroReceipt} from "../contracts/IRiscZeroVerifier.sol";
+import {BlobJournal} from "../contracts-cancun/BlobJournal.sol";
+
+/// @notice Verifier accepting any seal, to isolate the blob checks.
+contract AcceptingVerifier is IRiscZeroVerifier {
+    function verify(bytes calldata, bytes32, bytes32, bytes32) external pure returns (bool) {
+        return true;
+    }
+
+    function verify_integrity(RiscZeroReceipt calldata) external pure returns (bool) {
+        return true;
+    }
+}
+
+/// @notice Exposes the internal functions of the library.
+contract BlobJournalHarness {
+    function verify(
+        IRiscZeroVerifier verifier,
+        uint256 blobIndex,
+        bytes32 z,
+        bytes32 y,
+        bytes calldata commitment,
+        bytes calldata proof
+    ) external view returns (bool) {
+        return BlobJournal.verify(verifier, hex"", bytes32(0), bytes32(0), blobIndex, z, y, commitment, proof);
+    }
+}
+
+contract BlobJournalTest is Test {
+    bytes32 internal constant VERSIONED_HASH = bytes32(uint256(1) << 248);
+
+    BlobJournalHarness internal harness;
+    IRiscZeroVerifier internal verifier;
+
+    function setUp() external {
+        harness = new BlobJournalHarness();
+        verifier = new AcceptingVerifier();
+    }
+
+    function test_Digest() external pure {
+        bytes32 z = bytes32(uint256(2));
+        bytes32 y = bytes32(uint256(3));
+        require(
+            BlobJournal.digest(VERSIONED_HASH, z, y) == sha256(abi.encodePacked(VERSIONED_HASH, z, y)),
+            "digest must match the encoding committed by the guest"
+        );
+    }
+
+    function test_RejectMissingBlob() external view {
+        require(!harness.verify(verifier, 0, 0, 0, new bytes(48), new bytes(48)), "missing blob must not verify");
+    }
+
+    function test_RejectInvalidEvaluationProof() external {
+        bytes32[] memory hashes = new bytes32[](1);
+        hashes[0] = VERSIONED_HASH;
+        vm.blobhashes(hashes);
+        require(!harness.verify(verifier, 0, 0, 0, new bytes(48), new bytes(48)), "invalid proof must not verify");
+        require(!harness.verify(verifier, 0, 0, 0, new bytes(47), new bytes(48)), "short commitment must not verify");
+    }
+}
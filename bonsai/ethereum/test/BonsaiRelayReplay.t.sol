@@ -0,0 +1,129 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.17;
+
+import {Test} from "forge-std/Test.sol";
+
+import {Callback, CallbackAuthorization} from "../contracts/relay/IBonsaiRelay.sol";
+import {BonsaiTestRelay} from "../contracts/relay/BonsaiTestRelay.sol";
+import {Counter} from "./Counter.sol";
+
+contract BonsaiRelayReplayTest is Test {
+    bytes32 internal constant IMAGE_ID = bytes32(uint256(0x1234));
+    uint64 internal constant GAS_LIMIT = 100000;
+    string internal constant PENDING_ERROR = "BonsaiTestRelay: callback does not answer a pending request";
+
+    BonsaiTestRelay internal relay;
+    Counter internal counter;
+
+    function setUp() external {
+        relay = new BonsaiTestRelay(block.chainid);
+        counter = new Counter();
+    }
+
+    function callback(uint256 increment, uint256 nonce) internal view returns (Callback memory) {
+        bytes memory payload = abi.encodePacked(counter.callback.selector, increment, IMAGE_ID);
+        return Callback(
+            CallbackAuthorization(new bytes(0), bytes32(0)), address(counter), payload, GAS_LIMIT, nonce, sha256("")
+        );
+    }
+
+    function testRequestsAreAssignedNonces() external {
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        require(!relay.callbackIsPending(0), "zero nonce is pending");
+        require(relay.callbackIsPending(1), "first request is not pending");
+        require(relay.callbackIsPending(2), "second request is not pending");
+        require(!relay.callbackIsPending(3), "unknown request is pending");
+    }
+
+    function testCallbackIsDeliveredOnce() external {
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        relay.invokeCallback(callback(5, 1));
+        require(counter.value() == 5, "callback was not delivered");
+        require(!relay.callbackIsPending(1), "request is still pending");
+
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(callback(5, 1));
+    }
+
+    function testEachRequestIsAnsweredOnce() external {
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        relay.invokeCallback(callback(5, 1));
+        relay.invokeCallback(callback(7, 2));
+        require(counter.value() == 12, "second callback was not delivered");
+
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(callback(5, 2));
+    }
+
+    function testCallbackMustMatchRequest() external {
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+
+        Callback memory wrongGasLimit = callback(5, 1);
+        wrongGasLimit.gasLimit = GAS_LIMIT + 1;
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(wrongGasLimit);
+
+        Callback memory wrongImageId = callback(5, 1);
+        wrongImageId.payload = abi.encodePacked(counter.callback.selector, uint256(5), bytes32(0));
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(wrongImageId);
+
+        Callback memory wrongInput = callback(5, 1);
+        wrongInput.inputDigest = sha256("other input");
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(wrongInput);
+
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(callback(5, 2));
+    }
+
+    function testZeroNonceIsRejected() external {
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallback(callback(5, 0));
+
+        Callback[] memory callbacks = new Callback[](1);
+        callbacks[0] = callback(5, 0);
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallbacks(callbacks);
+    }
+
+    function testFailedCallbackInBatchCanBeRetried() external {
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        counter.request_callback(IMAGE_ID, "", GAS_LIMIT, relay);
+        Callback[] memory callbacks = new Callback[](1);
+        callbacks[0] = callback(5, 1);
+        callbacks[0].gasLimit = 1000;
+        // The request was made with a different gas limit.
+        vm.expectRevert(bytes(PENDING_ERROR));
+        relay.invokeCallbacks(callbacks);
+
+        // Make the next increment overflow the counter.
+        relay.invokeCallback(callback(1, 2));
+        callbacks[0] = callback(type(uint256).max, 1);
+        bool[] memory results = relay.invokeCallbacks(callbacks);
+        require(!results[0], "overflowing callback succeeded");
+        require(relay.callbackIsPending(1), "failed callback was consumed");
+
+        callbacks[0] = callback(5, 1);
+        results = relay.invokeCallbacks(callbacks);
+        require(results[0], "retried callback failed");
+        require(counter.value() == 6, "callback was not delivered");
+    }
+}
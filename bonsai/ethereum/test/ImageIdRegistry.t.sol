@@ -0,0 +1,90 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {ImageIdRegistry} from "../contracts/ImageIdRegistry.sol";
+
+contract ImageIdRegistryTest is Test {
+    bytes32 internal constant IMAGE_ID_A = bytes32(uint256(0xaa));
+    bytes32 internal constant IMAGE_ID_B = bytes32(uint256(0xbb));
+    bytes32 internal constant IMAGE_ID_C = bytes32(uint256(0xcc));
+
+    ImageIdRegistry internal registry;
+    bytes32 internal app;
+
+    function setUp() external {
+        registry = new ImageIdRegistry();
+        app = registry.registerApplication("app");
+        registry.addImageId(app, IMAGE_ID_A);
+        registry.addImageId(app, IMAGE_ID_B);
+    }
+
+    function test_AppId() external view {
+        assertEq(app, keccak256("app"));
+        assertEq(registry.appId("app"), app);
+        assertEq(registry.getAdmin(app), address(this));
+    }
+
+    function test_IsAllowed() external view {
+        require(registry.isAllowed(app, IMAGE_ID_A), "image ID A must be allowed");
+        require(registry.isAllowed(app, IMAGE_ID_B), "image ID B must be allowed");
+        require(!registry.isAllowed(app, IMAGE_ID_C), "image ID C must not be allowed");
+        require(!registry.isAllowed(keccak256("other"), IMAGE_ID_A), "image ID A must not be allowed for other apps");
+    }
+
+    function test_RemoveImageId() external {
+        registry.removeImageId(app, IMAGE_ID_A);
+        require(!registry.isAllowed(app, IMAGE_ID_A), "image ID A must be revoked");
+        require(registry.isAllowed(app, IMAGE_ID_B), "image ID B must still be allowed");
+        bytes32[] memory imageIds = registry.getImageIds(app);
+        assertEq(imageIds.length, 1);
+        assertEq(imageIds[0], IMAGE_ID_B);
+
+        vm.expectRevert("ImageIdRegistry: image ID not allowed");
+        registry.removeImageId(app, IMAGE_ID_A);
+    }
+
+    function test_RotateImageId() external {
+        registry.rotateImageId(app, IMAGE_ID_A, IMAGE_ID_C);
+        require(!registry.isAllowed(app, IMAGE_ID_A), "image ID A must be revoked");
+        require(registry.isAllowed(app, IMAGE_ID_C), "image ID C must be allowed");
+        assertEq(registry.getImageIds(app).length, 2);
+    }
+
+    function test_RejectsDuplicates() external {
+        vm.expectRevert("ImageIdRegistry: image ID already allowed");
+        registry.addImageId(app, IMAGE_ID_A);
+        vm.expectRevert("ImageIdRegistry: application already registered");
+        registry.registerApplication("app");
+    }
+
+    function test_OnlyAdminCanManageImageIds() external {
+        vm.startPrank(address(0xdead));
+        vm.expectRevert("ImageIdRegistry: caller is not the admin");
+        registry.addImageId(app, IMAGE_ID_C);
+        vm.expectRevert("ImageIdRegistry: caller is not the admin");
+        registry.removeImageId(app, IMAGE_ID_A);
+        vm.stopPrank();
+
+        registry.transferAdmin(app, address(0xdead));
+        vm.prank(address(0xdead));
+        registry.addImageId(app, IMAGE_ID_C);
+        require(registry.isAllowed(app, IMAGE_ID_C), "new admin must be able to add image IDs");
+    }
+}
@@ -16,6 +16,8 @@
 pragma solidity ^0.8.17;
 
 contract Proxy {
+    uint256 internal lastNonce;
+
     // Events
     event CallbackRequest(
         address account,
@@ -23,7 +25,8 @@ contract Proxy {
         bytes input,
         address callbackContract,
         bytes4 functionSelector,
-        uint64 gasLimit
+        uint64 gasLimit,
+        uint256 nonce
     );
 
     event ProofsSubmitted();
@@ -40,6 +43,8 @@ contract Proxy {
         address callbackContract;
         bytes payload;
         uint64 gasLimit;
+        uint256 nonce;
+        bytes32 inputDigest;
     }
 
     // Submit request
@@ -49,9 +54,10 @@ contract Proxy {
         address callbackContract,
         bytes4 functionSelector,
         uint64 gasLimit
-    ) public {
+    ) public returns (uint256 nonce) {
+        nonce = ++lastNonce;
         // Emit event
-        emit CallbackRequest(msg.sender, imageId, input, callbackContract, functionSelector, gasLimit);
+        emit CallbackRequest(msg.sender, imageId, input, callbackContract, functionSelector, gasLimit, nonce);
     }
 
     // Submit proofs
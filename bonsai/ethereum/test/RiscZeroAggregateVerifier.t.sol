@@ -0,0 +1,129 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {IRiscZeroVerifier, Receipt as RiscZeroReceipt} from "../contracts/IRiscZeroVerifier.sol";
+import {RiscZeroAggregateVerifier} from "../contracts/RiscZeroAggregateVerifier.sol";
+
+/// @notice Verifier accepting any receipt for the image ID and journal digest it was given.
+contract AcceptingVerifier is IRiscZeroVerifier {
+    bytes32 internal immutable imageId;
+    bytes32 internal immutable journalDigest;
+
+    constructor(bytes32 _imageId, bytes32 _journalDigest) {
+        imageId = _imageId;
+        journalDigest = _journalDigest;
+    }
+
+    function verify(bytes calldata, bytes32 _imageId, bytes32, bytes32 _journalDigest) external view returns (bool) {
+        return _imageId == imageId && _journalDigest == journalDigest;
+    }
+
+    function verify_integrity(RiscZeroReceipt calldata) external pure returns (bool) {
+        return false;
+    }
+}
+
+contract RiscZeroAggregateVerifierTest is Test {
+    bytes32 internal constant AGGREGATOR_IMAGE_ID = bytes32(uint256(0xa11));
+    bytes32 internal constant POST_DIGEST = bytes32(uint256(0xb0b));
+
+    bytes32[3] internal journalDigests = [bytes32(uint256(1)), bytes32(uint256(2)), bytes32(uint256(3))];
+    bytes32[3] internal leaves;
+    bytes32 internal root;
+
+    RiscZeroAggregateVerifier internal aggregateVerifier;
+
+    function hashPair(bytes32 a, bytes32 b) internal pure returns (bytes32) {
+        return a < b ? sha256(abi.encodePacked(a, b)) : sha256(abi.encodePacked(b, a));
+    }
+
+    function setUp() external {
+        // Leaves are the claim digests of executions of image ID `i` with the given journals.
+        leaves[0] = claimDigest(bytes32(uint256(0)), journalDigests[0]);
+        leaves[1] = claimDigest(bytes32(uint256(1)), journalDigests[1]);
+        leaves[2] = claimDigest(bytes32(uint256(2)), journalDigests[2]);
+        root = hashPair(hashPair(leaves[0], leaves[1]), leaves[2]);
+
+        IRiscZeroVerifier verifier = new AcceptingVerifier(AGGREGATOR_IMAGE_ID, sha256(abi.encodePacked(root)));
+        aggregateVerifier = new RiscZeroAggregateVerifier(verifier, AGGREGATOR_IMAGE_ID);
+    }
+
+    function claimDigest(bytes32 imageId, bytes32 journalDigest) internal pure returns (bytes32) {
+        bytes32 outputDigest = sha256(
+            abi.encodePacked(sha256("risc0.Output"), journalDigest, bytes32(0), uint16(2) << 8)
+        );
+        return sha256(
+            abi.encodePacked(
+                sha256("risc0.ReceiptClaim"),
+                bytes32(0),
+                imageId,
+                POST_DIGEST,
+                outputDigest,
+                uint32(0),
+                uint32(0),
+                uint16(4) << 8
+            )
+        );
+    }
+
+    function testVerifyIncludedClaims() external {
+        aggregateVerifier.submitAggregate(hex"", POST_DIGEST, root);
+
+        bytes32[] memory proof = new bytes32[](2);
+        proof[0] = leaves[1];
+        proof[1] = leaves[2];
+        require(
+            aggregateVerifier.verify(bytes32(uint256(0)), POST_DIGEST, journalDigests[0], root, proof),
+            "verification failed for leaf 0"
+        );
+
+        proof[0] = leaves[0];
+        require(
+            aggregateVerifier.verify(bytes32(uint256(1)), POST_DIGEST, journalDigests[1], root, proof),
+            "verification failed for leaf 1"
+        );
+
+        bytes32[] memory shortProof = new bytes32[](1);
+        shortProof[0] = hashPair(leaves[0], leaves[1]);
+        require(
+            aggregateVerifier.verify(bytes32(uint256(2)), POST_DIGEST, journalDigests[2], root, shortProof),
+            "verification failed for leaf 2"
+        );
+
+        require(
+            !aggregateVerifier.verify(bytes32(uint256(2)), POST_DIGEST, journalDigests[1], root, shortProof),
+            "verification passed for a claim not in the tree"
+        );
+    }
+
+    function testRejectUnverifiedRoot() external view {
+        bytes32[] memory shortProof = new bytes32[](1);
+        shortProof[0] = hashPair(leaves[0], leaves[1]);
+        require(
+            !aggregateVerifier.verify(bytes32(uint256(2)), POST_DIGEST, journalDigests[2], root, shortProof),
+            "verification passed before the aggregated receipt was submitted"
+        );
+    }
+
+    function testRejectInvalidAggregate() external {
+        vm.expectRevert("RiscZeroAggregateVerifier: invalid aggregated receipt");
+        aggregateVerifier.submitAggregate(hex"", POST_DIGEST, bytes32(uint256(0xbad)));
+    }
+}
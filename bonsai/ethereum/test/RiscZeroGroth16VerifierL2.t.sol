@@ -0,0 +1,114 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt as RiscZeroReceipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    ExitCode,
+    SystemExitCode
+} from "../contracts/IRiscZeroVerifier.sol";
+import {RiscZeroGroth16VerifierArbitrum} from "../contracts/groth16/RiscZeroGroth16VerifierArbitrum.sol";
+import {RiscZeroGroth16VerifierOptimism} from "../contracts/groth16/RiscZeroGroth16VerifierOptimism.sol";
+import {TestReceipt} from "./TestReceipt.sol";
+
+contract RiscZeroGroth16VerifierL2Test is Test {
+    using OutputLib for Output;
+    using ReceiptClaimLib for ReceiptClaim;
+
+    // TestReceipt.SEAL, encoded by `bonsai_ethereum_contracts::l2::encode_seal`.
+    bytes internal constant OPTIMISM_SEAL =
+        hex"2050cc0c981527e49570b0a99763e5209e7c68b7baacfc3e1de6dd0338a8835434dfb9acfbbee336661584b207eefbcf9b896a8bcbaf0d708fe20b781fe944261b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae0462d8fcc65ad1f03b94001be20a4e75a8b4456074d2ea59973b1dc50f02cdfe835";
+    bytes internal constant ARBITRUM_SEAL =
+        hex"2050cc0c981527e49570b0a99763e5209e7c68b7baacfc3e1de6dd0338a883541b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae0460d37ee6b3eefb8cd9985612c81fbbef3e6e25aa2f2ebc35c23f882de07fa51090e96423125defa12ccf193e6bf2ed33ce3ea2085a40ed1c88af7dd1f71d7fec90c5905ea06c54c2f8f27be48dcd055836066f9706f8ab1c3984a321fc2e4f11d2d8fcc65ad1f03b94001be20a4e75a8b4456074d2ea59973b1dc50f02cdfe835";
+
+    IRiscZeroVerifier internal optimism;
+    IRiscZeroVerifier internal arbitrum;
+
+    function setUp() external {
+        optimism = new RiscZeroGroth16VerifierOptimism();
+        arbitrum = new RiscZeroGroth16VerifierArbitrum();
+    }
+
+    function receipt(bytes memory seal) internal pure returns (RiscZeroReceipt memory) {
+        return RiscZeroReceipt(
+            seal,
+            ReceiptClaim(
+                TestReceipt.IMAGE_ID,
+                TestReceipt.POST_DIGEST,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(sha256(TestReceipt.JOURNAL), bytes32(0)).digest()
+            )
+        );
+    }
+
+    function testVerifyKnownGoodReceipt() external view {
+        require(optimism.verify_integrity(receipt(OPTIMISM_SEAL)), "optimism verification failed");
+        require(arbitrum.verify_integrity(receipt(ARBITRUM_SEAL)), "arbitrum verification failed");
+    }
+
+    function testVerifyKnownGoodImageIdAndJournal() external view {
+        require(
+            optimism.verify(OPTIMISM_SEAL, TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256(TestReceipt.JOURNAL)),
+            "optimism verification failed"
+        );
+        require(
+            arbitrum.verify(ARBITRUM_SEAL, TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256(TestReceipt.JOURNAL)),
+            "arbitrum verification failed"
+        );
+    }
+
+    function testRejectsWrongSealLength() external view {
+        require(!optimism.verify_integrity(receipt(ARBITRUM_SEAL)), "optimism accepted a 192 bytes seal");
+        require(!arbitrum.verify_integrity(receipt(OPTIMISM_SEAL)), "arbitrum accepted a 128 bytes seal");
+        require(!optimism.verify_integrity(receipt(TestReceipt.SEAL)), "optimism accepted an uncompressed seal");
+        require(!arbitrum.verify_integrity(receipt(TestReceipt.SEAL)), "arbitrum accepted an uncompressed seal");
+    }
+
+    function testVerifyMangledSeals() external view {
+        bytes memory seal = OPTIMISM_SEAL;
+        // Flip the sign of A.
+        seal[31] ^= bytes1(uint8(1));
+        require(!optimism.verify_integrity(receipt(seal)), "verification passed with the sign of A flipped");
+        seal[31] ^= bytes1(uint8(1));
+
+        // Flip the Fp2 square root hint of B.
+        seal[63] ^= bytes1(uint8(2));
+        require(!optimism.verify_integrity(receipt(seal)), "verification passed with the hint of B flipped");
+        seal[63] ^= bytes1(uint8(2));
+
+        seal[96] ^= bytes1(uint8(1));
+        require(!optimism.verify_integrity(receipt(seal)), "verification passed on mangled C");
+        seal[96] ^= bytes1(uint8(1));
+
+        seal = ARBITRUM_SEAL;
+        seal[100] ^= bytes1(uint8(1));
+        require(!arbitrum.verify_integrity(receipt(seal)), "verification passed on mangled B");
+        seal[100] ^= bytes1(uint8(1));
+
+        // Just a quick sanity check
+        require(optimism.verify_integrity(receipt(OPTIMISM_SEAL)), "verification failed");
+        require(arbitrum.verify_integrity(receipt(seal)), "verification failed");
+    }
+}
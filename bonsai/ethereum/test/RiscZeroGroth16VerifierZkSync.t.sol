@@ -0,0 +1,103 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt as RiscZeroReceipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    ExitCode,
+    SystemExitCode
+} from "../contracts/IRiscZeroVerifier.sol";
+import {RiscZeroGroth16VerifierZkSync} from "../contracts/groth16/RiscZeroGroth16VerifierZkSync.sol";
+import {TestReceipt} from "./TestReceipt.sol";
+
+contract RiscZeroGroth16VerifierZkSyncTest is Test {
+    using OutputLib for Output;
+    using ReceiptClaimLib for ReceiptClaim;
+
+    // Base field size
+    uint256 internal constant P = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+
+    IRiscZeroVerifier internal verifier;
+
+    function setUp() external {
+        verifier = new RiscZeroGroth16VerifierZkSync();
+    }
+
+    function receipt(bytes memory seal) internal pure returns (RiscZeroReceipt memory) {
+        return RiscZeroReceipt(
+            seal,
+            ReceiptClaim(
+                TestReceipt.IMAGE_ID,
+                TestReceipt.POST_DIGEST,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(sha256(TestReceipt.JOURNAL), bytes32(0)).digest()
+            )
+        );
+    }
+
+    function testVerifyKnownGoodReceipt() external view {
+        require(verifier.verify_integrity(receipt(TestReceipt.SEAL)), "verification failed");
+    }
+
+    function testVerifyKnownGoodImageIdAndJournal() external view {
+        require(
+            verifier.verify(
+                TestReceipt.SEAL, TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256(TestReceipt.JOURNAL)
+            ),
+            "verification failed"
+        );
+    }
+
+    function testRejectsWrongSealLength() external view {
+        bytes memory seal = bytes.concat(TestReceipt.SEAL, bytes32(0));
+        require(!verifier.verify_integrity(receipt(seal)), "verification passed with a trailing word");
+    }
+
+    function testRejectsUnreducedCoordinates() external view {
+        (uint256[2] memory a, uint256[2][2] memory b, uint256[2] memory c) =
+            abi.decode(TestReceipt.SEAL, (uint256[2], uint256[2][2], uint256[2]));
+        a[0] += P;
+        bytes memory seal = abi.encode(a, b, c);
+        require(!verifier.verify_integrity(receipt(seal)), "verification passed with an unreduced coordinate");
+    }
+
+    function testVerifyMangledSeals() external view {
+        bytes memory seal = TestReceipt.SEAL;
+        seal[31] ^= bytes1(uint8(1));
+        require(!verifier.verify_integrity(receipt(seal)), "verification passed on mangled A");
+        seal[31] ^= bytes1(uint8(1));
+
+        seal[100] ^= bytes1(uint8(1));
+        require(!verifier.verify_integrity(receipt(seal)), "verification passed on mangled B");
+        seal[100] ^= bytes1(uint8(1));
+
+        seal[200] ^= bytes1(uint8(1));
+        require(!verifier.verify_integrity(receipt(seal)), "verification passed on mangled C");
+        seal[200] ^= bytes1(uint8(1));
+
+        // Just a quick sanity check
+        require(verifier.verify_integrity(receipt(seal)), "verification failed");
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt as RiscZeroReceipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    ExitCode,
+    SystemExitCode
+} from "../contracts/IRiscZeroVerifier.sol";
+import {DevModeSeal, RiscZeroMockVerifier} from "../contracts/RiscZeroMockVerifier.sol";
+import {TestReceipt} from "./TestReceipt.sol";
+
+contract RiscZeroMockVerifierTest is Test {
+    using OutputLib for Output;
+    using ReceiptClaimLib for ReceiptClaim;
+
+    ReceiptClaim internal TEST_CLAIM = ReceiptClaim(
+        TestReceipt.IMAGE_ID,
+        TestReceipt.POST_DIGEST,
+        ExitCode(SystemExitCode.Halted, 0),
+        bytes32(0),
+        Output(sha256(TestReceipt.JOURNAL), bytes32(0)).digest()
+    );
+
+    IRiscZeroVerifier internal verifier;
+
+    function setUp() external {
+        verifier = new RiscZeroMockVerifier();
+    }
+
+    function testVerifyDevModeReceipt() external view {
+        require(
+            verifier.verify_integrity(RiscZeroReceipt(DevModeSeal.encode(TEST_CLAIM), TEST_CLAIM)),
+            "verification failed"
+        );
+    }
+
+    function testVerifyDevModeImageIdAndJournal() external view {
+        bytes memory seal =
+            DevModeSeal.encode(TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256(TestReceipt.JOURNAL));
+        require(
+            verifier.verify(seal, TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256(TestReceipt.JOURNAL)),
+            "verification failed"
+        );
+        require(
+            !verifier.verify(seal, TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256("other journal")),
+            "verification passed with another journal"
+        );
+    }
+
+    function testRejectsOtherSeals() external view {
+        require(
+            !verifier.verify_integrity(RiscZeroReceipt(TestReceipt.SEAL, TEST_CLAIM)),
+            "verification passed on a Groth16 seal"
+        );
+
+        bytes memory seal = DevModeSeal.encode(TEST_CLAIM);
+        seal[0] ^= bytes1(uint8(1));
+        require(!verifier.verify_integrity(RiscZeroReceipt(seal, TEST_CLAIM)), "verification passed on mangled tag");
+        seal[0] ^= bytes1(uint8(1));
+
+        seal[63] ^= bytes1(uint8(1));
+        require(!verifier.verify_integrity(RiscZeroReceipt(seal, TEST_CLAIM)), "verification passed on mangled digest");
+        seal[63] ^= bytes1(uint8(1));
+
+        // Just a quick sanity check
+        require(verifier.verify_integrity(RiscZeroReceipt(seal, TEST_CLAIM)), "verification failed");
+    }
+}
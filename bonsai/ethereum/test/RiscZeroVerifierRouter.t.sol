@@ -0,0 +1,98 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {IRiscZeroVerifier, Receipt as RiscZeroReceipt} from "../contracts/IRiscZeroVerifier.sol";
+import {RiscZeroVerifierRouter} from "../contracts/RiscZeroVerifierRouter.sol";
+
+/// @notice Verifier accepting only the seal it was constructed with.
+contract SealVerifier is IRiscZeroVerifier {
+    bytes internal expectedSeal;
+
+    constructor(bytes memory _expectedSeal) {
+        expectedSeal = _expectedSeal;
+    }
+
+    function verify(bytes calldata seal, bytes32, bytes32, bytes32) external view returns (bool) {
+        return keccak256(seal) == keccak256(expectedSeal);
+    }
+
+    function verify_integrity(RiscZeroReceipt calldata receipt) external view returns (bool) {
+        return keccak256(receipt.seal) == keccak256(expectedSeal);
+    }
+}
+
+contract RiscZeroVerifierRouterTest is Test {
+    bytes4 internal constant SELECTOR_A = bytes4(0xaaaaaaaa);
+    bytes4 internal constant SELECTOR_B = bytes4(0xbbbbbbbb);
+
+    RiscZeroVerifierRouter internal router;
+    SealVerifier internal verifierA;
+    SealVerifier internal verifierB;
+
+    function setUp() external {
+        router = new RiscZeroVerifierRouter(address(this));
+        verifierA = new SealVerifier(hex"01");
+        verifierB = new SealVerifier(hex"02");
+        router.addVerifier(SELECTOR_A, verifierA);
+        router.addVerifier(SELECTOR_B, verifierB);
+    }
+
+    function test_RoutesToSelectedVerifier() external view {
+        require(router.verify(abi.encodePacked(SELECTOR_A, hex"01"), 0, 0, 0), "seal for A must verify");
+        require(router.verify(abi.encodePacked(SELECTOR_B, hex"02"), 0, 0, 0), "seal for B must verify");
+        require(!router.verify(abi.encodePacked(SELECTOR_A, hex"02"), 0, 0, 0), "seal for B must not verify with A");
+    }
+
+    function test_GetVerifier() external view {
+        (IRiscZeroVerifier verifier, RiscZeroVerifierRouter.VerifierStatus status) = router.getVerifier(SELECTOR_A);
+        assertEq(address(verifier), address(verifierA));
+        assertEq(uint8(status), uint8(RiscZeroVerifierRouter.VerifierStatus.Active));
+
+        (, status) = router.getVerifier(bytes4(0));
+        assertEq(uint8(status), uint8(RiscZeroVerifierRouter.VerifierStatus.Unregistered));
+    }
+
+    function test_RejectsUnknownSelector() external {
+        vm.expectRevert("RiscZeroVerifierRouter: selector not active");
+        router.verify(abi.encodePacked(bytes4(0), hex"01"), 0, 0, 0);
+    }
+
+    function test_RejectsDeprecatedVerifier() external {
+        router.deprecateVerifier(SELECTOR_A);
+        vm.expectRevert("RiscZeroVerifierRouter: selector not active");
+        router.verify(abi.encodePacked(SELECTOR_A, hex"01"), 0, 0, 0);
+        require(router.verify(abi.encodePacked(SELECTOR_B, hex"02"), 0, 0, 0), "seal for B must still verify");
+    }
+
+    function test_DeprecatedSelectorCannotBeReused() external {
+        router.deprecateVerifier(SELECTOR_A);
+        vm.expectRevert("RiscZeroVerifierRouter: selector already used");
+        router.addVerifier(SELECTOR_A, verifierB);
+    }
+
+    function test_OnlyOwnerCanManageVerifiers() external {
+        vm.startPrank(address(0xdead));
+        vm.expectRevert("RiscZeroVerifierRouter: caller is not the owner");
+        router.addVerifier(bytes4(0), verifierA);
+        vm.expectRevert("RiscZeroVerifierRouter: caller is not the owner");
+        router.deprecateVerifier(SELECTOR_A);
+        vm.stopPrank();
+    }
+}
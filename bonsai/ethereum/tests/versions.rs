@@ -0,0 +1,27 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks that the versions reported by the zkVM match the Solidity verifier.
+
+use risc0_groth16::VerifyingKey;
+
+#[test]
+fn groth16_verifying_key() {
+    let solidity_verifier = include_str!("../contracts/groth16/Groth16Verifier.sol");
+    let vk = VerifyingKey::from_solidity(solidity_verifier).unwrap();
+    assert_eq!(
+        risc0_zkvm::versions().groth16_verifying_key,
+        vk.digest().unwrap()
+    );
+}
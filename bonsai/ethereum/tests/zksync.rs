@@ -0,0 +1,141 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test of the `RiscZeroGroth16VerifierZkSync` contract against an era-test-node.
+//!
+//! EraVM does not run EVM bytecode, so the contract must first be compiled with zksolc, e.g. with
+//! foundry-zksync:
+//!
+//! ```sh
+//! era_test_node run &
+//! forge build --zksync
+//! cargo test -p bonsai-ethereum-contracts --test zksync -- --ignored
+//! ```
+//!
+//! The node is reached at `ERA_TEST_NODE_URL`, defaulting to `http://localhost:8011`.
+
+use std::sync::Arc;
+
+use bonsai_ethereum_contracts::{testutils::solidity_constant, RiscZeroGroth16VerifierZkSync};
+use ethers::prelude::*;
+use sha2::{Digest as _, Sha256};
+
+const ERA_TEST_NODE_URL: &str = "http://localhost:8011";
+
+const ARTIFACT_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/zkout/RiscZeroGroth16VerifierZkSync.sol/RiscZeroGroth16VerifierZkSync.json"
+);
+
+// Address the verifier is installed at, above the kernel space of EraVM.
+const VERIFIER_ADDRESS: &str = "0x00000000000000000000000000000000c0ffee00";
+
+struct TestReceipt {
+    seal: Vec<u8>,
+    image_id: [u8; 32],
+    post_digest: [u8; 32],
+    journal_digest: [u8; 32],
+}
+
+// Reads the receipt of `test/TestReceipt.sol`.
+fn test_receipt() -> TestReceipt {
+    let source = include_str!("../test/TestReceipt.sol");
+    let constant = |name: &str| solidity_constant(source, name);
+    TestReceipt {
+        seal: constant("SEAL"),
+        image_id: constant("IMAGE_ID").try_into().unwrap(),
+        post_digest: constant("POST_DIGEST").try_into().unwrap(),
+        journal_digest: Sha256::digest(constant("JOURNAL")).into(),
+    }
+}
+
+// Installs the zksolc build of the verifier on the node, and returns a binding to it.
+async fn verifier() -> RiscZeroGroth16VerifierZkSync<Provider<Http>> {
+    let url = std::env::var("ERA_TEST_NODE_URL").unwrap_or(ERA_TEST_NODE_URL.to_string());
+    let provider = Provider::<Http>::try_from(url).unwrap();
+
+    let artifact: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(ARTIFACT_PATH)
+            .unwrap_or_else(|_| panic!("{ARTIFACT_PATH} not found, run `forge build --zksync`")),
+    )
+    .unwrap();
+    let bytecode = artifact["bytecode"]["object"].as_str().unwrap();
+    let bytecode = hex::decode(bytecode.trim_start_matches("0x")).unwrap();
+
+    // The verifier has no constructor, so its bytecode can be set directly.
+    let address: Address = VERIFIER_ADDRESS.parse().unwrap();
+    provider
+        .request::<_, serde_json::Value>("hardhat_setCode", (address, bytecode))
+        .await
+        .unwrap();
+
+    RiscZeroGroth16VerifierZkSync::new(address, Arc::new(provider))
+}
+
+#[tokio::test]
+#[ignore]
+async fn verify_test_receipt() {
+    let verifier = verifier().await;
+    let receipt = test_receipt();
+
+    assert_eq!(
+        verifier.seal_length().call().await.unwrap(),
+        U256::from(256)
+    );
+    assert!(verifier
+        .verify(
+            receipt.seal.into(),
+            receipt.image_id,
+            receipt.post_digest,
+            receipt.journal_digest,
+        )
+        .call()
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+#[ignore]
+async fn reject_mangled_seals() {
+    let verifier = verifier().await;
+    let receipt = test_receipt();
+
+    for index in [31, 100, 200] {
+        let mut seal = receipt.seal.clone();
+        seal[index] ^= 1;
+        assert!(!verifier
+            .verify(
+                seal.into(),
+                receipt.image_id,
+                receipt.post_digest,
+                receipt.journal_digest,
+            )
+            .call()
+            .await
+            .unwrap());
+    }
+
+    let mut seal = receipt.seal.clone();
+    seal.extend([0u8; 32]);
+    assert!(!verifier
+        .verify(
+            seal.into(),
+            receipt.image_id,
+            receipt.post_digest,
+            receipt.journal_digest,
+        )
+        .call()
+        .await
+        .unwrap());
+}
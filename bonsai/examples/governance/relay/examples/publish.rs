@@ -22,6 +22,8 @@ struct Args {
     image_id: String,
     address: Address,
     number: u64,
+    /// Nonce assigned to the callback request by the relay contract.
+    nonce: u64,
 }
 
 #[tokio::main]
@@ -51,6 +53,7 @@ async fn main() {
         gas_limit: 3000000,
         image_id: image_id.into(),
         input,
+        nonce: args.nonce.into(),
     };
 
     // Send the callback request to the Bonsai Relay.
@@ -433,6 +433,11 @@ abstract contract BonsaiGovernorTest is GovernorTest, BonsaiTest {
             bytes memory journal = finalizeVotesSolidityImpl(guestInput);
             bytes memory payload = abi.encodePacked(callbackSelector, journal, imageId);
 
+            // Request the callback from the relay, which assigns the nonce the callback must carry.
+            uint256 nonce =
+                bonsaiRelay.requestCallback(imageId, guestInput, address(bonsaiGov), callbackSelector, UINT64_MAX);
+            bonsaiRelay.discardLastCbrEventData();
+
             // Check the callback result and revert if the callback failed.
             // NOTE: When the revert is expected, the vm.expectRevert call will prevent this function
             // from reverting, thereby ensuring the results from collectBallots is preserved.
@@ -444,7 +449,8 @@ abstract contract BonsaiGovernorTest is GovernorTest, BonsaiTest {
             // { bytes4(selector) || journal bytes || bytes32(imageId) }
             // Here we are calling through the Relay and so assemble to call to be same structure.
             CallbackAuthorization memory auth = CallbackAuthorization(new bytes(0), bytes32(0));
-            Callback memory callback = Callback(auth, address(bonsaiGov), payload, UINT64_MAX);
+            Callback memory callback =
+                Callback(auth, address(bonsaiGov), payload, UINT64_MAX, nonce, sha256(guestInput));
             bonsaiRelay.invokeCallback(callback);
         }
     }
@@ -0,0 +1,185 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! NEAR contract verifying RISC Zero Groth16 receipts.
+//!
+//! The contract accepts the receipts of the zkVM release matching the control
+//! IDs in [CONTROL_ID_0] and [CONTROL_ID_1], which are generated along with the
+//! verifying key by `cargo xtask bootstrap-groth16`. Seals are passed as hex in
+//! the same encoding as for the Solidity verifier, see [VerifyArgs].
+
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    near_bindgen,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+pub mod verifier;
+mod vk;
+
+use verifier::{verify_groth16, Seal};
+pub use vk::{CONTROL_ID_0, CONTROL_ID_1, VERIFYING_KEY};
+
+/// Arguments of the `verify` method, with all byte strings hex encoded.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VerifyArgs {
+    /// Seal of a `CompactReceipt`.
+    pub seal: String,
+    pub image_id: String,
+    pub post_state_digest: String,
+    pub journal_digest: String,
+}
+
+impl VerifyArgs {
+    /// Build the arguments verifying a receipt for the given image ID, post
+    /// state digest and journal digest.
+    pub fn new(
+        seal: &[u8],
+        image_id: &[u8; 32],
+        post_state_digest: &[u8; 32],
+        journal_digest: &[u8; 32],
+    ) -> Self {
+        Self {
+            seal: hex::encode(seal),
+            image_id: hex::encode(image_id),
+            post_state_digest: hex::encode(post_state_digest),
+            journal_digest: hex::encode(journal_digest),
+        }
+    }
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RiscZeroVerifier {}
+
+#[near_bindgen]
+impl RiscZeroVerifier {
+    /// Verify that `seal` is a valid proof of an execution of `image_id` with
+    /// the given post state and journal digests.
+    ///
+    /// Like the `verify` method of the Solidity verifier, this checks that the
+    /// execution halted with exit code 0, with no input and no assumptions.
+    pub fn verify(
+        seal: String,
+        image_id: String,
+        post_state_digest: String,
+        journal_digest: String,
+    ) -> bool {
+        let claim_digest = claim_digest(
+            &decode_digest(&image_id),
+            &decode_digest(&post_state_digest),
+            &decode_digest(&journal_digest),
+        );
+        verify_integrity_inner(&seal, &claim_digest)
+    }
+
+    /// Verify that `seal` is a valid proof of the claim with the given digest.
+    pub fn verify_integrity(seal: String, claim_digest: String) -> bool {
+        verify_integrity_inner(&seal, &decode_digest(&claim_digest))
+    }
+}
+
+fn decode_digest(digest: &str) -> [u8; 32] {
+    hex::decode(digest)
+        .ok()
+        .and_then(|digest| digest.try_into().ok())
+        .unwrap_or_else(|| near_sdk::env::panic_str("digests must be 32 hex encoded bytes"))
+}
+
+fn verify_integrity_inner(seal: &str, claim_digest: &[u8; 32]) -> bool {
+    let seal = hex::decode(seal)
+        .ok()
+        .and_then(|seal| Seal::from_bytes(&seal))
+        .unwrap_or_else(|| near_sdk::env::panic_str("invalid seal"));
+    let (claim_0, claim_1) = split_digest(claim_digest);
+    verify_groth16(
+        &VERIFYING_KEY,
+        &seal,
+        &[CONTROL_ID_0, CONTROL_ID_1, claim_0, claim_1],
+    )
+}
+
+/// Compute the digest of the claim of a halted execution with exit code 0, no
+/// input and no assumptions, matching `ReceiptClaimLib.digest` in Solidity.
+pub fn claim_digest(
+    image_id: &[u8; 32],
+    post_state_digest: &[u8; 32],
+    journal_digest: &[u8; 32],
+) -> [u8; 32] {
+    let output_digest = Sha256::new()
+        .chain_update(Sha256::digest(b"risc0.Output"))
+        .chain_update(journal_digest)
+        .chain_update([0u8; 32])
+        .chain_update([2, 0])
+        .finalize();
+    Sha256::new()
+        .chain_update(Sha256::digest(b"risc0.ReceiptClaim"))
+        .chain_update([0u8; 32])
+        .chain_update(image_id)
+        .chain_update(post_state_digest)
+        .chain_update(output_digest)
+        .chain_update([0u8; 4])
+        .chain_update([0u8; 4])
+        .chain_update([4, 0])
+        .finalize()
+        .into()
+}
+
+/// Split a digest into the two public inputs of the Groth16 circuit, encoded
+/// as little endian scalars. This matches `splitDigest` in Solidity, which
+/// reads the digest as a little endian integer and splits it into its lower
+/// and upper 128 bits.
+pub fn split_digest(digest: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let mut lower = [0u8; 32];
+    let mut upper = [0u8; 32];
+    lower[..16].copy_from_slice(&digest[..16]);
+    upper[..16].copy_from_slice(&digest[16..]);
+    (lower, upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Receipt from bonsai/ethereum/test/TestReceipt.sol.
+    const SEAL: &str = "102866064c0a93f24ab85854cbb1f2904f3e345bdd567e1f0ef36e819c5441aa24cd64818e919f0cb611b69f717421dc0b859d748325506b76bd9f3b351869be1b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae0460d37ee6b3eefb8cd9985612c81fbbef3e6e25aa2f2ebc35c23f882de07fa51090e96423125defa12ccf193e6bf2ed33ce3ea2085a40ed1c88af7dd1f71d7fec90c5905ea06c54c2f8f27be48dcd055836066f9706f8ab1c3984a321fc2e4f11d16c7e632d68f81dca000df105273ad45a22b03a69752ccb9d8ee2878166ff41a1de23df9c89ef27a77980f84d8d780890cb89fe983f812a4fb6222797c0e529f";
+    const POST_DIGEST: &str = "0c832dac05ecf2eaf58d3cfff95e3b51e18314d377483703fd1f08296365ea71";
+    const IMAGE_ID: &str = "59eeb99a627ecaf527fa51262e797fc39ba69ad9ff36e9b2f5a634edc9fc622d";
+
+    fn verify(journal_digest: [u8; 32]) -> bool {
+        let args = VerifyArgs {
+            seal: SEAL.to_string(),
+            image_id: IMAGE_ID.to_string(),
+            post_state_digest: POST_DIGEST.to_string(),
+            journal_digest: hex::encode(journal_digest),
+        };
+        RiscZeroVerifier::verify(
+            args.seal,
+            args.image_id,
+            args.post_state_digest,
+            args.journal_digest,
+        )
+    }
+
+    #[test]
+    fn verify_test_receipt() {
+        assert!(verify(Sha256::digest(b"").into()));
+    }
+
+    #[test]
+    fn reject_wrong_journal() {
+        assert!(!verify([1u8; 32]));
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Groth16 verification over BN254 using the NEAR alt_bn128 host functions.
+//!
+//! The host functions take points in little endian: G1 points are `x || y`
+//! and G2 points are `x_c0 || x_c1 || y_c0 || y_c1`. Seals are accepted in the
+//! big endian layout of the Solidity verifier and converted by
+//! [Seal::from_bytes].
+
+use near_sdk::env;
+
+/// Number of public inputs of the RISC Zero Groth16 circuit.
+pub const NUM_PUBLIC_INPUTS: usize = 4;
+
+/// Groth16 verifying key.
+pub struct VerifyingKey {
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    pub ic: [[u8; 64]; NUM_PUBLIC_INPUTS + 1],
+}
+
+/// Groth16 proof.
+pub struct Seal {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+// Reverses each 32 bytes coordinate of `bytes`, in place.
+fn reverse_coordinates(bytes: &mut [u8]) {
+    for coordinate in bytes.chunks_mut(32) {
+        coordinate.reverse();
+    }
+}
+
+impl Seal {
+    /// Length of an encoded seal.
+    pub const LEN: usize = 256;
+
+    /// Decode a seal from the big endian `a || b || c` encoding produced by
+    /// `risc0_groth16::Seal::to_vec`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+        let mut a: [u8; 64] = bytes[..64].try_into().ok()?;
+        let mut b: [u8; 128] = bytes[64..192].try_into().ok()?;
+        let mut c: [u8; 64] = bytes[192..].try_into().ok()?;
+        reverse_coordinates(&mut a);
+        reverse_coordinates(&mut c);
+        reverse_coordinates(&mut b);
+        // Swap the components of each Fq2 coordinate from `c1 || c0` to `c0 || c1`.
+        let (x, y) = b.split_at_mut(64);
+        let (x_c1, x_c0) = x.split_at_mut(32);
+        x_c1.swap_with_slice(x_c0);
+        let (y_c1, y_c0) = y.split_at_mut(32);
+        y_c1.swap_with_slice(y_c0);
+        Some(Self { a, b, c })
+    }
+}
+
+/// Verify a Groth16 proof for the given public inputs, each encoded as a
+/// little endian scalar.
+pub fn verify_groth16(
+    vk: &VerifyingKey,
+    seal: &Seal,
+    public_inputs: &[[u8; 32]; NUM_PUBLIC_INPUTS],
+) -> bool {
+    // vk_x = IC0 + sum(public_inputs[i] * IC[i + 1])
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    let mut multiexp_input = Vec::with_capacity((NUM_PUBLIC_INPUTS + 1) * 96);
+    for (ic, scalar) in vk.ic.iter().zip([&one].into_iter().chain(public_inputs)) {
+        multiexp_input.extend_from_slice(ic);
+        multiexp_input.extend_from_slice(scalar);
+    }
+    let vk_x = env::alt_bn128_g1_multiexp(&multiexp_input);
+
+    // Negate `a` by summing it with a negative sign.
+    let neg_a = env::alt_bn128_g1_sum(&[&[1u8][..], &seal.a].concat());
+
+    let pairing_input = [
+        &neg_a[..],
+        &seal.b,
+        &vk.alpha_g1,
+        &vk.beta_g2,
+        &vk_x,
+        &vk.gamma_g2,
+        &seal.c,
+        &vk.delta_g2,
+    ]
+    .concat();
+    env::alt_bn128_pairing_check(&pairing_input)
+}
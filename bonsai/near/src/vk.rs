@@ -0,0 +1,78 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+// Constants from: bonsai/ethereum/contracts/groth16/Groth16Verifier.sol
+// and bonsai/ethereum/contracts/groth16/ControlID.sol
+
+use hex_literal::hex;
+
+use crate::verifier::VerifyingKey;
+
+/// Control ID hash for the identity_p254 predicate decomposed by `split_digest`.
+pub const CONTROL_ID_0: [u8; 32] =
+    hex!("290188641142c54bdb641329127e7d4400000000000000000000000000000000");
+pub const CONTROL_ID_1: [u8; 32] =
+    hex!("f9816c5c61137e14328ad247d29ac41200000000000000000000000000000000");
+
+/// Verifying key of the RISC Zero Groth16 circuit, in the little endian
+/// encoding expected by the NEAR alt_bn128 host functions.
+pub const VERIFYING_KEY: VerifyingKey = VerifyingKey {
+    alpha_g1: hex!(
+        "e2f26dbea299f5223b646cb1fb33eadb059d9407559d7441dfd902e3a79a4d2d"
+        "26194d00ffca76f0010323190a8389ce45e39f2060ecd861b0ce373c50ddbe14"
+    ),
+    beta_g2: hex!(
+        "abb73dc17fbc13021e2471e0c08bd67d8401f52b73d6d07483794cad4778180e"
+        "0c06f33bbc4c79a9cadef253a68084d382f17788f885c9afd176f7cb2f036709"
+        "c8ced07a54067fd5a905ea3ec6b796f892912f4dd2233131c7a857a4b1c13917"
+        "a74623114d9aa69d370d7a6bc4defdaa3c8c3fd947e8f5994a708ae0d1fb4c30"
+    ),
+    gamma_g2: hex!(
+        "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018"
+        "c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19"
+        "aa7dfa6601cce64c7bd3430c69e7d1e38f40cb8d8071ab4aeb6d8cdba55ec812"
+        "5b9722d1dcdaac55f38eb37033314bbc95330c69ad999eec75f05f58d0890609"
+    ),
+    delta_g2: hex!(
+        "8f4e044ed6eda7fc3195fee2418b6e14dc5677d2565cd65b9798fac793f2fa27"
+        "228f9e5ab59a286322147373d360cdcd4ebb3023207a0de2729a78eb0c5c0211"
+        "a874eb29c675b9b334c0acb39429945c8298beb3d6da1470e7ba38dc0688592a"
+        "2ec59e5edf8f7c9c2052c8ec4b9839a0dcdebb44e4576160ff39a2169e434510"
+    ),
+    ic: [
+        hex!(
+            "d9d3a7b8e78541a78e64f911c779a5764c858a9e3c438e618db4a93d4ae7b908"
+            "c0eee83cdd9c3b5df3555265a117ebeeb7a040e17cf66faa7c6687667a8d0516"
+        ),
+        hex!(
+            "4974555d965e3ee0e36948042e88d8459c8aa89dbc4bdbbc9810673c1c20c41b"
+            "07ad45be1054c3b7635d0229acfe6dfb3b1aae441274aeb0f64e3e4afba7b40d"
+        ),
+        hex!(
+            "4a4a7c35d1634db899fc46baf068b3ea165e170df38bb122a09c399f00b8ef24"
+            "c59b3c47540e433b59914c7b377b6a848eafb6826ef771530f7644df58098809"
+        ),
+        hex!(
+            "a17037533986158d4506f61c9f798ffa97b4b9f0c4367a8660b3d1896cb31d29"
+            "09be4034a46bc9cfe87f7a8264885233efe183f2fcfa878cb4182e9fb6ec0630"
+        ),
+        hex!(
+            "5f5e9fa5e8300a1b19e9edd5793d9e6de51430a1471bab1a5f3ba322a31ce51a"
+            "9d0de8915e39a23b10df911fba148ef521c7cdaec88c51e93080643a8642aa25"
+        ),
+    ],
+};
@@ -108,8 +108,8 @@ impl Prover {
                     .execute(env, elf)
                     .context("Executor failed to generate a successful session")?;
 
-                let receipt = Receipt {
-                    inner: InnerReceipt::Fake {
+                let receipt = Receipt::new(
+                    InnerReceipt::Fake {
                         claim: ReceiptClaim {
                             pre: MaybePruned::Pruned(Digest::ZERO),
                             post: MaybePruned::Pruned(Digest::ZERO),
@@ -118,8 +118,8 @@ impl Prover {
                             output: None.into(),
                         },
                     },
-                    journal: session.journal,
-                };
+                    session.journal.bytes,
+                );
                 let receipt_bytes = bincode::serialize(&receipt)?;
                 self.storage
                     .write()?
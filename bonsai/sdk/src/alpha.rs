@@ -44,6 +44,46 @@ pub enum SdkErr {
     /// Missing file
     #[error("failed to find file on disk")]
     FileNotFound(#[from] std::io::Error),
+    /// Unsuccessful HTTP response
+    #[error("HTTP request failed with status {status}: `{body}`")]
+    HttpStatusErr {
+        /// HTTP status code of the response
+        status: u16,
+        /// Body of the response
+        body: String,
+    },
+    /// Request still failing after exhausting all retries
+    #[error("request failed after {attempts} attempts")]
+    RetriesExhausted {
+        /// Number of attempts made
+        attempts: u32,
+        /// Error of the last attempt
+        #[source]
+        source: Box<SdkErr>,
+    },
+    /// Session or SNARK proof ended in a state other than `SUCCEEDED`
+    #[error("proof ended with status {status}: `{error_msg}`")]
+    ProofFailed {
+        /// Final status
+        status: String,
+        /// Error message reported by bonsai
+        error_msg: String,
+    },
+}
+
+impl SdkErr {
+    /// Returns true if the request that produced this error may succeed if
+    /// retried
+    ///
+    /// Transport failures, rate limiting and server side errors are
+    /// retryable, other client errors are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SdkErr::HttpErr(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+            SdkErr::HttpStatusErr { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
 }
 
 /// Collection of serialization object for the REST api
@@ -51,7 +91,7 @@ pub mod responses {
     use serde::{Deserialize, Serialize};
 
     /// Response of a upload request
-    #[derive(Deserialize, Serialize)]
+    #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct UploadRes {
         /// Presigned URL to be supplied to a PUT request
         pub url: String,
@@ -12,9 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::alpha::{
-    responses::{Quotas, SessionStatusRes, SnarkStatusRes},
-    Client, SdkErr, SessionId, SnarkId,
+use std::{future::Future, time::Duration};
+
+use bytes::Bytes;
+use futures::{stream, Stream};
+use reqwest::{header, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    alpha::{
+        responses::{
+            CreateSessRes, ImgUploadRes, ProofReq, Quotas, SessionStatusRes, SnarkReq,
+            SnarkStatusRes, UploadRes, VersionInfo,
+        },
+        Client, SdkErr, SessionId, SnarkId,
+    },
+    API_KEY_ENVVAR, API_KEY_HEADER, API_URL_ENVVAR, VERSION_HEADER,
 };
 
 /// Construct a Bonsai SDK Client from env var
@@ -142,3 +155,577 @@ pub async fn quotas(bonsai_client: Client) -> Result<Quotas, SdkErr> {
         .await
         .map_err(|err| SdkErr::InternalServerErr(format!("{err}")))?
 }
+
+/// Retry policy of the [AsyncClient]
+///
+/// Retryable failures, see [SdkErr::is_retryable], are retried after a delay
+/// doubling on each attempt, starting at `initial_backoff` and capped at
+/// `max_backoff`.
+///
+/// Only idempotent requests are retried. Requests creating a session or a
+/// SNARK proof are sent once: a request that failed in transit may still have
+/// been processed, and sending it again could start a duplicate proof.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy failing on the first error
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before the retry following the given attempt, counted from 0
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+/// Handle to an upload whose presigned URL has been allocated
+///
+/// The handle can be persisted and passed back to [AsyncClient::resume_upload]
+/// to complete an interrupted upload without allocating a new UUID. The
+/// presigned URL expires, so the upload must be completed within its validity.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UploadHandle {
+    /// Presigned URL to be supplied to a PUT request
+    pub url: String,
+    /// UUID of the uploaded object
+    pub uuid: String,
+}
+
+impl From<UploadRes> for UploadHandle {
+    fn from(res: UploadRes) -> Self {
+        Self {
+            url: res.url,
+            uuid: res.uuid,
+        }
+    }
+}
+
+/// Send `request` once, turning unsuccessful responses into errors
+async fn send_once(request: RequestBuilder) -> Result<Response, SdkErr> {
+    let res = request.send().await?;
+    if !res.status().is_success() {
+        let status = res.status().as_u16();
+        let body = res.text().await?;
+        return Err(SdkErr::HttpStatusErr { status, body });
+    }
+    Ok(res)
+}
+
+/// Asynchronous client of the REST api
+///
+/// Unlike [Client], requests are natively async and idempotent requests are
+/// retried according to a [RetryPolicy]. Failures are reported with typed
+/// [SdkErr] variants.
+#[derive(Clone)]
+pub struct AsyncClient {
+    pub(crate) url: String,
+    pub(crate) client: reqwest::Client,
+    pub(crate) retry: RetryPolicy,
+}
+
+/// Creates a [reqwest::Client] for internal connection pooling
+fn construct_async_req_client(api_key: &str, version: &str) -> Result<reqwest::Client, SdkErr> {
+    let mut headers = header::HeaderMap::new();
+    headers.insert(API_KEY_HEADER, header::HeaderValue::from_str(api_key)?);
+    headers.insert(VERSION_HEADER, header::HeaderValue::from_str(version)?);
+
+    Ok(reqwest::Client::builder()
+        .default_headers(headers)
+        .build()?)
+}
+
+impl AsyncClient {
+    /// Construct a [AsyncClient] from env vars
+    ///
+    /// Uses the BONSAI_API_URL and BONSAI_API_KEY environment variables to
+    /// construct a client. The risc0_version should be the crate version of the
+    /// risc0-zkvm crate
+    pub fn from_env(risc0_version: &str) -> Result<Self, SdkErr> {
+        let api_url = std::env::var(API_URL_ENVVAR).map_err(|_| SdkErr::MissingApiUrl)?;
+        let api_key = std::env::var(API_KEY_ENVVAR).map_err(|_| SdkErr::MissingApiKey)?;
+        Self::from_parts(api_url, api_key, risc0_version)
+    }
+
+    /// Construct a [AsyncClient] from url, api key, and zkvm version
+    pub fn from_parts(url: String, key: String, risc0_version: &str) -> Result<Self, SdkErr> {
+        let client = construct_async_req_client(&key, risc0_version)?;
+        let url = url.strip_suffix('/').unwrap_or(&url).to_string();
+        Ok(Self {
+            url,
+            client,
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Replace the [RetryPolicy] of the client
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Retry `op` according to the [RetryPolicy] of the client
+    async fn retry<T, F, Fut>(&self, mut op: F) -> Result<T, SdkErr>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SdkErr>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let err = match op().await {
+                Ok(val) => return Ok(val),
+                Err(err) if !err.is_retryable() => return Err(err),
+                Err(err) => err,
+            };
+            if attempt >= self.retry.max_retries {
+                return Err(SdkErr::RetriesExhausted {
+                    attempts: attempt + 1,
+                    source: Box::new(err),
+                });
+            }
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Send the idempotent request built by `request`, retrying on failures
+    async fn send<F>(&self, request: F) -> Result<Response, SdkErr>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        self.retry(|| send_once(request())).await
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, SdkErr> {
+        Ok(self.send(|| self.client.get(url)).await?.json().await?)
+    }
+
+    async fn post_json<B: Serialize, T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &B,
+    ) -> Result<T, SdkErr> {
+        Ok(send_once(self.client.post(url).json(body))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Upload `buf` to `handle`, retrying on failures
+    ///
+    /// Presigned URLs only accept the whole object in a single PUT request, so
+    /// each attempt sends the whole buffer again.
+    async fn upload(&self, handle: &UploadHandle, buf: Bytes) -> Result<(), SdkErr> {
+        self.send(|| self.client.put(&handle.url).body(buf.clone()))
+            .await?;
+        Ok(())
+    }
+
+    // - uploads
+
+    /// Allocate an upload to the /inputs/ route
+    pub async fn begin_input_upload(&self) -> Result<UploadHandle, SdkErr> {
+        let res: UploadRes = self
+            .get_json(&format!("{}/inputs/upload", self.url))
+            .await?;
+        Ok(res.into())
+    }
+
+    /// Allocate an upload to the /receipts/ route
+    pub async fn begin_receipt_upload(&self) -> Result<UploadHandle, SdkErr> {
+        let res: UploadRes = self
+            .get_json(&format!("{}/receipts/upload", self.url))
+            .await?;
+        Ok(res.into())
+    }
+
+    /// Upload `buf` to a previously allocated upload, returning its UUID
+    ///
+    /// This completes an upload that failed, for instance after the retries of
+    /// [AsyncClient::upload_input] were exhausted, against the same presigned
+    /// URL, so that the upload is not allocated a new UUID. Presigned URLs do
+    /// not support partial uploads, so the whole buffer is sent again.
+    pub async fn resume_upload(
+        &self,
+        handle: &UploadHandle,
+        buf: impl Into<Bytes>,
+    ) -> Result<String, SdkErr> {
+        self.upload(handle, buf.into()).await?;
+        Ok(handle.uuid.clone())
+    }
+
+    /// Upload a input buffer to the /inputs/ route
+    pub async fn upload_input(&self, buf: impl Into<Bytes>) -> Result<String, SdkErr> {
+        let handle = self.begin_input_upload().await?;
+        self.upload(&handle, buf.into()).await?;
+        Ok(handle.uuid)
+    }
+
+    /// Upload a receipt buffer to the /receipts/ route
+    pub async fn upload_receipt(&self, buf: impl Into<Bytes>) -> Result<String, SdkErr> {
+        let handle = self.begin_receipt_upload().await?;
+        self.upload(&handle, buf.into()).await?;
+        Ok(handle.uuid)
+    }
+
+    /// Upload a image buffer to the /images/ route
+    ///
+    /// The boolean return indicates if the image already exists in bonsai
+    ///
+    /// The image data can be either:
+    /// * ELF file bytes
+    /// * bincode encoded MemoryImage
+    pub async fn upload_img(&self, image_id: &str, buf: impl Into<Bytes>) -> Result<bool, SdkErr> {
+        let url = format!("{}/images/upload/{}", self.url, image_id);
+        let res = self.send(|| self.client.get(&url)).await?;
+        if res.status() == 204 {
+            return Ok(true);
+        }
+        let upload: ImgUploadRes = res.json().await?;
+
+        let buf = buf.into();
+        self.send(|| self.client.put(&upload.url).body(buf.clone()))
+            .await?;
+        Ok(false)
+    }
+
+    // - /sessions
+
+    /// Create a new proof request Session
+    ///
+    /// Supply the image_id and input_id created from uploading those files in
+    /// previous steps
+    pub async fn create_session(
+        &self,
+        img_id: String,
+        input_id: String,
+        assumptions: Vec<String>,
+    ) -> Result<SessionId, SdkErr> {
+        let req = ProofReq {
+            img: img_id,
+            input: input_id,
+            assumptions,
+        };
+        let res: CreateSessRes = self
+            .post_json(&format!("{}/sessions/create", self.url), &req)
+            .await?;
+        Ok(SessionId::new(res.uuid))
+    }
+
+    /// Fetches the current status of the Session
+    pub async fn session_status(&self, session: &SessionId) -> Result<SessionStatusRes, SdkErr> {
+        self.get_json(&format!("{}/sessions/status/{}", self.url, session.uuid))
+            .await
+    }
+
+    /// Fetches the zkvm guest logs for a session
+    ///
+    /// See [SessionId::logs].
+    pub async fn session_logs(&self, session: &SessionId) -> Result<String, SdkErr> {
+        let url = format!("{}/sessions/logs/{}", self.url, session.uuid);
+        Ok(self.send(|| self.client.get(&url)).await?.text().await?)
+    }
+
+    /// Stream the status of the Session until it leaves the `RUNNING` state
+    ///
+    /// The session is polled every `interval`, and a status is yielded each
+    /// time its status or proving state changes. The stream ends after the
+    /// final status, or after the first error.
+    pub fn session_status_stream(
+        &self,
+        session: SessionId,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<SessionStatusRes, SdkErr>> {
+        let state = (self.clone(), session, None, false);
+        stream::unfold(Some(state), move |state| async move {
+            let (client, session, mut last, mut wait) = state?;
+            loop {
+                if wait {
+                    tokio::time::sleep(interval).await;
+                }
+                wait = true;
+                let status = match client.session_status(&session).await {
+                    Ok(status) => status,
+                    Err(err) => return Some((Err(err), None)),
+                };
+                if status.status != "RUNNING" {
+                    return Some((Ok(status), None));
+                }
+                let current = Some((status.status.clone(), status.state.clone()));
+                if current != last {
+                    last = current;
+                    return Some((Ok(status), Some((client, session, last, wait))));
+                }
+            }
+        })
+    }
+
+    /// Wait for the Session to complete, polling its status every `interval`
+    ///
+    /// Returns [SdkErr::ProofFailed] if the session does not succeed.
+    pub async fn wait_for_session(
+        &self,
+        session: &SessionId,
+        interval: Duration,
+    ) -> Result<SessionStatusRes, SdkErr> {
+        loop {
+            let status = self.session_status(session).await?;
+            match status.status.as_str() {
+                "RUNNING" => tokio::time::sleep(interval).await,
+                "SUCCEEDED" => return Ok(status),
+                _ => {
+                    return Err(SdkErr::ProofFailed {
+                        status: status.status,
+                        error_msg: status.error_msg.unwrap_or_default(),
+                    })
+                }
+            }
+        }
+    }
+
+    // - /snark
+
+    /// Requests a SNARK proof be created from a existing sessionId
+    pub async fn create_snark(&self, session_id: String) -> Result<SnarkId, SdkErr> {
+        let req = SnarkReq { session_id };
+        let res: CreateSessRes = self
+            .post_json(&format!("{}/snark/create", self.url), &req)
+            .await?;
+        Ok(SnarkId::new(res.uuid))
+    }
+
+    /// Fetches the current status of the Snark Session
+    pub async fn snark_status(&self, snark: &SnarkId) -> Result<SnarkStatusRes, SdkErr> {
+        self.get_json(&format!("{}/snark/status/{}", self.url, snark.uuid))
+            .await
+    }
+
+    // Utilities
+
+    /// Download a given url to a buffer
+    ///
+    /// Useful to download a [SessionId] receipt_url
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, SdkErr> {
+        Ok(self
+            .send(|| self.client.get(url))
+            .await?
+            .bytes()
+            .await?
+            .into())
+    }
+
+    /// Fetches the current component versions from bonsai
+    pub async fn version(&self) -> Result<VersionInfo, SdkErr> {
+        self.get_json(&format!("{}/version", self.url)).await
+    }
+
+    /// Fetches your current users quotas
+    pub async fn quotas(&self) -> Result<Quotas, SdkErr> {
+        self.get_json(&format!("{}/user/quotas", self.url)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+
+    use futures::StreamExt;
+    use httpmock::prelude::*;
+    use uuid::Uuid;
+
+    use super::*;
+
+    const TEST_KEY: &str = "TESTKEY";
+    const TEST_VERSION: &str = "0.1.0";
+
+    fn test_client(server: &MockServer) -> AsyncClient {
+        AsyncClient::from_parts(server.base_url(), TEST_KEY.to_string(), TEST_VERSION)
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+            })
+    }
+
+    #[test]
+    fn backoff() {
+        let retry = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+        };
+        assert_eq!(retry.backoff(0), Duration::from_secs(1));
+        assert_eq!(retry.backoff(1), Duration::from_secs(2));
+        assert_eq!(retry.backoff(2), Duration::from_secs(4));
+        assert_eq!(retry.backoff(3), Duration::from_secs(5));
+        assert_eq!(retry.backoff(64), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn retry_server_errors() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/version");
+                then.status(503).body("unavailable");
+            })
+            .await;
+
+        let err = test_client(&server).version().await.err().unwrap();
+        match err {
+            SdkErr::RetriesExhausted { attempts, source } => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*source, SdkErr::HttpStatusErr { status: 503, .. }));
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+        mock.assert_hits_async(3).await;
+    }
+
+    #[tokio::test]
+    async fn fail_fast_on_client_errors() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/version");
+                then.status(401).body("unauthorized");
+            })
+            .await;
+
+        let err = test_client(&server).version().await.err().unwrap();
+        assert!(matches!(err, SdkErr::HttpStatusErr { status: 401, .. }));
+        mock.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn no_retry_on_session_create() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/sessions/create");
+                then.status(503).body("unavailable");
+            })
+            .await;
+
+        let err = test_client(&server)
+            .create_session("img".to_string(), "input".to_string(), vec![])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdkErr::HttpStatusErr { status: 503, .. }));
+        mock.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn resume_upload() {
+        let server = MockServer::start_async().await;
+        let input_uuid = Uuid::new_v4().to_string();
+        let handle = UploadHandle {
+            url: server.url(format!("/upload/{input_uuid}")),
+            uuid: input_uuid.clone(),
+        };
+
+        let put_mock = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path(format!("/upload/{input_uuid}"))
+                    .body("data");
+                then.status(200);
+            })
+            .await;
+
+        let uuid = test_client(&server)
+            .resume_upload(&handle, b"data".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(uuid, input_uuid);
+        put_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn retry_upload() {
+        let server = MockServer::start_async().await;
+        let input_uuid = Uuid::new_v4().to_string();
+        let path = format!("/upload/{input_uuid}");
+
+        let upload_mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/inputs/upload");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&UploadRes {
+                        url: server.url(&path),
+                        uuid: input_uuid.clone(),
+                    });
+            })
+            .await;
+        let put_mock = server
+            .mock_async(|when, then| {
+                when.method(PUT).path(&path).body("data");
+                then.status(503);
+            })
+            .await;
+
+        // Failed transfers send the whole buffer again, to the same URL.
+        let err = test_client(&server)
+            .upload_input(b"data".to_vec())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdkErr::RetriesExhausted { attempts: 3, .. }));
+        upload_mock.assert_async().await;
+        put_mock.assert_hits_async(3).await;
+    }
+
+    #[tokio::test]
+    async fn session_status_stream() {
+        let server = MockServer::start_async().await;
+        let session_id = SessionId::new(Uuid::new_v4().to_string());
+        let response = SessionStatusRes {
+            status: "SUCCEEDED".to_string(),
+            receipt_url: Some("receipt".to_string()),
+            error_msg: None,
+            state: None,
+        };
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/sessions/status/{}", session_id.uuid))
+                    .header(API_KEY_HEADER, TEST_KEY)
+                    .header(VERSION_HEADER, TEST_VERSION);
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&response);
+            })
+            .await;
+
+        let mut stream =
+            pin!(test_client(&server).session_status_stream(session_id, Duration::from_millis(1)));
+        let status = stream.next().await.unwrap().unwrap();
+        assert_eq!(status.status, "SUCCEEDED");
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Solana program verifying RISC Zero Groth16 receipts.
+//!
+//! The program accepts the receipts of the zkVM release matching the control
+//! IDs in [CONTROL_ID_0] and [CONTROL_ID_1], which are generated along with the
+//! verifying key by `cargo xtask bootstrap-groth16`. Like the `verify` method
+//! of the Solidity verifier, it checks that the receipt claims a halted
+//! execution with exit code 0, no input and no assumptions.
+//!
+//! The instruction data is the Groth16 seal followed by the image ID, the post
+//! state digest and the SHA-256 digest of the journal. It is encoded by
+//! [verify_instruction]. The program fails with [VerifierError] if the receipt
+//! is invalid, so other programs can verify receipts through a CPI. Note that
+//! verifying a receipt needs more than the default compute budget of an
+//! instruction.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, hash::hashv, instruction::Instruction,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+pub mod verifier;
+mod vk;
+
+use verifier::{verify_groth16, Seal};
+pub use vk::{CONTROL_ID_0, CONTROL_ID_1, VERIFYING_KEY};
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// Length of the instruction data accepted by the program.
+pub const INSTRUCTION_DATA_LEN: usize = Seal::LEN + 3 * 32;
+
+/// Errors returned by the verifier program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifierError {
+    /// The instruction data is not a seal followed by three digests.
+    InvalidInstructionData = 0,
+    /// The seal is not a valid proof for the claim.
+    InvalidProof = 1,
+}
+
+impl From<VerifierError> for ProgramError {
+    fn from(err: VerifierError) -> Self {
+        ProgramError::Custom(err as u32)
+    }
+}
+
+/// Encode the instruction data verifying a receipt for the given image ID,
+/// post state digest and journal digest.
+///
+/// `seal` is the seal of a `CompactReceipt`, the same bytes that are passed to
+/// the Solidity verifier.
+pub fn encode_instruction_data(
+    seal: &[u8],
+    image_id: &[u8; 32],
+    post_state_digest: &[u8; 32],
+    journal_digest: &[u8; 32],
+) -> Vec<u8> {
+    [seal, image_id, post_state_digest, journal_digest].concat()
+}
+
+/// Build an instruction calling the verifier program deployed at `program_id`.
+pub fn verify_instruction(
+    program_id: Pubkey,
+    seal: &[u8],
+    image_id: &[u8; 32],
+    post_state_digest: &[u8; 32],
+    journal_digest: &[u8; 32],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id,
+        &encode_instruction_data(seal, image_id, post_state_digest, journal_digest),
+        vec![],
+    )
+}
+
+/// Entrypoint of the verifier program.
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if instruction_data.len() != INSTRUCTION_DATA_LEN {
+        return Err(VerifierError::InvalidInstructionData.into());
+    }
+    let (seal, digests) = instruction_data.split_at(Seal::LEN);
+    let seal = Seal::from_bytes(seal).ok_or(VerifierError::InvalidInstructionData)?;
+    let digest = |i: usize| -> [u8; 32] { digests[i * 32..(i + 1) * 32].try_into().unwrap() };
+
+    let claim_digest = claim_digest(&digest(0), &digest(1), &digest(2));
+    let (claim_0, claim_1) = split_digest(&claim_digest);
+    match verify_groth16(
+        &VERIFYING_KEY,
+        &seal,
+        &[CONTROL_ID_0, CONTROL_ID_1, claim_0, claim_1],
+    ) {
+        Ok(true) => Ok(()),
+        _ => Err(VerifierError::InvalidProof.into()),
+    }
+}
+
+/// Compute the digest of the claim of a halted execution with exit code 0, no
+/// input and no assumptions, matching `ReceiptClaimLib.digest` in Solidity.
+pub fn claim_digest(
+    image_id: &[u8; 32],
+    post_state_digest: &[u8; 32],
+    journal_digest: &[u8; 32],
+) -> [u8; 32] {
+    let output_digest = hashv(&[
+        &hashv(&[b"risc0.Output"]).to_bytes(),
+        journal_digest,
+        &[0u8; 32],
+        &[2, 0],
+    ]);
+    hashv(&[
+        &hashv(&[b"risc0.ReceiptClaim"]).to_bytes(),
+        &[0u8; 32],
+        image_id,
+        post_state_digest,
+        &output_digest.to_bytes(),
+        &[0u8; 4],
+        &[0u8; 4],
+        &[4, 0],
+    ])
+    .to_bytes()
+}
+
+/// Split a digest into the two public inputs of the Groth16 circuit, as done
+/// by `splitDigest` in Solidity: the digest is read as a little endian integer
+/// and split into its lower and upper 128 bits.
+pub fn split_digest(digest: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let mut lower = [0u8; 32];
+    let mut upper = [0u8; 32];
+    for i in 0..16 {
+        lower[31 - i] = digest[i];
+        upper[31 - i] = digest[16 + i];
+    }
+    (lower, upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    // Receipt from bonsai/ethereum/test/TestReceipt.sol.
+    const SEAL: [u8; 256] = hex!(
+        "102866064c0a93f24ab85854cbb1f2904f3e345bdd567e1f0ef36e819c5441aa"
+        "24cd64818e919f0cb611b69f717421dc0b859d748325506b76bd9f3b351869be"
+        "1b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae046"
+        "0d37ee6b3eefb8cd9985612c81fbbef3e6e25aa2f2ebc35c23f882de07fa5109"
+        "0e96423125defa12ccf193e6bf2ed33ce3ea2085a40ed1c88af7dd1f71d7fec9"
+        "0c5905ea06c54c2f8f27be48dcd055836066f9706f8ab1c3984a321fc2e4f11d"
+        "16c7e632d68f81dca000df105273ad45a22b03a69752ccb9d8ee2878166ff41a"
+        "1de23df9c89ef27a77980f84d8d780890cb89fe983f812a4fb6222797c0e529f"
+    );
+    const POST_DIGEST: [u8; 32] =
+        hex!("0c832dac05ecf2eaf58d3cfff95e3b51e18314d377483703fd1f08296365ea71");
+    const IMAGE_ID: [u8; 32] =
+        hex!("59eeb99a627ecaf527fa51262e797fc39ba69ad9ff36e9b2f5a634edc9fc622d");
+
+    fn journal_digest() -> [u8; 32] {
+        hashv(&[b""]).to_bytes()
+    }
+
+    #[test]
+    fn verify_test_receipt() {
+        let data = encode_instruction_data(&SEAL, &IMAGE_ID, &POST_DIGEST, &journal_digest());
+        assert_eq!(data.len(), INSTRUCTION_DATA_LEN);
+        process_instruction(&Pubkey::default(), &[], &data).unwrap();
+    }
+
+    #[test]
+    fn reject_wrong_journal() {
+        let data = encode_instruction_data(&SEAL, &IMAGE_ID, &POST_DIGEST, &[1u8; 32]);
+        assert_eq!(
+            process_instruction(&Pubkey::default(), &[], &data),
+            Err(VerifierError::InvalidProof.into())
+        );
+    }
+
+    #[test]
+    fn reject_truncated_seal() {
+        let data =
+            encode_instruction_data(&SEAL[..255], &IMAGE_ID, &POST_DIGEST, &journal_digest());
+        assert_eq!(
+            process_instruction(&Pubkey::default(), &[], &data),
+            Err(VerifierError::InvalidInstructionData.into())
+        );
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Groth16 verification over BN254 using the Solana alt_bn128 syscalls.
+//!
+//! Points are encoded in big endian as expected by the syscalls, which follow
+//! the layout of the Ethereum precompiles: G1 points are `x || y` and G2
+//! points are `x_c1 || x_c0 || y_c1 || y_c0`. This is also the layout of the
+//! seal accepted by the Solidity verifier.
+
+use solana_program::alt_bn128::{
+    prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing},
+    AltBn128Error,
+};
+
+/// Modulus of the base field of BN254.
+const BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Number of public inputs of the RISC Zero Groth16 circuit.
+pub const NUM_PUBLIC_INPUTS: usize = 4;
+
+/// Groth16 verifying key.
+pub struct VerifyingKey {
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    pub ic: [[u8; 64]; NUM_PUBLIC_INPUTS + 1],
+}
+
+/// Groth16 proof.
+pub struct Seal {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+impl Seal {
+    /// Length of an encoded seal.
+    pub const LEN: usize = 256;
+
+    /// Decode a seal from `a || b || c`, as produced by `risc0_groth16::Seal::to_vec`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+        Some(Self {
+            a: bytes[..64].try_into().ok()?,
+            b: bytes[64..192].try_into().ok()?,
+            c: bytes[192..].try_into().ok()?,
+        })
+    }
+}
+
+// Negates a G1 point by replacing its y coordinate with `q - y`.
+fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut negated = *point;
+    if point[32..].iter().all(|byte| *byte == 0) {
+        return negated;
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = BASE_FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        negated[32 + i] = diff.rem_euclid(256) as u8;
+    }
+    negated
+}
+
+/// Verify a Groth16 proof for the given public inputs, each encoded as a big
+/// endian scalar.
+pub fn verify_groth16(
+    vk: &VerifyingKey,
+    seal: &Seal,
+    public_inputs: &[[u8; 32]; NUM_PUBLIC_INPUTS],
+) -> Result<bool, AltBn128Error> {
+    let mut vk_x = vk.ic[0].to_vec();
+    for (input, ic) in public_inputs.iter().zip(&vk.ic[1..]) {
+        let product = alt_bn128_multiplication(&[&ic[..], &input[..]].concat())?;
+        vk_x = alt_bn128_addition(&[&vk_x[..], &product[..]].concat())?;
+    }
+
+    let pairing_input = [
+        &negate_g1(&seal.a)[..],
+        &seal.b,
+        &vk.alpha_g1,
+        &vk.beta_g2,
+        &vk_x,
+        &vk.gamma_g2,
+        &seal.c,
+        &vk.delta_g2,
+    ]
+    .concat();
+    let result = alt_bn128_pairing(&pairing_input)?;
+    Ok(result.last() == Some(&1) && result[..result.len() - 1].iter().all(|byte| *byte == 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negate_g1_adds_up_to_modulus() {
+        let mut point = [0u8; 64];
+        point[63] = 2;
+        let negated = negate_g1(&point);
+        let mut expected = BASE_FIELD_MODULUS;
+        expected[31] -= 2;
+        assert_eq!(negated[32..], expected);
+        assert_eq!(negate_g1(&[0u8; 64]), [0u8; 64]);
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+// Constants from: bonsai/ethereum/contracts/groth16/Groth16Verifier.sol
+// and bonsai/ethereum/contracts/groth16/ControlID.sol
+
+use hex_literal::hex;
+
+use crate::verifier::VerifyingKey;
+
+/// Control ID hash for the identity_p254 predicate decomposed by `split_digest`.
+pub const CONTROL_ID_0: [u8; 32] =
+    hex!("00000000000000000000000000000000447d7e12291364db4bc5421164880129");
+pub const CONTROL_ID_1: [u8; 32] =
+    hex!("0000000000000000000000000000000012c49ad247d28a32147e13615c6c81f9");
+
+/// Verifying key of the RISC Zero Groth16 circuit, in the encoding expected by
+/// the alt_bn128 syscalls.
+pub const VERIFYING_KEY: VerifyingKey = VerifyingKey {
+    alpha_g1: hex!(
+        "2d4d9aa7e302d9df41749d5507949d05dbea33fbb16c643b22f599a2be6df2e2"
+        "14bedd503c37ceb061d8ec60209fe345ce89830a19230301f076caff004d1926"
+    ),
+    beta_g2: hex!(
+        "0967032fcbf776d1afc985f88877f182d38480a653f2decaa9794cbc3bf3060c"
+        "0e187847ad4c798374d0d6732bf501847dd68bc0e071241e0213bc7fc13db7ab"
+        "304cfbd1e08a704a99f5e847d93f8c3caafddec46b7a0d379da69a4d112346a7"
+        "1739c1b1a457a8c7313123d24d2f9192f896b7c63eea05a9d57f06547ad0cec8"
+    ),
+    gamma_g2: hex!(
+        "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"
+        "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"
+        "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"
+        "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+    ),
+    delta_g2: hex!(
+        "11025c0ceb789a72e20d7a202330bb4ecdcd60d37373142263289ab55a9e8f22"
+        "27faf293c7fa98975bd65c56d27756dc146e8b41e2fe9531fca7edd64e044e8f"
+        "1045439e16a239ff606157e444bbdedca039984becc852209c7c8fdf5e9ec52e"
+        "2a598806dc38bae77014dad6b3be98825c942994b3acc034b3b975c629eb74a8"
+    ),
+    ic: [
+        hex!(
+            "08b9e74a3da9b48d618e433c9e8a854c76a579c711f9648ea74185e7b8a7d3d9"
+            "16058d7a6687667caa6ff67ce140a0b7eeeb17a1655255f35d3b9cdd3ce8eec0"
+        ),
+        hex!(
+            "1bc4201c3c671098bcdb4bbc9da88a9c45d8882e044869e3e03e5e965d557449"
+            "0db4a7fb4a3e4ef6b0ae741244ae1a3bfb6dfeac29025d63b7c35410be45ad07"
+        ),
+        hex!(
+            "24efb8009f399ca022b18bf30d175e16eab368f0ba46fc99b84d63d1357c4a4a"
+            "09880958df44760f5371f76e82b6af8e846a7b377b4c91593b430e54473c9bc5"
+        ),
+        hex!(
+            "291db36c89d1b360867a36c4f0b9b497fa8f799f1cf606458d158639533770a1"
+            "3006ecb69f2e18b48c87fafcf283e1ef33528864827a7fe8cfc96ba43440be09"
+        ),
+        hex!(
+            "1ae51ca322a33b5f1aab1b47a13014e56d9e3d79d5ede9191b0a30e8a59f5e5f"
+            "25aa42863a648030e9518cc8aecdc721f58e14ba1f91df103ba2395e91e80d9d"
+        ),
+    ],
+};
@@ -0,0 +1,105 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to verify RISC Zero Groth16 receipts with the
+//! `risc0::groth16_verifier` Move module on Sui.
+//!
+//! The Move module takes the seal as arkworks compressed proof points, see
+//! [proof_points], and the digests as 32 bytes vectors. [VerifyArgs] encodes
+//! the arguments of `verify` as BCS, as expected for pure arguments of a
+//! programmable transaction.
+
+use anyhow::Result;
+use risc0_groth16::Seal;
+
+/// Convert the seal of a `CompactReceipt`, the same bytes that are passed to
+/// the Solidity verifier, into the proof points expected by the Move module.
+pub fn proof_points(seal: &[u8]) -> Result<Vec<u8>> {
+    // The compressed seal is encoded in big endian, while Sui expects the
+    // little endian encoding of arkworks, so each point is reversed.
+    let compressed = Seal::from_vec(seal)?.to_compressed_vec()?;
+    let (a, rest) = compressed.split_at(32);
+    let (b, c) = rest.split_at(64);
+    Ok([a, b, c]
+        .into_iter()
+        .flat_map(|point| point.iter().rev().copied())
+        .collect())
+}
+
+/// Arguments of `groth16_verifier::verify`.
+#[derive(Clone, Debug)]
+pub struct VerifyArgs {
+    pub proof_points: Vec<u8>,
+    pub image_id: [u8; 32],
+    pub post_state_digest: [u8; 32],
+    pub journal_digest: [u8; 32],
+}
+
+impl VerifyArgs {
+    /// Build the arguments verifying a receipt for the given image ID, post
+    /// state digest and journal digest.
+    pub fn new(
+        seal: &[u8],
+        image_id: [u8; 32],
+        post_state_digest: [u8; 32],
+        journal_digest: [u8; 32],
+    ) -> Result<Self> {
+        Ok(Self {
+            proof_points: proof_points(seal)?,
+            image_id,
+            post_state_digest,
+            journal_digest,
+        })
+    }
+
+    /// Encode each argument as a BCS `vector<u8>`, in the order of the
+    /// parameters of `verify`.
+    pub fn to_pure_args(&self) -> Result<Vec<Vec<u8>>> {
+        Ok(vec![
+            bcs::to_bytes(&self.proof_points)?,
+            bcs::to_bytes(&self.image_id.to_vec())?,
+            bcs::to_bytes(&self.post_state_digest.to_vec())?,
+            bcs::to_bytes(&self.journal_digest.to_vec())?,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Seal from bonsai/ethereum/test/TestReceipt.sol.
+    const SEAL: &str = "102866064c0a93f24ab85854cbb1f2904f3e345bdd567e1f0ef36e819c5441aa24cd64818e919f0cb611b69f717421dc0b859d748325506b76bd9f3b351869be1b0e3056568cbdfeadfbd01a6ca4bc84934f73137f9051e0a1968b132c7ae0460d37ee6b3eefb8cd9985612c81fbbef3e6e25aa2f2ebc35c23f882de07fa51090e96423125defa12ccf193e6bf2ed33ce3ea2085a40ed1c88af7dd1f71d7fec90c5905ea06c54c2f8f27be48dcd055836066f9706f8ab1c3984a321fc2e4f11d16c7e632d68f81dca000df105273ad45a22b03a69752ccb9d8ee2878166ff41a1de23df9c89ef27a77980f84d8d780890cb89fe983f812a4fb6222797c0e529f";
+
+    // Proof points used by tests/groth16_verifier_tests.move.
+    const PROOF_POINTS: &str = "aa41549c816ef30e1f7e56dd5b343e4f90f2b1cb5458b84af2930a4c066628900951fa07de82f8235cc3ebf2a25ae2e6f3befb812c618599cdb8ef3e6bee370d46e07a2c138b96a1e051907f13734f9384bca46c1ad0fbadfebd8c5656300e1b1af46f167828eed8b9cc5297a6032ba245ad735210df00a0dc818fd632e6c796";
+
+    #[test]
+    fn proof_points_match_move_tests() {
+        let seal = hex::decode(SEAL).unwrap();
+        assert_eq!(hex::encode(proof_points(&seal).unwrap()), PROOF_POINTS);
+    }
+
+    #[test]
+    fn pure_args_are_length_prefixed() {
+        let seal = hex::decode(SEAL).unwrap();
+        let args = VerifyArgs::new(&seal, [1; 32], [2; 32], [3; 32])
+            .unwrap()
+            .to_pure_args()
+            .unwrap();
+        assert_eq!(args[0][0], 128);
+        assert_eq!(args[0].len(), 129);
+        assert_eq!(args[1], [&[32u8][..], &[1; 32]].concat());
+    }
+}
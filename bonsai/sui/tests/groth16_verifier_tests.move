@@ -0,0 +1,41 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[test_only]
+module risc0::groth16_verifier_tests {
+    use std::hash::sha2_256;
+    use risc0::groth16_verifier;
+
+    // Receipt from bonsai/ethereum/test/TestReceipt.sol, with the seal
+    // converted by `risc0_sui_verifier::proof_points`.
+    const PROOF_POINTS: vector<u8> = x"aa41549c816ef30e1f7e56dd5b343e4f90f2b1cb5458b84af2930a4c066628900951fa07de82f8235cc3ebf2a25ae2e6f3befb812c618599cdb8ef3e6bee370d46e07a2c138b96a1e051907f13734f9384bca46c1ad0fbadfebd8c5656300e1b1af46f167828eed8b9cc5297a6032ba245ad735210df00a0dc818fd632e6c796";
+    const POST_DIGEST: vector<u8> = x"0c832dac05ecf2eaf58d3cfff95e3b51e18314d377483703fd1f08296365ea71";
+    const IMAGE_ID: vector<u8> = x"59eeb99a627ecaf527fa51262e797fc39ba69ad9ff36e9b2f5a634edc9fc622d";
+
+    #[test]
+    fun verify_test_receipt() {
+        assert!(groth16_verifier::verify(PROOF_POINTS, IMAGE_ID, POST_DIGEST, sha2_256(b"")), 0);
+    }
+
+    #[test]
+    fun reject_wrong_journal() {
+        assert!(!groth16_verifier::verify(PROOF_POINTS, IMAGE_ID, POST_DIGEST, sha2_256(b"journal")), 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = groth16_verifier::EInvalidDigest)]
+    fun reject_short_digest() {
+        groth16_verifier::verify(PROOF_POINTS, IMAGE_ID, POST_DIGEST, b"");
+    }
+}
@@ -0,0 +1,388 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read Ethereum account and storage state from a guest.
+//!
+//! The host answers the requests of the guest on the [SYS_EVM_STATE] syscall
+//! with the Merkle-Patricia proofs returned by `eth_getProof`, and the guest
+//! checks them against the state root of a block header it was given, so that
+//! the values it reads are authenticated by the hash of that block.
+
+pub mod mpt;
+
+use primitive_types::{H160, H256, U256};
+use risc0_zkvm::declare_syscall;
+#[cfg(target_os = "zkvm")]
+use risc0_zkvm::guest;
+use rlp::{Decodable, DecoderError, Rlp};
+use serde::{Deserialize, Serialize};
+
+pub use crate::mpt::MptError;
+
+declare_syscall!(
+    /// RISC0 syscall for requesting the proof of an account and its storage from the host.
+    pub SYS_EVM_STATE);
+
+/// Request sent by the guest on [SYS_EVM_STATE].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StateRequest {
+    /// Address of the account.
+    pub address: H160,
+    /// Storage slots to prove.
+    pub slots: Vec<H256>,
+}
+
+/// Account stored in the state trie.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Account {
+    pub nonce: u64,
+    pub balance: U256,
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
+impl Decodable for Account {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        Ok(Self {
+            nonce: rlp.val_at(0)?,
+            balance: rlp.val_at(1)?,
+            storage_root: rlp.val_at(2)?,
+            code_hash: rlp.val_at(3)?,
+        })
+    }
+}
+
+/// Proof of a storage slot in the storage trie of an account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StorageProof {
+    pub slot: H256,
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// Proof of an account in the state trie, along with proofs of its storage
+/// slots, as returned by `eth_getProof`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AccountProof {
+    pub address: H160,
+    pub proof: Vec<Vec<u8>>,
+    pub storage: Vec<StorageProof>,
+}
+
+/// Error returned when a proof supplied by the host is invalid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofError {
+    /// The proof does not match the trie.
+    Mpt(MptError),
+    /// The proven value could not be decoded.
+    InvalidValue,
+    /// The proof is for another account or storage slot.
+    UnexpectedKey,
+}
+
+impl core::fmt::Display for ProofError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProofError::Mpt(err) => write!(f, "invalid proof: {err}"),
+            ProofError::InvalidValue => write!(f, "invalid proven value"),
+            ProofError::UnexpectedKey => write!(f, "proof for an unexpected key"),
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+impl From<MptError> for ProofError {
+    fn from(err: MptError) -> Self {
+        ProofError::Mpt(err)
+    }
+}
+
+impl AccountProof {
+    /// Verify the proof of the account against `state_root`.
+    ///
+    /// Returns `None` if the account does not exist.
+    pub fn verify_account(&self, state_root: &H256) -> Result<Option<Account>, ProofError> {
+        let key = mpt::keccak(self.address);
+        let Some(value) = mpt::verify_proof(&state_root.0, &key, &self.proof)? else {
+            return Ok(None);
+        };
+        let account = Rlp::new(&value)
+            .as_val()
+            .map_err(|_| ProofError::InvalidValue)?;
+        Ok(Some(account))
+    }
+
+    /// Verify the proof of `slot` against the storage root of `account`.
+    ///
+    /// Returns zero for empty slots.
+    pub fn verify_storage(&self, account: &Account, slot: &H256) -> Result<U256, ProofError> {
+        let storage = self
+            .storage
+            .iter()
+            .find(|storage| storage.slot == *slot)
+            .ok_or(ProofError::UnexpectedKey)?;
+        let key = mpt::keccak(slot);
+        match mpt::verify_proof(&account.storage_root.0, &key, &storage.proof)? {
+            Some(value) => Rlp::new(&value)
+                .as_val()
+                .map_err(|_| ProofError::InvalidValue),
+            None => Ok(U256::zero()),
+        }
+    }
+}
+
+/// Fields of a block header used by the guest.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BlockCommitment {
+    /// Hash of the block.
+    pub hash: H256,
+    /// Number of the block.
+    pub number: u64,
+    /// State root of the block.
+    pub state_root: H256,
+}
+
+impl BlockCommitment {
+    /// Decode the RLP encoded header of a block.
+    pub fn from_header(header: &[u8]) -> Result<Self, DecoderError> {
+        let rlp = Rlp::new(header);
+        Ok(Self {
+            hash: H256(mpt::keccak(header)),
+            state_root: rlp.val_at(3)?,
+            number: rlp.val_at(8)?,
+        })
+    }
+}
+
+/// Journal committed by guests reading state with an [EvmStateReader].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ViewCallJournal {
+    /// Hash of the block the state was read at.
+    pub block_hash: H256,
+    /// Number of the block the state was read at.
+    pub block_number: u64,
+    /// Address of the account.
+    pub address: H160,
+    /// Storage slot read.
+    pub slot: H256,
+    /// Value of the storage slot.
+    pub value: U256,
+}
+
+/// Reader of the state of a block, for use in the guest.
+///
+/// Every value returned by the reader is checked against the state root of the
+/// block, so the guest must commit the hash of the block, and verifiers must
+/// check it against the chain.
+#[cfg(target_os = "zkvm")]
+pub struct EvmStateReader {
+    block: BlockCommitment,
+}
+
+#[cfg(target_os = "zkvm")]
+impl EvmStateReader {
+    /// Create a reader for the block with the given RLP encoded header.
+    pub fn new(header: &[u8]) -> Self {
+        Self {
+            block: BlockCommitment::from_header(header).expect("invalid block header"),
+        }
+    }
+
+    /// The block the state is read at.
+    pub fn block(&self) -> &BlockCommitment {
+        &self.block
+    }
+
+    fn request(&self, address: H160, slots: Vec<H256>) -> AccountProof {
+        let request = bincode::serialize(&StateRequest { address, slots }).unwrap();
+        let response: &[u8] = guest::env::send_recv_slice(SYS_EVM_STATE, &request);
+        let proof: AccountProof = bincode::deserialize(response).unwrap();
+        assert_eq!(proof.address, address, "proof for an unexpected account");
+        proof
+    }
+
+    /// Read the account at `address`, or `None` if it does not exist.
+    pub fn account(&self, address: H160) -> Option<Account> {
+        self.request(address, vec![])
+            .verify_account(&self.block.state_root)
+            .expect("invalid account proof")
+    }
+
+    /// Read the storage `slot` of the account at `address`.
+    ///
+    /// Returns zero if the account does not exist.
+    pub fn storage(&self, address: H160, slot: H256) -> U256 {
+        let proof = self.request(address, vec![slot]);
+        let Some(account) = proof
+            .verify_account(&self.block.state_root)
+            .expect("invalid account proof")
+        else {
+            return U256::zero();
+        };
+        proof
+            .verify_storage(&account, &slot)
+            .expect("invalid storage proof")
+    }
+}
+
+#[cfg(feature = "host")]
+pub mod host {
+    //! Host side handler of the [SYS_EVM_STATE](super::SYS_EVM_STATE) syscall.
+
+    use std::sync::Arc;
+
+    use anyhow::{anyhow, Result};
+    use ethers_core::types::{BlockId, BlockNumber};
+    use ethers_providers::Middleware;
+    use tokio::runtime::Handle;
+
+    use super::*;
+
+    /// Source of account proofs at a given block.
+    pub trait StateProvider {
+        /// Fetch the proof of the account at `address` and of its `slots`.
+        fn account_proof(&self, address: H160, slots: &[H256]) -> Result<AccountProof>;
+    }
+
+    /// Callback answering the requests of an [EvmStateReader](super) with
+    /// proofs from `provider`, to be registered with
+    /// `ExecutorEnvBuilder::io_callback` on [SYS_EVM_STATE].
+    pub fn state_callback<'a, P: StateProvider + 'a>(
+        provider: &'a P,
+    ) -> impl Fn(risc0_zkvm::Bytes) -> risc0_zkvm::Result<risc0_zkvm::Bytes> + 'a {
+        |data| {
+            let request: StateRequest = bincode::deserialize(&data)?;
+            let proof = provider.account_proof(request.address, &request.slots)?;
+            Ok(bincode::serialize(&proof)?.into())
+        }
+    }
+
+    /// [StateProvider] fetching proofs with `eth_getProof`.
+    pub struct EthersStateProvider<M: Middleware> {
+        client: Arc<M>,
+        block_number: u64,
+        handle: Handle,
+    }
+
+    impl<M: Middleware> EthersStateProvider<M> {
+        /// Create a provider for the state at `block_number`.
+        ///
+        /// Must be called from within a tokio runtime.
+        pub fn new(client: Arc<M>, block_number: u64) -> Self {
+            Self {
+                client,
+                block_number,
+                handle: Handle::current(),
+            }
+        }
+
+        /// Fetch the RLP encoded header of the block, with `debug_getRawHeader`.
+        pub fn header(&self) -> Result<Vec<u8>> {
+            let number = BlockNumber::Number(self.block_number.into());
+            let header: ethers_core::types::Bytes = self.handle.block_on(
+                self.client
+                    .provider()
+                    .request("debug_getRawHeader", [number]),
+            )?;
+            Ok(header.to_vec())
+        }
+    }
+
+    impl<M: Middleware> StateProvider for EthersStateProvider<M> {
+        fn account_proof(&self, address: H160, slots: &[H256]) -> Result<AccountProof> {
+            let block = Some(BlockId::from(self.block_number));
+            let res = self
+                .handle
+                .block_on(self.client.get_proof(address, slots.to_vec(), block))
+                .map_err(|err| anyhow!("eth_getProof failed: {err}"))?;
+            Ok(AccountProof {
+                address,
+                proof: res
+                    .account_proof
+                    .into_iter()
+                    .map(|node| node.to_vec())
+                    .collect(),
+                storage: res
+                    .storage_proof
+                    .into_iter()
+                    .map(|storage| StorageProof {
+                        slot: storage.key,
+                        proof: storage
+                            .proof
+                            .into_iter()
+                            .map(|node| node.to_vec())
+                            .collect(),
+                    })
+                    .collect(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rlp::RlpStream;
+
+    use super::*;
+
+    // Builds a trie holding a single leaf, returning its root and the proof of the leaf.
+    fn single_leaf_trie(key: [u8; 32], value: Vec<u8>) -> (H256, Vec<Vec<u8>>) {
+        let mut path = vec![0x20];
+        path.extend_from_slice(&key);
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&path);
+        stream.append(&value);
+        let node = stream.out().to_vec();
+        (H256(mpt::keccak(&node)), vec![node])
+    }
+
+    #[test]
+    fn account_and_storage() {
+        let address = H160::repeat_byte(0xaa);
+        let slot = H256::repeat_byte(0x01);
+
+        let (storage_root, storage_proof) =
+            single_leaf_trie(mpt::keccak(slot), rlp::encode(&U256::from(42)).to_vec());
+        let account = Account {
+            nonce: 1,
+            balance: U256::from(1000),
+            storage_root,
+            code_hash: H256(mpt::keccak([0u8; 0])),
+        };
+        let mut stream = RlpStream::new_list(4);
+        stream
+            .append(&account.nonce)
+            .append(&account.balance)
+            .append(&account.storage_root)
+            .append(&account.code_hash);
+        let (state_root, account_proof) =
+            single_leaf_trie(mpt::keccak(address), stream.out().to_vec());
+
+        let proof = AccountProof {
+            address,
+            proof: account_proof,
+            storage: vec![StorageProof {
+                slot,
+                proof: storage_proof,
+            }],
+        };
+        assert_eq!(proof.verify_account(&state_root), Ok(Some(account.clone())));
+        assert_eq!(proof.verify_storage(&account, &slot), Ok(U256::from(42)));
+        assert_eq!(
+            proof.verify_storage(&account, &H256::zero()),
+            Err(ProofError::UnexpectedKey)
+        );
+        assert!(proof.verify_account(&H256::zero()).is_err());
+    }
+}
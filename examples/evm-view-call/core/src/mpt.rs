@@ -0,0 +1,252 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of Merkle-Patricia trie proofs, as returned by `eth_getProof`.
+
+use rlp::Rlp;
+use sha3::{Digest as _, Keccak256};
+
+/// Keccak-256 hash of `data`.
+pub fn keccak(data: impl AsRef<[u8]>) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Error returned when a proof does not match the trie it claims to be part of.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MptError {
+    /// A node of the proof is not the one referenced by its parent.
+    UnexpectedNode(usize),
+    /// A node of the proof is not a valid RLP encoded trie node.
+    InvalidNode(usize),
+    /// The proof ends before reaching a leaf or a proof of absence.
+    IncompleteProof,
+}
+
+impl core::fmt::Display for MptError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MptError::UnexpectedNode(i) => write!(f, "node {i} is not referenced by its parent"),
+            MptError::InvalidNode(i) => write!(f, "node {i} is not a valid trie node"),
+            MptError::IncompleteProof => write!(f, "proof is incomplete"),
+        }
+    }
+}
+
+impl std::error::Error for MptError {}
+
+// Reference to a child node: nodes shorter than 32 bytes are embedded in their
+// parent, the others are referenced by their hash.
+enum NodeRef<'a> {
+    Hash([u8; 32]),
+    Inline(&'a [u8]),
+}
+
+impl<'a> NodeRef<'a> {
+    fn from_rlp(item: &Rlp<'a>) -> Result<Option<Self>, rlp::DecoderError> {
+        if item.is_list() {
+            return Ok(Some(NodeRef::Inline(item.as_raw())));
+        }
+        let data = item.data()?;
+        match data.len() {
+            0 => Ok(None),
+            32 => Ok(Some(NodeRef::Hash(data.try_into().unwrap()))),
+            _ => Err(rlp::DecoderError::RlpInvalidLength),
+        }
+    }
+
+    fn matches(&self, node: &[u8]) -> bool {
+        match self {
+            NodeRef::Hash(hash) => keccak(node) == *hash,
+            NodeRef::Inline(raw) => *raw == node,
+        }
+    }
+}
+
+/// Verify that `proof` is a valid path in the trie with the given `root` for
+/// `key`.
+///
+/// Returns the value stored under `key`, or `None` if the proof shows that the
+/// trie holds no value for it. For the state and storage tries, `key` is the
+/// keccak hash of the address or storage slot.
+pub fn verify_proof(
+    root: &[u8; 32],
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, MptError> {
+    let nibbles: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+    let mut path = &nibbles[..];
+    let mut expected = NodeRef::Hash(*root);
+
+    for (i, node) in proof.iter().enumerate() {
+        if !expected.matches(node) {
+            return Err(MptError::UnexpectedNode(i));
+        }
+        let invalid = |_| MptError::InvalidNode(i);
+        let rlp = Rlp::new(node);
+        match rlp.item_count().map_err(invalid)? {
+            17 => {
+                let Some((&nibble, rest)) = path.split_first() else {
+                    let value = rlp.at(16).and_then(|v| v.data().map(<[u8]>::to_vec));
+                    let value = value.map_err(invalid)?;
+                    return Ok((!value.is_empty()).then_some(value));
+                };
+                let child = rlp.at(nibble as usize).map_err(invalid)?;
+                match NodeRef::from_rlp(&child).map_err(invalid)? {
+                    Some(child) => expected = child,
+                    None => return Ok(None),
+                }
+                path = rest;
+            }
+            2 => {
+                let encoded_path = rlp.at(0).and_then(|p| p.data()).map_err(invalid)?;
+                let (is_leaf, node_path) =
+                    decode_path(encoded_path).ok_or(MptError::InvalidNode(i))?;
+                if is_leaf {
+                    if path != node_path {
+                        return Ok(None);
+                    }
+                    let value = rlp.at(1).and_then(|v| v.data().map(<[u8]>::to_vec));
+                    return Ok(Some(value.map_err(invalid)?));
+                }
+                let Some(rest) = path.strip_prefix(&node_path[..]) else {
+                    return Ok(None);
+                };
+                let child = rlp.at(1).map_err(invalid)?;
+                expected = NodeRef::from_rlp(&child)
+                    .map_err(invalid)?
+                    .ok_or(MptError::InvalidNode(i))?;
+                path = rest;
+            }
+            _ => return Err(MptError::InvalidNode(i)),
+        }
+    }
+    Err(MptError::IncompleteProof)
+}
+
+// Decodes a hex-prefix encoded path into its nibbles, along with whether it
+// belongs to a leaf.
+fn decode_path(encoded: &[u8]) -> Option<(bool, Vec<u8>)> {
+    let (&first, rest) = encoded.split_first()?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return None;
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(rest.iter().flat_map(|b| [b >> 4, b & 0x0f]));
+    Some((flag & 2 == 2, nibbles))
+}
+
+#[cfg(test)]
+mod tests {
+    use rlp::RlpStream;
+
+    use super::*;
+
+    fn encode_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 } + (nibbles.len() % 2) as u8;
+        let mut encoded = vec![];
+        let rest = if nibbles.len() % 2 == 1 {
+            encoded.push(flag << 4 | nibbles[0]);
+            &nibbles[1..]
+        } else {
+            encoded.push(flag << 4);
+            nibbles
+        };
+        encoded.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        encoded
+    }
+
+    fn leaf(nibbles: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&encode_path(nibbles, true));
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    fn nibbles(key: &[u8]) -> Vec<u8> {
+        key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+    }
+
+    // Builds a branch at the root holding two leaves, for keys starting with
+    // different nibbles.
+    fn two_leaf_trie(a: &[u8], b: &[u8]) -> ([u8; 32], Vec<u8>, Vec<u8>, Vec<u8>) {
+        let leaf_a = leaf(&nibbles(a)[1..], b"value a with enough bytes to be hashed");
+        let leaf_b = leaf(&nibbles(b)[1..], b"value b with enough bytes to be hashed");
+        let mut stream = RlpStream::new_list(17);
+        for i in 0..16u8 {
+            if i == nibbles(a)[0] {
+                stream.append(&keccak(&leaf_a).to_vec());
+            } else if i == nibbles(b)[0] {
+                stream.append(&keccak(&leaf_b).to_vec());
+            } else {
+                stream.append_empty_data();
+            }
+        }
+        stream.append_empty_data();
+        let branch = stream.out().to_vec();
+        (keccak(&branch), branch, leaf_a, leaf_b)
+    }
+
+    #[test]
+    fn single_leaf() {
+        let key = keccak(b"key");
+        let node = leaf(&nibbles(&key), b"value");
+        let root = keccak(&node);
+        assert_eq!(
+            verify_proof(&root, &key, &[node.clone()]),
+            Ok(Some(b"value".to_vec()))
+        );
+        assert_eq!(verify_proof(&root, &keccak(b"other"), &[node]), Ok(None));
+    }
+
+    #[test]
+    fn branch_with_leaves() {
+        let (a, b) = ([0x12; 32], [0x34; 32]);
+        let (root, branch, leaf_a, leaf_b) = two_leaf_trie(&a, &b);
+        assert_eq!(
+            verify_proof(&root, &a, &[branch.clone(), leaf_a.clone()]),
+            Ok(Some(b"value a with enough bytes to be hashed".to_vec()))
+        );
+        assert_eq!(
+            verify_proof(&root, &b, &[branch.clone(), leaf_b]),
+            Ok(Some(b"value b with enough bytes to be hashed".to_vec()))
+        );
+        // Proof of absence, stopping at an empty slot of the branch.
+        assert_eq!(
+            verify_proof(&root, &[0x56; 32], &[branch.clone()]),
+            Ok(None)
+        );
+        // The leaf of `a` is not referenced by the slot of `b`.
+        assert_eq!(
+            verify_proof(&root, &b, &[branch.clone(), leaf_a]),
+            Err(MptError::UnexpectedNode(1))
+        );
+        assert_eq!(
+            verify_proof(&root, &a, &[branch]),
+            Err(MptError::IncompleteProof)
+        );
+    }
+
+    #[test]
+    fn wrong_root() {
+        let (_, branch, leaf_a, _) = two_leaf_trie(&[0x12; 32], &[0x34; 32]);
+        assert_eq!(
+            verify_proof(&[0; 32], &[0x12; 32], &[branch, leaf_a]),
+            Err(MptError::UnexpectedNode(0))
+        );
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use evm_view_call_core::{EvmStateReader, ViewCallJournal};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let header: Vec<u8> = env::read();
+    let (address, slot) = env::read();
+
+    let reader = EvmStateReader::new(&header);
+    let value = reader.storage(address, slot);
+
+    let block = reader.block();
+    env::commit(&ViewCallJournal {
+        block_hash: block.hash,
+        block_number: block.number,
+        address,
+        slot,
+        value,
+    });
+}
@@ -0,0 +1,95 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use clap::Parser;
+use ethers_core::types::{H160, H256};
+use ethers_providers::{Http, Middleware, Provider};
+use evm_view_call_core::{
+    host::{state_callback, EthersStateProvider},
+    ViewCallJournal, SYS_EVM_STATE,
+};
+use evm_view_call_methods::{VIEW_CALL_ELF, VIEW_CALL_ID};
+use risc0_zkvm::{default_prover, ExecutorEnv};
+use tracing::info;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Ethereum JSON-RPC endpoint, supporting `eth_getProof` and `debug_getRawHeader`.
+    #[clap(short, long)]
+    rpc_url: String,
+
+    /// Address of the account to read.
+    #[clap(short, long)]
+    address: H160,
+
+    /// Storage slot to read.
+    #[clap(short, long)]
+    slot: H256,
+
+    /// Block to read the state at, defaults to the latest block.
+    #[clap(short, long)]
+    block_numb: Option<u64>,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
+        .init();
+
+    let args = Args::parse();
+
+    let client = Provider::<Http>::try_from(args.rpc_url).expect("Invalid RPC url");
+    let client = Arc::new(client);
+    let block_numb = match args.block_numb {
+        Some(numb) => numb,
+        None => client.get_block_number().await.unwrap().as_u64(),
+    };
+    info!(
+        "Reading slot {:?} of {:?} at block {}",
+        args.slot, args.address, block_numb
+    );
+
+    // The provider blocks on the runtime, so the zkVM runs on a blocking thread.
+    let receipt = tokio::task::spawn_blocking(move || {
+        let provider = EthersStateProvider::new(client, block_numb);
+        let header = provider.header().expect("Failed to fetch the block header");
+
+        let env = ExecutorEnv::builder()
+            .write(&header)
+            .unwrap()
+            .write(&(args.address, args.slot))
+            .unwrap()
+            .io_callback(SYS_EVM_STATE, state_callback(&provider))
+            .build()
+            .unwrap();
+
+        default_prover().prove(env, VIEW_CALL_ELF).unwrap()
+    })
+    .await
+    .unwrap();
+
+    receipt.verify(VIEW_CALL_ID).unwrap();
+    let journal: ViewCallJournal = receipt
+        .journal
+        .decode()
+        .expect("Failed to deserialize ViewCallJournal");
+    info!(
+        "block {} (0x{:x}): value {}",
+        journal.block_number, journal.block_hash, journal.value
+    );
+}
@@ -14,7 +14,8 @@
 
 extern crate alloc;
 
-use alloc::{collections::BTreeMap, vec, vec::Vec};
+use alloc::{collections::BTreeMap, sync::Arc, vec, vec::Vec};
+use core::ops::Deref;
 
 use anyhow::{ensure, Result};
 use risc0_zkp::core::{
@@ -25,7 +26,7 @@ use risc0_zkvm_platform::{
     memory::{GUEST_MAX_MEM, MEM_SIZE, PAGE_TABLE},
     syscall::DIGEST_BYTES,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{elf::Program, Digestible, SystemState};
 
@@ -34,10 +35,17 @@ use crate::{elf::Program, Digestible, SystemState};
 /// This is an image of the full memory state of the zkVM, including the data,
 /// text, inputs, page table, and system memory. In addition to the memory image
 /// proper, this includes some metadata about the page table.
+///
+/// Pages that were never written are not stored, and read as zeros. Cloning an
+/// image shares its pages with the clone, and a page is only copied when one
+/// of the images sharing it writes to it. This keeps the images of successive
+/// segments cheap, as they only differ by the pages touched in between. The
+/// pages are therefore private, and read with [MemoryImage::page] and
+/// [MemoryImage::pages].
 #[derive(Clone, Serialize, Deserialize)]
 pub struct MemoryImage {
     /// Sparse memory memory image as a map from page index to page.
-    pub pages: BTreeMap<u32, Vec<u8>>,
+    pages: BTreeMap<u32, Page>,
 
     /// Metadata about the structure of the page table
     pub info: PageTableInfo,
@@ -86,6 +94,57 @@ impl From<PageTableInfo> for PersistentPageTableInfo {
     }
 }
 
+/// A page of a [MemoryImage], shared by the images cloned from one another
+/// until it is modified.
+///
+/// A page serializes as its bytes, like a `Vec<u8>`.
+#[derive(Clone)]
+struct Page(Arc<Vec<u8>>);
+
+impl Page {
+    fn zeroed(page_size: u32) -> Self {
+        Self(Arc::new(vec![0; page_size as usize]))
+    }
+
+    /// Returns the bytes of this page, copying them first if the page is shared
+    /// with another image.
+    fn make_mut(&mut self) -> &mut [u8] {
+        Arc::make_mut(&mut self.0).as_mut_slice()
+    }
+
+    /// Returns true if both pages share the same bytes.
+    #[cfg(test)]
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for Page {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Page {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(Arc::new(bytes))
+    }
+}
+
+impl Serialize for Page {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Page {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from)
+    }
+}
+
 /// Compute and return the ImageID of the given `(merkle_root, pc)` pair.
 fn compute_image_id(merkle_root: &Digest, pc: u32) -> Digest {
     SystemState {
@@ -190,12 +249,42 @@ impl MemoryImage {
         Ok(img)
     }
 
+    /// Construct a memory image from its pages, as a map from page index to
+    /// page, and its page table metadata and program counter.
+    ///
+    /// This is the inverse of [MemoryImage::pages]. The page table is not
+    /// recomputed.
+    pub fn from_pages(pages: BTreeMap<u32, Vec<u8>>, info: PageTableInfo, pc: u32) -> Self {
+        Self {
+            pages: pages
+                .into_iter()
+                .map(|(page_idx, page)| (page_idx, page.into()))
+                .collect(),
+            info,
+            pc,
+        }
+    }
+
+    /// Returns the page specified by page_idx, or `None` if the page was never
+    /// written, in which case it reads as zeros.
+    pub fn page(&self, page_idx: u32) -> Option<&[u8]> {
+        self.pages.get(&page_idx).map(|page| &**page)
+    }
+
+    /// Returns the pages of this image that were written, with their index, in
+    /// increasing order of index.
+    pub fn pages(&self) -> impl Iterator<Item = (u32, &[u8])> {
+        self.pages
+            .iter()
+            .map(|(&page_idx, page)| (page_idx, &**page))
+    }
+
     /// Load a page specified by page_idx. If no page is found, a zero page is
     /// returned.
     pub fn load_page(&self, page_idx: u32) -> Vec<u8> {
         self.pages
             .get(&page_idx)
-            .cloned()
+            .map(|page| page.to_vec())
             .unwrap_or_else(|| vec![0; self.info.page_size as usize])
     }
 
@@ -204,15 +293,21 @@ impl MemoryImage {
     /// not overlap a page boundary.
     pub fn store_region_in_page(&mut self, addr: u32, bytes: &[u8]) {
         let page_idx = self.info.get_page_index(addr);
+        let page_start = self.info.get_page_addr(page_idx);
+        let offset = (addr - page_start) as usize;
+        if let Some(page) = self.pages.get(&page_idx) {
+            // Avoid copying a shared page when its contents do not change.
+            if page[offset..offset + bytes.len()] == *bytes {
+                return;
+            }
+        }
         let page = self.pages.entry(page_idx).or_insert_with(|| {
             if addr as usize >= MEM_SIZE {
                 panic!("address {addr:08X} outside MEM_SIZE")
             }
-            vec![0_u8; self.info.page_size as usize]
+            Page::zeroed(self.info.page_size)
         });
-        let page_start = self.info.get_page_addr(page_idx);
-        page[(addr - page_start) as usize..(addr - page_start) as usize + bytes.len()]
-            .clone_from_slice(bytes);
+        page.make_mut()[offset..offset + bytes.len()].clone_from_slice(bytes);
     }
 
     /// Reads the given byte array in this memory image at the given
@@ -337,6 +432,33 @@ mod tests {
 
     use crate::{elf::Program, image::PageTableInfo, MemoryImage};
 
+    #[test]
+    fn clone_shares_pages() {
+        const PAGE_SIZE: u32 = 1024;
+        let program = Program::load_elf(MULTI_TEST_ELF, GUEST_MAX_MEM as u32).unwrap();
+        let image = MemoryImage::new(&program, PAGE_SIZE).unwrap();
+        let page_idx = image.info.get_page_index(TEXT_START);
+
+        let mut copy = image.clone();
+        assert!(copy.pages[&page_idx].ptr_eq(&image.pages[&page_idx]));
+
+        // Writing the same bytes keeps the page shared.
+        let mut word = [0u8; 4];
+        copy.load_region_in_page(TEXT_START, &mut word).unwrap();
+        copy.store_region_in_page(TEXT_START, &word);
+        assert!(copy.pages[&page_idx].ptr_eq(&image.pages[&page_idx]));
+
+        // Writing different bytes only copies the written page.
+        copy.store_region_in_page(TEXT_START, &[0xff; 4]);
+        assert!(!copy.pages[&page_idx].ptr_eq(&image.pages[&page_idx]));
+        assert!(copy.pages[&image.info.root_idx].ptr_eq(&image.pages[&image.info.root_idx]));
+        image.load_region_in_page(TEXT_START, &mut word).unwrap();
+        assert_ne!(word, [0xff; 4]);
+
+        copy.hash_pages().unwrap();
+        assert_ne!(copy.compute_id().unwrap(), image.compute_id().unwrap());
+    }
+
     fn page_table_size(max_mem: u32, page_size: u32) -> u32 {
         PageTableInfo::new(max_mem, page_size)
             .unwrap()
@@ -12,16 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{fs, path::Path, process::Command};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use anyhow::{bail, Context, Result};
 use cargo_metadata::MetadataCommand;
 use docker_generate::DockerFile;
 use risc0_binfmt::{MemoryImage, Program};
+use risc0_zkp::core::hash::sha::{cpu, Sha256};
 use risc0_zkvm_platform::{
     memory::{GUEST_MAX_MEM, TEXT_START},
     PAGE_SIZE,
 };
+use serde::{Deserialize, Serialize};
 use tempfile::tempdir;
 
 const DOCKER_IGNORE: &str = r#"
@@ -34,8 +40,155 @@ const DOCKER_IGNORE: &str = r#"
 
 const TARGET_DIR: &str = "target/riscv-guest/riscv32im-risc0-zkvm-elf/docker";
 
+/// Docker image the packages are built in. The image is pinned by the digest
+/// it resolves to when a package is first built, which is recorded in the
+/// [Provenance] of the build and used by the rebuilds of [docker_verify].
+const BUILDER_IMAGE: &str = "risczero/risc0-guest-builder:v0.17";
+
+const PROVENANCE_FILE: &str = "provenance.json";
+
+/// Record of a docker build of a guest package, written as `provenance.json`
+/// next to its ELFs.
+///
+/// It lists everything the image IDs depend on, so that they can be audited
+/// independently by rebuilding the package with [docker_verify].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Docker image the package was built in.
+    pub builder_image: String,
+    /// Digest of the docker image the package was built in, e.g.
+    /// `sha256:<hex>`.
+    pub builder_image_digest: String,
+    /// Path of the manifest of the package, relative to the source directory.
+    pub manifest_path: String,
+    /// Features the package was built with.
+    pub features: Vec<String>,
+    /// SHA-256 of the `Cargo.lock` the build was locked to.
+    pub cargo_lock_sha256: Option<String>,
+    /// The packages the build depends on, i.e. its software bill of materials.
+    pub packages: Vec<PackageRecord>,
+    /// The binaries built.
+    pub methods: Vec<MethodRecord>,
+}
+
+/// A package of the software bill of materials of a [Provenance].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageRecord {
+    /// Name of the package.
+    pub name: String,
+    /// Version of the package.
+    pub version: String,
+    /// Source of the package, e.g. a registry or a git repository. `None` for
+    /// local packages.
+    pub source: Option<String>,
+    /// License of the package.
+    pub license: Option<String>,
+}
+
+/// A binary built by a docker build, recorded in its [Provenance].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MethodRecord {
+    /// Name of the binary.
+    pub name: String,
+    /// Path of the ELF, relative to the source directory.
+    pub elf_path: String,
+    /// SHA-256 of the ELF.
+    pub elf_sha256: String,
+    /// Image ID of the ELF.
+    pub image_id: String,
+}
+
 /// Build the package in the manifest path using a docker environment.
+///
+/// Writes the [Provenance] of the build next to the ELFs.
 pub fn docker_build(manifest_path: &Path, src_dir: &Path, features: &[String]) -> Result<()> {
+    let builder_image_digest = resolve_builder_image_digest()?;
+    let (provenance, elf_dir) = build_with_provenance(
+        manifest_path,
+        src_dir,
+        features,
+        &builder_image_digest,
+        &src_dir.join(TARGET_DIR),
+    )?;
+    let provenance_path = elf_dir.join(PROVENANCE_FILE);
+    fs::write(&provenance_path, serde_json::to_string_pretty(&provenance)?)?;
+    println!("Provenance written to {}", provenance_path.display());
+    Ok(())
+}
+
+/// Rebuild the package recorded in the [Provenance] at the given path, and
+/// check that it produces the same image IDs.
+///
+/// The package is rebuilt in a temporary directory, so neither the recorded
+/// build nor its [Provenance] is modified. It is rebuilt in the docker image
+/// with the recorded digest, so that the builder image cannot differ between
+/// the two builds.
+pub fn docker_verify(provenance_path: &Path, src_dir: &Path) -> Result<()> {
+    let expected: Provenance = serde_json::from_slice(&fs::read(provenance_path)?)
+        .with_context(|| format!("Failed to parse {}", provenance_path.display()))?;
+    if expected.builder_image != BUILDER_IMAGE {
+        bail!(
+            "The package was built with {}, but this version of risc0-build uses {BUILDER_IMAGE}",
+            expected.builder_image
+        );
+    }
+
+    let manifest_path = src_dir.join(&expected.manifest_path);
+    let lock_file = manifest_path
+        .parent()
+        .context("invalid manifest path")?
+        .join("Cargo.lock");
+    let cargo_lock_sha256 = sha256_file(&lock_file)?;
+    if cargo_lock_sha256 != expected.cargo_lock_sha256 {
+        bail!(
+            "{} differs from the Cargo.lock of the recorded build: expected SHA-256 {}, got {}",
+            lock_file.display(),
+            expected.cargo_lock_sha256.as_deref().unwrap_or("none"),
+            cargo_lock_sha256.as_deref().unwrap_or("none"),
+        );
+    }
+
+    let out_dir = tempdir()?;
+    let (actual, _) = build_with_provenance(
+        &manifest_path,
+        src_dir,
+        &expected.features,
+        &expected.builder_image_digest,
+        out_dir.path(),
+    )?;
+
+    let mut mismatches = Vec::new();
+    for method in expected.methods.iter() {
+        match actual
+            .methods
+            .iter()
+            .find(|other| other.name == method.name)
+        {
+            Some(other) if other.image_id == method.image_id => {
+                println!("{}: ImageID {} matches", method.name, method.image_id);
+            }
+            Some(other) => mismatches.push(format!(
+                "{}: expected ImageID {}, got {}",
+                method.name, method.image_id, other.image_id
+            )),
+            None => mismatches.push(format!("{}: not built", method.name)),
+        }
+    }
+    if !mismatches.is_empty() {
+        bail!("Image IDs do not match:\n{}", mismatches.join("\n"));
+    }
+    Ok(())
+}
+
+/// Builds the package into `out_dir`, and returns its [Provenance] along with
+/// the directory of its ELFs.
+fn build_with_provenance(
+    manifest_path: &Path,
+    src_dir: &Path,
+    features: &[String],
+    builder_image_digest: &str,
+    out_dir: &Path,
+) -> Result<(Provenance, PathBuf)> {
     let manifest_path = manifest_path
         .canonicalize()
         .context(format!("manifest_path: {manifest_path:?}"))?;
@@ -69,22 +222,93 @@ pub fn docker_build(manifest_path: &Path, src_dir: &Path, features: &[String]) -
         let temp_dir = tempdir()?;
         let temp_path = temp_dir.path();
         let rel_manifest_path = manifest_path.strip_prefix(&src_dir)?;
-        create_dockerfile(rel_manifest_path, temp_path, pkg_name.as_str(), features)?;
-        build(&src_dir, temp_path)?;
+        create_dockerfile(
+            rel_manifest_path,
+            temp_path,
+            pkg_name.as_str(),
+            features,
+            builder_image_digest,
+        )?;
+        build(&src_dir, temp_path, out_dir)?;
     }
     println!("ELFs ready at:");
 
-    let target_dir = src_dir.join(TARGET_DIR);
+    let elf_dir = out_dir.join(&pkg_name);
+    let mut methods = Vec::new();
     for target in root_pkg.targets.iter() {
         if target.is_bin() {
-            let elf_path = target_dir.join(&pkg_name).join(&target.name);
+            let elf_path = elf_dir.join(&target.name);
             let image_id = compute_image_id(&elf_path)?;
             let rel_elf_path = Path::new(TARGET_DIR).join(&pkg_name).join(&target.name);
             println!("ImageID: {} - {:?}", image_id, rel_elf_path);
+            methods.push(MethodRecord {
+                name: target.name.clone(),
+                elf_path: rel_elf_path.display().to_string(),
+                elf_sha256: sha256_file(&elf_path)?.unwrap(),
+                image_id,
+            });
         }
     }
 
-    Ok(())
+    let mut packages: Vec<PackageRecord> = meta
+        .packages
+        .iter()
+        .map(|pkg| PackageRecord {
+            name: pkg.name.clone(),
+            version: pkg.version.to_string(),
+            source: pkg.source.as_ref().map(|source| source.to_string()),
+            license: pkg.license.clone(),
+        })
+        .collect();
+    packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+    let lock_file = manifest_path.parent().unwrap().join("Cargo.lock");
+    let provenance = Provenance {
+        builder_image: BUILDER_IMAGE.to_string(),
+        builder_image_digest: builder_image_digest.to_string(),
+        manifest_path: manifest_path.strip_prefix(&src_dir)?.display().to_string(),
+        features: features.to_vec(),
+        cargo_lock_sha256: sha256_file(&lock_file)?,
+        packages,
+        methods,
+    };
+
+    Ok((provenance, elf_dir))
+}
+
+/// Pulls the [BUILDER_IMAGE], and returns the digest it resolves to.
+fn resolve_builder_image_digest() -> Result<String> {
+    if !Command::new("docker")
+        .args(["pull", BUILDER_IMAGE])
+        .status()
+        .context("docker failed to execute")?
+        .success()
+    {
+        bail!("docker pull {BUILDER_IMAGE} failed");
+    }
+    let output = Command::new("docker")
+        .args(["image", "inspect", "--format", "{{index .RepoDigests 0}}"])
+        .arg(BUILDER_IMAGE)
+        .output()
+        .context("docker failed to execute")?;
+    if !output.status.success() {
+        bail!("docker image inspect {BUILDER_IMAGE} failed");
+    }
+    // The repository digest has the form `<repository>@sha256:<hex>`.
+    let repo_digest = String::from_utf8(output.stdout)?;
+    match repo_digest.trim().split_once('@') {
+        Some((_, digest)) if digest.starts_with("sha256:") => Ok(digest.to_string()),
+        _ => bail!("unexpected digest of {BUILDER_IMAGE}: {repo_digest}"),
+    }
+}
+
+/// Returns the SHA-256 of the file, or `None` if it does not exist.
+fn sha256_file(path: &Path) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let data = fs::read(path)?;
+    Ok(Some(cpu::Impl::hash_bytes(&data).to_string()))
 }
 
 /// Create the dockerfile.
@@ -95,7 +319,9 @@ fn create_dockerfile(
     temp_dir: &Path,
     pkg_name: &str,
     features: &[String],
+    builder_image_digest: &str,
 ) -> Result<()> {
+    let builder_image = format!("{BUILDER_IMAGE}@{builder_image_digest}");
     let manifest_env = &[("CARGO_MANIFEST_PATH", manifest_path.to_str().unwrap())];
     let rustflags = format!(
         "-C passes=loweratomic -C link-arg=-Ttext=0x{TEXT_START:08X} -C link-arg=--fatal-warnings",
@@ -128,7 +354,7 @@ fn create_dockerfile(
     .join(" ");
 
     let build = DockerFile::new()
-        .from_alias("build", "risczero/risc0-guest-builder:v0.17")
+        .from_alias("build", &builder_image)
         .workdir("/src")
         .copy(".", ".")
         .env(manifest_env)
@@ -156,15 +382,14 @@ fn create_dockerfile(
     Ok(())
 }
 
-/// Build the dockerfile and ouputs the ELF.
+/// Build the dockerfile and ouputs the ELF to `out_dir`.
 ///
 /// Overwrites if an ELF with the same name already exists.
-fn build(src_dir: &Path, temp_dir: &Path) -> Result<()> {
-    let target_dir = src_dir.join(TARGET_DIR);
-    let target_dir = target_dir.to_str().unwrap();
+fn build(src_dir: &Path, temp_dir: &Path, out_dir: &Path) -> Result<()> {
+    let out_dir = out_dir.to_str().unwrap();
     if Command::new("docker")
         .arg("build")
-        .arg(format!("--output={target_dir}"))
+        .arg(format!("--output={out_dir}"))
         .arg("-f")
         .arg(temp_dir.join("Dockerfile"))
         .arg(src_dir)
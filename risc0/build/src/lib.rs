@@ -17,6 +17,7 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 mod docker;
+mod toolchain;
 
 use std::{
     collections::HashMap,
@@ -29,13 +30,17 @@ use std::{
 };
 
 use cargo_metadata::{Message, MetadataCommand, Package};
-pub use docker::docker_build;
+pub use docker::{docker_build, docker_verify, MethodRecord, PackageRecord, Provenance};
 use risc0_binfmt::compute_image_id;
-use risc0_zkp::core::digest::{Digest, DIGEST_WORDS};
+use risc0_zkp::core::{
+    digest::{Digest, DIGEST_WORDS},
+    hash::sha::{cpu, Sha256},
+};
 use risc0_zkvm_platform::memory;
 use serde::Deserialize;
 
-const RUSTUP_TOOLCHAIN_NAME: &str = "risc0";
+use self::toolchain::rustup_toolchain_name;
+pub use self::toolchain::{ToolchainManifest, ToolchainPin, TOOLCHAIN_MANIFEST};
 
 #[derive(Debug, Deserialize)]
 struct Risc0Metadata {
@@ -244,8 +249,9 @@ fn sanitized_cmd(tool: &str) -> Command {
 /// Creates a std::process::Command to execute the given cargo
 /// command in an environment suitable for targeting the zkvm guest.
 pub fn cargo_command(subcmd: &str, rust_flags: &[&str]) -> Command {
+    let toolchain = rustup_toolchain_name();
     let rustc = sanitized_cmd("rustup")
-        .args(["+risc0", "which", "rustc"])
+        .args([&format!("+{toolchain}"), "which", "rustc"])
         .output()
         .expect("rustup failed to find risc0 toolchain")
         .stdout;
@@ -395,11 +401,41 @@ fn build_guest_package<P>(
 
     fs::create_dir_all(target_dir.as_ref()).unwrap();
 
-    let mut cmd = if let Some(lib) = runtime_lib {
-        cargo_command("build", &["-C", &format!("link_arg={}", lib)])
-    } else {
-        cargo_command("build", &[])
+    // Running the guest cargo build, even when it has nothing to do, dominates
+    // the incremental build times of large workspaces, so it is skipped when
+    // nothing it depends on has changed since the last build.
+    let rust_flags: Vec<String> = match runtime_lib {
+        Some(lib) => vec!["-C".to_string(), format!("link_arg={lib}")],
+        None => vec![],
+    };
+    let rust_flags: Vec<&str> = rust_flags.iter().map(String::as_str).collect();
+    let use_cache = get_env_var("RISC0_BUILD_NO_CACHE").is_empty();
+    let build_key = match guest_build_key(pkg, guest_opts, &rust_flags) {
+        Ok(key) => Some(key),
+        Err(err) => {
+            println!(
+                "cargo:warning={}: failed to hash the guest sources: {err}",
+                pkg.name
+            );
+            None
+        }
     };
+    let key_path = target_dir.as_ref().join(format!("{}.build-key", pkg.name));
+    if use_cache
+        && build_key.as_ref().is_some_and(|build_key| {
+            fs::read_to_string(&key_path).is_ok_and(|key| &key == build_key)
+        })
+        && guest_methods(pkg, &target_dir)
+            .iter()
+            .all(|method| method.elf_path.is_file())
+    {
+        println!("{}: guest is up to date, skipping its build", pkg.name);
+        return;
+    }
+    // Remove the key first, so that an interrupted build is never reused.
+    let _ = fs::remove_file(&key_path);
+
+    let mut cmd = cargo_command("build", &rust_flags);
 
     let features_str = guest_opts.features.join(",");
     if !features_str.is_empty() {
@@ -456,9 +492,106 @@ fn build_guest_package<P>(
     if !res.success() {
         std::process::exit(res.code().unwrap());
     }
+
+    if let Some(build_key) = build_key {
+        fs::write(key_path, build_key).unwrap();
+    }
 }
 
-fn detect_toolchain(name: &str) {
+/// Returns a hash of everything the build of a guest package depends on: the
+/// sources of the local packages of its workspace and of its path and `[patch]`
+/// dependencies, its `Cargo.lock`, the manifest and cargo configuration of its
+/// workspace, the toolchain, and the build options.
+fn guest_build_key(
+    pkg: &Package,
+    guest_opts: &GuestOptions,
+    rust_flags: &[&str],
+) -> std::io::Result<String> {
+    let meta = MetadataCommand::new()
+        .manifest_path(&pkg.manifest_path)
+        .exec()
+        .expect("cargo metadata command failed");
+
+    let mut data = Vec::new();
+    let mut add = |bytes: &[u8]| {
+        data.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        data.extend_from_slice(bytes);
+    };
+    add(env!("CARGO_PKG_VERSION").as_bytes());
+    add(toolchain_version().as_bytes());
+    add(guest_opts.features.join(",").as_bytes());
+    add(rust_flags.join(" ").as_bytes());
+    add(&[is_debug() as u8]);
+    add(get_env_var("RISC0_RUST_SRC").as_bytes());
+    add(&[env::var_os("RISC0_BUILD_LOCKED").is_some() as u8]);
+    add(&fs::read(meta.workspace_root.join("Cargo.lock")).unwrap_or_default());
+    // The `[patch]` section lives in the workspace manifest, which is not part of
+    // any package when the workspace is virtual.
+    add(&fs::read(meta.workspace_root.join("Cargo.toml")).unwrap_or_default());
+    // Hidden directories are skipped below, so cargo configuration, which can
+    // also patch dependencies, is hashed explicitly.
+    for dir in pkg
+        .manifest_path
+        .parent()
+        .unwrap()
+        .as_std_path()
+        .ancestors()
+    {
+        for name in [".cargo/config", ".cargo/config.toml"] {
+            add(&fs::read(dir.join(name)).unwrap_or_default());
+        }
+    }
+
+    // Path dependencies, including those introduced by `[patch]`, have no
+    // source; the others are pinned by the `Cargo.lock`.
+    let mut local_dirs: Vec<&Path> = meta
+        .packages
+        .iter()
+        .filter(|pkg| pkg.source.is_none())
+        .map(|pkg| pkg.manifest_path.parent().unwrap().as_std_path())
+        .collect();
+    local_dirs.sort();
+    local_dirs.dedup();
+    for dir in local_dirs {
+        add(dir.to_str().unwrap().as_bytes());
+        hash_dir(dir, &mut add)?;
+    }
+
+    Ok(cpu::Impl::hash_bytes(&data).to_string())
+}
+
+/// Adds the paths and contents of the files in the directory to the hash,
+/// skipping build outputs and hidden files.
+fn hash_dir(dir: &Path, add: &mut impl FnMut(&[u8])) -> std::io::Result<()> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    paths.sort();
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy();
+        if name.starts_with('.') || name == "target" {
+            continue;
+        }
+        if path.is_dir() {
+            hash_dir(&path, add)?;
+        } else {
+            add(path.to_str().unwrap().as_bytes());
+            add(&fs::read(&path)?);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the version of the rustc of the risc0 toolchain.
+fn toolchain_version() -> String {
+    let output = sanitized_cmd("rustup")
+        .args(["run", &rustup_toolchain_name(), "rustc", "-vV"])
+        .output()
+        .expect("rustup failed to find risc0 toolchain");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn has_toolchain(name: &str) -> bool {
     let result = Command::new("rustup")
         .args(["toolchain", "list", "--verbose"])
         .stderr(Stdio::inherit())
@@ -470,13 +603,58 @@ fn detect_toolchain(name: &str) {
     }
 
     let stdout = String::from_utf8(result.stdout).unwrap();
-    if !stdout.lines().any(|line| line.trim().starts_with(name)) {
-        eprintln!("The 'risc0' toolchain could not be found.");
-        eprintln!("To install the risc0 toolchain, use cargo-risczero.");
-        eprintln!("For example:");
-        eprintln!("  cargo binstall cargo-risczero");
-        eprintln!("  cargo risczero install");
-        std::process::exit(-1);
+    stdout
+        .lines()
+        .any(|line| line.split_whitespace().next() == Some(name))
+}
+
+fn detect_toolchain(name: &str) {
+    if has_toolchain(name) {
+        return;
+    }
+
+    // A toolchain pinned by the project is installed on demand, so that all the
+    // developers of the project build with the same one, unless network access
+    // is disabled.
+    if name != toolchain::DEFAULT_TOOLCHAIN_NAME && !toolchain::is_offline() {
+        eprintln!("Installing the '{name}' toolchain pinned by {TOOLCHAIN_MANIFEST}...");
+        let installed = sanitized_cmd("cargo")
+            .args(["risczero", "install"])
+            .status()
+            .is_ok_and(|status| status.success());
+        if installed && has_toolchain(name) {
+            return;
+        }
+    }
+
+    eprintln!("The '{name}' toolchain could not be found.");
+    eprintln!("To install the risc0 toolchain, use cargo-risczero.");
+    eprintln!("For example:");
+    eprintln!("  cargo binstall cargo-risczero");
+    eprintln!("  cargo risczero install");
+    std::process::exit(-1);
+}
+
+/// Checks that the guest package is built with the version of risc0-zkvm pinned
+/// by the [ToolchainManifest] of the project, if any.
+fn check_zkvm_pin(guest_pkg: &Package) {
+    let Some((path, manifest)) = toolchain::current_manifest() else {
+        return;
+    };
+    if manifest.toolchain.risc0_zkvm.is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let meta = MetadataCommand::new()
+        .manifest_path(&guest_pkg.manifest_path)
+        .exec()
+        .expect("cargo metadata command failed");
+    for pkg in meta.packages.iter().filter(|pkg| pkg.name == "risc0-zkvm") {
+        if let Err(err) = manifest.check_zkvm_version(&pkg.version) {
+            eprintln!("ERROR: {}: {err}", guest_pkg.name);
+            std::process::exit(-1);
+        }
     }
 }
 
@@ -536,10 +714,11 @@ pub fn embed_methods_with_options(mut guest_pkg_to_options: HashMap<&str, GuestO
         .write_all(b"use risc0_build::GuestListEntry;\n")
         .unwrap();
 
-    detect_toolchain(RUSTUP_TOOLCHAIN_NAME);
+    detect_toolchain(&rustup_toolchain_name());
 
     for guest_pkg in guest_packages {
         println!("Building guest package {}.{}", pkg.name, guest_pkg.name);
+        check_zkvm_pin(&guest_pkg);
 
         let guest_opts = guest_pkg_to_options
             .remove(guest_pkg.name.as_str())
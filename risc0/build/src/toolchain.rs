@@ -0,0 +1,171 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-project pinning of the risc0 toolchain.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use cargo_metadata::semver::{Version, VersionReq};
+use serde::Deserialize;
+
+/// Name of the file pinning the risc0 toolchain of a project.
+pub const TOOLCHAIN_MANIFEST: &str = "risc0-toolchain.toml";
+
+/// Name of the rustup toolchain used when none is pinned.
+pub(crate) const DEFAULT_TOOLCHAIN_NAME: &str = "risc0";
+
+/// Pins of the risc0 toolchain of a project, read from a
+/// `risc0-toolchain.toml` file in the project directory or one of its parents,
+/// akin to `rust-toolchain.toml`:
+///
+/// ```toml
+/// [toolchain]
+/// # Release of the risc0 Rust toolchain installed by `cargo risczero install`.
+/// rust = "v2024-01-31.1"
+/// # Versions of risc0-zkvm the guests may be built with.
+/// risc0-zkvm = "0.21"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct ToolchainManifest {
+    /// The pinned versions.
+    #[serde(default)]
+    pub toolchain: ToolchainPin,
+}
+
+/// The versions pinned by a [ToolchainManifest].
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ToolchainPin {
+    /// Release tag of the risc0 Rust toolchain.
+    pub rust: Option<String>,
+    /// Version requirement on the risc0-zkvm crate used by the guests.
+    pub risc0_zkvm: Option<VersionReq>,
+}
+
+impl ToolchainManifest {
+    /// Parses the manifest at the given path.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Finds and parses the manifest of the project in the given directory,
+    /// looking in its parents too. Returns `None` if there is none.
+    pub fn find(dir: &Path) -> Result<Option<(PathBuf, Self)>> {
+        for dir in dir.ancestors() {
+            let path = dir.join(TOOLCHAIN_MANIFEST);
+            if path.is_file() {
+                let manifest = Self::from_path(&path)?;
+                return Ok(Some((path, manifest)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the name of the rustup toolchain to build guests with.
+    ///
+    /// A pinned release is linked under its own name, e.g.
+    /// `risc0-v2024-01-31.1`, so that projects pinning different releases can
+    /// be built side by side.
+    pub fn rustup_toolchain_name(&self) -> String {
+        match &self.toolchain.rust {
+            Some(tag) => format!("{DEFAULT_TOOLCHAIN_NAME}-{tag}"),
+            None => DEFAULT_TOOLCHAIN_NAME.to_string(),
+        }
+    }
+
+    /// Checks that the given version of risc0-zkvm matches the pin.
+    pub fn check_zkvm_version(&self, version: &Version) -> Result<()> {
+        match &self.toolchain.risc0_zkvm {
+            Some(req) if !req.matches(version) => bail!(
+                "risc0-zkvm {version} does not match the version {req} pinned by {TOOLCHAIN_MANIFEST}"
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Finds the [ToolchainManifest] of the project in the current directory.
+///
+/// A manifest that cannot be read or parsed is reported as an error, rather
+/// than silently building with the default toolchain.
+pub(crate) fn current_manifest() -> Option<(PathBuf, ToolchainManifest)> {
+    let dir = std::env::current_dir().unwrap();
+    match ToolchainManifest::find(&dir) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            eprintln!("ERROR: {err:#}");
+            std::process::exit(-1);
+        }
+    }
+}
+
+/// Returns the name of the rustup toolchain to build guests with, following
+/// the [ToolchainManifest] of the project in the current directory, if any.
+pub(crate) fn rustup_toolchain_name() -> String {
+    current_manifest()
+        .map(|(_, manifest)| manifest.rustup_toolchain_name())
+        .unwrap_or(DEFAULT_TOOLCHAIN_NAME.to_string())
+}
+
+/// Returns whether network access is disabled with `RISC0_OFFLINE` or
+/// `CARGO_NET_OFFLINE`.
+pub(crate) fn is_offline() -> bool {
+    ["RISC0_OFFLINE", "CARGO_NET_OFFLINE"]
+        .iter()
+        .any(|var| std::env::var(var).is_ok_and(|value| value == "1" || value == "true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use cargo_metadata::semver::Version;
+
+    use super::{ToolchainManifest, TOOLCHAIN_MANIFEST};
+
+    #[test]
+    fn parse() {
+        let manifest: ToolchainManifest = toml::from_str(
+            r#"
+            [toolchain]
+            rust = "v2024-01-31.1"
+            risc0-zkvm = "0.21"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.rustup_toolchain_name(), "risc0-v2024-01-31.1");
+        assert!(manifest.check_zkvm_version(&Version::new(0, 21, 3)).is_ok());
+        assert!(manifest
+            .check_zkvm_version(&Version::new(0, 20, 0))
+            .is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(TOOLCHAIN_MANIFEST), "[toolchain]\nrust = 1").unwrap();
+        assert!(ToolchainManifest::find(dir.path()).is_err());
+    }
+
+    #[test]
+    fn unpinned() {
+        let manifest: ToolchainManifest = toml::from_str("").unwrap();
+        assert_eq!(manifest.rustup_toolchain_name(), "risc0");
+        assert!(manifest.check_zkvm_version(&Version::new(0, 20, 0)).is_ok());
+    }
+}
@@ -26,6 +26,10 @@ const CUDA_INCS: &[(&str, &str)] = &[
     ("fpext.h", include_str!("../kernels/cuda/fpext.h")),
 ];
 
+// Compute capabilities the CUDA kernels are compiled for by default: Turing,
+// Ampere, Ada Lovelace and Hopper, which covers consumer and datacenter GPUs.
+const CUDA_ARCHS: &[&str] = &["75", "80", "86", "89", "90"];
+
 const METAL_INCS: &[(&str, &str)] = &[
     ("fp.h", include_str!("../kernels/metal/fp.h")),
     ("fpext.h", include_str!("../kernels/metal/fpext.h")),
@@ -135,27 +139,42 @@ impl KernelBuild {
     }
 
     fn compile_cuda(&mut self, output: &str) {
+        println!("cargo:rerun-if-env-changed=RISC0_CUDA_ARCHS");
+        println!("cargo:rerun-if-env-changed=RISC0_CUDA_OPT");
+        println!("cargo:rerun-if-env-changed=NVCC_PREPEND_FLAGS");
+        println!("cargo:rerun-if-env-changed=NVCC_APPEND_FLAGS");
+
+        // Note: we default to -O1 because O3 can upwards of 5 hours (or more)
+        // to compile on the current CUDA toolchain. Using O1 only shows a ~10%
+        // decrease in performance but a compile time in the minutes. Use
+        // RISC0_CUDA_OPT=3 for any performance critical releases / builds / testing
+        let ptx_opt_level = env::var("RISC0_CUDA_OPT").unwrap_or_else(|_| "1".to_string());
+        let mut args = vec![format!("--ptxas-options=-O{ptx_opt_level}")];
+
+        // The fatbin holds machine code for each architecture, selected by the
+        // driver when loading the module, and PTX for the newest one, which the
+        // driver compiles for newer GPUs.
+        let archs = cuda_archs();
+        for arch in archs.iter() {
+            args.push(format!("-gencode=arch=compute_{arch},code=sm_{arch}"));
+        }
+        if let Some(arch) = archs.last() {
+            args.push(format!("-gencode=arch=compute_{arch},code=compute_{arch}"));
+        }
+        args.extend(self.flags.iter().cloned());
+
         self.cached_compile(
             output,
             "fatbin",
             CUDA_INCS,
+            &args,
             |_out_dir, out_path, sys_inc_dir| {
-                println!("cargo:rerun-if-env-changed=RISC0_CUDA_OPT");
-                println!("cargo:rerun-if-env-changed=NVCC_PREPEND_FLAGS");
-                println!("cargo:rerun-if-env-changed=NVCC_APPEND_FLAGS");
-
                 let mut cmd = Command::new("nvcc");
                 cmd.arg("--fatbin");
                 cmd.arg("-o").arg(out_path);
                 cmd.args(self.files.iter());
                 cmd.arg("-I").arg(sys_inc_dir);
-
-                // Note: we default to -O1 because O3 can upwards of 5 hours (or more)
-                // to compile on the current CUDA toolchain. Using O1 only shows a ~10%
-                // decrease in performance but a compile time in the minutes. Use
-                // RISC0_CUDA_OPT=3 for any performance critical releases / builds / testing
-                let ptx_opt_level = env::var("RISC0_CUDA_OPT").unwrap_or_else(|_| "1".to_string());
-                cmd.arg(format!("--ptxas-options=-O{ptx_opt_level}"));
+                cmd.args(args.iter());
                 for inc_dir in self.inc_dirs.iter() {
                     cmd.arg("-I").arg(inc_dir);
                 }
@@ -174,6 +193,7 @@ impl KernelBuild {
             output,
             "metallib",
             METAL_INCS,
+            &[],
             |out_dir, out_path, sys_inc_dir| {
                 let mut air_paths = vec![];
                 for src in self.files.iter() {
@@ -218,6 +238,7 @@ impl KernelBuild {
         output: &str,
         extension: &str,
         assets: &[(&str, &str)],
+        args: &[String],
         inner: F,
     ) {
         let out_dir = env::var("OUT_DIR").map(PathBuf::from).unwrap();
@@ -245,6 +266,9 @@ impl KernelBuild {
         for dep in self.deps.iter() {
             hasher.add_file(dep);
         }
+        for arg in args {
+            hasher.add_bytes(arg.as_bytes());
+        }
         let digest = hasher.finalize();
         let cache_path = cache_dir.join(digest).with_extension(extension);
         if !cache_path.is_file() {
@@ -259,7 +283,27 @@ impl KernelBuild {
     }
 }
 
+/// Returns the compute capabilities to compile the CUDA kernels for, from the
+/// comma-separated list in `RISC0_CUDA_ARCHS`, e.g. `80,86`, or the default
+/// ones.
+fn cuda_archs() -> Vec<String> {
+    match env::var("RISC0_CUDA_ARCHS") {
+        Ok(archs) => archs
+            .split(',')
+            .map(|arch| arch.trim().trim_start_matches("sm_").to_string())
+            .filter(|arch| !arch.is_empty())
+            .collect(),
+        Err(_) => CUDA_ARCHS.iter().map(|arch| arch.to_string()).collect(),
+    }
+}
+
+/// Returns the directory of the artifacts shared by all the RISC Zero builds of
+/// the user, which can be overridden with `RISC0_CACHE_DIR`.
 fn risc0_cache() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=RISC0_CACHE_DIR");
+    if let Ok(dir) = env::var("RISC0_CACHE_DIR") {
+        return dir.into();
+    }
     directories::ProjectDirs::from("com.risczero", "RISC Zero", "risc0")
         .unwrap()
         .cache_dir()
@@ -280,6 +324,10 @@ impl Hasher {
         self.sha.update(bytes);
     }
 
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        self.sha.update(bytes);
+    }
+
     pub fn finalize(self) -> String {
         hex::encode(self.sha.finalize())
     }
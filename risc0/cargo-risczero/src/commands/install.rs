@@ -15,6 +15,7 @@
 // This is based on cargo-wasix: https://github.com/wasix-org/cargo-wasix
 
 use std::{
+    env::current_dir,
     fs::File,
     io::BufReader,
     path::{Path, PathBuf},
@@ -27,6 +28,7 @@ use flate2::bufread::GzDecoder;
 use reqwest::{header::HeaderMap, Client};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use risc0_build::ToolchainManifest;
 use serde::Deserialize;
 use tar::Archive;
 use tempfile::tempdir;
@@ -39,6 +41,8 @@ use crate::{
 /// `cargo risczero install`
 #[derive(Parser)]
 pub struct Install {
+    /// Release of the toolchain to install. Defaults to the one pinned by the
+    /// `risc0-toolchain.toml` of the current project, or to the latest one.
     #[arg(long)]
     version: Option<String>,
 }
@@ -66,8 +70,21 @@ impl Install {
         let lockfile_path = root_dir.join("rustup-lock");
         let _lock = flock(&lockfile_path);
 
+        // A release pinned by the project is linked under its own name, leaving
+        // the default toolchain of the user untouched.
+        let (version, name) = match (&self.version, ToolchainManifest::find(&current_dir()?)?) {
+            (None, Some((path, manifest))) if manifest.toolchain.rust.is_some() => {
+                eprintln!("Using the toolchain pinned by {}", path.display());
+                (
+                    manifest.toolchain.rust.clone(),
+                    manifest.rustup_toolchain_name(),
+                )
+            }
+            _ => (self.version.clone(), RUSTUP_TOOLCHAIN_NAME.to_string()),
+        };
+
         let toolchain_dir = root_dir.join("toolchains");
-        let chain = self.install_prebuilt_toolchain(&toolchain_dir)?;
+        let chain = self.install_prebuilt_toolchain(&toolchain_dir, version.as_deref(), &name)?;
 
         eprintln!(
             "Toolchain {} downloaded and installed to path {}.",
@@ -82,10 +99,15 @@ impl Install {
     /// Tries to download a pre-built toolchain if possible.
     ///
     /// Returns the path to the toolchain.
-    fn install_prebuilt_toolchain(&self, toolchain_dir: &Path) -> Result<RustupToolchain> {
+    fn install_prebuilt_toolchain(
+        &self,
+        toolchain_dir: &Path,
+        version: Option<&str>,
+        name: &str,
+    ) -> Result<RustupToolchain> {
         if let Some(target) = guess_host_target() {
-            match self.download_toolchain(target, toolchain_dir) {
-                Ok(path) => RustupToolchain::link(RUSTUP_TOOLCHAIN_NAME, &path.join("rust")),
+            match self.download_toolchain(target, toolchain_dir, version) {
+                Ok(path) => RustupToolchain::link(name, &path.join("rust")),
                 Err(err) => {
                     eprintln!("Could not download pre-built toolchain: {err:?}");
                     Err(err.context("Download of pre-built toolchain failed"))
@@ -97,7 +119,12 @@ impl Install {
     }
 
     /// Download a pre-built toolchain from Github releases.
-    fn download_toolchain(&self, target: &str, toolchains_root_dir: &Path) -> Result<PathBuf> {
+    fn download_toolchain(
+        &self,
+        target: &str,
+        toolchains_root_dir: &Path,
+        version: Option<&str>,
+    ) -> Result<PathBuf> {
         let mut headers = HeaderMap::new();
 
         // Use api token if specified via env var.
@@ -126,7 +153,8 @@ impl Install {
             .build();
         let rt = tokio::runtime::Runtime::new()?;
 
-        let (tag_name, download_url) = rt.block_on(self.get_download_url(&client, target))?;
+        let (tag_name, download_url) =
+            rt.block_on(self.get_download_url(&client, target, version))?;
 
         let toolchain_dir = toolchains_root_dir.join(format!("{target}_{}", tag_name));
         if toolchain_dir.is_dir() {
@@ -180,11 +208,9 @@ impl Install {
         &self,
         client: &ClientWithMiddleware,
         target: &str,
+        version: Option<&str>,
     ) -> Result<(String, String)> {
-        let tag = self
-            .version
-            .clone()
-            .map_or("latest".to_string(), |tag| format!("tags/{tag}"));
+        let tag = version.map_or("latest".to_string(), |tag| format!("tags/{tag}"));
 
         let repo = RUST_REPO
             .trim_start_matches("https://github.com/")
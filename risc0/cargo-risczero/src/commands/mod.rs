@@ -18,3 +18,4 @@ pub mod build_guest;
 pub mod build_toolchain;
 pub mod install;
 pub mod new;
+pub mod verify_guest;
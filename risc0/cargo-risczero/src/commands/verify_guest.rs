@@ -0,0 +1,36 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+/// `cargo risczero verify`
+#[derive(Parser)]
+pub struct VerifyGuest {
+    /// Location of the provenance.json written by `cargo risczero build`.
+    ///
+    /// This path is relative to the current directory, which must be the
+    /// source directory of the recorded build.
+    #[arg(long)]
+    pub provenance: PathBuf,
+}
+
+impl VerifyGuest {
+    pub fn run(&self) -> Result<()> {
+        let src_dir = std::env::current_dir().unwrap();
+        risc0_build::docker_verify(&self.provenance, &src_dir)
+    }
+}
@@ -28,6 +28,7 @@ use clap::{Parser, Subcommand};
 use self::commands::build::BuildCommand;
 use self::commands::{
     build_guest::BuildGuest, build_toolchain::BuildToolchain, install::Install, new::NewCommand,
+    verify_guest::VerifyGuest,
 };
 
 #[derive(Parser)]
@@ -58,6 +59,8 @@ pub enum RisczeroCmd {
     Install(Install),
     /// Creates a new risczero starter project.
     New(NewCommand),
+    /// Rebuild guest code and check that it matches a recorded build.
+    Verify(VerifyGuest),
     /// Build a crate for RISC Zero.
     #[cfg(feature = "experimental")]
     BuildCrate(BuildCommand),
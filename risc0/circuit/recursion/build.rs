@@ -33,6 +33,14 @@ fn main() {
     download_zkr();
 }
 
+/// Fetches the recursion programs, checking them against their pinned hash.
+///
+/// The archive is copied from `RECURSION_SRC_PATH` when set, or from the
+/// shared RISC Zero cache directory, which can be overridden with
+/// `RISC0_CACHE_DIR`. Otherwise it is downloaded from each of the
+/// comma-separated base URLs of `RISC0_ARTIFACTS_MIRROR` in turn, and stored
+/// in the cache. Setting `RISC0_OFFLINE` or `CARGO_NET_OFFLINE` to `true`
+/// disables the download.
 #[cfg(feature = "prove")]
 fn download_zkr() {
     use std::{
@@ -41,12 +49,13 @@ fn download_zkr() {
         str::FromStr,
     };
 
-    use downloader::{verify, Download, DownloadSummary, Downloader};
+    use downloader::{verify, Download, Downloader};
     use sha2::{Digest, Sha256};
 
     const FILENAME: &str = "recursion_zkr.zip";
     const SRC_PATH: &str = "src/recursion_zkr.zip";
     const SHA256_HASH: &str = "ae5736a42189aec2f04936c3aee4b5441e48b26b4fa1fae28657cf50cdf3cae4";
+    const DEFAULT_MIRROR: &str = "https://risc0-artifacts.s3.us-west-2.amazonaws.com";
 
     fn check_sha2(path: &Path) -> bool {
         let data = fs::read(path).unwrap();
@@ -58,6 +67,10 @@ fn download_zkr() {
     }
 
     println!("cargo:rerun-if-env-changed=RECURSION_SRC_PATH");
+    println!("cargo:rerun-if-env-changed=RISC0_ARTIFACTS_MIRROR");
+    println!("cargo:rerun-if-env-changed=RISC0_OFFLINE");
+    println!("cargo:rerun-if-env-changed=CARGO_NET_OFFLINE");
+    println!("cargo:rerun-if-env-changed=RISC0_CACHE_DIR");
 
     let src_path = env::var("RECURSION_SRC_PATH").unwrap_or(SRC_PATH.to_string());
     let src_path = PathBuf::from_str(src_path.as_str()).unwrap();
@@ -77,20 +90,86 @@ fn download_zkr() {
         return;
     }
 
+    let cache_path = risc0_cache().join("zkr").join(format!("{SHA256_HASH}.zip"));
+    if cache_path.exists() && check_sha2(&cache_path) {
+        fs::copy(&cache_path, &out_path).unwrap();
+        return;
+    }
+
+    let offline = ["RISC0_OFFLINE", "CARGO_NET_OFFLINE"]
+        .iter()
+        .any(|var| env::var(var).is_ok_and(|value| value == "1" || value == "true"));
+    let mirrors = env::var("RISC0_ARTIFACTS_MIRROR").unwrap_or(DEFAULT_MIRROR.to_string());
+    let urls: Vec<String> = mirrors
+        .split(',')
+        .map(|mirror| mirror.trim().trim_end_matches('/'))
+        .filter(|mirror| !mirror.is_empty())
+        .map(|mirror| format!("{mirror}/zkr/{SHA256_HASH}.zip"))
+        .collect();
+    let instructions = format!(
+        "Download {} and set RECURSION_SRC_PATH to its path, or set \
+         RISC0_ARTIFACTS_MIRROR to a comma-separated list of mirrors serving zkr/{SHA256_HASH}.zip",
+        urls.first()
+            .cloned()
+            .unwrap_or(format!("{DEFAULT_MIRROR}/zkr/{SHA256_HASH}.zip")),
+    );
+    if offline {
+        panic!(
+            "{FILENAME} is not available at {} and downloads are disabled by offline mode. \
+             {instructions}",
+            src_path.display()
+        );
+    }
+
     let mut downloader = Downloader::builder()
         .download_folder(out_dir)
         .build()
         .unwrap();
-    let url = format!("https://risc0-artifacts.s3.us-west-2.amazonaws.com/zkr/{SHA256_HASH}.zip");
-    eprintln!("Downloading {url}");
-    let dl = Download::new(&url)
-        .file_name(&PathBuf::from_str(FILENAME).unwrap())
-        .verify(verify::with_digest::<Sha256>(
-            hex::decode(SHA256_HASH).unwrap(),
-        ));
-    let results = downloader.download(&[dl]).unwrap();
-    for result in results {
-        let summary: DownloadSummary = result.unwrap();
-        eprintln!("{summary}");
+    for url in &urls {
+        eprintln!("Downloading {url}");
+        let dl = Download::new(url)
+            .file_name(&PathBuf::from_str(FILENAME).unwrap())
+            .verify(verify::with_digest::<Sha256>(
+                hex::decode(SHA256_HASH).unwrap(),
+            ));
+        match downloader
+            .download(&[dl])
+            .and_then(|mut results| results.remove(0))
+        {
+            Ok(summary) => {
+                eprintln!("{summary}");
+                if let Err(err) = store_in_cache(&out_path, &cache_path) {
+                    eprintln!("Failed to cache {FILENAME}: {err}");
+                }
+                return;
+            }
+            Err(err) => eprintln!("Failed to download {url}: {err}"),
+        }
+    }
+    panic!("Failed to download {FILENAME}. {instructions}");
+}
+
+/// Copies the file into the cache, through a temporary file so that concurrent
+/// builds never see a partial copy.
+#[cfg(feature = "prove")]
+fn store_in_cache(path: &std::path::Path, cache_path: &std::path::Path) -> std::io::Result<()> {
+    use std::fs;
+
+    fs::create_dir_all(cache_path.parent().unwrap())?;
+    let tmp_path = cache_path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::copy(path, &tmp_path)?;
+    fs::rename(&tmp_path, cache_path)
+}
+
+/// Returns the directory of the artifacts shared by all the RISC Zero builds
+/// of the user, e.g. `~/.cache/risc0` on Linux.
+#[cfg(feature = "prove")]
+fn risc0_cache() -> std::path::PathBuf {
+    if let Ok(dir) = env::var("RISC0_CACHE_DIR") {
+        return dir.into();
     }
+    directories::ProjectDirs::from("com.risczero", "RISC Zero", "risc0")
+        .unwrap()
+        .cache_dir()
+        .into()
 }
@@ -32,7 +32,7 @@ pub mod zkr;
 
 use risc0_core::field::baby_bear::{BabyBearElem, BabyBearExtElem};
 use risc0_zkp::{
-    adapter::{CircuitCoreDef, TapsProvider},
+    adapter::{CircuitCoreDef, CircuitMetadata, TapsProvider},
     field::baby_bear::BabyBear,
     taps::TapSet,
 };
@@ -52,6 +52,12 @@ impl CircuitImpl {
     pub const fn new() -> Self {
         CircuitImpl
     }
+
+    /// Returns the structure of the circuit: its taps, column groups and
+    /// number of constraints.
+    pub fn metadata(&self) -> CircuitMetadata {
+        CircuitMetadata::new(self, &poly_ext::DEF)
+    }
 }
 
 impl TapsProvider for CircuitImpl {
@@ -29,7 +29,8 @@ pub mod poly_ext;
 mod taps;
 
 use risc0_zkp::{
-    adapter::{CircuitCoreDef, TapsProvider},
+    adapter::{CircuitCoreDef, CircuitMetadata, TapsProvider},
+    core::{digest::Digest, hash::sha::Sha256},
     field::baby_bear::BabyBear,
     taps::TapSet,
 };
@@ -47,6 +48,19 @@ impl CircuitImpl {
     pub const fn new() -> Self {
         CircuitImpl
     }
+
+    /// Returns the structure of the circuit: its taps, column groups and
+    /// number of constraints.
+    pub fn metadata(&self) -> CircuitMetadata {
+        CircuitMetadata::new(self, &poly_ext::DEF)
+    }
+
+    /// Returns the digest of the taps and constraints of the circuit, which
+    /// identifies its version. See
+    /// [risc0_zkp::adapter::PolyExtStepDef::digest].
+    pub fn digest<S: Sha256>(&self) -> Digest {
+        poly_ext::DEF.digest::<S>(self.get_taps())
+    }
 }
 
 impl TapsProvider for CircuitImpl {
@@ -101,6 +115,24 @@ mod tests {
             .step_exec(&ctx, &mut custom, args.as_slice())
             .unwrap();
     }
+
+    #[test]
+    fn metadata() {
+        let metadata = CircuitImpl::new().metadata();
+        let groups = metadata.column_groups();
+        assert_eq!(
+            groups.iter().map(|group| group.name).collect::<Vec<_>>(),
+            ["accum", "code", "data"]
+        );
+        for group in &groups {
+            assert_eq!(group.columns, metadata.taps.group_size(group.id));
+        }
+        assert_eq!(
+            groups.iter().map(|group| group.taps).sum::<usize>(),
+            metadata.taps.tap_size()
+        );
+        assert!(metadata.constraints > 0);
+    }
 }
 
 #[cfg(feature = "prove")]
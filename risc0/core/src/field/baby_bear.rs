@@ -312,18 +312,16 @@ impl From<u64> for Elem {
 
 /// Wrapping addition of [Elem] using Baby Bear field modulus
 fn add(lhs: u32, rhs: u32) -> u32 {
-    let x = lhs.wrapping_add(rhs);
-    if x >= P {
-        x - P
-    } else {
-        x
-    }
+    reduce(lhs.wrapping_add(rhs))
 }
 
 /// Wrapping subtraction of [Elem] using Baby Bear field modulus
 fn sub(lhs: u32, rhs: u32) -> u32 {
     let x = lhs.wrapping_sub(rhs);
-    if x > P {
+    if cfg!(feature = "constant-time") {
+        // The subtraction wrapped iff `lhs < rhs`, in which case `x > P`.
+        x.wrapping_add(P & mask(lhs < rhs))
+    } else if x > P {
         x.wrapping_add(P)
     } else {
         x
@@ -344,13 +342,30 @@ const fn mul(lhs: u32, rhs: u32) -> u32 {
     // uint32_t ret = o64 >> 32;
     let ret = (o64 >> 32) as u32;
     // return (ret >= P ? ret - P : ret);
-    if ret >= P {
-        ret - P
+    reduce(ret)
+}
+
+/// Subtracts P from a value in `[0, 2P)`, if needed.
+///
+/// With the `constant-time` feature, the reduction does not branch on the
+/// value, so that the time taken by addition, subtraction and multiplication
+/// does not depend on their operands.
+const fn reduce(x: u32) -> u32 {
+    if cfg!(feature = "constant-time") {
+        let (y, borrow) = x.overflowing_sub(P);
+        (y & !mask(borrow)) | (x & mask(borrow))
+    } else if x >= P {
+        x - P
     } else {
-        ret
+        x
     }
 }
 
+/// Returns all ones if `bit` is set, and zero otherwise.
+const fn mask(bit: bool) -> u32 {
+    0u32.wrapping_sub(bit as u32)
+}
+
 /// Encode to Montgomery form from direct form.
 const fn encode(a: u32) -> u32 {
     mul(R2, a)
@@ -723,6 +738,16 @@ mod tests {
         field::tests::test_field_ops::<Elem>(P_U64);
     }
 
+    #[test]
+    fn reduction_boundaries() {
+        let max = Elem::new(P - 1);
+        assert_eq!(max + Elem::ONE, Elem::ZERO);
+        assert_eq!(max + max, Elem::new(P - 2));
+        assert_eq!(Elem::ZERO - Elem::ONE, max);
+        assert_eq!(Elem::ONE - max, Elem::new(2));
+        assert_eq!(max * max, Elem::ONE);
+    }
+
     #[test]
     pub fn linear() {
         let x = ExtElem::new(
@@ -24,6 +24,15 @@ use bytemuck::{Pod, Zeroable};
 
 use crate::field::{self, Elem as FieldElem};
 
+/// Definition of this field for operations that operate on the Goldilocks
+/// field and its 2nd degree extension.
+pub struct Goldilocks;
+
+impl field::Field for Goldilocks {
+    type Elem = Elem;
+    type ExtElem = ExtElem;
+}
+
 /// The Goldilocks class is an element of the finite field F_p, where P is the
 /// prime number 2^64 - 2^32 + 1. Here we implement integer
 /// arithmetic modulo P for both Goldilocks and for a field extension of
@@ -0,0 +1,66 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+// Constants from: bonsai/ethereum/contracts/groth16/Groth16Verifier.sol
+pub(crate) const ALPHA_X: &str =
+    "20491192805390485299153009773594534940189261866228447918068658471970481763042";
+pub(crate) const ALPHA_Y: &str =
+    "9383485363053290200918347156157836566562967994039712273449902621266178545958";
+pub(crate) const BETA_X1: &str =
+    "4252822878758300859123897981450591353533073413197771768651442665752259397132";
+pub(crate) const BETA_X2: &str =
+    "6375614351688725206403948262868962793625744043794305715222011528459656738731";
+pub(crate) const BETA_Y1: &str =
+    "21847035105528745403288232691147584728191162732299865338377159692350059136679";
+pub(crate) const BETA_Y2: &str =
+    "10505242626370262277552901082094356697409835680220590971873171140371331206856";
+pub(crate) const GAMMA_X1: &str =
+    "11559732032986387107991004021392285783925812861821192530917403151452391805634";
+pub(crate) const GAMMA_X2: &str =
+    "10857046999023057135944570762232829481370756359578518086990519993285655852781";
+pub(crate) const GAMMA_Y1: &str =
+    "4082367875863433681332203403145435568316851327593401208105741076214120093531";
+pub(crate) const GAMMA_Y2: &str =
+    "8495653923123431417604973247489272438418190587263600148770280649306958101930";
+pub(crate) const DELTA_X1: &str =
+    "7693487429026351977103582346623702458502994930724273067160489676068253241122";
+pub(crate) const DELTA_X2: &str =
+    "18083587067730303898088465773541035809657139089748028791075809901844080381583";
+pub(crate) const DELTA_Y1: &str =
+    "7359384703862385660061748803901931167856917172356103761995576336394013820206";
+pub(crate) const DELTA_Y2: &str =
+    "19155327851717349610711498610928624259731705665803906701543914342469719520424";
+pub(crate) const IC0_X: &str =
+    "3946965800586156692981925338368207536736705930062938727478976206429630223321";
+pub(crate) const IC0_Y: &str =
+    "9960693350301697546955796394716396930467039081099556382890913872882520616640";
+pub(crate) const IC1_X: &str =
+    "12558970553567495705160565934359147265891951005223735094684712986779479012425";
+pub(crate) const IC1_Y: &str =
+    "6199258869647841500919704073457246710601253972070160340767955790036898000135";
+pub(crate) const IC2_X: &str =
+    "16706808935575787802711250967470306094414582323642394044367277865965614549578";
+pub(crate) const IC2_Y: &str =
+    "4311171349764489922968166376528109775195413405587105360819319395803197119429";
+pub(crate) const IC3_X: &str =
+    "18597303695576860451572687600097059285572952378680993135977532372976749998241";
+pub(crate) const IC3_Y: &str =
+    "21723251549997210319914312194189948104957781222830842952199420842095495134729";
+pub(crate) const IC4_X: &str =
+    "12164939688015960624286304530289921602116255917351297336606146584068755512927";
+pub(crate) const IC4_Y: &str =
+    "17036398532634379807038707418819848170237695473144751623976431755966389161373";
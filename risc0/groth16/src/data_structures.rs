@@ -17,11 +17,13 @@ extern crate alloc;
 use alloc::{vec, vec::Vec};
 
 use anyhow::{anyhow, Error, Result};
-use ark_bn254::{Bn254, Fr};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
 use ark_groth16::{prepare_verifying_key, PreparedVerifyingKey, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
-use crate::{from_u256, g1_from_bytes, g2_from_bytes};
+use crate::{from_u256, g1_from_bytes, g1_to_bytes, g2_from_bytes, g2_to_bytes};
 
 /// Groth16 seal object encoded in big endian.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -39,6 +41,10 @@ impl Seal {
     const G1_GROUP_SIZE: usize = Self::ELEMENT_SIZE * 2;
     const G2_GROUP_SIZE: usize = Self::ELEMENT_SIZE * 4;
     const SIZE: usize = Self::G1_GROUP_SIZE * 2 + Self::G2_GROUP_SIZE;
+    const COMPRESSED_G1_GROUP_SIZE: usize = Self::ELEMENT_SIZE;
+    const COMPRESSED_G2_GROUP_SIZE: usize = Self::ELEMENT_SIZE * 2;
+    const COMPRESSED_SIZE: usize =
+        Self::COMPRESSED_G1_GROUP_SIZE * 2 + Self::COMPRESSED_G2_GROUP_SIZE;
 
     /// Serialize the Groth16 `Seal` into a `Vec<u8>`
     pub fn to_vec(&self) -> Vec<u8> {
@@ -99,6 +105,50 @@ impl Seal {
 
         Ok(Seal { a, b, c })
     }
+
+    /// Serialize the Groth16 `Seal` into a `Vec<u8>` using compressed points.
+    ///
+    /// Each point is encoded as its x coordinate in big endian, with the sign of y and the
+    /// point at infinity flagged in the most significant bits. The result is half the size of
+    /// [Seal::to_vec].
+    pub fn to_compressed_vec(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::with_capacity(Self::COMPRESSED_SIZE);
+        result.extend(compress(&g1_from_bytes(&self.a)?)?);
+        result.extend(compress(&g2_from_bytes(&self.b)?)?);
+        result.extend(compress(&g1_from_bytes(&self.c)?)?);
+        Ok(result)
+    }
+
+    /// Method to convert back from a `Vec<u8>` produced by [Seal::to_compressed_vec]
+    pub fn from_compressed_vec(data: &[u8]) -> Result<Seal, Error> {
+        if data.len() != Self::COMPRESSED_SIZE {
+            return Err(anyhow!("Data length mismatch"));
+        }
+
+        let (a, rest) = data.split_at(Self::COMPRESSED_G1_GROUP_SIZE);
+        let (b, c) = rest.split_at(Self::COMPRESSED_G2_GROUP_SIZE);
+        Ok(Seal {
+            a: g1_to_bytes(&decompress::<G1Affine>(a)?)?,
+            b: g2_to_bytes(&decompress::<G2Affine>(b)?)?,
+            c: g1_to_bytes(&decompress::<G1Affine>(c)?)?,
+        })
+    }
+}
+
+// Serialize a point in compressed form, converting it to big-endian format
+fn compress<P: CanonicalSerialize>(point: &P) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    point
+        .serialize_compressed(&mut bytes)
+        .map_err(|err| anyhow!(err))?;
+    bytes.reverse();
+    Ok(bytes)
+}
+
+// Deserialize a point in compressed form from bytes in big-endian format
+fn decompress<P: CanonicalDeserialize>(bytes: &[u8]) -> Result<P, Error> {
+    let bytes: Vec<u8> = bytes.iter().rev().cloned().collect();
+    P::deserialize_compressed(&*bytes).map_err(|err| anyhow!(err))
 }
 
 impl TryFrom<ProofJson> for Seal {
@@ -155,6 +205,11 @@ pub struct VerifyingKeyJson {
 impl VerifyingKeyJson {
     /// Computes the prepared verifying key
     pub fn prepared_verifying_key(&self) -> Result<PreparedVerifyingKey<Bn254>, Error> {
+        Ok(prepare_verifying_key(&self.verifying_key()?))
+    }
+
+    // Parses the curve points of the verifying key
+    pub(crate) fn verifying_key(&self) -> Result<VerifyingKey<Bn254>, Error> {
         if self.vk_alpha_1.len() < 2 {
             return Err(anyhow!("Malformed G1 element field: vk_alpha_1"));
         }
@@ -227,8 +282,69 @@ impl VerifyingKeyJson {
             gamma_abc_g1,
         };
 
-        Ok(prepare_verifying_key(&vk))
+        Ok(vk)
     }
+
+    // Encodes the curve points of the verifying key
+    pub(crate) fn from_verifying_key(vk: &VerifyingKey<Bn254>) -> Result<Self, Error> {
+        let alphabeta = prepare_verifying_key(vk).alpha_g1_beta_g2;
+        let vk_alphabeta_12 = [alphabeta.c0, alphabeta.c1]
+            .iter()
+            .map(|fq6| {
+                [fq6.c0, fq6.c1, fq6.c2]
+                    .iter()
+                    .map(fq2_to_decimal)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            protocol: "groth16".to_string(),
+            curve: "bn128".to_string(),
+            n_public: vk.gamma_abc_g1.len().saturating_sub(1) as u32,
+            vk_alpha_1: g1_to_decimal(&vk.alpha_g1)?,
+            vk_beta_2: g2_to_decimal(&vk.beta_g2)?,
+            vk_gamma_2: g2_to_decimal(&vk.gamma_g2)?,
+            vk_delta_2: g2_to_decimal(&vk.delta_g2)?,
+            vk_alphabeta_12,
+            ic: vk
+                .gamma_abc_g1
+                .iter()
+                .map(g1_to_decimal)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+// Encode a base field element as a decimal string
+fn fq_to_decimal(elem: &Fq) -> Result<String, Error> {
+    let mut bytes = Vec::new();
+    elem.serialize_uncompressed(&mut bytes)
+        .map_err(|err| anyhow!(err))?;
+    Ok(BigUint::from_bytes_le(&bytes).to_string())
+}
+
+// Encode a quadratic extension field element as decimal strings
+fn fq2_to_decimal(elem: &Fq2) -> Result<Vec<String>, Error> {
+    Ok(vec![fq_to_decimal(&elem.c0)?, fq_to_decimal(&elem.c1)?])
+}
+
+// Encode an element over the G1 group in the snarkjs projective format
+fn g1_to_decimal(elem: &G1Affine) -> Result<Vec<String>, Error> {
+    Ok(vec![
+        fq_to_decimal(&elem.x)?,
+        fq_to_decimal(&elem.y)?,
+        "1".to_string(),
+    ])
+}
+
+// Encode an element over the G2 group in the snarkjs projective format
+fn g2_to_decimal(elem: &G2Affine) -> Result<Vec<Vec<String>>, Error> {
+    Ok(vec![
+        fq2_to_decimal(&elem.x)?,
+        fq2_to_decimal(&elem.y)?,
+        vec!["1".to_string(), "0".to_string()],
+    ])
 }
 
 /// Groth16 Public witness encoded as JSON.
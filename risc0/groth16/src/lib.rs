@@ -18,13 +18,16 @@ use core::str::FromStr;
 
 use anyhow::{anyhow, Error, Result};
 use ark_bn254::{Fr, G1Affine, G2Affine};
-use ark_serialize::CanonicalDeserialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use num_bigint::BigInt;
 use risc0_zkp::core::digest::Digest;
 
+mod constants;
 pub mod data_structures;
 #[cfg(feature = "prove")]
 pub mod docker;
+#[cfg(feature = "remote")]
+pub mod remote;
 #[cfg(feature = "prove")]
 mod seal_format;
 #[cfg(feature = "prove")]
@@ -34,7 +37,7 @@ pub mod verifier;
 pub use data_structures::{ProofJson, PublicInputsJson, Seal, VerifyingKeyJson};
 #[cfg(feature = "prove")]
 pub use seal_to_json::to_json;
-pub use verifier::Verifier;
+pub use verifier::{Verifier, VerifyingKey};
 
 // Deserialize a scalar field from bytes in big-endian format
 pub(crate) fn fr_from_bytes(scalar: &[u8]) -> Result<Fr, Error> {
@@ -74,6 +77,32 @@ pub(crate) fn g2_from_bytes(elem: &Vec<Vec<Vec<u8>>>) -> Result<G2Affine, Error>
     G2Affine::deserialize_uncompressed(&*g2_affine).map_err(|err| anyhow!(err))
 }
 
+// Serialize an element over the G1 group into bytes in big-endian format
+pub(crate) fn g1_to_bytes(elem: &G1Affine) -> Result<Vec<Vec<u8>>, Error> {
+    let mut g1_affine = Vec::new();
+    elem.serialize_uncompressed(&mut g1_affine)
+        .map_err(|err| anyhow!(err))?;
+    Ok(g1_affine
+        .chunks(32)
+        .map(|chunk| chunk.iter().rev().cloned().collect())
+        .collect())
+}
+
+// Serialize an element over the G2 group into bytes in big-endian format
+pub(crate) fn g2_to_bytes(elem: &G2Affine) -> Result<Vec<Vec<Vec<u8>>>, Error> {
+    let mut g2_affine = Vec::new();
+    elem.serialize_uncompressed(&mut g2_affine)
+        .map_err(|err| anyhow!(err))?;
+    let elem: Vec<Vec<u8>> = g2_affine
+        .chunks(32)
+        .map(|chunk| chunk.iter().rev().cloned().collect())
+        .collect();
+    Ok(vec![
+        vec![elem[1].clone(), elem[0].clone()],
+        vec![elem[3].clone(), elem[2].clone()],
+    ])
+}
+
 // Convert the U256 value to a byte array in big-endian format
 pub(crate) fn from_u256(value: &str) -> Result<Vec<u8>, Error> {
     let value = if let Some(stripped) = value.strip_prefix("0x") {
@@ -0,0 +1,56 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+use crate::{to_json, ProofJson, Seal};
+
+/// Compact a given seal of an `identity_p254` receipt into a Groth16 `Seal`
+/// using a remote SNARK proving endpoint.
+///
+/// The seal is converted with [to_json] and posted to `url`. The endpoint is
+/// expected to run the `stark_verify` witness generator and Groth16 prover on
+/// it, replying with the resulting proof in the snarkjs JSON format. The
+/// request fails if no reply is received within `timeout`.
+pub async fn stark_to_snark(
+    url: &str,
+    timeout: Duration,
+    identity_p254_seal_bytes: &[u8],
+) -> Result<Seal> {
+    tracing::debug!("seal-to-json");
+    let mut seal_json = Vec::new();
+    to_json(identity_p254_seal_bytes, &mut seal_json)?;
+
+    tracing::debug!("remote stark-to-snark: {url}");
+    let client = reqwest::Client::builder().timeout(timeout).build()?;
+    let res = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(seal_json)
+        .send()
+        .await?;
+    if !res.status().is_success() {
+        let status = res.status();
+        bail!(
+            "remote SNARK prover returned {status}: {}",
+            res.text().await?
+        );
+    }
+
+    tracing::debug!("Parsing proof");
+    let proof_json: ProofJson = res.json().await?;
+    proof_json.try_into()
+}
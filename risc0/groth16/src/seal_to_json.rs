@@ -12,9 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{Read, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use num_bigint::BigUint;
 use num_traits::Num;
 use risc0_core::field::baby_bear::BabyBearElem;
@@ -26,32 +26,44 @@ use risc0_zkp::core::{
 use crate::seal_format::{IopType, K_SEAL_ELEMS, K_SEAL_TYPES, K_SEAL_WORDS};
 
 /// Convert a seal into a JSON format compatible with the `stark_verify` witness generator.
-pub fn to_json<R: Read, W: Write>(mut reader: R, mut writer: W) -> Result<()> {
-    let mut iop = vec![0u32; K_SEAL_WORDS];
-    reader.read_exact(bytemuck::cast_slice_mut(&mut iop))?;
+///
+/// The seal is consumed from `reader` one element at a time and each converted
+/// element is written out immediately, so memory use is bounded regardless of
+/// the seal size and `writer` may be a pipe feeding the SNARK prover directly.
+pub fn to_json<R: Read, W: Write>(reader: R, writer: W) -> Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut writer = BufWriter::new(writer);
 
     writeln!(writer, "{{\n  \"iop\" : [")?;
 
     let mut pos = 0;
-    for seal_type in K_SEAL_TYPES.iter().take(K_SEAL_ELEMS) {
-        if pos != 0 {
+    let mut words = [0u32; DIGEST_WORDS];
+    for (i, seal_type) in K_SEAL_TYPES.iter().take(K_SEAL_ELEMS).enumerate() {
+        if i != 0 {
             writeln!(writer, ",")?;
         }
         match seal_type {
             IopType::Fp => {
-                let value = BabyBearElem::new_raw(iop[pos]).as_u32();
+                reader
+                    .read_exact(bytemuck::cast_slice_mut(&mut words[..1]))
+                    .with_context(|| format!("seal truncated at element {i}"))?;
+                let value = BabyBearElem::new_raw(words[0]).as_u32();
                 pos += 1;
                 writeln!(writer, "    \"{value}\"")?;
             }
-            _ => {
-                let digest = Digest::try_from(&iop[pos..pos + DIGEST_WORDS])?;
-                let value = digest_to_decimal(&digest)?;
-                pos += 8;
+            IopType::Digest => {
+                reader
+                    .read_exact(bytemuck::cast_slice_mut(&mut words))
+                    .with_context(|| format!("seal truncated at element {i}"))?;
+                let value = digest_to_decimal(&Digest::from(words))?;
+                pos += DIGEST_WORDS;
                 writeln!(writer, "    \"{value}\"")?;
             }
         }
     }
+    ensure!(pos == K_SEAL_WORDS, "seal layout mismatch");
     write!(writer, "  ]\n}}")?;
+    writer.flush()?;
 
     Ok(())
 }
@@ -66,3 +78,25 @@ fn to_decimal(s: &str) -> Option<String> {
         .and_then(|stripped| BigUint::from_str_radix(stripped, 16).ok())
         .map(|n| n.to_str_radix(10))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_valid_json() {
+        let seal = vec![0u32; K_SEAL_WORDS];
+        let mut out = Vec::new();
+        to_json(bytemuck::cast_slice::<u32, u8>(&seal), &mut out).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["iop"].as_array().unwrap().len(), K_SEAL_ELEMS);
+    }
+
+    #[test]
+    fn rejects_truncated_seal() {
+        let seal = vec![0u32; K_SEAL_WORDS - 1];
+        let err = to_json(bytemuck::cast_slice::<u32, u8>(&seal), std::io::sink()).unwrap_err();
+        assert!(err.to_string().contains("seal truncated"));
+    }
+}
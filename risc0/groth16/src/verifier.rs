@@ -14,56 +14,124 @@
 
 use anyhow::{anyhow, Error, Result};
 use ark_bn254::{Bn254, Fr, G1Projective};
-use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    CurveGroup,
+};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
+use risc0_zkp::core::digest::Digest;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
 
 use crate::{
+    constants::{
+        ALPHA_X, ALPHA_Y, BETA_X1, BETA_X2, BETA_Y1, BETA_Y2, DELTA_X1, DELTA_X2, DELTA_Y1,
+        DELTA_Y2, GAMMA_X1, GAMMA_X2, GAMMA_Y1, GAMMA_Y2, IC0_X, IC0_Y, IC1_X, IC1_Y, IC2_X, IC2_Y,
+        IC3_X, IC3_Y, IC4_X, IC4_Y,
+    },
     from_u256, g1_from_bytes, g2_from_bytes, ProofJson, PublicInputsJson, Seal, VerifyingKeyJson,
 };
 
-// Constants from: bonsai/ethereum/contracts/groth16/Groth16Verifier.sol
-// When running a new ceremony, update them by running cargo xtask bootstrap-groth16.
-const ALPHA_X: &str =
-    "20491192805390485299153009773594534940189261866228447918068658471970481763042";
-const ALPHA_Y: &str =
-    "9383485363053290200918347156157836566562967994039712273449902621266178545958";
-const BETA_X1: &str =
-    "4252822878758300859123897981450591353533073413197771768651442665752259397132";
-const BETA_X2: &str =
-    "6375614351688725206403948262868962793625744043794305715222011528459656738731";
-const BETA_Y1: &str =
-    "21847035105528745403288232691147584728191162732299865338377159692350059136679";
-const BETA_Y2: &str =
-    "10505242626370262277552901082094356697409835680220590971873171140371331206856";
-const GAMMA_X1: &str =
-    "11559732032986387107991004021392285783925812861821192530917403151452391805634";
-const GAMMA_X2: &str =
-    "10857046999023057135944570762232829481370756359578518086990519993285655852781";
-const GAMMA_Y1: &str =
-    "4082367875863433681332203403145435568316851327593401208105741076214120093531";
-const GAMMA_Y2: &str =
-    "8495653923123431417604973247489272438418190587263600148770280649306958101930";
-const DELTA_X1: &str =
-    "7693487429026351977103582346623702458502994930724273067160489676068253241122";
-const DELTA_X2: &str =
-    "18083587067730303898088465773541035809657139089748028791075809901844080381583";
-const DELTA_Y1: &str =
-    "7359384703862385660061748803901931167856917172356103761995576336394013820206";
-const DELTA_Y2: &str =
-    "19155327851717349610711498610928624259731705665803906701543914342469719520424";
-
-const IC0_X: &str = "3946965800586156692981925338368207536736705930062938727478976206429630223321";
-const IC0_Y: &str = "9960693350301697546955796394716396930467039081099556382890913872882520616640";
-const IC1_X: &str = "12558970553567495705160565934359147265891951005223735094684712986779479012425";
-const IC1_Y: &str = "6199258869647841500919704073457246710601253972070160340767955790036898000135";
-const IC2_X: &str = "16706808935575787802711250967470306094414582323642394044367277865965614549578";
-const IC2_Y: &str = "4311171349764489922968166376528109775195413405587105360819319395803197119429";
-const IC3_X: &str = "18597303695576860451572687600097059285572952378680993135977532372976749998241";
-const IC3_Y: &str = "21723251549997210319914312194189948104957781222830842952199420842095495134729";
-const IC4_X: &str = "12164939688015960624286304530289921602116255917351297336606146584068755512927";
-const IC4_Y: &str = "17036398532634379807038707418819848170237695473144751623976431755966389161373";
+/// Groth16 verifying key over the BN_254 curve.
+///
+/// The key produced by the RISC Zero trusted setup ceremony is returned by [verifying_key].
+/// Deployments running their own setup can load their key from the snarkjs JSON export, via
+/// `TryFrom<VerifyingKeyJson>`, or from bytes produced by [VerifyingKey::to_bytes].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifyingKey(pub ark_groth16::VerifyingKey<Bn254>);
+
+impl VerifyingKey {
+    /// Deserialize a `VerifyingKey` from its uncompressed encoding in little endian.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let vk = ark_groth16::VerifyingKey::<Bn254>::deserialize_uncompressed(bytes)
+            .map_err(|err| anyhow!(err))?;
+        Ok(Self(vk))
+    }
+
+    /// Reads the `VerifyingKey` from the `uint256 constant` declarations of a snarkjs generated
+    /// Solidity verifier, e.g. `Groth16Verifier.sol`.
+    pub fn from_solidity(source: &str) -> Result<Self, Error> {
+        let constant = |name: &str| {
+            source
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("uint256 constant "))
+                .filter_map(|decl| decl.split_once('='))
+                .find(|(key, _)| key.trim() == name)
+                .map(|(_, value)| value.trim().trim_end_matches(';').trim())
+                .ok_or_else(|| anyhow!("{name} not found in the Solidity verifier"))
+                .and_then(from_u256)
+        };
+        let g1 = |x: &str, y: &str| g1_from_bytes(&[constant(x)?, constant(y)?]);
+        let g2 = |name: &str| {
+            g2_from_bytes(&vec![
+                vec![
+                    constant(&format!("{name}x1"))?,
+                    constant(&format!("{name}x2"))?,
+                ],
+                vec![
+                    constant(&format!("{name}y1"))?,
+                    constant(&format!("{name}y2"))?,
+                ],
+            ])
+        };
+        let gamma_abc_g1 = (0..)
+            .take_while(|i| constant(&format!("IC{i}x")).is_ok())
+            .map(|i| g1(&format!("IC{i}x"), &format!("IC{i}y")))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self(ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: g1("alphax", "alphay")?,
+            beta_g2: g2("beta")?,
+            gamma_g2: g2("gamma")?,
+            delta_g2: g2("delta")?,
+            gamma_abc_g1,
+        }))
+    }
+
+    /// Serialize the `VerifyingKey` into its uncompressed encoding in little endian.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.0
+            .serialize_uncompressed(&mut bytes)
+            .map_err(|err| anyhow!(err))?;
+        Ok(bytes)
+    }
+
+    /// Computes the SHA-256 digest of the uncompressed encoding of the `VerifyingKey`, which
+    /// identifies the key, e.g. to check that a deployed verifier uses the expected one.
+    pub fn digest(&self) -> Result<Digest, Error> {
+        let digest: [u8; 32] = Sha256::digest(self.to_bytes()?).into();
+        Ok(digest.into())
+    }
+
+    /// Serialize the `VerifyingKey` into its compressed encoding in little endian.
+    pub fn to_compressed_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.0
+            .serialize_compressed(&mut bytes)
+            .map_err(|err| anyhow!(err))?;
+        Ok(bytes)
+    }
+
+    /// Computes the prepared verifying key.
+    pub fn prepared(&self) -> PreparedVerifyingKey<Bn254> {
+        prepare_verifying_key(&self.0)
+    }
+
+    /// Encode the `VerifyingKey` in the snarkjs JSON format.
+    pub fn to_json(&self) -> Result<VerifyingKeyJson, Error> {
+        VerifyingKeyJson::from_verifying_key(&self.0)
+    }
+}
+
+impl TryFrom<VerifyingKeyJson> for VerifyingKey {
+    type Error = Error;
+    fn try_from(vk: VerifyingKeyJson) -> Result<Self, Error> {
+        Ok(Self(vk.verifying_key()?))
+    }
+}
 
 /// Groth16 `Verifier` instance over the BN_254 curve encoded in little endian.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -141,10 +209,87 @@ impl Verifier {
             false => Err(anyhow!("Invalid proof")),
         }
     }
+
+    /// Verifies a batch of Groth16 proofs sharing the same verifying key.
+    ///
+    /// The pairing equations of all proofs are combined into a single check using a random
+    /// linear combination, so the cost of the final exponentiation is paid only once. The
+    /// random coefficients are derived by hashing the whole batch. If the batch fails to
+    /// verify, each proof must be checked with [Verifier::verify] to identify the invalid ones.
+    pub fn verify_batch(verifiers: &[Verifier]) -> Result<(), Error> {
+        let Some(first) = verifiers.first() else {
+            return Ok(());
+        };
+        if verifiers
+            .iter()
+            .any(|verifier| verifier.encoded_pvk != first.encoded_pvk)
+        {
+            return Err(anyhow!("Batch contains different verifying keys"));
+        }
+        let pvk = PreparedVerifyingKey::<Bn254>::deserialize_uncompressed(&*first.encoded_pvk)
+            .map_err(|err| anyhow!(err))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&first.encoded_pvk);
+        for verifier in verifiers {
+            hasher.update(&verifier.encoded_proof);
+            hasher.update(&verifier.encoded_prepared_inputs);
+        }
+        let seed = hasher.finalize();
+
+        let mut a = Vec::with_capacity(verifiers.len() + 2);
+        let mut b = Vec::with_capacity(verifiers.len() + 2);
+        let mut sum_r = Fr::default();
+        let mut sum_inputs = G1Projective::default();
+        let mut sum_c = G1Projective::default();
+        for (i, verifier) in verifiers.iter().enumerate() {
+            let proof = Proof::<Bn254>::deserialize_uncompressed(&*verifier.encoded_proof)
+                .map_err(|err| anyhow!(err))?;
+            let prepared_inputs =
+                G1Projective::deserialize_uncompressed(&*verifier.encoded_prepared_inputs)
+                    .map_err(|err| anyhow!(err))?;
+            let r = batch_coefficient(&seed, i)?;
+
+            a.push(<Bn254 as Pairing>::G1Prepared::from(
+                (proof.a * r).into_affine(),
+            ));
+            b.push(<Bn254 as Pairing>::G2Prepared::from(proof.b));
+            sum_r += r;
+            sum_inputs += prepared_inputs * r;
+            sum_c += proof.c * r;
+        }
+        a.push(sum_inputs.into_affine().into());
+        b.push(pvk.gamma_g2_neg_pc.clone());
+        a.push(sum_c.into_affine().into());
+        b.push(pvk.delta_g2_neg_pc.clone());
+
+        let qap = Bn254::multi_miller_loop(a, b);
+        let test = Bn254::final_exponentiation(qap).ok_or(anyhow!("Invalid proof"))?;
+        match test == PairingOutput(pvk.alpha_g1_beta_g2) * sum_r {
+            true => Ok(()),
+            false => Err(anyhow!("Invalid proof")),
+        }
+    }
+}
+
+// Derives the coefficient of the `index`-th proof in a batch from the batch seed
+fn batch_coefficient(seed: &[u8], index: usize) -> Result<Fr, Error> {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    hasher.update((index as u64).to_le_bytes());
+    let mut scalar: [u8; 32] = hasher.finalize().into();
+    // Clear the most significant byte so the scalar is below the field modulus.
+    scalar[31] = 0;
+    Fr::deserialize_uncompressed(&scalar[..]).map_err(|err| anyhow!(err))
 }
 
 /// Computes the default prepared verifying key, used by Bonsai.
 pub fn prepared_verifying_key() -> Result<PreparedVerifyingKey<Bn254>, Error> {
+    Ok(verifying_key()?.prepared())
+}
+
+/// Returns the default verifying key, used by Bonsai.
+pub fn verifying_key() -> Result<VerifyingKey, Error> {
     let alpha_g1 = g1_from_bytes(&[from_u256(ALPHA_X)?, from_u256(ALPHA_Y)?])?;
     let beta_g2 = g2_from_bytes(&vec![
         vec![from_u256(BETA_X1)?, from_u256(BETA_X2)?],
@@ -166,13 +311,11 @@ pub fn prepared_verifying_key() -> Result<PreparedVerifyingKey<Bn254>, Error> {
     let ic4 = g1_from_bytes(&[from_u256(IC4_X)?, from_u256(IC4_Y)?])?;
     let gamma_abc_g1 = vec![ic0, ic1, ic2, ic3, ic4];
 
-    let vk = VerifyingKey::<Bn254> {
+    Ok(VerifyingKey(ark_groth16::VerifyingKey::<Bn254> {
         alpha_g1,
         beta_g2,
         gamma_g2,
         delta_g2,
         gamma_abc_g1,
-    };
-
-    Ok(prepare_verifying_key(&vk))
+    }))
 }
@@ -0,0 +1,49 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use hex::FromHex;
+use risc0_groth16::{split_digest, Seal, Verifier, VerifyingKey, VerifyingKeyJson};
+use risc0_zkp::core::digest::Digest;
+use serde_json::Value;
+
+#[test]
+fn verify_archived_vectors() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    let mut count = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(true, |ext| ext != "json") {
+            continue;
+        }
+
+        let vector: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let field = |name: &str| vector[name].as_str().unwrap().to_string();
+
+        let verifying_key: VerifyingKeyJson =
+            serde_json::from_value(vector["verifying_key"].clone()).unwrap();
+        let verifying_key: VerifyingKey = verifying_key.try_into().unwrap();
+        let (a0, a1) = split_digest(Digest::from_hex(field("allowed_ids_root")).unwrap()).unwrap();
+        let (c0, c1) = split_digest(Digest::from_hex(field("claim_digest")).unwrap()).unwrap();
+        let seal = Seal::from_vec(&hex::decode(field("seal")).unwrap()).unwrap();
+
+        Verifier::new(&seal, vec![a0, a1, c0, c1], verifying_key.prepared())
+            .unwrap()
+            .verify()
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        count += 1;
+    }
+    assert!(count > 0, "no test vectors found in tests/vectors");
+}
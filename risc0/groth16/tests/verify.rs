@@ -14,7 +14,9 @@
 
 #[cfg(test)]
 mod tests {
-    use risc0_groth16::{ProofJson, PublicInputsJson, Verifier, VerifyingKeyJson};
+    use risc0_groth16::{
+        ProofJson, PublicInputsJson, Seal, Verifier, VerifyingKey, VerifyingKeyJson,
+    };
 
     const TEST_VERIFICATION_KEY: &str = include_str!("data/verification_key.json");
     const TEST_PROOF: &str = include_str!("data/proof.json");
@@ -30,4 +32,72 @@ mod tests {
         let verifier = Verifier::from_json(proof, public_inputs, verifying_key).unwrap();
         verifier.verify().unwrap();
     }
+
+    #[test]
+    fn test_verify_custom_verifying_key() {
+        let verifying_key: VerifyingKeyJson = serde_json::from_str(TEST_VERIFICATION_KEY).unwrap();
+        let verifying_key: VerifyingKey = verifying_key.try_into().unwrap();
+        let verifying_key = VerifyingKey::from_bytes(&verifying_key.to_bytes().unwrap()).unwrap();
+
+        let proof: ProofJson = serde_json::from_str(TEST_PROOF).unwrap();
+        let public_inputs = PublicInputsJson {
+            values: serde_json::from_str(TEST_PUBLIC_INPUTS).unwrap(),
+        };
+        let verifier = Verifier::new(
+            &proof.try_into().unwrap(),
+            public_inputs.to_scalar().unwrap(),
+            verifying_key.prepared(),
+        )
+        .unwrap();
+        verifier.verify().unwrap();
+    }
+
+    #[test]
+    fn test_seal_compressed_roundtrip() {
+        let proof: ProofJson = serde_json::from_str(TEST_PROOF).unwrap();
+        let seal: Seal = proof.try_into().unwrap();
+        let compressed = seal.to_compressed_vec().unwrap();
+        assert_eq!(compressed.len() * 2, seal.to_vec().len());
+        assert_eq!(Seal::from_compressed_vec(&compressed).unwrap(), seal);
+    }
+
+    #[test]
+    fn test_verifying_key_to_json() {
+        let expected: serde_json::Value = serde_json::from_str(TEST_VERIFICATION_KEY).unwrap();
+        let verifying_key: VerifyingKeyJson = serde_json::from_str(TEST_VERIFICATION_KEY).unwrap();
+        let verifying_key: VerifyingKey = verifying_key.try_into().unwrap();
+
+        let json = verifying_key.to_json().unwrap();
+        let actual = serde_json::to_value(&json).unwrap();
+        for field in [
+            "protocol",
+            "curve",
+            "nPublic",
+            "vk_alpha_1",
+            "vk_beta_2",
+            "vk_gamma_2",
+            "vk_delta_2",
+            "IC",
+        ] {
+            assert_eq!(actual[field], expected[field], "{field}");
+        }
+        assert_eq!(VerifyingKey::try_from(json).unwrap(), verifying_key);
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let verifier = |public_input: &str| {
+            let verifying_key: VerifyingKeyJson =
+                serde_json::from_str(TEST_VERIFICATION_KEY).unwrap();
+            let proof: ProofJson = serde_json::from_str(TEST_PROOF).unwrap();
+            let public_inputs = PublicInputsJson {
+                values: vec![public_input.to_string()],
+            };
+            Verifier::from_json(proof, public_inputs, verifying_key).unwrap()
+        };
+
+        Verifier::verify_batch(&[]).unwrap();
+        Verifier::verify_batch(&[verifier("33"), verifier("33"), verifier("33")]).unwrap();
+        Verifier::verify_batch(&[verifier("33"), verifier("34")]).unwrap_err();
+    }
 }
@@ -0,0 +1,103 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Python bindings for the RISC Zero zkVM.
+
+use pyo3::{exceptions::PyRuntimeError, prelude::*, types::PyBytes};
+use risc0_zkvm::{default_executor, default_prover, sha::Digest, ExecutorEnv, Receipt};
+
+fn to_py_err(err: impl ToString) -> PyErr {
+    PyRuntimeError::new_err(err.to_string())
+}
+
+fn build_env(input: &[u8]) -> PyResult<ExecutorEnv<'static>> {
+    ExecutorEnv::builder()
+        .write_slice(input)
+        .build()
+        .map_err(to_py_err)
+}
+
+/// Result of executing a guest.
+#[pyclass(get_all)]
+struct SessionInfo {
+    /// The data publicly committed by the guest.
+    journal: Py<PyBytes>,
+    /// The exit code of the guest, such as `Halted(0)`.
+    exit_code: String,
+    /// The number of user cycles, without the overhead of the segments.
+    cycles: u64,
+    /// The number of segments.
+    segments: usize,
+}
+
+/// Returns the image ID of a guest ELF.
+#[pyfunction]
+fn compute_image_id<'py>(py: Python<'py>, elf: &[u8]) -> PyResult<&'py PyBytes> {
+    let image_id = risc0_zkvm::compute_image_id(elf).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, image_id.as_bytes()))
+}
+
+/// Executes a guest ELF with the given input, without proving it.
+#[pyfunction]
+fn execute(py: Python<'_>, elf: &[u8], input: &[u8]) -> PyResult<SessionInfo> {
+    let env = build_env(input)?;
+    let session = default_executor().execute(env, elf).map_err(to_py_err)?;
+    Ok(SessionInfo {
+        journal: PyBytes::new(py, &session.journal.bytes).into(),
+        exit_code: format!("{:?}", session.exit_code),
+        cycles: session.segments.iter().map(|s| s.cycles as u64).sum(),
+        segments: session.segments.len(),
+    })
+}
+
+/// Proves the execution of a guest ELF with the given input, returning the
+/// receipt in the wire format.
+#[pyfunction]
+fn prove<'py>(py: Python<'py>, elf: &[u8], input: &[u8]) -> PyResult<&'py PyBytes> {
+    let env = build_env(input)?;
+    let receipt = default_prover().prove(env, elf).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &receipt.to_bytes().map_err(to_py_err)?))
+}
+
+/// Verifies that a receipt, in the wire format, proves a successful execution
+/// of the guest with the given image ID, raising an error otherwise.
+#[pyfunction]
+fn verify(py: Python<'_>, receipt: &[u8], image_id: &[u8]) -> PyResult<()> {
+    let receipt = Receipt::from_bytes(receipt).map_err(to_py_err)?;
+    let image_id =
+        Digest::try_from(image_id).map_err(|_| to_py_err("image IDs must be 32 bytes long"))?;
+    // Let other Python threads run while the receipt is verified.
+    py.allow_threads(|| receipt.verify(image_id))
+        .map_err(to_py_err)
+}
+
+/// Returns the journal of a receipt, in the wire format.
+///
+/// The journal is only trustworthy once the receipt has been verified.
+#[pyfunction]
+fn journal<'py>(py: Python<'py>, receipt: &[u8]) -> PyResult<&'py PyBytes> {
+    let receipt = Receipt::from_bytes(receipt).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &receipt.journal.bytes))
+}
+
+#[pymodule]
+fn risc0(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<SessionInfo>()?;
+    m.add_function(wrap_pyfunction!(compute_image_id, m)?)?;
+    m.add_function(wrap_pyfunction!(execute, m)?)?;
+    m.add_function(wrap_pyfunction!(prove, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(journal, m)?)?;
+    Ok(())
+}
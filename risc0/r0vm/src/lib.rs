@@ -160,10 +160,9 @@ impl Cli {
             HashFn::Sha256 => "sha-256",
             HashFn::Poseidon => "poseidon",
         };
-        let opts = ProverOpts {
-            hashfn: hashfn.to_string(),
-            prove_guest_errors: self.prove_guest_errors,
-        };
+        let mut opts = ProverOpts::default();
+        opts.hashfn = hashfn.to_string();
+        opts.prove_guest_errors = self.prove_guest_errors;
 
         get_prover_server(&opts).unwrap()
     }
@@ -0,0 +1,66 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Status returned by the functions of this library.
+typedef enum {
+  // The call succeeded.
+  RISC0_OK = 0,
+  // A required pointer argument was null.
+  RISC0_ERR_NULL = 1,
+  // The receipt could not be decoded.
+  RISC0_ERR_DECODE = 2,
+  // The receipt is invalid for the given image ID.
+  RISC0_ERR_VERIFY = 3,
+  // The output buffer is too small.
+  RISC0_ERR_BUFFER_TOO_SMALL = 4,
+  // The library panicked.
+  RISC0_ERR_PANIC = 5,
+} risc0_status;
+
+// Verifies that `receipt`, in the wire format, proves a successful execution
+// of the guest with the 32 byte `image_id`.
+risc0_status risc0_verify(const uint8_t* receipt, size_t receipt_len, const uint8_t* image_id);
+
+// Verifies the integrity of `receipt`, in the wire format, including that its
+// journal is attested to by the seal, without checking the image ID or the
+// exit code of the guest.
+risc0_status risc0_verify_integrity(const uint8_t* receipt, size_t receipt_len);
+
+// Copies the journal of `receipt`, in the wire format, into `out`, and sets
+// `journal_len` to its length. If `out_len` is too small, nothing is copied,
+// `journal_len` is still set, and RISC0_ERR_BUFFER_TOO_SMALL is returned.
+//
+// The journal is only trustworthy once the receipt has been verified.
+risc0_status risc0_receipt_journal(const uint8_t* receipt,
+                                   size_t receipt_len,
+                                   uint8_t* out,
+                                   size_t out_len,
+                                   size_t* journal_len);
+
+// Returns the message of the last error on the calling thread, or null if the
+// last call succeeded. The string is valid until the next call on the thread.
+const char* risc0_last_error(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif
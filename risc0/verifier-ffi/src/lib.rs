@@ -0,0 +1,226 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C ABI for verifying RISC Zero receipts.
+//!
+//! The functions are declared in `include/risc0_verifier.h`. Each of them
+//! returns a [Status], and records the message of its error, if any, which is
+//! returned by [risc0_last_error]. A panic is caught before it reaches the
+//! caller, and reported as [Status::ErrPanic].
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::CString,
+    os::raw::c_char,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use risc0_zkvm::{
+    sha::{Digest, DIGEST_BYTES},
+    Receipt, VerifierContext,
+};
+
+/// Status returned by the functions of this library.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    ErrNull = 1,
+    /// The receipt could not be decoded.
+    ErrDecode = 2,
+    /// The receipt is invalid for the given image ID.
+    ErrVerify = 3,
+    /// The output buffer is too small.
+    ErrBufferTooSmall = 4,
+    /// The library panicked.
+    ErrPanic = 5,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(msg: Option<String>) {
+    let msg = msg.map(|msg| CString::new(msg.replace('\0', " ")).unwrap());
+    LAST_ERROR.with(|last| *last.borrow_mut() = msg);
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(msg) => msg.to_string(),
+            Err(_) => "unknown panic".into(),
+        },
+    }
+}
+
+// Runs `f`, recording the message of its error, if any. Panics must not
+// unwind into the caller, so they are caught and reported as errors.
+fn run(f: impl FnOnce() -> Result<(), (Status, String)>) -> Status {
+    let result = catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err((Status::ErrPanic, panic_message(payload))));
+    match result {
+        Ok(()) => {
+            set_last_error(None);
+            Status::Ok
+        }
+        Err((status, msg)) => {
+            set_last_error(Some(msg));
+            status
+        }
+    }
+}
+
+unsafe fn decode_receipt(
+    receipt: *const u8,
+    receipt_len: usize,
+) -> Result<Receipt, (Status, String)> {
+    if receipt.is_null() {
+        return Err((Status::ErrNull, "receipt is null".into()));
+    }
+    let bytes = slice::from_raw_parts(receipt, receipt_len);
+    Receipt::from_bytes(bytes).map_err(|err| (Status::ErrDecode, err.to_string()))
+}
+
+/// Verifies that `receipt`, in the wire format, proves a successful execution
+/// of the guest with the 32 byte `image_id`.
+///
+/// # Safety
+///
+/// `receipt` must point to `receipt_len` readable bytes, and `image_id` to 32.
+#[no_mangle]
+pub unsafe extern "C" fn risc0_verify(
+    receipt: *const u8,
+    receipt_len: usize,
+    image_id: *const u8,
+) -> Status {
+    run(|| {
+        let receipt = decode_receipt(receipt, receipt_len)?;
+        if image_id.is_null() {
+            return Err((Status::ErrNull, "image ID is null".into()));
+        }
+        let image_id = Digest::try_from(slice::from_raw_parts(image_id, DIGEST_BYTES)).unwrap();
+        receipt
+            .verify(image_id)
+            .map_err(|err| (Status::ErrVerify, err.to_string()))
+    })
+}
+
+/// Verifies the integrity of `receipt`, in the wire format, including that its
+/// journal is attested to by the seal, without checking the image ID or the
+/// exit code of the guest.
+///
+/// # Safety
+///
+/// `receipt` must point to `receipt_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn risc0_verify_integrity(receipt: *const u8, receipt_len: usize) -> Status {
+    run(|| {
+        decode_receipt(receipt, receipt_len)?
+            .verify_integrity_with_context(&VerifierContext::default())
+            .map_err(|err| (Status::ErrVerify, err.to_string()))
+    })
+}
+
+/// Copies the journal of `receipt`, in the wire format, into `out`, and sets
+/// `journal_len` to its length.
+///
+/// If `out_len` is too small, nothing is copied, `journal_len` is still set,
+/// and [Status::ErrBufferTooSmall] is returned.
+///
+/// # Safety
+///
+/// `receipt` must point to `receipt_len` readable bytes, `out` to `out_len`
+/// writable bytes, and `journal_len` to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn risc0_receipt_journal(
+    receipt: *const u8,
+    receipt_len: usize,
+    out: *mut u8,
+    out_len: usize,
+    journal_len: *mut usize,
+) -> Status {
+    run(|| {
+        let receipt = decode_receipt(receipt, receipt_len)?;
+        if journal_len.is_null() {
+            return Err((Status::ErrNull, "journal_len is null".into()));
+        }
+        let journal = &receipt.journal.bytes;
+        *journal_len = journal.len();
+        if journal.len() > out_len {
+            return Err((
+                Status::ErrBufferTooSmall,
+                format!(
+                    "journal of {} bytes does not fit in {out_len} bytes",
+                    journal.len()
+                ),
+            ));
+        }
+        if !journal.is_empty() {
+            if out.is_null() {
+                return Err((Status::ErrNull, "out is null".into()));
+            }
+            ptr::copy_nonoverlapping(journal.as_ptr(), out, journal.len());
+        }
+        Ok(())
+    })
+}
+
+/// Returns the message of the last error on the calling thread, or null if
+/// the last call succeeded.
+///
+/// The string is valid until the next call on the thread.
+#[no_mangle]
+pub extern "C" fn risc0_last_error() -> *const c_char {
+    catch_unwind(|| {
+        LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map_or(ptr::null(), |msg| msg.as_ptr())
+        })
+    })
+    .unwrap_or(ptr::null())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_receipts() {
+        let junk = [0u8; 16];
+        let status = unsafe { risc0_verify(junk.as_ptr(), junk.len(), [0u8; 32].as_ptr()) };
+        assert_eq!(status, Status::ErrDecode);
+        let msg = unsafe { CStr::from_ptr(risc0_last_error()) };
+        assert!(msg.to_str().unwrap().contains("not an encoded receipt"));
+
+        let status = unsafe { risc0_verify_integrity(ptr::null(), 0) };
+        assert_eq!(status, Status::ErrNull);
+    }
+
+    #[test]
+    fn catches_panics() {
+        let status = run(|| panic!("boom"));
+        assert_eq!(status, Status::ErrPanic);
+        let msg = unsafe { CStr::from_ptr(risc0_last_error()) };
+        assert_eq!(msg.to_str().unwrap(), "boom");
+    }
+}
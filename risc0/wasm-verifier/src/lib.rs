@@ -0,0 +1,62 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebAssembly bindings for verifying RISC Zero receipts.
+//!
+//! Receipts are accepted in the versioned wire format of
+//! [Receipt::to_bytes], or in the JSON format of [Receipt::to_json]. Failures
+//! are thrown as JavaScript errors, carrying the message of the Rust error.
+
+use risc0_zkvm::{sha::Digest, Receipt, VerifierContext};
+use wasm_bindgen::prelude::*;
+
+/// Verify that a receipt, in the wire format, proves a successful execution
+/// of the guest with the given image ID.
+#[wasm_bindgen]
+pub fn verify(receipt: &[u8], image_id: &[u8]) -> Result<(), JsError> {
+    let receipt = Receipt::from_bytes(receipt)?;
+    receipt.verify(to_digest(image_id)?)?;
+    Ok(())
+}
+
+/// Verify that a receipt, in JSON, proves a successful execution of the
+/// guest with the given image ID.
+#[wasm_bindgen(js_name = verifyJson)]
+pub fn verify_json(receipt: &str, image_id: &[u8]) -> Result<(), JsError> {
+    let receipt = Receipt::from_json(receipt)?;
+    receipt.verify(to_digest(image_id)?)?;
+    Ok(())
+}
+
+/// Verify the integrity of a receipt, in the wire format, including that its
+/// journal is attested to by the seal, without checking the image ID or the
+/// exit code of the guest.
+#[wasm_bindgen(js_name = verifyIntegrity)]
+pub fn verify_integrity(receipt: &[u8]) -> Result<(), JsError> {
+    let receipt = Receipt::from_bytes(receipt)?;
+    receipt.verify_integrity_with_context(&VerifierContext::default())?;
+    Ok(())
+}
+
+/// Return the journal of a receipt, in the wire format.
+///
+/// The journal is only trustworthy once the receipt has been verified.
+#[wasm_bindgen]
+pub fn journal(receipt: &[u8]) -> Result<Vec<u8>, JsError> {
+    Ok(Receipt::from_bytes(receipt)?.journal.bytes)
+}
+
+fn to_digest(image_id: &[u8]) -> Result<Digest, JsError> {
+    Digest::try_from(image_id).map_err(|_| JsError::new("image IDs must be 32 bytes long"))
+}
@@ -16,7 +16,10 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use risc0_core::field::{baby_bear::BabyBearElem, Elem};
 use risc0_zkp::core::hash::{
     poseidon::{poseidon_mix, CELLS as POSEIDON_CELLS},
-    poseidon2::{poseidon2_mix, CELLS as POSEIDON2_CELLS},
+    poseidon2::{
+        batch::{poseidon2_mix_batch, LANES},
+        poseidon2_mix, CELLS as POSEIDON2_CELLS,
+    },
 };
 
 fn benchmark_poseidon_mix(c: &mut Criterion) {
@@ -31,5 +34,18 @@ fn benchmark_poseidon2_mix(c: &mut Criterion) {
     c.bench_function("poseidon2_mix", |b| b.iter(|| poseidon2_mix(&mut cells)));
 }
 
-criterion_group!(benches, benchmark_poseidon_mix, benchmark_poseidon2_mix);
+fn benchmark_poseidon2_mix_batch(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut states = [[BabyBearElem::random(&mut rng); POSEIDON2_CELLS]; LANES];
+    c.bench_function("poseidon2_mix_batch", |b| {
+        b.iter(|| poseidon2_mix_batch(&mut states))
+    });
+}
+
+criterion_group!(
+    benches,
+    benchmark_poseidon_mix,
+    benchmark_poseidon2_mix,
+    benchmark_poseidon2_mix_batch
+);
 criterion_main!(benches);
@@ -19,7 +19,11 @@ use alloc::vec::Vec;
 use anyhow::Result;
 use risc0_core::field::{Elem, ExtElem, Field};
 
-use crate::{hal::cpu::SyncSlice, taps::TapSet};
+use crate::{
+    core::{digest::Digest, hash::sha::Sha256},
+    hal::cpu::SyncSlice,
+    taps::TapSet,
+};
 
 // TODO: Remove references to these constants so we don't depend on a
 // fixed set of register groups.
@@ -206,6 +210,54 @@ impl PolyExtStep {
 }
 
 impl PolyExtStepDef {
+    /// Returns the number of constraints of the circuit, i.e. of polynomials
+    /// which must vanish on every cycle of the trace, some of them only when a
+    /// condition holds.
+    pub fn constraint_count(&self) -> usize {
+        self.block
+            .iter()
+            .filter(|op| matches!(op, PolyExtStep::AndEqz(..)))
+            .count()
+    }
+
+    /// Computes a digest of the structure of the circuit, i.e. of its taps
+    /// and of its constraints, which changes whenever the circuit is
+    /// regenerated with different constraints.
+    pub fn digest<S: Sha256>(&self, taps: &TapSet) -> Digest {
+        let mut words = Vec::new();
+        for tap in taps.taps {
+            words.extend([
+                tap.offset as u32,
+                tap.back as u32,
+                tap.group as u32,
+                tap.combo as u32,
+                tap.skip as u32,
+            ]);
+        }
+        words.extend(taps.combo_taps.iter().map(|&x| x as u32));
+        words.extend(taps.combo_begin.iter().map(|&x| x as u32));
+        words.extend(taps.group_begin.iter().map(|&x| x as u32));
+        for op in self.block {
+            match *op {
+                PolyExtStep::Const(value) => words.extend([0, value]),
+                PolyExtStep::Get(tap) => words.extend([1, tap as u32]),
+                PolyExtStep::GetGlobal(base, offset) => {
+                    words.extend([2, base as u32, offset as u32])
+                }
+                PolyExtStep::Add(x1, x2) => words.extend([3, x1 as u32, x2 as u32]),
+                PolyExtStep::Sub(x1, x2) => words.extend([4, x1 as u32, x2 as u32]),
+                PolyExtStep::Mul(x1, x2) => words.extend([5, x1 as u32, x2 as u32]),
+                PolyExtStep::True => words.push(6),
+                PolyExtStep::AndEqz(x, val) => words.extend([7, x as u32, val as u32]),
+                PolyExtStep::AndCond(x, cond, inner) => {
+                    words.extend([8, x as u32, cond as u32, inner as u32])
+                }
+            }
+        }
+        words.push(self.ret as u32);
+        *S::hash_words(&words)
+    }
+
     pub fn step<F: Field>(
         &self,
         mix: &F::ExtElem,
@@ -230,3 +282,56 @@ impl PolyExtStepDef {
         mix_vars[self.ret]
     }
 }
+
+/// Structure of a circuit, for tools which analyze it without parsing the
+/// generated code.
+#[derive(Debug)]
+pub struct CircuitMetadata {
+    /// The taps of the circuit, i.e. the columns and row offsets read by its
+    /// constraints.
+    pub taps: &'static TapSet<'static>,
+    /// Number of constraints. See [PolyExtStepDef::constraint_count].
+    pub constraints: usize,
+    /// Number of elements of the circuit outputs.
+    pub output_size: usize,
+    /// Number of elements of the mix.
+    pub mix_size: usize,
+}
+
+/// A group of columns of the trace, committed together.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnGroup {
+    /// Index of the group, e.g. [REGISTER_GROUP_CODE].
+    pub id: usize,
+    /// Name of the group.
+    pub name: &'static str,
+    /// Number of columns of the group.
+    pub columns: usize,
+    /// Number of taps on the columns of the group.
+    pub taps: usize,
+}
+
+impl CircuitMetadata {
+    /// Collects the metadata of the given circuit, whose constraints are
+    /// described by `poly_ext`.
+    pub fn new<C: CircuitInfo + TapsProvider>(circuit: &C, poly_ext: &PolyExtStepDef) -> Self {
+        Self {
+            taps: circuit.get_taps(),
+            constraints: poly_ext.constraint_count(),
+            output_size: C::OUTPUT_SIZE,
+            mix_size: C::MIX_SIZE,
+        }
+    }
+
+    /// Returns the column groups of the circuit.
+    pub fn column_groups(&self) -> Vec<ColumnGroup> {
+        (0..self.taps.num_groups())
+            .map(|id| ColumnGroup {
+                id,
+                name: self.taps.group_names[id],
+                columns: self.taps.group_size(id),
+                taps: self.taps.group_taps(id).count(),
+            })
+            .collect()
+    }
+}
@@ -19,9 +19,11 @@ pub mod poseidon;
 pub mod poseidon2;
 #[cfg(feature = "prove")]
 pub mod poseidon_254;
+#[cfg(feature = "std")]
+pub mod registry;
 pub mod sha;
 
-use alloc::{boxed::Box, rc::Rc, string::String};
+use alloc::{boxed::Box, rc::Rc, string::String, vec::Vec};
 
 use risc0_core::field::Field;
 
@@ -39,6 +41,34 @@ pub trait HashFn<F: Field>: Send + Sync {
     /// Generate a hash from a slice of extension field element.  This may be
     /// unpadded so this is only safe to used when the size is known.
     fn hash_ext_elem_slice(&self, slice: &[F::ExtElem]) -> Box<Digest>;
+
+    /// Generate the hashes of the pairs of consecutive [Digest]s of `input`
+    /// into `output`, which holds half as many digests. Hash functions which
+    /// can hash several values at once override this to speed up proving.
+    fn hash_pairs(&self, input: &[Digest], output: &mut [Digest]) {
+        assert_eq!(input.len(), 2 * output.len());
+        for (pair, output) in input.chunks_exact(2).zip(output.iter_mut()) {
+            *output = *self.hash_pair(&pair[0], &pair[1]);
+        }
+    }
+
+    /// Generate the hashes of slices of field elements of the same length into
+    /// `output`, where `elem(idx, i)` returns the element `i` of the slice
+    /// hashed into `output[idx]`. Hash functions which can hash several values
+    /// at once override this to speed up proving.
+    fn hash_elem_slices(
+        &self,
+        len: usize,
+        elem: &dyn Fn(usize, usize) -> F::Elem,
+        output: &mut [Digest],
+    ) {
+        let mut slice = Vec::with_capacity(len);
+        for (idx, output) in output.iter_mut().enumerate() {
+            slice.clear();
+            slice.extend((0..len).map(|i| elem(idx, i)));
+            *output = *self.hash_elem_slice(&slice);
+        }
+    }
 }
 
 /// A trait that sets the PRNG used by Fiat-Shamir.  We allow specialization at
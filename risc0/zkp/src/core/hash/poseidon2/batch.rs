@@ -0,0 +1,262 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Poseidon2 permutations of several states at once.
+//!
+//! The states are transposed so that each cell holds the values of [LANES]
+//! states, and the field arithmetic is written without branches. This lets the
+//! compiler turn every operation into a handful of vector instructions, using
+//! NEON on aarch64, and AVX2 on x86_64 when the CPU supports it. The results are
+//! identical to [poseidon2_mix](super::poseidon2_mix) applied to each state.
+
+use core::array;
+
+use risc0_core::field::baby_bear::BabyBearElem;
+
+use super::{
+    consts::{M_INT_DIAG_ULVT, ROUNDS_HALF_FULL, ROUNDS_PARTIAL, ROUND_CONSTANTS},
+    CELLS, CELLS_OUT, CELLS_RATE,
+};
+
+/// Number of states permuted at once.
+pub const LANES: usize = 8;
+
+// The Baby Bear modulus and the Montgomery constant used for multiplication.
+const P: u32 = 15 * (1 << 27) + 1;
+const M: u32 = 0x88000001;
+
+// The Montgomery representation of one value per lane.
+type Lanes = [u32; LANES];
+
+#[inline(always)]
+fn splat(x: BabyBearElem) -> Lanes {
+    [x.as_u32_montgomery(); LANES]
+}
+
+#[inline(always)]
+fn add(a: Lanes, b: Lanes) -> Lanes {
+    array::from_fn(|i| {
+        // The words of a digest read from a seal may not be reduced, so the
+        // sum can overflow. It wraps around, as in the scalar version.
+        let x = a[i].wrapping_add(b[i]);
+        // x - P wraps around, and so is larger than x, unless x >= P.
+        x.min(x.wrapping_sub(P))
+    })
+}
+
+#[inline(always)]
+fn mul(a: Lanes, b: Lanes) -> Lanes {
+    array::from_fn(|i| {
+        let mut o64 = a[i] as u64 * b[i] as u64;
+        let low = (o64 as u32).wrapping_neg();
+        let red = M.wrapping_mul(low);
+        o64 += red as u64 * P as u64;
+        let ret = (o64 >> 32) as u32;
+        ret.min(ret.wrapping_sub(P))
+    })
+}
+
+#[inline(always)]
+fn sbox(x: Lanes) -> Lanes {
+    let x2 = mul(x, x);
+    let x4 = mul(x2, x2);
+    let x6 = mul(x4, x2);
+    mul(x6, x)
+}
+
+#[inline(always)]
+fn multiply_by_4x4_circulant(x: [Lanes; 4]) -> [Lanes; 4] {
+    let t0 = add(x[0], x[1]);
+    let t1 = add(x[2], x[3]);
+    let t2 = add(add(x[1], x[1]), t1);
+    let t3 = add(add(x[3], x[3]), t0);
+    let t1_2 = add(t1, t1);
+    let t0_2 = add(t0, t0);
+    let t4 = add(add(t1_2, t1_2), t3);
+    let t5 = add(add(t0_2, t0_2), t2);
+    let t6 = add(t3, t5);
+    let t7 = add(t2, t4);
+    [t6, t5, t7, t4]
+}
+
+#[inline(always)]
+fn multiply_by_m_ext(cells: &mut [Lanes; CELLS]) {
+    let one = [1; LANES];
+    let mut tmp_sums = [[0; LANES]; 4];
+    for i in 0..CELLS / 4 {
+        let out = multiply_by_4x4_circulant(array::from_fn(|j| cells[i * 4 + j]));
+        for j in 0..4 {
+            // Matches the scaling by the raw value 1 of the scalar version.
+            let to_add = mul(one, out[j]);
+            tmp_sums[j] = add(tmp_sums[j], to_add);
+            cells[i * 4 + j] = to_add;
+        }
+    }
+    for i in 0..CELLS {
+        cells[i] = add(cells[i], tmp_sums[i % 4]);
+    }
+}
+
+#[inline(always)]
+fn multiply_by_m_int(cells: &mut [Lanes; CELLS]) {
+    let sum = cells.iter().fold([0; LANES], |acc, x| add(acc, *x));
+    for i in 0..CELLS {
+        cells[i] = add(sum, mul(splat(M_INT_DIAG_ULVT[i]), cells[i]));
+    }
+}
+
+#[inline(always)]
+fn full_round(cells: &mut [Lanes; CELLS], round: usize) {
+    for i in 0..CELLS {
+        cells[i] = sbox(add(cells[i], splat(ROUND_CONSTANTS[round * CELLS + i])));
+    }
+    multiply_by_m_ext(cells);
+}
+
+#[inline(always)]
+fn partial_round(cells: &mut [Lanes; CELLS], round: usize) {
+    cells[0] = sbox(add(cells[0], splat(ROUND_CONSTANTS[round * CELLS])));
+    multiply_by_m_int(cells);
+}
+
+#[inline(always)]
+fn mix(cells: &mut [Lanes; CELLS]) {
+    let mut round = 0;
+    multiply_by_m_ext(cells);
+    for _i in 0..ROUNDS_HALF_FULL {
+        full_round(cells, round);
+        round += 1;
+    }
+    for _i in 0..ROUNDS_PARTIAL {
+        partial_round(cells, round);
+        round += 1;
+    }
+    for _i in 0..ROUNDS_HALF_FULL {
+        full_round(cells, round);
+        round += 1;
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn mix_avx2(cells: &mut [Lanes; CELLS]) {
+    mix(cells)
+}
+
+fn mix_lanes(cells: &mut [Lanes; CELLS]) {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2, as checked above.
+        return unsafe { mix_avx2(cells) };
+    }
+    mix(cells)
+}
+
+/// Applies the Poseidon2 permutation to [LANES] states.
+pub fn poseidon2_mix_batch(states: &mut [[BabyBearElem; CELLS]; LANES]) {
+    let mut cells: [Lanes; CELLS] =
+        array::from_fn(|i| array::from_fn(|lane| states[lane][i].as_u32_montgomery()));
+    mix_lanes(&mut cells);
+    for (lane, state) in states.iter_mut().enumerate() {
+        for (i, cell) in state.iter_mut().enumerate() {
+            *cell = BabyBearElem::new_raw(cells[i][lane]);
+        }
+    }
+}
+
+/// Computes the [unpadded_hash](super::unpadded_hash) of [LANES] inputs of the
+/// same length, where `elem(lane, i)` returns the element `i` of the input of
+/// the given lane.
+pub fn unpadded_hash_batch<F>(len: usize, elem: F) -> [[BabyBearElem; CELLS_OUT]; LANES]
+where
+    F: Fn(usize, usize) -> BabyBearElem,
+{
+    let mut cells = [[0; LANES]; CELLS];
+    for start in (0..len).step_by(CELLS_RATE) {
+        let absorbed = CELLS_RATE.min(len - start);
+        for (i, cell) in cells[..absorbed].iter_mut().enumerate() {
+            let vals = array::from_fn(|lane| elem(lane, start + i).as_u32_montgomery());
+            *cell = add(*cell, vals);
+        }
+        mix_lanes(&mut cells);
+    }
+    if len == 0 {
+        mix_lanes(&mut cells);
+    }
+    array::from_fn(|lane| array::from_fn(|i| BabyBearElem::new_raw(cells[i][lane])))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use risc0_core::field::{baby_bear::BabyBearElem, Elem};
+
+    use super::{poseidon2_mix_batch, unpadded_hash_batch, LANES, P};
+    use crate::core::hash::poseidon2::{poseidon2_mix, unpadded_hash, CELLS};
+
+    #[test]
+    fn mix_matches_scalar() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut states: [[BabyBearElem; CELLS]; LANES] =
+            core::array::from_fn(|_| core::array::from_fn(|_| BabyBearElem::random(&mut rng)));
+        let mut expected = states;
+        for state in expected.iter_mut() {
+            poseidon2_mix(state);
+        }
+        poseidon2_mix_batch(&mut states);
+        assert_eq!(states, expected);
+    }
+
+    #[test]
+    fn hash_matches_scalar() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for len in [0, 1, 15, 16, 17, 100] {
+            let inputs: Vec<Vec<BabyBearElem>> = (0..LANES)
+                .map(|_| (0..len).map(|_| BabyBearElem::random(&mut rng)).collect())
+                .collect();
+            let hashes = unpadded_hash_batch(len, |lane, i| inputs[lane][i]);
+            for lane in 0..LANES {
+                assert_eq!(
+                    hashes[lane],
+                    unpadded_hash(inputs[lane].iter()),
+                    "len {len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hash_unreduced_words() {
+        // Digest words from an untrusted seal are not necessarily below P.
+        // Words below 2 * P are reduced when they are absorbed, as in the
+        // scalar version.
+        let words = [P, P + 1, 2 * P - 1, 0x87ffffff, P - 1, 0, 1, 2];
+        let inputs: Vec<Vec<BabyBearElem>> = (0..LANES)
+            .map(|lane| {
+                (0..16)
+                    .map(|i| BabyBearElem::new_raw(words[(lane + i) % words.len()]))
+                    .collect()
+            })
+            .collect();
+        let hashes = unpadded_hash_batch(16, |lane, i| inputs[lane][i]);
+        for lane in 0..LANES {
+            assert_eq!(hashes[lane], unpadded_hash(inputs[lane].iter()));
+        }
+
+        // Larger words must not overflow.
+        unpadded_hash_batch(16, |_, _| BabyBearElem::new_raw(u32::MAX));
+    }
+}
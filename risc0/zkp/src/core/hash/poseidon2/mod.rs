@@ -14,6 +14,7 @@
 
 //! An implementation of Poseidon2 targeting the Baby Bear.
 
+pub mod batch;
 pub(crate) mod consts;
 mod rng;
 
@@ -59,6 +60,41 @@ impl HashFn<BabyBear> for Poseidon2HashFn {
             slice.iter().flat_map(|ee| ee.subelems().iter()),
         ))
     }
+
+    fn hash_pairs(&self, input: &[Digest], output: &mut [Digest]) {
+        assert_eq!(input.len(), 2 * output.len());
+        let word = |pair: usize, i: usize| {
+            BabyBearElem::new_raw(input[2 * pair + i / DIGEST_WORDS].as_words()[i % DIGEST_WORDS])
+        };
+        hash_batches(2 * DIGEST_WORDS, &word, output);
+    }
+
+    fn hash_elem_slices(
+        &self,
+        len: usize,
+        elem: &dyn Fn(usize, usize) -> BabyBearElem,
+        output: &mut [Digest],
+    ) {
+        hash_batches(len, elem, output);
+    }
+}
+
+// Hashes the inputs of the outputs in batches of [batch::LANES], and the
+// remaining ones one by one.
+fn hash_batches(len: usize, elem: &dyn Fn(usize, usize) -> BabyBearElem, output: &mut [Digest]) {
+    let mut chunks = output.chunks_exact_mut(batch::LANES);
+    let mut base = 0;
+    for chunk in &mut chunks {
+        let hashes = batch::unpadded_hash_batch(len, |lane, i| elem(base + lane, i));
+        for (output, hash) in chunk.iter_mut().zip(hashes) {
+            *output = *to_digest(hash);
+        }
+        base += batch::LANES;
+    }
+    for (idx, output) in chunks.into_remainder().iter_mut().enumerate() {
+        let slice: Vec<BabyBearElem> = (0..len).map(|i| elem(base + idx, i)).collect();
+        *output = *to_digest(unpadded_hash(slice.iter()));
+    }
 }
 
 struct Poseidon2RngFactory;
@@ -0,0 +1,208 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registry of [HashSuite]s provided by other crates.
+//!
+//! The hash suites of this crate are selected by name, e.g. with the `hashfn`
+//! of the zkVM prover options, and looked up by the name recorded in receipts
+//! when verifying. [register] makes a suite implemented elsewhere, such as a
+//! hardware-accelerated or chain-specific hash, available by name in the same
+//! way, to both the provers and the verifiers of this process.
+//!
+//! The control IDs of a circuit depend on the hash suite its code is committed
+//! with, so a suite is registered along with the control IDs computed with it,
+//! which verifiers accept in the proofs using the suite.
+//!
+//! ```ignore
+//! use risc0_zkp::core::hash::registry;
+//!
+//! registry::register("my-hash", MyHashSuite::new_suite, &MY_HASH_CONTROL_IDS)?;
+//! ```
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter};
+use std::sync::Mutex;
+
+use risc0_core::field::baby_bear::BabyBear;
+
+use super::HashSuite;
+use crate::core::digest::Digest;
+
+/// Constructs a [HashSuite]. A suite is constructed for each use, as it is not
+/// thread safe.
+pub type HashSuiteFactory = fn() -> HashSuite<BabyBear>;
+
+/// Names of the hash suites implemented by this crate, which cannot be
+/// registered.
+pub const BUILTIN_SUITES: &[&str] = &["blake2b", "poseidon", "poseidon2", "sha-256"];
+
+struct Entry {
+    factory: HashSuiteFactory,
+    control_ids: Vec<Digest>,
+}
+
+static REGISTRY: Mutex<BTreeMap<String, Entry>> = Mutex::new(BTreeMap::new());
+
+/// Registers a hash suite under the given name, with the control IDs computed
+/// with it.
+///
+/// The name must match the [HashSuite::name] of the constructed suites, as it
+/// is the name recorded in proofs, and must not already be in use.
+pub fn register(
+    name: &str,
+    factory: HashSuiteFactory,
+    control_ids: &[Digest],
+) -> Result<(), RegistryError> {
+    if BUILTIN_SUITES.contains(&name) {
+        return Err(RegistryError::Builtin(name.to_string()));
+    }
+    let suite_name = factory().name;
+    if suite_name != name {
+        return Err(RegistryError::NameMismatch {
+            name: name.to_string(),
+            suite_name,
+        });
+    }
+    let mut registry = REGISTRY.lock().unwrap();
+    if registry.contains_key(name) {
+        return Err(RegistryError::AlreadyRegistered(name.to_string()));
+    }
+    registry.insert(
+        name.to_string(),
+        Entry {
+            factory,
+            control_ids: control_ids.to_vec(),
+        },
+    );
+    Ok(())
+}
+
+/// Constructs the registered hash suite with the given name, if any.
+pub fn get(name: &str) -> Option<HashSuite<BabyBear>> {
+    let factory = REGISTRY
+        .lock()
+        .unwrap()
+        .get(name)
+        .map(|entry| entry.factory);
+    factory.map(|factory| factory())
+}
+
+/// Returns the control IDs registered with the hash suite with the given name,
+/// if any.
+pub fn control_ids(name: &str) -> Option<Vec<Digest>> {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .get(name)
+        .map(|entry| entry.control_ids.clone())
+}
+
+/// Constructs all the registered hash suites.
+pub fn suites() -> Vec<HashSuite<BabyBear>> {
+    let factories: Vec<HashSuiteFactory> = REGISTRY
+        .lock()
+        .unwrap()
+        .values()
+        .map(|entry| entry.factory)
+        .collect();
+    factories.into_iter().map(|factory| factory()).collect()
+}
+
+/// Error returned by [register].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RegistryError {
+    /// The name is the one of a hash suite implemented by this crate.
+    Builtin(String),
+    /// A hash suite is already registered with this name.
+    AlreadyRegistered(String),
+    /// The hash suite reports another name than the one it is registered with.
+    NameMismatch {
+        /// The name the suite was registered with.
+        name: String,
+        /// The name of the constructed suite.
+        suite_name: String,
+    },
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::Builtin(name) => write!(f, "hash suite {name} is built in"),
+            Self::AlreadyRegistered(name) => write!(f, "hash suite {name} is already registered"),
+            Self::NameMismatch { name, suite_name } => {
+                write!(f, "hash suite registered as {name} is named {suite_name}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+#[cfg(test)]
+mod tests {
+    use risc0_core::field::baby_bear::BabyBear;
+
+    use super::{control_ids, get, register, suites, RegistryError};
+    use crate::core::{
+        digest::Digest,
+        hash::{sha::Sha256HashSuite, HashSuite},
+    };
+
+    fn test_suite() -> HashSuite<BabyBear> {
+        HashSuite {
+            name: "registry-test".into(),
+            ..Sha256HashSuite::new_suite()
+        }
+    }
+
+    fn misnamed_suite() -> HashSuite<BabyBear> {
+        Sha256HashSuite::new_suite()
+    }
+
+    #[test]
+    fn register_and_get() {
+        let ids = [Digest::from([1u32; 8])];
+        assert!(get("registry-test").is_none());
+        register("registry-test", test_suite, &ids).unwrap();
+        assert_eq!(get("registry-test").unwrap().name, "registry-test");
+        assert_eq!(control_ids("registry-test").unwrap(), ids);
+        assert!(suites().iter().any(|suite| suite.name == "registry-test"));
+        assert_eq!(
+            register("registry-test", test_suite, &[]),
+            Err(RegistryError::AlreadyRegistered("registry-test".into()))
+        );
+    }
+
+    #[test]
+    fn invalid_names() {
+        assert_eq!(
+            register("sha-256", misnamed_suite, &[]),
+            Err(RegistryError::Builtin("sha-256".into()))
+        );
+        assert_eq!(
+            register("registry-misnamed", misnamed_suite, &[]),
+            Err(RegistryError::NameMismatch {
+                name: "registry-misnamed".into(),
+                suite_name: "sha-256".into(),
+            })
+        );
+        assert!(get("registry-misnamed").is_none());
+        assert!(control_ids("registry-misnamed").is_none());
+    }
+}
@@ -36,10 +36,21 @@ use crate::{
     FRI_FOLD,
 };
 
+// Number of hashes computed by each task of `hash_rows` and `hash_fold`, so
+// that hash functions can compute several of them at once.
+const HASH_CHUNK_SIZE: usize = 64;
+
 pub struct CpuHal<F: Field> {
     suite: HashSuite<F>,
 }
 
+/// A CPU HAL over the Goldilocks field.
+///
+/// This is experimental, for comparing field choices: no circuit is defined
+/// over Goldilocks, and only the SHA-256 hash suite supports it.
+#[cfg(feature = "goldilocks")]
+pub type GoldilocksCpuHal = CpuHal<risc0_core::field::goldilocks::Goldilocks>;
+
 impl<F: Field> CpuHal<F> {
     pub fn new(suite: HashSuite<F>) -> Self {
         Self { suite }
@@ -209,6 +220,23 @@ impl<T: Default + Clone + Pod> CpuBuffer<T> {
     pub fn as_slice_sync(&self) -> SyncSlice<'_, T> {
         SyncSlice::new(self.as_slice_mut())
     }
+
+    /// Returns the contents of the buffer, without copying them if the buffer
+    /// is neither shared nor a slice of a larger buffer.
+    pub fn into_vec(self) -> Vec<T> {
+        let range = self.region.range();
+        match Rc::try_unwrap(self.buf) {
+            Ok(buf) => {
+                let mut buf = buf.into_inner();
+                if range == (0..buf.0.len()) {
+                    std::mem::take(&mut buf.0)
+                } else {
+                    buf.0[range].to_vec()
+                }
+            }
+            Err(buf) => buf.borrow().0[range].to_vec(),
+        }
+    }
 }
 
 impl<T: Default + Clone + Pod> From<Vec<T>> for CpuBuffer<T> {
@@ -555,25 +583,28 @@ impl<F: Field> Hal for CpuHal<F> {
         let mut output = output.as_slice_mut();
         let matrix = &*matrix.as_slice();
         let hashfn = self.suite.hashfn.as_ref();
-        output.par_iter_mut().enumerate().for_each(|(idx, output)| {
-            let column: Vec<Self::Elem> =
-                (0..col_size).map(|i| matrix[i * row_size + idx]).collect();
-            *output = *hashfn.hash_elem_slice(column.as_slice());
-        });
+        output
+            .par_chunks_mut(HASH_CHUNK_SIZE)
+            .enumerate()
+            .for_each(|(chunk, output)| {
+                let base = chunk * HASH_CHUNK_SIZE;
+                let elem = |idx: usize, i: usize| matrix[i * row_size + base + idx];
+                hashfn.hash_elem_slices(col_size, &elem, output);
+            });
     }
 
     fn hash_fold(&self, io: &Self::Buffer<Digest>, input_size: usize, output_size: usize) {
         assert!(io.size() >= 2 * input_size);
         assert_eq!(input_size, 2 * output_size);
-        let io = io.as_slice_sync();
-        let output = io.slice(output_size, output_size);
-        let input = io.slice(input_size, input_size);
+        let mut io = io.as_slice_mut();
+        let (output, input) = io.split_at_mut(input_size);
+        let output = &mut output[output_size..];
+        let input = &input[..input_size];
         let hashfn = self.suite.hashfn.as_ref();
-        (0..output.size()).into_par_iter().for_each(|idx| {
-            let in1 = input.get(2 * idx);
-            let in2 = input.get(2 * idx + 1);
-            output.set(idx, *hashfn.hash_pair(&in1, &in2));
-        });
+        output
+            .par_chunks_mut(HASH_CHUNK_SIZE)
+            .zip(input.par_chunks(2 * HASH_CHUNK_SIZE))
+            .for_each(|(output, input)| hashfn.hash_pairs(input, output));
     }
 
     fn gather_sample(
@@ -604,10 +635,10 @@ impl<F: Field> Hal for CpuHal<F> {
 mod tests {
     use hex::FromHex;
     use rand::thread_rng;
-    use risc0_core::field::baby_bear::BabyBear;
+    use risc0_core::field::baby_bear::{BabyBear, BabyBearElem};
 
     use super::*;
-    use crate::core::hash::sha::Sha256HashSuite;
+    use crate::core::hash::{poseidon2::Poseidon2HashSuite, sha::Sha256HashSuite};
 
     #[test]
     #[should_panic]
@@ -618,6 +649,14 @@ mod tests {
         hal.eltwise_add_elem(&a, &b, &b);
     }
 
+    #[test]
+    fn into_vec() {
+        let buf = CpuBuffer::from((0..10u32).collect::<Vec<_>>());
+        let slice = buf.slice(2, 3);
+        assert_eq!(slice.into_vec(), vec![2, 3, 4]);
+        assert_eq!(buf.into_vec(), (0..10).collect::<Vec<_>>());
+    }
+
     #[test]
     fn fp() {
         let hal: CpuHal<BabyBear> = CpuHal::new(Sha256HashSuite::new_suite());
@@ -674,6 +713,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn poseidon2_batched_hashes() {
+        let hal: CpuHal<BabyBear> = CpuHal::new(Poseidon2HashSuite::new_suite());
+        let hashfn = hal.suite.hashfn.clone();
+        let mut rng = thread_rng();
+        let (rows, cols) = (100, 20);
+
+        let matrix = hal.alloc_elem("matrix", rows * cols);
+        matrix.view_mut(|matrix| {
+            for elem in matrix.iter_mut() {
+                *elem = BabyBearElem::random(&mut rng);
+            }
+        });
+        let output = hal.alloc_digest("output", rows);
+        hal.hash_rows(&output, &matrix);
+        matrix.view(|matrix| {
+            output.view(|output| {
+                for (idx, digest) in output.iter().enumerate() {
+                    let column: Vec<_> = (0..cols).map(|i| matrix[i * rows + idx]).collect();
+                    assert_eq!(*digest, *hashfn.hash_elem_slice(&column));
+                }
+            });
+        });
+
+        let io = hal.alloc_digest("io", 2 * rows);
+        io.view_mut(|io| {
+            for (idx, digest) in io[rows..].iter_mut().enumerate() {
+                *digest = *hashfn.hash_elem_slice(&[BabyBearElem::new(idx as u32)]);
+            }
+        });
+        hal.hash_fold(&io, rows, rows / 2);
+        io.view(|io| {
+            for idx in 0..rows / 2 {
+                let (in1, in2) = (io[rows + 2 * idx], io[rows + 2 * idx + 1]);
+                assert_eq!(io[rows / 2 + idx], *hashfn.hash_pair(&in1, &in2));
+            }
+        });
+    }
+
     #[test]
     fn hash_rows() {
         do_hash_rows(
@@ -682,4 +760,58 @@ mod tests {
             &["da5698be17b9b46962335799779fbeca8ce5d491c0d26243bafef9ea1837a9d8"],
         );
     }
+
+    #[cfg(feature = "goldilocks")]
+    mod goldilocks {
+        use rand::thread_rng;
+        use risc0_core::field::{
+            goldilocks::{Goldilocks, GoldilocksElem},
+            Elem,
+        };
+
+        use super::test_binary;
+        use crate::{
+            core::hash::sha::Sha256HashSuite,
+            hal::{cpu::GoldilocksCpuHal, Buffer, Hal},
+        };
+
+        #[test]
+        fn fp() {
+            let hal = GoldilocksCpuHal::new(Sha256HashSuite::new_suite());
+            const COUNT: usize = 1024 * 1024;
+            test_binary(
+                &hal,
+                |o, a, b| {
+                    hal.eltwise_add_elem(o, a, b);
+                },
+                |a, b| *a + *b,
+                COUNT,
+            );
+        }
+
+        #[test]
+        fn hash_rows() {
+            let hal = GoldilocksCpuHal::new(Sha256HashSuite::<Goldilocks>::new_suite());
+            let hashfn = hal.get_hash_suite().hashfn.clone();
+            let mut rng = thread_rng();
+            let (rows, cols) = (100, 20);
+
+            let matrix = hal.alloc_elem("matrix", rows * cols);
+            matrix.view_mut(|matrix| {
+                for elem in matrix.iter_mut() {
+                    *elem = GoldilocksElem::random(&mut rng);
+                }
+            });
+            let output = hal.alloc_digest("output", rows);
+            hal.hash_rows(&output, &matrix);
+            matrix.view(|matrix| {
+                output.view(|output| {
+                    for (idx, digest) in output.iter().enumerate() {
+                        let column: Vec<_> = (0..cols).map(|i| matrix[i * rows + idx]).collect();
+                        assert_eq!(*digest, *hashfn.hash_elem_slice(&column));
+                    }
+                });
+            });
+        }
+    }
 }
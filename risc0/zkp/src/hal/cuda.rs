@@ -431,7 +431,10 @@ impl<CH: CudaHash> CudaHal<CH> {
             .get_attribute(DeviceAttribute::MaxThreadsPerBlock)
             .unwrap();
         let _context = CONTEXT.clone();
-        let module = Module::from_fatbin(KERNELS_FATBIN, &[]).unwrap();
+        let module = Module::from_fatbin(KERNELS_FATBIN, &[]).expect(
+            "Failed to load the CUDA kernels, which may not be built for this GPU: \
+             rebuild with its compute capability in RISC0_CUDA_ARCHS",
+        );
         let mut hal = Self {
             max_threads: max_threads as u32,
             module,
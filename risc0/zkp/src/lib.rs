@@ -29,6 +29,7 @@ pub mod layout;
 mod merkle;
 #[cfg(feature = "prove")]
 pub mod prove;
+pub mod security;
 pub mod taps;
 #[cfg(not(target_os = "zkvm"))]
 pub mod verify;
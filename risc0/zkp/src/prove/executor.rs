@@ -166,20 +166,25 @@ where
     }
 
     fn compute_verify(&mut self) {
-        let mut rng = thread_rng();
+        // Make code be all zeros of zk cycles, and data be random. Columns are
+        // contiguous, so they are filled in parallel.
+        let (cycle, steps) = (self.cycle, self.steps);
+        self.code
+            .as_slice_mut()
+            .par_chunks_mut(steps)
+            .for_each(|column| column[cycle..].fill(F::Elem::ZERO));
+        self.data
+            .as_slice_mut()
+            .par_chunks_mut(steps)
+            .for_each_init(thread_rng, |rng, column| {
+                for elem in column[cycle..].iter_mut() {
+                    *elem = F::Elem::random(rng);
+                }
+            });
+
         let code_buf = self.code.as_slice_sync();
         let io_buf = self.io.as_slice_sync();
         let data_buf = self.data.as_slice_sync();
-
-        // Make code be all zeros of zk cycles, and data be random
-        for i in self.cycle..self.steps {
-            for j in 0..self.code_size {
-                code_buf.set(j * self.steps + i, F::Elem::ZERO);
-            }
-            for j in 0..self.data_size {
-                data_buf.set(j * self.steps + i, F::Elem::random(&mut rng));
-            }
-        }
         // Do the verify cycles
         let args: &[SyncSlice<F::Elem>] = &[code_buf, io_buf, data_buf];
 
@@ -225,3 +230,78 @@ where
             .for_each(|value| *value = value.valid_or_zero());
     }
 }
+
+/// An [Executor] whose buffers are held in vectors, so that it can be sent to
+/// another thread whenever its step handler can.
+///
+/// This allows generating the trace of a segment on one thread while another
+/// commits to the trace of the previous segment.
+pub struct DetachedExecutor<F, C, S>
+where
+    F: Field,
+    C: 'static + CircuitProveDef<F>,
+    S: CircuitStepHandler<F::Elem>,
+{
+    circuit: &'static C,
+    handler: S,
+    code: Vec<F::Elem>,
+    code_size: usize,
+    data: Vec<F::Elem>,
+    data_size: usize,
+    io: Vec<F::Elem>,
+    po2: usize,
+    steps: usize,
+    halted: bool,
+    max_po2: usize,
+    cycle: usize,
+}
+
+impl<F, C, S> Executor<F, C, S>
+where
+    F: Field,
+    C: 'static + CircuitProveDef<F>,
+    S: CircuitStepHandler<F::Elem>,
+{
+    /// Moves the buffers of the executor into vectors.
+    pub fn detach(self) -> DetachedExecutor<F, C, S> {
+        DetachedExecutor {
+            circuit: self.circuit,
+            handler: self.handler,
+            code: self.code.into_vec(),
+            code_size: self.code_size,
+            data: self.data.into_vec(),
+            data_size: self.data_size,
+            io: self.io.into_vec(),
+            po2: self.po2,
+            steps: self.steps,
+            halted: self.halted,
+            max_po2: self.max_po2,
+            cycle: self.cycle,
+        }
+    }
+}
+
+impl<F, C, S> DetachedExecutor<F, C, S>
+where
+    F: Field,
+    C: 'static + CircuitProveDef<F>,
+    S: CircuitStepHandler<F::Elem>,
+{
+    /// Moves the vectors back into the buffers of an [Executor].
+    pub fn attach(self) -> Executor<F, C, S> {
+        Executor {
+            circuit: self.circuit,
+            handler: self.handler,
+            code: CpuBuffer::from(self.code),
+            code_size: self.code_size,
+            data: CpuBuffer::from(self.data),
+            data_size: self.data_size,
+            io: CpuBuffer::from(self.io),
+            po2: self.po2,
+            steps: self.steps,
+            halted: self.halted,
+            max_po2: self.max_po2,
+            cycle: self.cycle,
+        }
+    }
+}
@@ -143,11 +143,12 @@ impl<H: Hal> MerkleTreeProver<H> {
 
 #[cfg(test)]
 mod tests {
+    use core::cell::RefCell;
+
     use rand::Rng;
-    use risc0_core::field::{
-        baby_bear::{BabyBear, BabyBearElem},
-        Elem,
-    };
+    #[cfg(feature = "goldilocks")]
+    use risc0_core::field::goldilocks::Goldilocks;
+    use risc0_core::field::{baby_bear::BabyBear, Elem, Field};
 
     use super::*;
     use crate::{
@@ -159,7 +160,7 @@ mod tests {
             log2_ceil,
         },
         hal::cpu::CpuHal,
-        verify::{MerkleTreeVerifier, ReadIOP, VerificationError},
+        verify::{MerkleBatch, MerkleTreeVerifier, ReadIOP, VerificationError},
     };
 
     fn init_prover<H: Hal>(
@@ -192,8 +193,8 @@ mod tests {
         bad_row_access(Poseidon2HashSuite::new_suite(), rows, cols, queries);
     }
 
-    fn possibly_bad_verify(
-        suite: HashSuite<BabyBear>,
+    fn possibly_bad_verify<F: Field>(
+        suite: HashSuite<F>,
         rows: usize,
         cols: usize,
         queries: usize,
@@ -213,7 +214,7 @@ mod tests {
             for c_idx in 0..cols {
                 assert_eq!(
                     col[c_idx],
-                    BabyBearElem::from_u64((u32::MAX / 2) as u64 - ((r_idx + c_idx * rows) as u64))
+                    F::Elem::from_u64((u32::MAX / 2) as u64 - ((r_idx + c_idx * rows) as u64))
                 );
             }
         }
@@ -233,7 +234,7 @@ mod tests {
                     assert!(false, "Cannot test for bad query if there is only one row");
                 }
                 let r_idx = (r_idx + 1) % rows;
-                let verification = verifier.verify(&mut r_iop, hashfn, r_idx);
+                let verification = verifier.verify(&mut r_iop, hashfn, r_idx, None);
                 match verification {
                     Ok(_) => assert!(
                         false,
@@ -248,11 +249,11 @@ mod tests {
                 err = true;
                 break;
             }
-            let col = verifier.verify(&mut r_iop, hashfn, r_idx).unwrap();
+            let col = verifier.verify(&mut r_iop, hashfn, r_idx, None).unwrap();
             for c_idx in 0..cols {
                 assert_eq!(
                     col[c_idx],
-                    BabyBearElem::from((u32::MAX / 2) - ((r_idx + c_idx * rows) as u32))
+                    F::Elem::from_u64((u32::MAX / 2) as u64 - ((r_idx + c_idx * rows) as u64))
                 );
             }
         }
@@ -261,6 +262,65 @@ mod tests {
         }
     }
 
+    fn batched_verify<F: Field>(
+        suite: HashSuite<F>,
+        rows: usize,
+        cols: usize,
+        queries: usize,
+        bad_query: usize,
+    ) {
+        let hal = CpuHal::new(suite);
+        let hashfn = hal.get_hash_suite().hashfn.as_ref();
+        let rng = hal.get_hash_suite().rng.as_ref();
+        let prover = init_prover(&hal, rows, cols, queries);
+
+        let mut iop = WriteIOP::new(rng);
+        prover.commit(&mut iop);
+        for _query in 0..queries {
+            let r_idx = iop.rng.random_bits(log2_ceil(rows)) as usize;
+            prover.prove(&hal, &mut iop, r_idx);
+        }
+        let mut r_iop = ReadIOP::new(&iop.proof, rng);
+        let verifier = MerkleTreeVerifier::new(&mut r_iop, hashfn, rows, cols, queries);
+        let batch = RefCell::new(MerkleBatch::default());
+        for query in 0..queries {
+            let r_idx = r_iop.random_bits(log2_ceil(rows)) as usize;
+            let r_idx = if query == bad_query {
+                (r_idx + 1) % rows
+            } else {
+                r_idx
+            };
+            verifier
+                .verify(&mut r_iop, hashfn, r_idx, Some(&batch))
+                .unwrap();
+        }
+        r_iop.verify_complete();
+        let verification = batch.into_inner().verify(hashfn);
+        if bad_query < queries {
+            assert_eq!(verification, Err(VerificationError::InvalidProof));
+        } else {
+            assert_eq!(verification, Ok(()));
+        }
+    }
+
+    fn batched_verify_all(rows: usize, cols: usize, queries: usize, bad_query: usize) {
+        for suite in [
+            Sha256HashSuite::new_suite(),
+            PoseidonHashSuite::new_suite(),
+            Poseidon2HashSuite::new_suite(),
+        ] {
+            batched_verify(suite, rows, cols, queries, bad_query);
+        }
+        #[cfg(feature = "goldilocks")]
+        batched_verify(
+            Sha256HashSuite::<Goldilocks>::new_suite(),
+            rows,
+            cols,
+            queries,
+            bad_query,
+        );
+    }
+
     fn possibly_bad_verify_all(
         rows: usize,
         cols: usize,
@@ -292,6 +352,15 @@ mod tests {
             bad_query,
             manipulate_proof,
         );
+        #[cfg(feature = "goldilocks")]
+        possibly_bad_verify(
+            Sha256HashSuite::<Goldilocks>::new_suite(),
+            rows,
+            cols,
+            queries,
+            bad_query,
+            manipulate_proof,
+        );
     }
 
     fn randomize_sizes() -> (usize, usize, usize) {
@@ -408,4 +477,22 @@ mod tests {
             possibly_bad_verify_all(rows, cols, queries, queries + 1, true);
         }
     }
+
+    #[test]
+    fn merkle_cpu_randomized_batched_verify() {
+        for _rep in 0..20 {
+            let (rows, cols, queries) = randomize_sizes();
+            batched_verify_all(rows, cols, queries, queries + 1);
+        }
+    }
+
+    #[test]
+    fn merkle_cpu_randomized_batched_bad_query() {
+        let mut rng = rand::thread_rng();
+        let (rows, cols, queries) = randomize_sizes();
+        // At least two rows are required to test querying an incorrect row
+        let rows = if rows == 1 { 2 } else { rows };
+        let bad_query = rng.gen::<usize>() % queries;
+        batched_verify_all(rows, cols, queries, bad_query);
+    }
 }
@@ -0,0 +1,234 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parameters of the FRI protocol and the security level they provide, and of
+//! the zero-knowledge blinding of the trace.
+
+use core::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{INV_RATE, QUERIES, ZK_CYCLES};
+
+/// Number of bits of the extension field from which the verifier draws its
+/// challenges, i.e. `floor(4 * log2(P))` for the Baby Bear prime `P`.
+const EXT_FIELD_BITS: u32 = 123;
+
+/// Parameters of the FRI protocol used to prove the low degree of the trace.
+///
+/// Increasing the blowup factor or the number of queries raises the security
+/// level at the cost of slower proving, and of larger proofs respectively.
+/// Grinding bits make the prover search for a proof-of-work before drawing the
+/// queries, adding security for a proving cost of about `2^pow_bits` hashes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FriParams {
+    /// Inverse of the rate of the Reed-Solomon code. Must be a power of two
+    /// larger than one.
+    pub inv_rate: usize,
+    /// Number of FRI queries.
+    pub queries: usize,
+    /// Number of proof-of-work grinding bits.
+    pub pow_bits: u32,
+}
+
+impl FriParams {
+    /// Construct [FriParams] from the given blowup factor, number of queries and
+    /// grinding bits.
+    pub fn new(inv_rate: usize, queries: usize, pow_bits: u32) -> Self {
+        Self {
+            inv_rate,
+            queries,
+            pow_bits,
+        }
+    }
+
+    /// Checks that the parameters describe a valid FRI configuration.
+    pub fn validate(&self) -> Result<(), FriParamsError> {
+        if !self.inv_rate.is_power_of_two() || self.inv_rate < 2 {
+            return Err(FriParamsError::InvalidRate(self.inv_rate));
+        }
+        if self.queries == 0 {
+            return Err(FriParamsError::NoQueries);
+        }
+        Ok(())
+    }
+
+    /// Returns the conjectured security level of proofs using these
+    /// parameters, in bits.
+    ///
+    /// Following the conjecture commonly used for FRI-based STARKs, each query
+    /// contributes `log2(inv_rate)` bits, and grinding adds `pow_bits`. The
+    /// result is capped by the size of the extension field. This does not
+    /// account for the security of the hash function.
+    pub fn conjectured_security_bits(&self) -> u32 {
+        let per_query = self.inv_rate.max(1).ilog2();
+        let bits = (self.queries as u64)
+            .saturating_mul(per_query as u64)
+            .saturating_add(self.pow_bits as u64);
+        bits.min(EXT_FIELD_BITS as u64) as u32
+    }
+}
+
+impl Default for FriParams {
+    /// Return the [FriParams] used by the zkVM circuits, which provide about
+    /// 100 bits of conjectured security.
+    fn default() -> Self {
+        Self::new(INV_RATE, QUERIES, 0)
+    }
+}
+
+/// Error returned by [FriParams::validate].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FriParamsError {
+    /// The inverse rate is not a power of two larger than one.
+    InvalidRate(usize),
+    /// The number of queries is zero.
+    NoQueries,
+}
+
+impl Display for FriParamsError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::InvalidRate(inv_rate) => write!(
+                f,
+                "invalid FRI inverse rate {inv_rate}: must be a power of two larger than one"
+            ),
+            Self::NoQueries => write!(f, "FRI requires at least one query"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FriParamsError {}
+
+/// Parameters of the zero-knowledge blinding of the trace.
+///
+/// The last `zk_cycles` rows of the code and data columns are filled with
+/// random values before the trace is committed, and the accumulator columns
+/// are mixed with the same kind of randomness. Each FRI query reveals the
+/// committed columns at one point of the low-degree extension, so as long as
+/// there are at least as many random rows as queries, the revealed values are
+/// uniformly distributed and independent of the execution trace.
+///
+/// This hides the contents of the trace, i.e. the private inputs and the
+/// memory of the guest. It does not hide the size of the trace: a segment
+/// receipt reveals its `po2`, and a composite receipt the number of its
+/// segments. Compressing the receipt with recursion hides both.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ZkParams {
+    /// Number of rows of random values at the end of the trace.
+    pub zk_cycles: usize,
+}
+
+impl ZkParams {
+    /// Construct [ZkParams] from the given number of random rows.
+    pub fn new(zk_cycles: usize) -> Self {
+        Self { zk_cycles }
+    }
+
+    /// Checks that the blinding hides the trace from the queries of the given
+    /// FRI configuration.
+    pub fn validate(&self, fri: &FriParams) -> Result<(), ZkParamsError> {
+        if self.zk_cycles < fri.queries {
+            return Err(ZkParamsError::TooFewCycles {
+                zk_cycles: self.zk_cycles,
+                queries: fri.queries,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for ZkParams {
+    /// Return the [ZkParams] used by the zkVM circuits, which blind as many
+    /// rows as there are queries in the default [FriParams].
+    fn default() -> Self {
+        Self::new(ZK_CYCLES)
+    }
+}
+
+/// Error returned by [ZkParams::validate].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZkParamsError {
+    /// There are fewer random rows than FRI queries.
+    TooFewCycles {
+        /// The number of random rows.
+        zk_cycles: usize,
+        /// The number of FRI queries.
+        queries: usize,
+    },
+}
+
+impl Display for ZkParamsError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::TooFewCycles { zk_cycles, queries } => write!(
+                f,
+                "{zk_cycles} zero-knowledge cycles do not hide the trace from {queries} FRI queries"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ZkParamsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{FriParams, FriParamsError, ZkParams, ZkParamsError};
+
+    #[test]
+    fn default_security() {
+        let params = FriParams::default();
+        assert_eq!(params.validate(), Ok(()));
+        assert_eq!(params.conjectured_security_bits(), 100);
+    }
+
+    #[test]
+    fn security_estimate() {
+        assert_eq!(FriParams::new(8, 40, 0).conjectured_security_bits(), 120);
+        assert_eq!(FriParams::new(4, 40, 16).conjectured_security_bits(), 96);
+        assert_eq!(FriParams::new(16, 1000, 0).conjectured_security_bits(), 123);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            FriParams::new(3, 50, 0).validate(),
+            Err(FriParamsError::InvalidRate(3))
+        );
+        assert_eq!(
+            FriParams::new(1, 50, 0).validate(),
+            Err(FriParamsError::InvalidRate(1))
+        );
+        assert_eq!(
+            FriParams::new(4, 0, 0).validate(),
+            Err(FriParamsError::NoQueries)
+        );
+    }
+
+    #[test]
+    fn zk_params() {
+        assert_eq!(ZkParams::default().validate(&FriParams::default()), Ok(()));
+        assert_eq!(
+            ZkParams::new(40).validate(&FriParams::new(4, 50, 0)),
+            Err(ZkParamsError::TooFewCycles {
+                zk_cycles: 40,
+                queries: 50
+            })
+        );
+    }
+}
@@ -69,9 +69,12 @@ where
         let quot = *pos / round.domain;
         let group = *pos % round.domain;
         // Get the column data
-        let data = round
-            .merkle
-            .verify(iop, self.suite.hashfn.as_ref(), group)?;
+        let data = round.merkle.verify(
+            iop,
+            self.suite.hashfn.as_ref(),
+            group,
+            self.deferred.as_ref(),
+        )?;
         let mut data_ext: Vec<F::ExtElem> = (0..FRI_FOLD)
             .map(|i| {
                 let mut inps = Vec::with_capacity(F::ExtElem::EXT_SIZE);
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use alloc::{boxed::Box, vec::Vec};
+use core::cell::RefCell;
 
 use risc0_core::field::Field;
 
@@ -136,11 +137,16 @@ impl<'a> MerkleTreeVerifier<'a> {
     }
 
     /// Verifies a branch provided by an IOP.
+    ///
+    /// When `deferred` is set, the hashes of the branch above its leaf are not
+    /// computed, and the branch is added to the batch instead, to be checked
+    /// with [MerkleBatch::verify].
     pub fn verify<F: Field>(
         &self,
         iop: &mut ReadIOP<'a, F>,
         hashfn: &dyn HashFn<F>,
         mut idx: usize,
+        deferred: Option<&RefCell<MerkleBatch<'a>>>,
     ) -> Result<&'a [F::Elem], VerificationError> {
         if idx >= self.params.row_size {
             return Err(VerificationError::MerkleQueryOutOfRange {
@@ -151,37 +157,121 @@ impl<'a> MerkleTreeVerifier<'a> {
         // Initialize a vector to hold field elements.
         let out: &[F::Elem] = iop.read_field_elem_slice(self.params.col_size);
         // Get the hash at the leaf of the tree by hashing these field elements.
-        let mut cur = hashfn.hash_elem_slice(out);
+        let mut cur = *hashfn.hash_elem_slice(out);
         // Shift idx to start of the row
         idx += self.params.row_size;
-        while idx >= 2 * self.params.top_size {
+        // Retrieve the siblings of the branch below the top row from the IOP.
+        let mut levels = 0;
+        while idx >> levels >= 2 * self.params.top_size {
+            levels += 1;
+        }
+        let siblings: &'a [Digest] = iop.read_pod_slice(levels);
+        // Once we reduce to an index for which we have the hash, check that it's
+        // correct.
+        let top_idx = idx >> levels;
+        let present_hash = if top_idx >= self.params.top_size {
+            self.top[self.params.idx_to_top(top_idx)]
+        } else {
+            *self.rest[self.params.idx_to_rest(top_idx)]
+        };
+        if let Some(batch) = deferred {
+            batch.borrow_mut().branches.push(Branch {
+                cur,
+                idx,
+                siblings,
+                expected: present_hash,
+            });
+            return Ok(out);
+        }
+        for other in siblings {
             // low_bit determines whether hash cur at idx is the left (0) or right (1)
             // child.
             let low_bit = idx % 2;
-            // Retrieve the other parent from the IOP.
-            let other: &Digest = match iop.read_pod_slice(1) {
-                [other] => other,
-                _ => unreachable!(),
-            };
             // Now ascend to the parent index, and compute the hash there.
             idx /= 2;
             if low_bit == 1 {
-                cur = hashfn.hash_pair(other, &cur);
+                cur = *hashfn.hash_pair(other, &cur);
             } else {
-                cur = hashfn.hash_pair(&cur, other);
+                cur = *hashfn.hash_pair(&cur, other);
             }
         }
-        // Once we reduce to an index for which we have the hash, check that it's
-        // correct.
-        let present_hash: &Digest = if idx >= self.params.top_size {
-            &self.top[self.params.idx_to_top(idx)]
-        } else {
-            &self.rest[self.params.idx_to_rest(idx)]
-        };
-        if *present_hash == *cur {
+        if present_hash == cur {
             Ok(out)
         } else {
             Err(VerificationError::InvalidProof)
         }
     }
 }
+
+// A Merkle branch whose hashes above the leaf remain to be computed.
+struct Branch<'a> {
+    // The hash at the current index of the branch.
+    cur: Digest,
+    // The virtual index of the leaf of the branch.
+    idx: usize,
+    // The siblings of the branch, from the leaf up.
+    siblings: &'a [Digest],
+    // The hash expected at the top of the branch.
+    expected: Digest,
+}
+
+/// Merkle branches collected while verifying one or more seals, to be checked
+/// together.
+///
+/// The branches are ascended one level at a time, hashing the nodes of the
+/// level of all the branches with a single call to [HashFn::hash_pairs], which
+/// hash functions with batched implementations compute several at a time.
+#[derive(Default)]
+pub(crate) struct MerkleBatch<'a> {
+    branches: Vec<Branch<'a>>,
+}
+
+impl<'a> MerkleBatch<'a> {
+    /// Moves the branches of another batch into this one.
+    pub fn append(&mut self, other: &mut Self) {
+        self.branches.append(&mut other.branches);
+    }
+
+    /// Checks that all the branches hash to their expected nodes.
+    pub fn verify<F: Field>(mut self, hashfn: &dyn HashFn<F>) -> Result<(), VerificationError> {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        let mut active = Vec::new();
+        let mut level = 0;
+        loop {
+            active.clear();
+            active.extend(
+                (0..self.branches.len()).filter(|&i| self.branches[i].siblings.len() > level),
+            );
+            if active.is_empty() {
+                break;
+            }
+            inputs.clear();
+            for &i in &active {
+                let branch = &self.branches[i];
+                let other = branch.siblings[level];
+                if (branch.idx >> level) % 2 == 1 {
+                    inputs.extend([other, branch.cur]);
+                } else {
+                    inputs.extend([branch.cur, other]);
+                }
+            }
+            outputs.clear();
+            outputs.resize(active.len(), Digest::ZERO);
+            hashfn.hash_pairs(&inputs, &mut outputs);
+            for (&i, output) in active.iter().zip(&outputs) {
+                self.branches[i].cur = *output;
+            }
+            level += 1;
+        }
+        if self
+            .branches
+            .iter()
+            .all(|branch| branch.cur == branch.expected)
+        {
+            Ok(())
+        } else {
+            Err(VerificationError::InvalidProof)
+        }
+    }
+}
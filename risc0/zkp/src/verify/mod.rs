@@ -21,7 +21,7 @@ mod read_iop;
 use alloc::{vec, vec::Vec};
 use core::{cell::RefCell, fmt, iter::zip};
 
-pub(crate) use merkle::MerkleTreeVerifier;
+pub(crate) use merkle::{MerkleBatch, MerkleTreeVerifier};
 pub use read_iop::ReadIOP;
 use risc0_core::field::{Elem, ExtElem, Field, RootsOfUnity};
 
@@ -45,6 +45,7 @@ pub enum VerificationError {
     InvalidHashSuite,
     FaultStateMismatch,
     ValidFaultReceipt,
+    FakeReceipt,
 }
 
 impl fmt::Debug for VerificationError {
@@ -77,6 +78,9 @@ impl fmt::Display for VerificationError {
             VerificationError::ValidFaultReceipt => {
                 write!(f, "Receipt is a valid fault proof")
             }
+            VerificationError::FakeReceipt => {
+                write!(f, "Fake receipt rejected by strict verification")
+            }
         }
     }
 }
@@ -106,6 +110,7 @@ where
     out: Option<&'a [F::Elem]>,
     mix: Vec<F::Elem>,
     tap_cache: RefCell<Option<TapCache<F>>>,
+    deferred: Option<RefCell<MerkleBatch<'a>>>,
 }
 
 impl<'a, F: Field, C> VerifyParams<F> for Verifier<'a, F, C> {}
@@ -124,6 +129,7 @@ where
             out: None,
             mix: Vec::new(),
             tap_cache: RefCell::new(None),
+            deferred: None,
         }
     }
 
@@ -401,11 +407,11 @@ where
             // tracing::debug!("fri_verify");
             let x = gen.pow(idx);
             let rows = [
-                accum_merkle.verify(iop, hashfn, idx)?,
-                code_merkle.verify(iop, hashfn, idx)?,
-                data_merkle.verify(iop, hashfn, idx)?,
+                accum_merkle.verify(iop, hashfn, idx, self.deferred.as_ref())?,
+                code_merkle.verify(iop, hashfn, idx, self.deferred.as_ref())?,
+                data_merkle.verify(iop, hashfn, idx, self.deferred.as_ref())?,
             ];
-            let check_row = check_merkle.verify(iop, hashfn, idx)?;
+            let check_row = check_merkle.verify(iop, hashfn, idx, self.deferred.as_ref())?;
             let ret = self.fri_eval_taps(taps, mix, &combo_u, check_row, back_one, x, z, rows);
             Ok(ret)
         })?;
@@ -449,3 +455,32 @@ where
 {
     Verifier::<F, C>::new(circuit, suite).verify(seal, check_code)
 }
+
+/// Verify several seals for the same circuit and hash suite.
+///
+/// This is equivalent to calling [verify] on each seal, but the Merkle
+/// branches opened by the FRI queries of all the seals are checked together,
+/// which amortizes their hashing when the hash function computes several
+/// hashes at a time. If verification fails, the failing seal is not
+/// identified.
+#[tracing::instrument(skip_all)]
+pub fn verify_batch<'a, F, C, CheckCode>(
+    circuit: &'a C,
+    suite: &'a HashSuite<F>,
+    seals: &[&'a [u32]],
+    check_code: CheckCode,
+) -> Result<(), VerificationError>
+where
+    F: Field,
+    C: CircuitCoreDef<F>,
+    CheckCode: Fn(u32, &Digest) -> Result<(), VerificationError>,
+{
+    let mut batch = MerkleBatch::default();
+    for seal in seals {
+        let mut verifier = Verifier::<F, C>::new(circuit, suite);
+        verifier.deferred = Some(RefCell::default());
+        verifier.verify(seal, &check_code)?;
+        batch.append(&mut verifier.deferred.take().unwrap().into_inner());
+    }
+    batch.verify(suite.hashfn.as_ref())
+}
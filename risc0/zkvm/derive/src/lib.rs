@@ -0,0 +1,337 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros for the RISC Zero zkVM.
+//!
+//! These are re-exported by `risc0-zkvm` with the `derive` feature, and should
+//! be used through it.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Expr, Field,
+    Fields, GenericArgument, GenericParam, Generics, Ident, Lit, PathArguments, Type,
+};
+
+/// Derives `risc0_zkvm::serde::JournalSchema`.
+///
+/// The schema digest covers a normalized description of the type, made of its
+/// name, generic parameters, `#[serde]` attributes, and the names and types of
+/// its fields and variants, along with the schema digests of the types of its
+/// fields. Renaming, reordering, adding or removing any of them, changing the
+/// serde representation, or changing the schema of a field's type, changes the
+/// digest. Types are described by the last segment of their path, so that
+/// `Vec<u8>` and `alloc::vec::Vec<u8>` have the same schema.
+///
+/// The description is built from the tokens of the type, independently of how
+/// the compiler would print them: identifiers are unescaped, string literals
+/// are described by their value and integer literals, including array lengths,
+/// by their decimal value.
+///
+/// The types of the fields must implement `JournalSchema` too, unless they are
+/// skipped by serde. Fields referring to the type itself, e.g. `Box<Self>` in a
+/// recursive type, are only described by name. Types referring to each other
+/// are not supported, and overflow the stack when computing their digest.
+#[proc_macro_derive(JournalSchema)]
+pub fn derive_journal_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let (schema, nested) = match describe(&input) {
+        Ok(schema) => schema,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut generics = input.generics.clone();
+    let type_params: Vec<_> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::risc0_zkvm::serde::JournalSchema));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::risc0_zkvm::serde::JournalSchema for #ident #ty_generics #where_clause {
+            fn schema_digest() -> ::risc0_zkvm::sha::Digest {
+                ::risc0_zkvm::serde::schema_digest(
+                    #schema,
+                    &[#(<#nested as ::risc0_zkvm::serde::JournalSchema>::schema_digest()),*],
+                )
+            }
+        }
+    }
+    .into()
+}
+
+// Canonical description of the shape of a type, hashed into its schema digest,
+// and the types of the fields whose schema is nested in it.
+fn describe(input: &DeriveInput) -> syn::Result<(String, Vec<&Type>)> {
+    let ident = &input.ident;
+    let name = format!(
+        "{}{}{}",
+        describe_attrs(&input.attrs),
+        ident.unraw(),
+        describe_generics(&input.generics)?
+    );
+    let mut nested = Vec::new();
+    let schema = match &input.data {
+        Data::Struct(data) => format!(
+            "struct {name}{}",
+            describe_fields(&data.fields, ident, &mut nested)?
+        ),
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    Ok(format!(
+                        "{}{}{}",
+                        describe_attrs(&variant.attrs),
+                        variant.ident.unraw(),
+                        describe_fields(&variant.fields, ident, &mut nested)?
+                    ))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            format!("enum {name} {{ {} }}", variants.join(", "))
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "JournalSchema cannot be derived for unions",
+            ))
+        }
+    };
+    Ok((schema, nested))
+}
+
+// The names of the generic parameters, and the types of the const parameters.
+// Bounds do not change the encoding.
+fn describe_generics(generics: &Generics) -> syn::Result<String> {
+    if generics.params.is_empty() {
+        return Ok(String::new());
+    }
+    let params = generics
+        .params
+        .iter()
+        .map(|param| {
+            Ok(match param {
+                GenericParam::Type(param) => param.ident.unraw().to_string(),
+                GenericParam::Lifetime(param) => format!("'{}", param.lifetime.ident.unraw()),
+                GenericParam::Const(param) => {
+                    format!(
+                        "const {}: {}",
+                        param.ident.unraw(),
+                        describe_type(&param.ty)?
+                    )
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(format!("<{}>", params.join(", ")))
+}
+
+fn describe_fields<'a>(
+    fields: &'a Fields,
+    self_ident: &Ident,
+    nested: &mut Vec<&'a Type>,
+) -> syn::Result<String> {
+    let described = fields
+        .iter()
+        .map(|field| {
+            if !is_skipped(field) {
+                collect_nested(&field.ty, self_ident, nested);
+            }
+            let ident = field
+                .ident
+                .as_ref()
+                .map(|ident| format!("{}: ", ident.unraw()))
+                .unwrap_or_default();
+            Ok(format!(
+                "{}{ident}{}",
+                describe_attrs(&field.attrs),
+                describe_type(&field.ty)?
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(match fields {
+        Fields::Named(_) => format!(" {{ {} }}", described.join(", ")),
+        Fields::Unnamed(_) => format!("({})", described.join(", ")),
+        Fields::Unit => String::new(),
+    })
+}
+
+// Adds the schema of a field's type to the nested schemas. The parts of the
+// type referring to the type being derived are left out, since computing their
+// schema would recurse forever; they are still named in the description.
+fn collect_nested<'a>(ty: &'a Type, self_ident: &Ident, nested: &mut Vec<&'a Type>) {
+    if !refers_to(ty.to_token_stream(), self_ident) {
+        nested.push(ty);
+        return;
+    }
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last().unwrap();
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                for arg in args.args.iter() {
+                    if let GenericArgument::Type(ty) = arg {
+                        collect_nested(ty, self_ident, nested);
+                    }
+                }
+            }
+        }
+        Type::Array(array) => collect_nested(&array.elem, self_ident, nested),
+        Type::Tuple(tuple) => {
+            for elem in tuple.elems.iter() {
+                collect_nested(elem, self_ident, nested);
+            }
+        }
+        Type::Paren(paren) => collect_nested(&paren.elem, self_ident, nested),
+        Type::Group(group) => collect_nested(&group.elem, self_ident, nested),
+        _ => {}
+    }
+}
+
+// Whether the tokens name the type being derived, by its name or as `Self`.
+fn refers_to(tokens: TokenStream2, self_ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "Self" || ident.unraw() == self_ident.unraw(),
+        TokenTree::Group(group) => refers_to(group.stream(), self_ident),
+        _ => false,
+    })
+}
+
+// The `#[serde]` attributes, which change the encoding of the type.
+fn describe_attrs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .map(|attr| match attr.meta.require_list() {
+            Ok(list) => format!("#[serde({})] ", describe_tokens(list.tokens.clone())),
+            Err(_) => String::from("#[serde] "),
+        })
+        .collect()
+}
+
+// Canonical form of a token stream: tokens separated by single spaces, with
+// unescaped identifiers and normalized literals.
+fn describe_tokens(tokens: TokenStream2) -> String {
+    let described: Vec<_> = tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) => ident.unraw().to_string(),
+            TokenTree::Punct(punct) => punct.as_char().to_string(),
+            TokenTree::Literal(literal) => match Lit::new(literal) {
+                Lit::Str(lit) => format!("{:?}", lit.value()),
+                Lit::Int(lit) => match lit.base10_parse::<u128>() {
+                    Ok(value) => value.to_string(),
+                    Err(_) => lit.base10_digits().to_string(),
+                },
+                Lit::Bool(lit) => lit.value.to_string(),
+                lit => lit.to_token_stream().to_string(),
+            },
+            TokenTree::Group(group) => {
+                let inner = describe_tokens(group.stream());
+                match group.delimiter() {
+                    Delimiter::Parenthesis => format!("({inner})"),
+                    Delimiter::Brace => format!("{{{inner}}}"),
+                    Delimiter::Bracket => format!("[{inner}]"),
+                    Delimiter::None => inner,
+                }
+            }
+        })
+        .collect();
+    described.join(" ")
+}
+
+fn is_skipped(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .flat_map(|list| list.tokens.clone())
+        .any(|token| {
+            matches!(token, TokenTree::Ident(ident)
+                if ident == "skip" || ident == "skip_serializing" || ident == "skip_deserializing")
+        })
+}
+
+// Describes a type by the last segment of its path, recursively.
+fn describe_type(ty: &Type) -> syn::Result<String> {
+    Ok(match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last().unwrap();
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) => {
+                    let args = args
+                        .args
+                        .iter()
+                        .map(|arg| match arg {
+                            GenericArgument::Type(ty) => describe_type(ty),
+                            GenericArgument::Const(expr) => describe_len(expr),
+                            arg => Ok(describe_tokens(arg.to_token_stream())),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    format!("{}<{}>", segment.ident.unraw(), args.join(", "))
+                }
+                _ => segment.ident.unraw().to_string(),
+            }
+        }
+        Type::Array(array) => format!(
+            "[{}; {}]",
+            describe_type(&array.elem)?,
+            describe_len(&array.len)?
+        ),
+        Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(describe_type)
+                .collect::<syn::Result<Vec<_>>>()?;
+            format!("({})", elems.join(", "))
+        }
+        Type::Paren(paren) => describe_type(&paren.elem)?,
+        Type::Group(group) => describe_type(&group.elem)?,
+        ty => describe_tokens(ty.to_token_stream()),
+    })
+}
+
+// Describes an array length or a const generic argument, which must be an
+// integer literal, described by its value, or a const parameter, described by
+// its name.
+fn describe_len(expr: &Expr) -> syn::Result<String> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => Ok(int.base10_parse::<u128>()?.to_string()),
+            lit => Err(syn::Error::new_spanned(
+                lit,
+                "JournalSchema requires array lengths to be integer literals or const parameters",
+            )),
+        },
+        Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+            Ok(path.path.get_ident().unwrap().unraw().to_string())
+        }
+        Expr::Group(group) => describe_len(&group.expr),
+        Expr::Paren(paren) => describe_len(&paren.expr),
+        expr => Err(syn::Error::new_spanned(
+            expr,
+            "JournalSchema requires array lengths to be integer literals or const parameters",
+        )),
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Aggregation guest of the `RiscZeroAggregateVerifier` contract: verifies a
+//! list of claims and commits the root of the Merkle tree over their digests.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use risc0_zkvm::{
+    guest::env,
+    sha::{Digest, Digestible, Impl, Sha256},
+    ReceiptClaim,
+};
+
+risc0_zkvm::entry!(main);
+
+// Inner nodes are `sha256(min(a, b) || max(a, b))`, comparing the digests as
+// big-endian integers like Solidity does for `bytes32`.
+fn hash_pair(a: &Digest, b: &Digest) -> Digest {
+    let (left, right) = if a.as_bytes() < b.as_bytes() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    *Impl::hash_bytes(&[left.as_bytes(), right.as_bytes()].concat())
+}
+
+fn main() {
+    let claims: Vec<ReceiptClaim> = env::read();
+    assert!(!claims.is_empty(), "no claims to aggregate");
+
+    let mut level: Vec<Digest> = claims
+        .iter()
+        .map(|claim| {
+            env::verify_integrity(claim).unwrap();
+            claim.digest()
+        })
+        .collect();
+    // A node without a sibling is promoted to the next level unchanged.
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    env::commit_slice(level[0].as_bytes());
+}
@@ -19,19 +19,19 @@
 
 extern crate alloc;
 
-use alloc::{format, vec};
+use alloc::{format, vec, vec::Vec};
 use core::arch::asm;
 
 use getrandom::getrandom;
 use risc0_zkp::core::hash::sha::testutil::test_sha_impl;
 use risc0_zkvm::{
-    guest::{env, memory_barrier, sha},
+    guest::{bigint as bigint_wide, env, memory_barrier, sha},
     sha::{Digest, Sha256},
     ReceiptClaim,
 };
 use risc0_zkvm_methods::multi_test::{MultiTestSpec, SYS_MULTI_TEST};
 use risc0_zkvm_platform::{
-    fileno,
+    fileno, heap,
     memory::{self, SYSTEM},
     syscall::{bigint, sys_bigint, sys_log, sys_read, sys_read_words, sys_write},
     PAGE_SIZE,
@@ -109,6 +109,20 @@ fn main() {
             env::pause(exit_code);
             env::log("after");
         }
+        MultiTestSpec::PauseResume => loop {
+            let value: u32 = env::read();
+            env::commit(&value);
+            if value == 0 {
+                break;
+            }
+            env::pause(0);
+        },
+        MultiTestSpec::PauseSysVerify(pairs) => {
+            env::pause(0);
+            for (image_id, journal) in pairs.into_iter() {
+                env::verify(image_id, &journal).unwrap();
+            }
+        }
         MultiTestSpec::ReadWriteMem { values } => {
             for (addr, value) in values.into_iter() {
                 if value != 0 {
@@ -223,6 +237,25 @@ fn main() {
             }
             env::log("Busy loop complete");
         }
+        MultiTestSpec::CycleCount { cycles } => {
+            let start = env::cycle_count_u64();
+            let mut end = start;
+            while end < start + cycles {
+                end = env::cycle_count_u64();
+            }
+            env::commit(&(start, end));
+        }
+        MultiTestSpec::CycleBudget { margin } => {
+            let first = env::cycle_budget();
+            let mut rounds = 0u32;
+            while env::cycle_budget()
+                .session
+                .map_or(false, |session| session > margin)
+            {
+                rounds += 1;
+            }
+            env::commit(&(first.segment, first.session, rounds));
+        }
         MultiTestSpec::BigInt { x, y, modulus } => {
             let mut result = [0u32; bigint::WIDTH_WORDS];
             unsafe {
@@ -230,11 +263,49 @@ fn main() {
             }
             env::commit_slice(&result);
         }
+        MultiTestSpec::BigIntWide { x, y, modulus } => {
+            let mut result = vec![0u32; modulus.len()];
+            bigint_wide::modmul(&mut result, &x, &y, &modulus);
+            env::commit_slice(&result);
+        }
+        MultiTestSpec::BigIntModPow {
+            base,
+            exponent,
+            modulus,
+        } => {
+            let mut result = vec![0u32; modulus.len()];
+            bigint_wide::modpow(&mut result, &base, &exponent, &modulus);
+            env::commit_slice(&result);
+        }
         MultiTestSpec::LibM => {
             use core::hint::black_box;
             let f = black_box(1.0_f32);
             black_box(f.min(1.0));
         }
+        MultiTestSpec::LogRecord => {
+            use risc0_zkvm::guest::log::{info, warn};
+            info!("hello from the guest: {}", 42);
+            warn!(target: "custom", "careful");
+        }
+        MultiTestSpec::ReadFrame => {
+            use risc0_zkvm::serde::FrameError;
+            // The first frame has an outdated version, and is skipped.
+            assert_eq!(
+                env::read_frame::<u32>(1, 2),
+                Err(FrameError::VersionMismatch {
+                    expected: 2,
+                    found: 1
+                })
+            );
+            let value: u32 = env::read_frame(1, 2).unwrap();
+            env::commit(&value);
+        }
+        MultiTestSpec::NamedOutputs => {
+            env::output("alpha").write_slice(b"hello");
+            env::output("beta").write(&42u32);
+            env::output("alpha").write_slice(b" world");
+            env::commit(&1u32);
+        }
         MultiTestSpec::Oom => {
             use core::hint::black_box;
             // SYSTEM memory starts above the guest memory so this is guaranteed
@@ -242,6 +313,48 @@ fn main() {
             let len = memory::SYSTEM.start() as usize;
             let _data = black_box(vec![0_u8; len]);
         }
+        MultiTestSpec::Threads => {
+            use risc0_zkvm::guest::{sync::Mutex, thread};
+
+            let handles: Vec<_> = (1..=4u32).map(|i| thread::spawn(move || i * i)).collect();
+            let squares: u32 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+            let order = Mutex::new(Vec::new());
+            thread::scope(|s| {
+                for i in 0..4u32 {
+                    let order = &order;
+                    s.spawn(move || order.lock().unwrap().push(i));
+                }
+            });
+            let order = order.into_inner().unwrap();
+            env::commit(&(squares, order));
+        }
+        MultiTestSpec::HeapStats => {
+            use core::hint::black_box;
+            let before = heap::stats();
+            let data = black_box(vec![0_u8; PAGE_SIZE]);
+            let during = heap::stats();
+            drop(data);
+            let after = heap::stats();
+            env::report_heap_stats();
+            assert_eq!((during.used - before.used) as usize, PAGE_SIZE);
+            assert_eq!(after.used, before.used);
+            assert!(during.peak >= during.used && after.peak == during.peak);
+            assert!(during.free < before.free);
+            env::commit(&(during.used, during.free));
+        }
+        MultiTestSpec::TryAlloc => {
+            use core::alloc::Layout;
+            // As above, this is larger than the available heap.
+            let layout = Layout::from_size_align(memory::SYSTEM.start() as usize, 4).unwrap();
+            assert!(heap::try_alloc(layout).is_none());
+
+            // The heap remains usable after a failed allocation.
+            let layout = Layout::from_size_align(PAGE_SIZE, 4).unwrap();
+            let ptr = heap::try_alloc(layout).unwrap();
+            unsafe { heap::dealloc(ptr, layout) };
+            let _data = vec![0_u8; PAGE_SIZE];
+        }
         MultiTestSpec::RsaCompat => {
             // This test comes from: https://github.com/RustCrypto/RSA/blob/master/tests/pkcs1v15.rs
             use risc0_zkvm::sha::rust_crypto::Sha256;
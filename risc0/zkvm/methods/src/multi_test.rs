@@ -39,6 +39,11 @@ pub enum MultiTestSpec {
     Fault,
     Halt(u8),
     PauseContinue(u8),
+    /// Read a value from the host and commit it in each round, pausing after the rounds with a
+    /// non-zero value and halting after a zero
+    PauseResume,
+    /// Pause, then verify the given receipts once resumed
+    PauseSysVerify(Vec<(Digest, Vec<u8>)>),
     ReadWriteMem {
         /// Tuples of (address, value). Zero means read the value and
         /// output it; nonzero means write that value.
@@ -76,12 +81,38 @@ pub enum MultiTestSpec {
         y: [u32; bigint::WIDTH_WORDS],
         modulus: [u32; bigint::WIDTH_WORDS],
     },
+    BigIntWide {
+        x: Vec<u32>,
+        y: Vec<u32>,
+        modulus: Vec<u32>,
+    },
+    BigIntModPow {
+        base: Vec<u32>,
+        exponent: Vec<u32>,
+        modulus: Vec<u32>,
+    },
     BusyLoop {
         /// Busy loop until the guest has run for at least this number of cycles
         cycles: u32,
     },
+    CycleCount {
+        /// Busy loop for at least this number of cycles, as measured by `env::cycle_count_u64`, and
+        /// commit the counts before and after the loop
+        cycles: u64,
+    },
+    /// Loop while the remaining session budget is larger than the margin, and commit the first
+    /// budget and the number of rounds
+    CycleBudget {
+        margin: u64,
+    },
+    HeapStats,
     LibM,
+    LogRecord,
+    NamedOutputs,
     Oom,
+    ReadFrame,
+    Threads,
+    TryAlloc,
     OutOfBounds,
     OutOfBoundsEcall,
     RsaCompat,
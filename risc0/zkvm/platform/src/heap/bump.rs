@@ -0,0 +1,77 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bump pointer allocator, which never reuses memory.
+
+use core::ptr::null_mut;
+
+use crate::WORD_SIZE;
+
+// Pointer to next heap address to use, or 0 if the heap has not yet been
+// initialized.
+static mut HEAP_POS: usize = 0;
+
+/// Allocates `bytes` bytes aligned to `align`, returning null if the heap is
+/// exhausted.
+///
+/// # Safety
+///
+/// `align` must be a power of two.
+pub unsafe fn alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
+    let mut heap_pos = heap_pos();
+
+    // Honor requested alignment if larger than word size.
+    let align = usize::max(align, WORD_SIZE);
+
+    let offset = heap_pos & (align - 1);
+    if offset != 0 {
+        heap_pos += align - offset;
+    }
+
+    let ptr = heap_pos as *mut u8;
+
+    // Check to make sure heap doesn't collide with SYSTEM memory.
+    match heap_pos.checked_add(bytes) {
+        Some(end) if end <= crate::memory::SYSTEM.start() => {
+            unsafe { HEAP_POS = end };
+            ptr
+        }
+        _ => null_mut(),
+    }
+}
+
+/// Returns the number of bytes between the next heap address and the end of
+/// the heap.
+pub fn free() -> usize {
+    crate::memory::SYSTEM.start().saturating_sub(heap_pos())
+}
+
+// Next heap address to use.
+fn heap_pos() -> usize {
+    extern "C" {
+        // This symbol is defined by the loader and marks the end
+        // of all elf sections, so this is where we start our
+        // heap.
+        //
+        // This is generated automatically by the linker; see
+        // https://lld.llvm.org/ELF/linker_script.html#sections-command
+        static _end: u8;
+    }
+
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    match unsafe { HEAP_POS } {
+        0 => unsafe { (&_end) as *const u8 as usize },
+        heap_pos => heap_pos,
+    }
+}
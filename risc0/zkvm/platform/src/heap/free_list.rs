@@ -0,0 +1,129 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! First-fit free list allocator, which reuses the memory released by
+//! deallocations.
+//!
+//! Free blocks are kept in a list sorted by address, and merged with their
+//! neighbours when released. Allocations take the first free block that fits,
+//! and fall back to the [bump](super::bump) allocator otherwise.
+
+use core::{
+    alloc::Layout,
+    mem::size_of,
+    ptr::{addr_of_mut, null_mut},
+};
+
+use super::bump;
+
+struct Node {
+    next: *mut Node,
+    size: usize,
+}
+
+// Blocks are rounded up to this size and alignment, so that any free block can
+// hold a `Node`.
+const BLOCK_SIZE: usize = size_of::<Node>();
+
+static mut HEAD: *mut Node = null_mut();
+
+// Size and alignment of the block holding an allocation of the given layout.
+fn block_layout(layout: Layout) -> (usize, usize) {
+    let size = (layout.size().max(1) + BLOCK_SIZE - 1) & !(BLOCK_SIZE - 1);
+    (size, layout.align().max(BLOCK_SIZE))
+}
+
+/// Allocates memory for the given layout, returning null if the heap is
+/// exhausted.
+///
+/// # Safety
+///
+/// Must not be called concurrently with [dealloc].
+pub unsafe fn alloc(layout: Layout) -> *mut u8 {
+    let (size, align) = block_layout(layout);
+
+    let mut prev = addr_of_mut!(HEAD);
+    let mut node = *prev;
+    while !node.is_null() {
+        let block_size = (*node).size;
+        if (node as usize & (align - 1)) == 0 && block_size >= size {
+            if block_size == size {
+                *prev = (*node).next;
+            } else {
+                // Return the end of the block to the list.
+                let rest = (node as usize + size) as *mut Node;
+                rest.write(Node {
+                    next: (*node).next,
+                    size: block_size - size,
+                });
+                *prev = rest;
+            }
+            return node as *mut u8;
+        }
+        prev = addr_of_mut!((*node).next);
+        node = *prev;
+    }
+
+    bump::alloc_aligned(size, align)
+}
+
+/// Releases memory allocated with [alloc].
+///
+/// # Safety
+///
+/// `ptr` must have been allocated by [alloc] with the same `layout`, and not
+/// released yet.
+pub unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+    let (size, _) = block_layout(layout);
+    let start = ptr as usize;
+
+    // Find the neighbours of the block, keeping the list sorted by address.
+    let mut prev: *mut Node = null_mut();
+    let mut next = HEAD;
+    while !next.is_null() && (next as usize) < start {
+        prev = next;
+        next = (*next).next;
+    }
+
+    let node = ptr as *mut Node;
+    node.write(Node { next, size });
+    if !next.is_null() && start + size == next as usize {
+        (*node).size += (*next).size;
+        (*node).next = (*next).next;
+    }
+
+    if prev.is_null() {
+        HEAD = node;
+    } else if prev as usize + (*prev).size == start {
+        (*prev).size += (*node).size;
+        (*prev).next = (*node).next;
+    } else {
+        (*prev).next = node;
+    }
+}
+
+/// Returns the total size of the free blocks.
+///
+/// # Safety
+///
+/// Must not be called concurrently with [alloc] or [dealloc].
+pub unsafe fn free() -> usize {
+    let mut total = 0;
+    let mut node = HEAD;
+    while !node.is_null() {
+        total += (*node).size;
+        node = (*node).next;
+    }
+    total
+}
@@ -0,0 +1,187 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Guest heap allocators.
+//!
+//! The heap grows from the end of the program up to the start of
+//! [crate::memory::SYSTEM]. By default, memory is handed out by a bump
+//! allocator, which is the cheapest in cycles but never reuses memory. Guests
+//! that repeatedly free and allocate large buffers can enable the
+//! `heap-free-list` feature, which reuses the memory released by
+//! deallocations.
+//!
+//! Allocations through the global allocator abort the guest when the heap is
+//! exhausted. [try_alloc] returns `None` instead, and [set_oom_hook] registers
+//! a function called before the guest aborts, e.g. to log the state of the
+//! program. [stats] reports how much of the heap is in use, which helps
+//! diagnosing such failures.
+
+pub mod bump;
+#[cfg(feature = "heap-free-list")]
+pub mod free_list;
+
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::syscall::sys_panic;
+
+static mut OOM_HOOK: Option<fn(usize)> = None;
+
+// Bytes currently allocated, and the highest value it reached.
+static mut USED: usize = 0;
+static mut PEAK: usize = 0;
+
+/// Usage of the guest heap, in bytes.
+///
+/// The same type describes the usage reported to the host, in
+/// `Session::heap_stats`, so the counts are 32-bit on both sides.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Bytes currently allocated.
+    pub used: u32,
+    /// Highest number of bytes allocated at once.
+    pub peak: u32,
+    /// Bytes that can still be allocated, at most, as alignment and
+    /// fragmentation may make some of them unusable.
+    pub free: u32,
+}
+
+/// Returns the current usage of the heap.
+///
+/// The used and peak counts are the sizes requested by the allocations, and
+/// include the buffers allocated for inputs by `sys_alloc_words`.
+pub fn stats() -> HeapStats {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    unsafe {
+        // The guest address space is 32-bit, so the counts fit in a u32.
+        HeapStats {
+            used: USED as u32,
+            peak: PEAK as u32,
+            free: free() as u32,
+        }
+    }
+}
+
+/// Records an allocation of the given size in the heap usage.
+pub(crate) fn record_alloc(bytes: usize) {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    unsafe {
+        USED += bytes;
+        PEAK = PEAK.max(USED);
+    }
+}
+
+/// Registers a function called with the size of the failed allocation when
+/// the heap is exhausted, before the guest aborts.
+///
+/// The hook runs while no memory is available, so it must not allocate.
+pub fn set_oom_hook(hook: fn(usize)) {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    unsafe { OOM_HOOK = Some(hook) };
+}
+
+/// Allocates memory for the given layout, returning `None` if the heap is
+/// exhausted.
+///
+/// The memory can be released with [dealloc], or with `alloc::alloc::dealloc`
+/// as it comes from the same allocator.
+pub fn try_alloc(layout: Layout) -> Option<NonNull<u8>> {
+    // SAFETY: The allocator only touches memory past the end of the program.
+    NonNull::new(unsafe { alloc(layout) })
+}
+
+/// Releases memory allocated with [try_alloc].
+///
+/// # Safety
+///
+/// `ptr` must have been allocated with the same `layout`, and not released yet.
+pub unsafe fn dealloc(ptr: NonNull<u8>, layout: Layout) {
+    release(ptr.as_ptr(), layout)
+}
+
+/// Allocates memory for the given layout, returning null if the heap is
+/// exhausted.
+pub(crate) unsafe fn alloc(layout: Layout) -> *mut u8 {
+    let ptr = alloc_block(layout);
+    if !ptr.is_null() {
+        record_alloc(layout.size());
+    }
+    ptr
+}
+
+/// Releases memory allocated with [alloc].
+pub(crate) unsafe fn release(ptr: *mut u8, layout: Layout) {
+    USED -= layout.size();
+    release_block(ptr, layout)
+}
+
+#[cfg(feature = "heap-free-list")]
+use free_list::{alloc as alloc_block, dealloc as release_block};
+
+#[cfg(feature = "heap-free-list")]
+unsafe fn free() -> usize {
+    bump::free() + free_list::free()
+}
+
+#[cfg(not(feature = "heap-free-list"))]
+unsafe fn alloc_block(layout: Layout) -> *mut u8 {
+    bump::alloc_aligned(layout.size(), layout.align())
+}
+
+#[cfg(not(feature = "heap-free-list"))]
+unsafe fn release_block(_ptr: *mut u8, _layout: Layout) {
+    // The bump allocator never deallocates memory.
+}
+
+#[cfg(not(feature = "heap-free-list"))]
+unsafe fn free() -> usize {
+    bump::free()
+}
+
+/// Aborts the guest after a failed allocation of the given size.
+pub(crate) fn alloc_failed(bytes: usize) -> ! {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    if let Some(hook) = unsafe { OOM_HOOK } {
+        hook(bytes);
+    }
+
+    // The heap is exhausted, so the message is formatted on the stack.
+    let mut msg = [0u8; 128];
+    let mut len = 0;
+    let mut push = |part: &[u8]| {
+        msg[len..len + part.len()].copy_from_slice(part);
+        len += part.len();
+    };
+    let mut digits = [0u8; 20];
+    push(b"Out of memory! Failed to allocate ");
+    push(format_usize(&mut digits, bytes));
+    push(b" bytes, with ");
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    push(format_usize(&mut digits, unsafe { USED }));
+    push(b" bytes in use");
+    unsafe { sys_panic(msg.as_ptr(), len) }
+}
+
+// Formats a number in decimal into the given buffer.
+fn format_usize(digits: &mut [u8; 20], mut value: usize) -> &[u8] {
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    &digits[start..]
+}
@@ -16,6 +16,8 @@
 #![no_std]
 #![allow(unused_variables)]
 
+#[cfg(feature = "export-syscalls")]
+pub mod heap;
 pub mod memory;
 #[macro_use]
 pub mod syscall;
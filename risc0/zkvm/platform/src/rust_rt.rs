@@ -18,14 +18,14 @@
 //! * It defines an entrypoint ensuring initialization and finalization are done
 //!   properly.
 //! * It includes a panic handler.
-//! * It includes an allocator.
+//! * It includes an allocator, selected by the `heap-free-list` feature.
 
 use core::{
     alloc::{GlobalAlloc, Layout},
     panic::PanicInfo,
 };
 
-use crate::syscall::{sys_alloc_aligned, sys_panic};
+use crate::{heap, syscall::sys_panic};
 
 extern crate alloc;
 
@@ -80,17 +80,21 @@ mod entrypoint {
     );
 }
 
-struct BumpPointerAlloc;
+struct HeapAlloc;
 
-unsafe impl GlobalAlloc for BumpPointerAlloc {
+unsafe impl GlobalAlloc for HeapAlloc {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        sys_alloc_aligned(layout.size(), layout.align())
+        let ptr = heap::alloc(layout);
+        if ptr.is_null() {
+            heap::alloc_failed(layout.size());
+        }
+        ptr
     }
 
-    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {
-        // this allocator never deallocates memory
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        heap::release(ptr, layout)
     }
 }
 
 #[global_allocator]
-static HEAP: BumpPointerAlloc = BumpPointerAlloc;
+static HEAP: HeapAlloc = HeapAlloc;
@@ -123,11 +123,16 @@ macro_rules! declare_syscall {
 }
 
 pub mod nr {
+    declare_syscall!(pub SYS_BIGINT_DIVREM);
+    declare_syscall!(pub SYS_CYCLE_BUDGET);
     declare_syscall!(pub SYS_CYCLE_COUNT);
     declare_syscall!(pub SYS_GETENV);
+    declare_syscall!(pub SYS_HEAP_STATS);
     declare_syscall!(pub SYS_ARGC);
     declare_syscall!(pub SYS_ARGV);
     declare_syscall!(pub SYS_LOG);
+    declare_syscall!(pub SYS_LOG_RECORD);
+    declare_syscall!(pub SYS_OUTPUT);
     declare_syscall!(pub SYS_PANIC);
     declare_syscall!(pub SYS_RANDOM);
     declare_syscall!(pub SYS_READ);
@@ -410,6 +415,12 @@ pub extern "C" fn sys_cycle_count() -> usize {
     a0 as usize
 }
 
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub extern "C" fn sys_cycle_count64() -> u64 {
+    let Return(lo, hi) = unsafe { syscall_0(nr::SYS_CYCLE_COUNT, null_mut(), 0) };
+    (hi as u64) << 32 | lo as u64
+}
+
 /// Reads the given number of bytes into the given buffer, posix-style.  Returns
 /// the number of bytes actually read.  On end of file, returns 0.
 ///
@@ -654,46 +665,12 @@ pub extern "C" fn sys_alloc_words(nwords: usize) -> *mut u32 {
 ///
 /// This function should be safe to call, but clippy complains if it is not marked as `unsafe`.
 pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
-    extern "C" {
-        // This symbol is defined by the loader and marks the end
-        // of all elf sections, so this is where we start our
-        // heap.
-        //
-        // This is generated automatically by the linker; see
-        // https://lld.llvm.org/ELF/linker_script.html#sections-command
-        static _end: u8;
-    }
-
-    // Pointer to next heap address to use, or 0 if the heap has not yet been
-    // initialized.
-    static mut HEAP_POS: usize = 0;
-
-    // SAFETY: Single threaded, so nothing else can touch this while we're working.
-    let mut heap_pos = unsafe { HEAP_POS };
-
-    if heap_pos == 0 {
-        heap_pos = unsafe { (&_end) as *const u8 as usize };
-    }
-
-    // Honor requested alignment if larger than word size.
     // Note: align is typically a power of two.
-    let align = usize::max(align, WORD_SIZE);
-
-    let offset = heap_pos & (align - 1);
-    if offset != 0 {
-        heap_pos += align - offset;
+    let ptr = crate::heap::bump::alloc_aligned(bytes, align);
+    if ptr.is_null() {
+        crate::heap::alloc_failed(bytes);
     }
-
-    let ptr = heap_pos as *mut u8;
-    heap_pos += bytes;
-
-    // Check to make sure heap doesn't collide with SYSTEM memory.
-    if crate::memory::SYSTEM.start() < heap_pos {
-        const MSG: &[u8] = "Out of memory!".as_bytes();
-        unsafe { sys_panic(MSG.as_ptr(), MSG.len()) };
-    }
-
-    unsafe { HEAP_POS = heap_pos };
+    crate::heap::record_alloc(bytes);
     ptr
 }
 
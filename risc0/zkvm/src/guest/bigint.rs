@@ -0,0 +1,177 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Modular arithmetic on integers wider than the BigInt accelerator.
+//!
+//! The BigInt accelerator multiplies integers of [bigint::WIDTH_BITS] bits.
+//! Wider integers, such as the 381-bit base field elements of BLS12-381 or RSA
+//! moduli, are split into limbs of half that width, whose products are computed
+//! by the accelerator. The quotient and remainder of the product by the modulus
+//! are provided by the host, and checked by the guest as
+//! `x * y == quotient * modulus + remainder` with `remainder < modulus`. A
+//! dishonest host can therefore only cause the guest to abort.
+//!
+//! Modular exponentiation, as used to verify RSA signatures, is built on the
+//! same path by [modpow].
+
+use alloc::{vec, vec::Vec};
+
+use risc0_zkvm_platform::syscall::{bigint, nr::SYS_BIGINT_DIVREM, sys_bigint};
+
+use crate::guest::{abort, env};
+
+/// Maximum width, in words, of the integers accepted by [modmul] and [modpow].
+///
+/// This allows for the 4096-bit moduli used by RSA.
+pub const MAX_WIDTH_WORDS: usize = 128;
+
+// Width, in words, of the limbs multiplied by the accelerator.
+const LIMB_WORDS: usize = bigint::WIDTH_WORDS / 2;
+
+const ZERO: [u32; bigint::WIDTH_WORDS] = [0; bigint::WIDTH_WORDS];
+
+/// Computes `x * y mod modulus` and writes it into `result`.
+///
+/// Integers are encoded as little-endian arrays of words, which must all have
+/// the same length, of at most [MAX_WIDTH_WORDS] words.
+///
+/// # Panics
+///
+/// Panics if the lengths of the arguments differ or exceed
+/// [MAX_WIDTH_WORDS], or if `modulus` is zero.
+pub fn modmul(result: &mut [u32], x: &[u32], y: &[u32], modulus: &[u32]) {
+    let width = modulus.len();
+    assert!(
+        x.len() == width && y.len() == width && result.len() == width,
+        "bigint operands must have the same width"
+    );
+    assert!(
+        width <= MAX_WIDTH_WORDS,
+        "bigint operands must be at most {MAX_WIDTH_WORDS} words"
+    );
+    assert!(is_nonzero(modulus), "bigint modulus must be non-zero");
+
+    let product = mul(x, y);
+
+    // Request the quotient and remainder of the product by the modulus.
+    let mut to_host = Vec::with_capacity(3 * width);
+    to_host.extend_from_slice(&product[..2 * width]);
+    to_host.extend_from_slice(modulus);
+    let mut from_host = vec![0u32; 3 * width];
+    env::syscall(
+        SYS_BIGINT_DIVREM,
+        bytemuck::cast_slice(&to_host),
+        &mut from_host,
+    );
+    let (quotient, remainder) = from_host.split_at(2 * width);
+
+    let mut check = mul(quotient, modulus);
+    let carry = add_assign(&mut check, remainder);
+    let (check_lo, check_hi) = check.split_at(product.len());
+    if carry || check_lo != product || is_nonzero(check_hi) || !less_than(remainder, modulus) {
+        abort("host returned an invalid bigint quotient and remainder");
+    }
+
+    result.copy_from_slice(remainder);
+}
+
+/// Computes `base ^ exponent mod modulus` and writes it into `result`.
+///
+/// Integers are encoded as little-endian arrays of words. `base`, `modulus`
+/// and `result` must have the same length, as in [modmul], while `exponent`
+/// may have any length.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [modmul].
+pub fn modpow(result: &mut [u32], base: &[u32], exponent: &[u32], modulus: &[u32]) {
+    let width = modulus.len();
+    let mut one = vec![0u32; width];
+    if let Some(word) = one.first_mut() {
+        *word = 1;
+    }
+    // Reduce the initial value, so that the result is correct for a modulus of one.
+    let mut acc = vec![0u32; width];
+    modmul(&mut acc, &one, &one, modulus);
+
+    let mut tmp = vec![0u32; width];
+    let mut started = false;
+    for bit in (0..exponent.len() * 32).rev() {
+        if started {
+            modmul(&mut tmp, &acc, &acc, modulus);
+            core::mem::swap(&mut acc, &mut tmp);
+        }
+        if (exponent[bit / 32] >> (bit % 32)) & 1 == 1 {
+            modmul(&mut tmp, &acc, base, modulus);
+            core::mem::swap(&mut acc, &mut tmp);
+            started = true;
+        }
+    }
+    result.copy_from_slice(&acc);
+}
+
+// Multiplies `a` by `b`, one pair of limbs at a time. The product of two limbs
+// always fits in the accelerator, with a zero modulus.
+fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let a_limbs = (a.len() + LIMB_WORDS - 1) / LIMB_WORDS;
+    let b_limbs = (b.len() + LIMB_WORDS - 1) / LIMB_WORDS;
+    let mut out = vec![0u32; (a_limbs + b_limbs) * LIMB_WORDS];
+    for (i, a_limb) in a.chunks(LIMB_WORDS).enumerate() {
+        if !is_nonzero(a_limb) {
+            continue;
+        }
+        let mut lhs = ZERO;
+        lhs[..a_limb.len()].copy_from_slice(a_limb);
+        for (j, b_limb) in b.chunks(LIMB_WORDS).enumerate() {
+            if !is_nonzero(b_limb) {
+                continue;
+            }
+            let mut rhs = ZERO;
+            rhs[..b_limb.len()].copy_from_slice(b_limb);
+            let mut limb_product = ZERO;
+            // SAFETY: All the arguments are aligned arrays of the accelerator width.
+            unsafe {
+                sys_bigint(&mut limb_product, bigint::OP_MULTIPLY, &lhs, &rhs, &ZERO);
+            }
+            // The partial sums never exceed the full product, so the carry cannot
+            // propagate past the end of `out`.
+            add_assign(&mut out[(i + j) * LIMB_WORDS..], &limb_product);
+        }
+    }
+    out
+}
+
+// Adds `b` to `a` in place, returning the carry out of `a`.
+fn add_assign(a: &mut [u32], b: &[u32]) -> bool {
+    let mut carry = 0u64;
+    for (i, word) in a.iter_mut().enumerate() {
+        let sum = *word as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        *word = sum as u32;
+        carry = sum >> 32;
+    }
+    carry != 0
+}
+
+fn less_than(a: &[u32], b: &[u32]) -> bool {
+    for (a, b) in a.iter().rev().zip(b.iter().rev()) {
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+fn is_nonzero(a: &[u32]) -> bool {
+    a.iter().any(|&word| word != 0)
+}
@@ -14,14 +14,18 @@
 
 //! Functions for interacting with the host environment.
 
+use alloc::vec;
 use core::{fmt, mem::MaybeUninit};
 
 use bytemuck::Pod;
 use risc0_zkvm_platform::{
-    fileno,
+    fileno, heap,
     syscall::{
-        self, sys_alloc_words, sys_cycle_count, sys_halt, sys_log, sys_pause, sys_read,
-        sys_read_words, sys_verify, sys_verify_integrity, sys_write, syscall_2, SyscallName,
+        self,
+        nr::{SYS_CYCLE_BUDGET, SYS_HEAP_STATS, SYS_OUTPUT},
+        sys_alloc_words, sys_cycle_count, sys_cycle_count64, sys_halt, sys_log, sys_pause,
+        sys_read, sys_read_words, sys_verify, sys_verify_integrity, sys_write, syscall_2,
+        SyscallName,
     },
     WORD_SIZE,
 };
@@ -29,7 +33,7 @@ use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     align_up,
-    serde::{Deserializer, Serializer, WordRead, WordWrite},
+    serde::{from_slice, Deserializer, FrameError, FrameHeader, Serializer, WordRead, WordWrite},
     sha::{
         rust_crypto::{Digest as _, Sha256},
         Digest, Digestible, DIGEST_WORDS,
@@ -164,17 +168,12 @@ pub fn verify(image_id: impl Into<Digest>, journal: &[impl Pod]) -> Result<(), V
     // post_state_digest and fix all fields that are required to have a certain
     // value. This assumption will only be resolvable if there exists a receipt
     // matching this claim.
-    let assumption_claim = ReceiptClaim {
-        pre: MaybePruned::Pruned(image_id),
-        post: MaybePruned::Pruned(post_state_digest),
+    let assumption_claim = ReceiptClaim::new(
+        image_id,
+        post_state_digest,
         exit_code,
-        input: Digest::ZERO,
-        output: Some(Output {
-            journal: MaybePruned::Pruned(journal_digest),
-            assumptions: MaybePruned::Pruned(Digest::ZERO),
-        })
-        .into(),
-    };
+        MaybePruned::Pruned(journal_digest),
+    );
     unsafe { ASSUMPTIONS_DIGEST.add(assumption_claim.into()) };
 
     Ok(())
@@ -317,6 +316,22 @@ pub fn read_slice<T: Pod>(slice: &mut [T]) {
     stdin().read_slice(slice)
 }
 
+/// Read a framed value from the host, checking its tag and version.
+///
+/// The frame is written on the host with `ExecutorEnvBuilder::write_frame`. If
+/// its tag or version does not match the expected ones, the body of the frame
+/// is skipped and an error is returned, so that the following inputs can still
+/// be read.
+pub fn read_frame<T: DeserializeOwned>(tag: u32, version: u32) -> Result<T, FrameError> {
+    let mut header = [0u32; FrameHeader::WORDS];
+    read_slice(&mut header);
+    let header = FrameHeader::from_words(header);
+    let mut body = vec![0u32; header.len as usize];
+    read_slice(&mut body);
+    header.check(tag, version)?;
+    Ok(from_slice(&body)?)
+}
+
 /// Serialize the given data and write it to the STDOUT of the zkVM.
 ///
 /// This is available to the host as the private output on the prover.
@@ -351,14 +366,77 @@ pub fn commit_slice<T: Pod>(slice: &[T]) {
     journal().write_slice(slice);
 }
 
-/// Return the number of processor cycles that have occurred since the guest
-/// began.
+/// Return the number of cycles consumed by the guest since it began, truncated
+/// to a `usize`.
+///
+/// See [cycle_count_u64] for how the cycles are counted. Sessions may run for
+/// more than `u32::MAX` cycles, in which case this count wraps around.
 ///
 /// WARNING: The cycle count is provided by the host and is not checked by the zkVM circuit.
 pub fn cycle_count() -> usize {
     sys_cycle_count()
 }
 
+/// Return the number of cycles consumed by the guest since it began.
+///
+/// The count is exact: it includes the cycles spent paging memory in and out,
+/// across all the segments of the session, but not the fixed overhead of each
+/// segment. The difference between two calls is therefore the cost of the code
+/// in between, and can be committed to the journal as a measurement.
+///
+/// WARNING: The cycle count is provided by the host and is not checked by the zkVM circuit.
+pub fn cycle_count_u64() -> u64 {
+    sys_cycle_count64()
+}
+
+/// Cycles remaining before the guest reaches a limit of the execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CycleBudget {
+    /// Cycles remaining before the current segment is full.
+    ///
+    /// Reaching the end of a segment is transparent to the guest, which
+    /// continues in the next one, but work split on segment boundaries avoids
+    /// paging the same memory in more than one segment.
+    pub segment: u64,
+
+    /// Cycles remaining before the session limit set by the host, if any.
+    ///
+    /// Execution fails when the session limit is reached, so a guest running
+    /// an open-ended computation can use this to stop, or [pause], while it
+    /// can still commit its progress.
+    pub session: Option<u64>,
+}
+
+/// Return the number of cycles remaining before the end of the current segment
+/// and before the session limit.
+///
+/// This lets adaptive algorithms, such as iterative refinement or chunked
+/// processing, decide when to stop. The budget is an upper bound: committing
+/// the results and halting also consume cycles.
+///
+/// WARNING: The budget is provided by the host and is not checked by the zkVM circuit.
+pub fn cycle_budget() -> CycleBudget {
+    let mut session = [0u32; 2];
+    let syscall::Return(segment, has_session) = syscall(SYS_CYCLE_BUDGET, &[], &mut session);
+    CycleBudget {
+        segment: segment as u64,
+        session: (has_session != 0).then(|| ((session[1] as u64) << 32) | session[0] as u64),
+    }
+}
+
+/// Send the current usage of the heap to the host, which logs it.
+///
+/// The usage is also available to the guest itself, with
+/// `risc0_zkvm_platform::heap::stats`. Reporting it at the end of the guest,
+/// or before a large allocation, shows how close the guest is to running out
+/// of memory.
+pub fn report_heap_stats() {
+    let stats = heap::stats();
+    let words = [stats.used, stats.peak, stats.free];
+    syscall(SYS_HEAP_STATS, bytemuck::cast_slice(&words), &mut []);
+}
+
 /// Print a message to the debug console.
 pub fn log(msg: &str) {
     let msg = msg.as_bytes();
@@ -384,6 +462,18 @@ pub fn journal() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
     })
 }
 
+/// Return a writer for the private output with the given name.
+///
+/// Named outputs let the guest return several streams of data to the host,
+/// which registers a writer for each of them with
+/// `ExecutorEnvBuilder::output`. Like [stdout], and unlike the [journal], they
+/// are private: they are not part of the receipt, and not bound by its claim.
+/// Execution fails if the host has not registered an output with this name.
+pub fn output(name: &str) -> FdWriter<impl for<'a> Fn(&'a [u8])> {
+    let syscall::Return(fd, _) = syscall(SYS_OUTPUT, name.as_bytes(), &mut []);
+    FdWriter::new(fd, |_| {})
+}
+
 /// Return a reader for the standard input
 pub fn stdin() -> FdReader {
     FdReader::new(fileno::STDIN)
@@ -396,6 +486,17 @@ pub trait Read {
 
     /// Read raw data from the host.
     fn read_slice<T: Pod>(&mut self, buf: &mut [T]);
+
+    /// Read data encoded with [postcard](crate::serde::postcard) from the host.
+    #[cfg(feature = "postcard")]
+    fn read_postcard<T: DeserializeOwned>(&mut self) -> T {
+        let mut len = [0u32];
+        self.read_slice(&mut len);
+        let mut words = vec![0u32; 1 + align_up(len[0] as usize, WORD_SIZE) / WORD_SIZE];
+        words[0] = len[0];
+        self.read_slice(&mut words[1..]);
+        crate::serde::postcard::from_words(&words).unwrap()
+    }
 }
 
 impl<R: Read + ?Sized> Read for &mut R {
@@ -500,6 +601,12 @@ pub trait Write {
 
     /// Write raw data.
     fn write_slice<T: Pod>(&mut self, buf: &[T]);
+
+    /// Write an object encoded with [postcard](crate::serde::postcard).
+    #[cfg(feature = "postcard")]
+    fn write_postcard<T: Serialize>(&mut self, val: T) {
+        self.write_slice(&crate::serde::postcard::to_words(&val).unwrap());
+    }
 }
 
 impl<W: Write + ?Sized> Write for &mut W {
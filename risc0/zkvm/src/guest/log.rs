@@ -0,0 +1,151 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Levelled logging from the guest to the host.
+//!
+//! The macros in this module send the formatted message to the host, along with
+//! its level and target, where it is emitted as a `tracing` event with the
+//! `risc0_zkvm::guest` target. The target of the record, which defaults to the
+//! module path of the call site, is attached as the `guest_target` field, so
+//! that guest logs can be filtered like any other host logs:
+//!
+//! ```ignore
+//! use risc0_zkvm::guest::log::{debug, info};
+//!
+//! info!("processing {} transactions", txs.len());
+//! debug!(target: "state", "root: {root}");
+//! ```
+//!
+//! Unlike [crate::guest::env::log], which writes raw messages to the standard
+//! output of the host, records are subject to the filtering of the host's
+//! `tracing` subscriber. Formatting the message still costs cycles in the
+//! guest, even if the host discards it.
+
+use alloc::{format, vec::Vec};
+use core::fmt;
+
+use risc0_zkvm_platform::syscall::nr::SYS_LOG_RECORD;
+
+use crate::guest::env;
+
+/// Level of a log record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Level {
+    /// Designates very serious errors.
+    Error = 1,
+    /// Designates hazardous situations.
+    Warn = 2,
+    /// Designates useful information.
+    Info = 3,
+    /// Designates lower priority information.
+    Debug = 4,
+    /// Designates very low priority, often extremely verbose, information.
+    Trace = 5,
+}
+
+/// Sends a log record to the host.
+///
+/// This is the function called by the logging macros, which should be
+/// preferred.
+pub fn log(level: Level, target: &str, args: fmt::Arguments) {
+    let msg = format!("{args}");
+    let mut record = Vec::with_capacity(8 + target.len() + msg.len());
+    record.extend_from_slice(&(level as u32).to_le_bytes());
+    record.extend_from_slice(&(target.len() as u32).to_le_bytes());
+    record.extend_from_slice(target.as_bytes());
+    record.extend_from_slice(msg.as_bytes());
+    env::syscall(SYS_LOG_RECORD, &record, &mut []);
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_log {
+    (target: $target:expr, $level:expr, $($arg:tt)+) => {
+        $crate::guest::log::log($level, $target, ::core::format_args!($($arg)+))
+    };
+    ($level:expr, $($arg:tt)+) => {
+        $crate::__guest_log!(target: ::core::module_path!(), $level, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_log_error {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::__guest_log!(target: $target, $crate::guest::log::Level::Error, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::__guest_log!($crate::guest::log::Level::Error, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_log_warn {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::__guest_log!(target: $target, $crate::guest::log::Level::Warn, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::__guest_log!($crate::guest::log::Level::Warn, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_log_info {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::__guest_log!(target: $target, $crate::guest::log::Level::Info, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::__guest_log!($crate::guest::log::Level::Info, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_log_debug {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::__guest_log!(target: $target, $crate::guest::log::Level::Debug, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::__guest_log!($crate::guest::log::Level::Debug, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_log_trace {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::__guest_log!(target: $target, $crate::guest::log::Level::Trace, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::__guest_log!($crate::guest::log::Level::Trace, $($arg)+)
+    };
+}
+
+/// Logs a message at the error level.
+pub use crate::__guest_log_error as error;
+
+/// Logs a message at the warn level.
+pub use crate::__guest_log_warn as warn;
+
+/// Logs a message at the info level.
+pub use crate::__guest_log_info as info;
+
+/// Logs a message at the debug level.
+pub use crate::__guest_log_debug as debug;
+
+/// Logs a message at the trace level.
+pub use crate::__guest_log_trace as trace;
@@ -79,8 +79,15 @@
 
 #![deny(missing_docs)]
 
+pub mod bigint;
 pub mod env;
+#[cfg(target_os = "zkvm")]
+pub mod log;
 pub mod sha;
+#[cfg(target_os = "zkvm")]
+pub mod sync;
+#[cfg(target_os = "zkvm")]
+pub mod thread;
 
 #[cfg(target_os = "zkvm")]
 use core::arch::asm;
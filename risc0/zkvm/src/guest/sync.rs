@@ -0,0 +1,81 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single-threaded stand-ins for the locks of `std::sync`.
+//!
+//! The guest runs on a single core, and the threads of
+//! [crate::guest::thread] run one after the other, so a lock is never
+//! contended. Locking a [Mutex] that is already locked is a bug which would
+//! deadlock with real threads, and aborts the guest instead.
+
+use core::{
+    cell::{RefCell, RefMut},
+    convert::Infallible,
+    fmt,
+};
+
+/// Result of locking a [Mutex], as in `std::sync::LockResult`.
+///
+/// A panic aborts the guest, so a lock is never poisoned.
+pub type LockResult<T> = Result<T, Infallible>;
+
+/// Mutual exclusion lock without synchronization.
+pub struct Mutex<T: ?Sized>(RefCell<T>);
+
+// SAFETY: The guest has a single thread of execution.
+unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+/// Guard of a locked [Mutex], which is unlocked when dropped.
+pub type MutexGuard<'a, T> = RefMut<'a, T>;
+
+impl<T> Mutex<T> {
+    /// Creates an unlocked mutex holding the given value.
+    pub const fn new(value: T) -> Self {
+        Self(RefCell::new(value))
+    }
+
+    /// Consumes the mutex, returning the value it holds.
+    pub fn into_inner(self) -> LockResult<T> {
+        Ok(self.0.into_inner())
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Locks the mutex, aborting the guest if it is already locked.
+    pub fn lock(&self) -> LockResult<MutexGuard<'_, T>> {
+        Ok(self.0.borrow_mut())
+    }
+
+    /// Locks the mutex, if it is not already locked.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.0.try_borrow_mut().ok()
+    }
+
+    /// Returns a mutable reference to the value, which needs no locking.
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        Ok(self.0.get_mut())
+    }
+}
+
+impl<T: Default> Default for Mutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Mutex").field(&&self.0).finish()
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single-threaded stand-ins for the `std::thread` API.
+//!
+//! The zkVM has a single core, and the guest target cannot create threads.
+//! This module lets code written for several threads run in the guest instead:
+//! a spawned thread runs to completion when it is spawned, so [JoinHandle::join]
+//! returns immediately. The order of execution is therefore the order of the
+//! calls to [spawn], which keeps the execution deterministic.
+//!
+//! Threads that wait for one another, e.g. on a channel fed by a thread spawned
+//! later, deadlock. Locks shared between threads can use
+//! [crate::guest::sync::Mutex].
+
+use alloc::boxed::Box;
+use core::{any::Any, marker::PhantomData, num::NonZeroUsize};
+
+/// Result of joining a thread, as in `std::thread::Result`.
+///
+/// A panic aborts the guest, so joining a thread never returns an error.
+pub type Result<T> = core::result::Result<T, Box<dyn Any + Send + 'static>>;
+
+/// Runs the given closure, as a thread which runs to completion immediately.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    JoinHandle(f())
+}
+
+/// Handle to a spawned thread, holding its result.
+pub struct JoinHandle<T>(T);
+
+impl<T> JoinHandle<T> {
+    /// Returns the result of the thread.
+    pub fn join(self) -> Result<T> {
+        Ok(self.0)
+    }
+
+    /// Returns whether the thread has finished, which is always the case.
+    pub fn is_finished(&self) -> bool {
+        true
+    }
+}
+
+/// Creates a scope for spawning threads that borrow local variables.
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    f(&Scope {
+        scope: PhantomData,
+        env: PhantomData,
+    })
+}
+
+/// Scope for spawning threads that borrow local variables, created by [scope].
+pub struct Scope<'scope, 'env: 'scope> {
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Runs the given closure, as a thread which runs to completion
+    /// immediately.
+    pub fn spawn<F, T>(&'scope self, f: F) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        ScopedJoinHandle(f(), PhantomData)
+    }
+}
+
+/// Handle to a thread spawned in a [Scope], holding its result.
+pub struct ScopedJoinHandle<'scope, T>(T, PhantomData<&'scope ()>);
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    /// Returns the result of the thread.
+    pub fn join(self) -> Result<T> {
+        Ok(self.0)
+    }
+
+    /// Returns whether the thread has finished, which is always the case.
+    pub fn is_finished(&self) -> bool {
+        true
+    }
+}
+
+/// Does nothing, as no other thread is waiting to run.
+pub fn yield_now() {}
+
+/// Returns the number of threads that can run in parallel, which is one.
+pub fn available_parallelism() -> core::result::Result<NonZeroUsize, core::convert::Infallible> {
+    Ok(NonZeroUsize::MIN)
+}
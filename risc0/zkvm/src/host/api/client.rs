@@ -394,6 +394,7 @@ impl Client {
             slice_ios: env.slice_io.borrow().inner.keys().cloned().collect(),
             read_fds: env.posix_io.borrow().read_fds.keys().cloned().collect(),
             write_fds: env.posix_io.borrow().write_fds.keys().cloned().collect(),
+            outputs: env.outputs.clone(),
             segment_limit_po2: env.segment_limit_po2,
             session_limit: env.session_limit,
             trace_events: (!env.trace.is_empty()).then_some(()),
@@ -17,7 +17,7 @@ use std::{collections::BTreeMap, path::PathBuf};
 use anyhow::{anyhow, bail, Result};
 use prost::{Message, Name};
 use risc0_binfmt::{MemoryImage, PageTableInfo, SystemState};
-use risc0_zkp::core::digest::Digest;
+use risc0_zkp::{core::digest::Digest, security::ZkParams};
 
 use super::{malformed_err, path_to_string, pb, Asset, AssetRequest};
 use crate::{
@@ -25,8 +25,8 @@ use crate::{
         receipt::{decode_receipt_claim_from_seal, CompositeReceipt, InnerReceipt, SegmentReceipt},
         recursion::SuccinctReceipt,
     },
-    Assumptions, ExitCode, Journal, MaybePruned, Output, ProverOpts, Receipt, ReceiptClaim,
-    TraceEvent,
+    Assumptions, ExitCode, GuestFault, GuestPanic, MaybePruned, Output, ProverOpts, Receipt,
+    ReceiptClaim, ReceiptKind, ReceiptMetadata, SessionLimit, TraceEvent,
 };
 
 mod ver {
@@ -183,23 +183,71 @@ impl From<Result<(), anyhow::Error>> for pb::api::GenericReply {
 
 impl From<anyhow::Error> for pb::api::GenericError {
     fn from(err: anyhow::Error) -> Self {
+        let kind = if let Some(fault) = err.downcast_ref::<GuestFault>() {
+            Some(pb::api::generic_error::Kind::GuestFault(
+                pb::api::GuestFault {
+                    pc: fault.pc,
+                    cause: fault.cause.clone(),
+                },
+            ))
+        } else if let Some(panic) = err.downcast_ref::<GuestPanic>() {
+            Some(pb::api::generic_error::Kind::GuestPanic(
+                pb::api::GuestPanic {
+                    message: panic.message.clone(),
+                    location: panic
+                        .location
+                        .clone()
+                        .map(|(file, line, column)| pb::api::SourceLocation { file, line, column }),
+                },
+            ))
+        } else {
+            err.downcast_ref::<SessionLimit>().map(|limit| {
+                pb::api::generic_error::Kind::SessionLimit(pb::api::SessionLimit {
+                    limit: limit.limit,
+                })
+            })
+        };
         Self {
             reason: err.to_string(),
+            kind,
         }
     }
 }
 
 impl From<pb::api::GenericError> for anyhow::Error {
     fn from(err: pb::api::GenericError) -> Self {
-        anyhow::Error::msg(err.reason)
+        match err.kind {
+            Some(pb::api::generic_error::Kind::GuestFault(fault)) => GuestFault {
+                pc: fault.pc,
+                cause: fault.cause,
+            }
+            .into(),
+            Some(pb::api::generic_error::Kind::GuestPanic(panic)) => GuestPanic {
+                message: panic.message,
+                location: panic
+                    .location
+                    .map(|location| (location.file, location.line, location.column)),
+            }
+            .into(),
+            Some(pb::api::generic_error::Kind::SessionLimit(limit)) => {
+                SessionLimit { limit: limit.limit }.into()
+            }
+            None => anyhow::Error::msg(err.reason),
+        }
     }
 }
 
 impl From<pb::api::ProverOpts> for ProverOpts {
     fn from(opts: pb::api::ProverOpts) -> Self {
+        let receipt_kind = match opts.receipt_kind() {
+            pb::api::ReceiptKind::Composite => ReceiptKind::Composite,
+            pb::api::ReceiptKind::Succinct => ReceiptKind::Succinct,
+        };
         Self {
             hashfn: opts.hashfn,
             prove_guest_errors: opts.prove_guest_errors,
+            receipt_kind,
+            zk: opts.zk.map(Into::into).unwrap_or_default(),
         }
     }
 }
@@ -209,6 +257,26 @@ impl From<ProverOpts> for pb::api::ProverOpts {
         Self {
             hashfn: opts.hashfn,
             prove_guest_errors: opts.prove_guest_errors,
+            zk: Some(opts.zk.into()),
+            receipt_kind: match opts.receipt_kind {
+                ReceiptKind::Composite => pb::api::ReceiptKind::Composite,
+                ReceiptKind::Succinct => pb::api::ReceiptKind::Succinct,
+            }
+            .into(),
+        }
+    }
+}
+
+impl From<pb::api::ZkParams> for ZkParams {
+    fn from(params: pb::api::ZkParams) -> Self {
+        Self::new(params.zk_cycles as usize)
+    }
+}
+
+impl From<ZkParams> for pb::api::ZkParams {
+    fn from(params: ZkParams) -> Self {
+        Self {
+            zk_cycles: params.zk_cycles as u32,
         }
     }
 }
@@ -216,11 +284,10 @@ impl From<ProverOpts> for pb::api::ProverOpts {
 impl From<MemoryImage> for pb::core::MemoryImage {
     fn from(value: MemoryImage) -> Self {
         let pages = value
-            .pages
-            .iter()
+            .pages()
             .map(|(addr, data)| pb::core::PageEntry {
-                addr: *addr,
-                data: data.clone(),
+                addr,
+                data: data.to_vec(),
             })
             .collect();
         Self {
@@ -246,11 +313,11 @@ impl TryFrom<pb::core::MemoryImage> for MemoryImage {
                 .into_iter()
                 .map(|entry| (entry.addr, entry.data)),
         );
-        Ok(Self {
+        Ok(Self::from_pages(
             pages,
-            info: value.info.ok_or(malformed_err())?.try_into()?,
-            pc: value.pc,
-        })
+            value.info.ok_or(malformed_err())?.try_into()?,
+            value.pc,
+        ))
     }
 }
 
@@ -301,6 +368,7 @@ impl From<Receipt> for pb::core::Receipt {
     fn from(value: Receipt) -> Self {
         Self {
             version: Some(ver::RECEIPT),
+            metadata: Some(value.metadata().clone().into()),
             inner: Some(value.inner.into()),
             journal: value.journal.bytes,
         }
@@ -315,10 +383,33 @@ impl TryFrom<pb::core::Receipt> for Receipt {
         if version > ver::RECEIPT.value {
             bail!("Incompatible Receipt version: {version}");
         }
-        Ok(Self {
-            inner: value.inner.ok_or(malformed_err())?.try_into()?,
-            journal: Journal::new(value.journal),
-        })
+        Ok(Self::new(
+            value.inner.ok_or(malformed_err())?.try_into()?,
+            value.journal,
+        )
+        .with_metadata(value.metadata.map(Into::into).unwrap_or_default()))
+    }
+}
+
+impl From<ReceiptMetadata> for pb::core::ReceiptMetadata {
+    fn from(value: ReceiptMetadata) -> Self {
+        Self {
+            zkvm_version: value.zkvm_version,
+            hash_suite: value.hash_suite,
+            prover: value.prover,
+            proving_time_ms: value.proving_time_ms,
+        }
+    }
+}
+
+impl From<pb::core::ReceiptMetadata> for ReceiptMetadata {
+    fn from(value: pb::core::ReceiptMetadata) -> Self {
+        Self {
+            zkvm_version: value.zkvm_version,
+            hash_suite: value.hash_suite,
+            prover: value.prover,
+            proving_time_ms: value.proving_time_ms,
+        }
     }
 }
 
@@ -332,9 +332,7 @@ impl Server {
         }
 
         let msg = inner(&mut conn, request).unwrap_or_else(|err| pb::api::ServerReply {
-            kind: Some(pb::api::server_reply::Kind::Error(pb::api::GenericError {
-                reason: err.to_string(),
-            })),
+            kind: Some(pb::api::server_reply::Kind::Error(err.into())),
         });
 
         tracing::trace!("tx: {msg:?}");
@@ -356,6 +354,7 @@ impl Server {
             let prover = get_prover_server(&opts)?;
             let ctx = VerifierContext::default();
             let receipt = prover.prove_with_ctx(env, &ctx, &bytes)?;
+            let receipt = prover.compress_to_kind(receipt, opts.receipt_kind)?;
 
             let receipt_pb: pb::core::Receipt = receipt.into();
             let receipt_bytes = receipt_pb.encode_to_vec();
@@ -377,9 +376,7 @@ impl Server {
         }
 
         let msg = inner(&mut conn, request).unwrap_or_else(|err| pb::api::ServerReply {
-            kind: Some(pb::api::server_reply::Kind::Error(pb::api::GenericError {
-                reason: err.to_string(),
-            })),
+            kind: Some(pb::api::server_reply::Kind::Error(err.into())),
         });
 
         tracing::trace!("tx: {msg:?}");
@@ -418,11 +415,7 @@ impl Server {
         }
 
         let msg = inner(request).unwrap_or_else(|err| pb::api::ProveSegmentReply {
-            kind: Some(pb::api::prove_segment_reply::Kind::Error(
-                pb::api::GenericError {
-                    reason: err.to_string(),
-                },
-            )),
+            kind: Some(pb::api::prove_segment_reply::Kind::Error(err.into())),
         });
 
         tracing::trace!("tx: {msg:?}");
@@ -454,9 +447,7 @@ impl Server {
         }
 
         let msg = inner(request).unwrap_or_else(|err| pb::api::LiftReply {
-            kind: Some(pb::api::lift_reply::Kind::Error(pb::api::GenericError {
-                reason: err.to_string(),
-            })),
+            kind: Some(pb::api::lift_reply::Kind::Error(err.into())),
         });
 
         tracing::debug!("tx: {msg:?}");
@@ -491,9 +482,7 @@ impl Server {
         }
 
         let msg = inner(request).unwrap_or_else(|err| pb::api::JoinReply {
-            kind: Some(pb::api::join_reply::Kind::Error(pb::api::GenericError {
-                reason: err.to_string(),
-            })),
+            kind: Some(pb::api::join_reply::Kind::Error(err.into())),
         });
 
         tracing::debug!("tx: {msg:?}");
@@ -540,9 +529,7 @@ impl Server {
         }
 
         let msg = inner(request).unwrap_or_else(|err| pb::api::ResolveReply {
-            kind: Some(pb::api::resolve_reply::Kind::Error(pb::api::GenericError {
-                reason: err.to_string(),
-            })),
+            kind: Some(pb::api::resolve_reply::Kind::Error(err.into())),
         });
 
         tracing::debug!("tx: {msg:?}");
@@ -580,11 +567,7 @@ impl Server {
         }
 
         let msg = inner(request).unwrap_or_else(|err| pb::api::IdentityP254Reply {
-            kind: Some(pb::api::identity_p254_reply::Kind::Error(
-                pb::api::GenericError {
-                    reason: err.to_string(),
-                },
-            )),
+            kind: Some(pb::api::identity_p254_reply::Kind::Error(err.into())),
         });
 
         tracing::debug!("tx: {msg:?}");
@@ -608,6 +591,7 @@ fn build_env<'a>(
         let proxy = PosixIoProxy::new(*fd, conn.try_clone()?);
         env_builder.write_fd(*fd, proxy);
     }
+    env_builder.outputs(request.outputs.clone());
     let proxy = SliceIoProxy::new(conn.try_clone()?);
     for name in request.slice_ios.iter() {
         env_builder.slice_io(&name, proxy.try_clone()?);
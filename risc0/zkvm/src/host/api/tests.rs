@@ -28,8 +28,8 @@ use test_log::test;
 
 use super::{Asset, AssetRequest, ConnectionWrapper, Connector, TcpConnection};
 use crate::{
-    recursion::SuccinctReceipt, ApiClient, ApiServer, ExecutorEnv, InnerReceipt, ProverOpts,
-    Receipt, SegmentReceipt, SessionInfo, VerifierContext,
+    recursion::SuccinctReceipt, serde::to_vec, ApiClient, ApiServer, ExecutorEnv, GuestPanic,
+    InnerReceipt, ProverOpts, Receipt, SegmentReceipt, SessionInfo, SessionLimit, VerifierContext,
 };
 
 struct TestClientConnector {
@@ -134,6 +134,10 @@ impl TestClient {
 }
 
 fn with_server<T, F: FnOnce() -> Result<T>>(addr: SocketAddr, f: F) -> T {
+    try_with_server(addr, f).unwrap()
+}
+
+fn try_with_server<T, F: FnOnce() -> Result<T>>(addr: SocketAddr, f: F) -> Result<T> {
     let addr = addr.to_string();
     let handle = thread::Builder::new()
         .name("server".into())
@@ -143,7 +147,7 @@ fn with_server<T, F: FnOnce() -> Result<T>>(addr: SocketAddr, f: F) -> T {
         })
         .unwrap();
 
-    let result = f().unwrap();
+    let result = f();
     handle.join().unwrap();
     result
 }
@@ -305,6 +309,29 @@ fn lift_resolve() {
     receipt.verify(MULTI_TEST_ID).unwrap();
 }
 
+#[test]
+fn named_outputs() {
+    let mut alpha = Vec::new();
+    let mut beta = Vec::new();
+    let session = {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::NamedOutputs)
+            .unwrap()
+            .output("alpha", &mut alpha)
+            .output("beta", &mut beta)
+            .build()
+            .unwrap();
+        let binary = Asset::Inline(MULTI_TEST_ELF.into());
+        TestClient::new().execute(env, binary)
+    };
+    assert_eq!(session.journal.decode::<u32>().unwrap(), 1);
+    assert_eq!(alpha, b"hello world");
+    assert_eq!(
+        beta,
+        bytemuck::cast_slice::<u32, u8>(&to_vec(&42u32).unwrap())
+    );
+}
+
 #[test]
 #[should_panic(expected = "MultiTestSpec::Panic invoked")]
 fn guest_error_forwarding() {
@@ -316,3 +343,38 @@ fn guest_error_forwarding() {
     let binary = Asset::Inline(MULTI_TEST_ELF.into());
     TestClient::new().execute(env, binary);
 }
+
+#[test]
+fn typed_error_forwarding() {
+    fn execute(env: ExecutorEnv<'_>) -> anyhow::Error {
+        let client = TestClient::new();
+        let binary = Asset::Inline(MULTI_TEST_ELF.into());
+        try_with_server(client.addr, || {
+            client
+                .client
+                .execute(&env, binary, AssetRequest::Inline, |_, _| Ok(()))
+        })
+        .unwrap_err()
+    }
+
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::Panic)
+        .unwrap()
+        .build()
+        .unwrap();
+    let err = execute(env);
+    let panic = err.downcast_ref::<GuestPanic>().unwrap();
+    assert!(panic.message.contains("MultiTestSpec::Panic invoked"));
+
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .session_limit(Some(0))
+        .build()
+        .unwrap();
+    let err = execute(env);
+    assert_eq!(
+        err.downcast_ref::<SessionLimit>(),
+        Some(&SessionLimit { limit: 0 })
+    );
+}
@@ -29,7 +29,7 @@ use bytes::Bytes;
 use risc0_zkvm_platform::{self, fileno};
 use serde::Serialize;
 
-use crate::serde::to_vec;
+use crate::serde::{to_frame, to_vec};
 use crate::{
     host::client::{
         exec::TraceEvent,
@@ -39,6 +39,10 @@ use crate::{
     Assumption,
 };
 
+// File descriptors assigned to named outputs, starting far above the ones
+// usually passed to `write_fd`.
+const OUTPUT_FD_START: u32 = 0x8000_0000;
+
 /// A builder pattern used to construct an [ExecutorEnv].
 #[derive(Default)]
 pub struct ExecutorEnvBuilder<'a> {
@@ -81,6 +85,7 @@ pub struct ExecutorEnv<'a> {
     pub(crate) session_limit: Option<u64>,
     pub(crate) posix_io: Rc<RefCell<PosixIo<'a>>>,
     pub(crate) slice_io: Rc<RefCell<SliceIoTable<'a>>>,
+    pub(crate) outputs: HashMap<String, u32>,
     pub(crate) input: Vec<u8>,
     pub(crate) trace: Vec<Rc<RefCell<dyn TraceCallback + 'a>>>,
     pub(crate) assumptions: Rc<RefCell<Assumptions>>,
@@ -249,6 +254,54 @@ impl<'a> ExecutorEnvBuilder<'a> {
         Ok(self.write_slice(&to_vec(data)?))
     }
 
+    /// Write input data to the zkVM guest stdin, encoded with
+    /// [postcard](crate::serde::postcard).
+    ///
+    /// A corresponding `env::stdin().read_postcard()` can be used within the
+    /// guest to read the data.
+    #[cfg(feature = "postcard")]
+    pub fn write_postcard<T: Serialize>(&mut self, data: &T) -> Result<&mut Self> {
+        Ok(self.write_slice(&crate::serde::postcard::to_words(data)?))
+    }
+
+    /// Write input data to the zkVM guest stdin, framed with a tag and a
+    /// version.
+    ///
+    /// This function serializes `data` into a frame, as defined by
+    /// [crate::serde::FrameHeader]. A corresponding `env::read_frame` can be
+    /// used within the guest to read the data, which fails deterministically
+    /// if the guest expects another tag or version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Input {
+    ///     a: u32,
+    ///     b: u32,
+    /// }
+    ///
+    /// const INPUT_TAG: u32 = 1;
+    /// const INPUT_VERSION: u32 = 2;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .write_frame(INPUT_TAG, INPUT_VERSION, &Input { a: 1, b: 2 })
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn write_frame<T: Serialize>(
+        &mut self,
+        tag: u32,
+        version: u32,
+        data: &T,
+    ) -> Result<&mut Self> {
+        Ok(self.write_slice(&to_frame(tag, version, data)?))
+    }
+
     /// Write input data to the zkVM guest stdin.
     ///
     /// This function writes a slice directly to the underlying buffer. A
@@ -302,6 +355,41 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Add a writer for the private output with the given name.
+    ///
+    /// The guest writes to it with `env::output(name)`. Named outputs are
+    /// returned to the host only; unlike the journal, they are not part of
+    /// the receipt.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let mut witness = Vec::new();
+    /// let env = ExecutorEnv::builder()
+    ///     .output("witness", &mut witness)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn output(&mut self, name: &str, writer: impl Write + 'a) -> &mut Self {
+        let next_fd = OUTPUT_FD_START + self.inner.outputs.len() as u32;
+        let fd = *self
+            .inner
+            .outputs
+            .entry(name.to_string())
+            .or_insert(next_fd);
+        self.write_fd(fd, writer)
+    }
+
+    /// Restore the file descriptors of named outputs, whose writers are added
+    /// with [ExecutorEnvBuilder::write_fd].
+    #[cfg(feature = "prove")]
+    pub(crate) fn outputs(&mut self, outputs: HashMap<String, u32>) -> &mut Self {
+        self.inner.outputs = outputs;
+        self
+    }
+
     /// Add a handler for simple I/O handling.
     pub fn slice_io(&mut self, channel: &str, handler: impl SliceIo + 'a) -> &mut Self {
         self.inner
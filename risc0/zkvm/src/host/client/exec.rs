@@ -57,3 +57,102 @@ impl std::fmt::Debug for TraceEvent {
         }
     }
 }
+
+/// Error returned by the executor when the guest faults, e.g. on an illegal
+/// instruction or an access outside of the guest memory.
+///
+/// It can be recovered from the error returned by the executor with
+/// [anyhow::Error::downcast_ref].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GuestFault {
+    /// The address of the faulting instruction.
+    pub pc: u32,
+
+    /// The cause of the fault, as reported by the instruction decoder.
+    pub cause: String,
+}
+
+impl std::fmt::Display for GuestFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "execution encountered a fault at pc 0x{:08x}: {}",
+            self.pc, self.cause
+        )
+    }
+}
+
+impl std::error::Error for GuestFault {}
+
+/// Error returned by the executor when the guest panics.
+///
+/// The location is the one reported by the panic handler of the guest. It is `None` if the guest
+/// did not report one, e.g. when calling `sys_panic` directly.
+///
+/// It can be recovered from the error returned by the executor with
+/// [anyhow::Error::downcast_ref].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GuestPanic {
+    /// The panic message.
+    pub message: String,
+
+    /// The source file, line and column where the guest panicked.
+    pub location: Option<(String, u32, u32)>,
+}
+
+impl GuestPanic {
+    // Parses the message formatted by the panic handler of the guest, as
+    // `panicked at {file}:{line}:{column}:\n{message}`.
+    #[cfg(feature = "prove")]
+    pub(crate) fn parse(msg: &str) -> Self {
+        let parsed = msg.strip_prefix("panicked at ").and_then(|rest| {
+            let (location, message) = rest.split_once(":\n")?;
+            let mut parts = location.rsplitn(3, ':');
+            let column = parts.next()?.parse().ok()?;
+            let line = parts.next()?.parse().ok()?;
+            let file = parts.next()?;
+            Some(Self {
+                message: message.to_string(),
+                location: Some((file.to_string(), line, column)),
+            })
+        });
+        parsed.unwrap_or_else(|| Self {
+            message: msg.to_string(),
+            location: None,
+        })
+    }
+}
+
+impl std::fmt::Display for GuestPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Guest panicked: {}", self.message)?;
+        if let Some((file, line, column)) = &self.location {
+            write!(f, " (at {file}:{line}:{column})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GuestPanic {}
+
+/// Error returned by the executor when the session exceeds the limit set with
+/// [crate::ExecutorEnvBuilder::session_limit].
+///
+/// It can be recovered from the error returned by the executor with
+/// [anyhow::Error::downcast_ref].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SessionLimit {
+    /// The session limit, in cycles.
+    pub limit: u64,
+}
+
+impl std::fmt::Display for SessionLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Session limit exceeded: {} cycles", self.limit)
+    }
+}
+
+impl std::error::Error for SessionLimit {}
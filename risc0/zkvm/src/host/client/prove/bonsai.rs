@@ -18,7 +18,10 @@ use anyhow::{anyhow, bail, ensure, Result};
 use bonsai_sdk::alpha::Client;
 
 use super::Prover;
-use crate::{compute_image_id, sha::Digestible, ExecutorEnv, ProverOpts, Receipt, VerifierContext};
+use crate::{
+    compute_image_id, sha::Digestible, ExecutorEnv, ProverOpts, Receipt, ReceiptKind,
+    VerifierContext,
+};
 
 /// An implementation of a [Prover] that runs proof workloads via Bonsai.
 ///
@@ -49,6 +52,10 @@ impl Prover for BonsaiProver {
         elf: &[u8],
         opts: &ProverOpts,
     ) -> Result<Receipt> {
+        ensure!(
+            opts.receipt_kind == ReceiptKind::Composite,
+            "Bonsai only produces composite receipts"
+        );
         let client = Client::from_env(crate::VERSION)?;
 
         // Compute the ImageID and upload the ELF binary
@@ -43,7 +43,9 @@ impl Prover for LocalProver {
         elf: &[u8],
         opts: &ProverOpts,
     ) -> Result<Receipt> {
-        get_prover_server(opts)?.prove_with_ctx(env, ctx, elf)
+        let prover = get_prover_server(opts)?;
+        let receipt = prover.prove_with_ctx(env, ctx, elf)?;
+        prover.compress_to_kind(receipt, opts.receipt_kind)
     }
 
     fn get_name(&self) -> String {
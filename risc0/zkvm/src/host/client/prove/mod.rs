@@ -20,6 +20,7 @@ pub(crate) mod local;
 use std::{path::PathBuf, rc::Rc};
 
 use anyhow::Result;
+use risc0_zkp::security::{FriParams, ZkParams};
 use serde::{Deserialize, Serialize};
 
 use self::{bonsai::BonsaiProver, external::ExternalProver};
@@ -93,6 +94,12 @@ pub trait Executor {
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ProverOpts {
     /// The hash function to use.
+    ///
+    /// The CPU prover also accepts the hash suites added to the
+    /// [registry](risc0_zkp::core::hash::registry). Proofs are checked against
+    /// the [VerifierContext], so prove with a context built with
+    /// [VerifierContext::with_registered_suites], whose parameters include the
+    /// control IDs of the rv32im circuit computed with such a suite.
     pub hashfn: String,
     /// When false, only prove execution sessions that end in a successful
     /// [crate::ExitCode] (i.e. `Halted(0)` or `Paused(0)`).
@@ -104,15 +111,66 @@ pub struct ProverOpts {
     // moment if there is a better place. At some point before 1.0, this option should be moved or
     // dropped.
     pub prove_guest_errors: bool,
+    /// Parameters of the zero-knowledge blinding of the trace. See [ZkParams]
+    /// for the privacy they provide. Local provers currently only support the
+    /// default parameters.
+    #[serde(default)]
+    pub zk: ZkParams,
+    /// The kind of receipt to produce.
+    #[serde(default)]
+    pub receipt_kind: ReceiptKind,
+}
+
+/// The kind of [Receipt] produced by a [Prover].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ReceiptKind {
+    /// A [crate::CompositeReceipt], with one receipt per segment.
+    #[default]
+    Composite,
+    /// A [crate::SuccinctReceipt], in which the segment receipts are compressed
+    /// into one with recursion.
+    Succinct,
+}
+
+impl ProverOpts {
+    /// Return [ProverOpts] for proving over secret inputs.
+    ///
+    /// The trace is blinded with the default [ZkParams], and the receipt is
+    /// compressed with recursion, using the Poseidon hash function, into a
+    /// [ReceiptKind::Succinct] receipt. Unlike a composite receipt, it does not
+    /// reveal the number of segments of the execution, nor their sizes, which
+    /// bound the number of cycles executed by the guest. The journal and the
+    /// image ID remain public, as does the [crate::ReceiptMetadata], which
+    /// includes the proving time.
+    pub fn max_privacy() -> Self {
+        Self {
+            hashfn: "poseidon".to_string(),
+            prove_guest_errors: false,
+            zk: ZkParams::default(),
+            receipt_kind: ReceiptKind::Succinct,
+        }
+    }
+
+    /// Returns the conjectured security level of the proofs produced with
+    /// these options, in bits. See [FriParams::conjectured_security_bits].
+    ///
+    /// Provers only support the default [FriParams] for now.
+    pub fn conjectured_security_bits(&self) -> u32 {
+        FriParams::default().conjectured_security_bits()
+    }
 }
 
 impl Default for ProverOpts {
-    /// Return [ProverOpts] with the Poseidon hash function and
-    /// `prove_guest_errors` set to false.
+    /// Return [ProverOpts] with the Poseidon hash function, the default
+    /// [ZkParams], `prove_guest_errors` set to false, and producing
+    /// [ReceiptKind::Composite] receipts.
     fn default() -> Self {
         Self {
             hashfn: "poseidon".to_string(),
             prove_guest_errors: false,
+            zk: ZkParams::default(),
+            receipt_kind: ReceiptKind::Composite,
         }
     }
 }
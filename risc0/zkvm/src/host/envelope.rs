@@ -0,0 +1,270 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encryption of receipts at rest.
+//!
+//! A journal can hold data that should not be readable by whoever stores the
+//! receipt, such as a database or an object store. An [EncryptedReceipt] holds
+//! a receipt, encoded with [Receipt::to_bytes], encrypted and authenticated
+//! with ChaCha20-Poly1305 under a key provided by the caller. Decrypting it
+//! fails if the key is wrong or the envelope was tampered with, and
+//! [EncryptedReceipt::decrypt_and_verify] verifies the receipt once decrypted.
+//!
+//! Key management is left to the caller. Keys should be generated randomly, or
+//! derived from other key material with a KDF, and never reused across trust
+//! domains.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+
+use super::receipt::{Receipt, ReceiptDecodeError, VerifierContext};
+use crate::serde::Error;
+
+// Length of the magic and the version.
+const HEADER_BYTES: usize = 8;
+
+// Length of a ChaCha20-Poly1305 nonce.
+const NONCE_BYTES: usize = 12;
+
+/// A [Receipt] encrypted with a caller-provided key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedReceipt {
+    nonce: [u8; NONCE_BYTES],
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedReceipt {
+    /// Magic bytes at the start of an encoded envelope.
+    pub const FORMAT_MAGIC: [u8; 4] = *b"R0RE";
+
+    /// Version of the envelope format written by this release.
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Encrypt a receipt with the given key.
+    ///
+    /// A random nonce is generated for every call, so a key can be used to
+    /// encrypt many receipts.
+    pub fn encrypt(receipt: &Receipt, key: &[u8; 32]) -> Result<Self, EnvelopeError> {
+        let plaintext = receipt.to_bytes().map_err(EnvelopeError::Encode)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher(key)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &Self::header(),
+                },
+            )
+            .map_err(|_| EnvelopeError::Encrypt)?;
+        Ok(Self {
+            nonce: nonce.as_slice().try_into().unwrap(),
+            ciphertext,
+        })
+    }
+
+    /// Decrypt the receipt with the given key.
+    ///
+    /// The receipt is not verified. Use [EncryptedReceipt::decrypt_and_verify]
+    /// to check that it proves an execution of the expected image.
+    pub fn decrypt(&self, key: &[u8; 32]) -> Result<Receipt, EnvelopeError> {
+        let plaintext = cipher(key)
+            .decrypt(
+                Nonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &Self::header(),
+                },
+            )
+            .map_err(|_| EnvelopeError::Decrypt)?;
+        Receipt::from_bytes(&plaintext).map_err(EnvelopeError::Decode)
+    }
+
+    /// Decrypt the receipt with the given key, and verify it with
+    /// [Receipt::verify].
+    pub fn decrypt_and_verify(
+        &self,
+        key: &[u8; 32],
+        image_id: impl Into<Digest>,
+    ) -> Result<Receipt, EnvelopeError> {
+        self.decrypt_and_verify_with_context(&VerifierContext::default(), key, image_id)
+    }
+
+    /// Decrypt the receipt with the given key, and verify it with
+    /// [Receipt::verify_with_context].
+    pub fn decrypt_and_verify_with_context(
+        &self,
+        ctx: &VerifierContext,
+        key: &[u8; 32],
+        image_id: impl Into<Digest>,
+    ) -> Result<Receipt, EnvelopeError> {
+        let receipt = self.decrypt(key)?;
+        receipt
+            .verify_with_context(ctx, image_id)
+            .map_err(EnvelopeError::Verify)?;
+        Ok(receipt)
+    }
+
+    /// Encode the envelope as bytes.
+    ///
+    /// The encoding is [EncryptedReceipt::FORMAT_MAGIC], the format version as
+    /// a little-endian `u32`, the nonce and the ciphertext. The magic and the
+    /// version are authenticated along with the ciphertext.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_BYTES + NONCE_BYTES + self.ciphertext.len());
+        bytes.extend_from_slice(&Self::header());
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Decode an envelope encoded with [EncryptedReceipt::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        if bytes.len() < HEADER_BYTES + NONCE_BYTES || bytes[..4] != Self::FORMAT_MAGIC {
+            return Err(EnvelopeError::BadMagic);
+        }
+        let version = u32::from_le_bytes(bytes[4..HEADER_BYTES].try_into().unwrap());
+        if version != Self::FORMAT_VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(version));
+        }
+        let (nonce, ciphertext) = bytes[HEADER_BYTES..].split_at(NONCE_BYTES);
+        Ok(Self {
+            nonce: nonce.try_into().unwrap(),
+            ciphertext: ciphertext.to_vec(),
+        })
+    }
+
+    fn header() -> [u8; HEADER_BYTES] {
+        let mut header = [0u8; HEADER_BYTES];
+        header[..4].copy_from_slice(&Self::FORMAT_MAGIC);
+        header[4..].copy_from_slice(&Self::FORMAT_VERSION.to_le_bytes());
+        header
+    }
+}
+
+fn cipher(key: &[u8; 32]) -> ChaCha20Poly1305 {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+}
+
+/// Error returned when encrypting or decrypting an [EncryptedReceipt].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EnvelopeError {
+    /// The bytes do not start with [EncryptedReceipt::FORMAT_MAGIC].
+    BadMagic,
+    /// The envelope was encoded with another version of the format.
+    UnsupportedVersion(u32),
+    /// The receipt could not be encoded.
+    Encode(Error),
+    /// The receipt could not be encrypted.
+    Encrypt,
+    /// The envelope could not be decrypted, because the key is wrong or the
+    /// envelope was tampered with.
+    Decrypt,
+    /// The decrypted receipt could not be decoded.
+    Decode(ReceiptDecodeError),
+    /// The decrypted receipt failed verification.
+    Verify(VerificationError),
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not an encrypted receipt"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported encrypted receipt version {version}")
+            }
+            Self::Encode(err) => write!(f, "failed to encode receipt: {err}"),
+            Self::Encrypt => write!(f, "failed to encrypt receipt"),
+            Self::Decrypt => write!(
+                f,
+                "failed to decrypt receipt: wrong key or tampered envelope"
+            ),
+            Self::Decode(err) => write!(f, "failed to decode decrypted receipt: {err}"),
+            Self::Verify(err) => write!(f, "decrypted receipt failed verification: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+
+    use super::{EncryptedReceipt, EnvelopeError};
+    use crate::{host::testutils::fake_receipt, ExitCode, VerifierContext};
+
+    const KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn round_trip() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        let envelope = EncryptedReceipt::encrypt(&receipt, &KEY).unwrap();
+        let envelope = EncryptedReceipt::from_bytes(&envelope.to_bytes()).unwrap();
+        assert_eq!(envelope.decrypt(&KEY).unwrap(), receipt);
+    }
+
+    #[test]
+    fn hides_journal() {
+        let mut receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        receipt.journal.bytes = b"secret journal".to_vec();
+        let bytes = EncryptedReceipt::encrypt(&receipt, &KEY)
+            .unwrap()
+            .to_bytes();
+        assert!(!bytes.windows(14).any(|window| window == b"secret journal"));
+    }
+
+    #[test]
+    fn wrong_key() {
+        let envelope =
+            EncryptedReceipt::encrypt(&fake_receipt(ExitCode::Halted(0), &[1, 2, 3]), &KEY)
+                .unwrap();
+        assert!(matches!(
+            envelope.decrypt(&[8; 32]),
+            Err(EnvelopeError::Decrypt)
+        ));
+    }
+
+    #[test]
+    fn tampered() {
+        let mut bytes =
+            EncryptedReceipt::encrypt(&fake_receipt(ExitCode::Halted(0), &[1, 2, 3]), &KEY)
+                .unwrap()
+                .to_bytes();
+        *bytes.last_mut().unwrap() ^= 1;
+        let envelope = EncryptedReceipt::from_bytes(&bytes).unwrap();
+        assert!(matches!(
+            envelope.decrypt(&KEY),
+            Err(EnvelopeError::Decrypt)
+        ));
+    }
+
+    #[test]
+    fn verifies() {
+        let envelope =
+            EncryptedReceipt::encrypt(&fake_receipt(ExitCode::Halted(0), &[1, 2, 3]), &KEY)
+                .unwrap();
+        let ctx = VerifierContext::default().with_strict(true);
+        assert!(matches!(
+            envelope.decrypt_and_verify_with_context(&ctx, &KEY, Digest::ZERO),
+            Err(EnvelopeError::Verify(VerificationError::FakeReceipt))
+        ));
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inspection of receipts, for debugging.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+use risc0_zkvm_platform::WORD_SIZE;
+
+use super::receipt::{InnerReceipt, Receipt, ReceiptMetadata};
+use crate::{sha::Digestible, ExitCode};
+
+/// Structured description of a [Receipt], returned by [Receipt::inspect].
+///
+/// The summary is decoded from the receipt without verifying it, so that
+/// receipts failing verification can be inspected too.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ReceiptSummary {
+    /// Kind of the inner receipt, e.g. `composite` or `succinct`.
+    pub kind: &'static str,
+    /// Digest of the [crate::ReceiptClaim].
+    pub claim_digest: Digest,
+    /// Image ID the execution started from.
+    pub image_id: Digest,
+    /// Digest of the post state of the execution.
+    pub post_state_digest: Digest,
+    /// Exit code of the execution.
+    pub exit_code: ExitCode,
+    /// Digest of the input of the execution.
+    pub input: Digest,
+    /// Digest of the journal attached to the receipt.
+    pub journal_digest: Digest,
+    /// Length of the journal attached to the receipt, in bytes.
+    pub journal_len: usize,
+    /// Digests of the unresolved assumptions in the claim, or `None` if they
+    /// are pruned from the claim.
+    pub assumptions: Option<Vec<Digest>>,
+    /// Claim digests of the assumption receipts attached to a composite
+    /// receipt.
+    pub attached_assumptions: Vec<Digest>,
+    /// Number of segment receipts, for a composite receipt.
+    pub segments: usize,
+    /// Total size of the seals, in bytes.
+    pub seal_size: usize,
+    /// Metadata attached by the prover.
+    pub metadata: ReceiptMetadata,
+}
+
+/// A field which differs between two [ReceiptSummary].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptDiff {
+    /// Name of the field.
+    pub field: &'static str,
+    /// Value of the field in the first summary.
+    pub left: String,
+    /// Value of the field in the second summary.
+    pub right: String,
+}
+
+impl Receipt {
+    /// Describe this receipt, for debugging.
+    ///
+    /// Fails if the claim cannot be decoded from the receipt, e.g. for a
+    /// composite receipt without segments.
+    pub fn inspect(&self) -> Result<ReceiptSummary, VerificationError> {
+        let claim = self.inner.get_claim()?;
+        let assumptions = match claim.output.as_value() {
+            Ok(Some(output)) => output
+                .assumptions
+                .as_value()
+                .ok()
+                .map(|assumptions| assumptions.iter().map(|x| x.digest()).collect()),
+            Ok(None) => Some(Vec::new()),
+            Err(_) => None,
+        };
+        let (segments, attached_assumptions) = match &self.inner {
+            InnerReceipt::Composite(receipt) => (
+                receipt.segments.len(),
+                receipt
+                    .assumptions
+                    .iter()
+                    .map(|x| Ok(x.get_claim()?.digest()))
+                    .collect::<Result<_, VerificationError>>()?,
+            ),
+            _ => (0, Vec::new()),
+        };
+        Ok(ReceiptSummary {
+            kind: kind_name(&self.inner),
+            claim_digest: claim.digest(),
+            image_id: claim.pre.digest(),
+            post_state_digest: claim.post.digest(),
+            exit_code: claim.exit_code,
+            input: claim.input,
+            journal_digest: self.journal.digest(),
+            journal_len: self.journal.bytes.len(),
+            assumptions,
+            attached_assumptions,
+            segments,
+            seal_size: seal_size(&self.inner),
+            metadata: self.metadata().clone(),
+        })
+    }
+}
+
+impl ReceiptSummary {
+    /// Returns the fields which differ between this summary and `other`.
+    ///
+    /// Comparing the summaries of receipts produced by two provers for the
+    /// same execution shows where they disagree.
+    pub fn diff(&self, other: &Self) -> Vec<ReceiptDiff> {
+        let mut diffs = Vec::new();
+        let mut cmp = |field, left: String, right: String| {
+            if left != right {
+                diffs.push(ReceiptDiff { field, left, right });
+            }
+        };
+        cmp("kind", self.kind.into(), other.kind.into());
+        cmp(
+            "claim_digest",
+            format!("{}", self.claim_digest),
+            format!("{}", other.claim_digest),
+        );
+        cmp(
+            "image_id",
+            format!("{}", self.image_id),
+            format!("{}", other.image_id),
+        );
+        cmp(
+            "post_state_digest",
+            format!("{}", self.post_state_digest),
+            format!("{}", other.post_state_digest),
+        );
+        cmp(
+            "exit_code",
+            format!("{:?}", self.exit_code),
+            format!("{:?}", other.exit_code),
+        );
+        cmp(
+            "input",
+            format!("{}", self.input),
+            format!("{}", other.input),
+        );
+        cmp(
+            "journal_digest",
+            format!("{}", self.journal_digest),
+            format!("{}", other.journal_digest),
+        );
+        cmp(
+            "journal_len",
+            format!("{}", self.journal_len),
+            format!("{}", other.journal_len),
+        );
+        cmp(
+            "assumptions",
+            format!("{:?}", self.assumptions),
+            format!("{:?}", other.assumptions),
+        );
+        cmp(
+            "attached_assumptions",
+            format!("{:?}", self.attached_assumptions),
+            format!("{:?}", other.attached_assumptions),
+        );
+        cmp(
+            "segments",
+            format!("{}", self.segments),
+            format!("{}", other.segments),
+        );
+        cmp(
+            "seal_size",
+            format!("{}", self.seal_size),
+            format!("{}", other.seal_size),
+        );
+        cmp(
+            "metadata",
+            format!("{:?}", self.metadata),
+            format!("{:?}", other.metadata),
+        );
+        diffs
+    }
+}
+
+impl fmt::Display for ReceiptSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "kind: {}", self.kind)?;
+        writeln!(f, "claim digest: {}", self.claim_digest)?;
+        writeln!(f, "image id: {}", self.image_id)?;
+        writeln!(f, "post state digest: {}", self.post_state_digest)?;
+        writeln!(f, "exit code: {:?}", self.exit_code)?;
+        writeln!(f, "input: {}", self.input)?;
+        writeln!(
+            f,
+            "journal: {} bytes, digest {}",
+            self.journal_len, self.journal_digest
+        )?;
+        match &self.assumptions {
+            Some(assumptions) => writeln!(f, "assumptions: {assumptions:?}")?,
+            None => writeln!(f, "assumptions: pruned")?,
+        }
+        writeln!(f, "attached assumptions: {:?}", self.attached_assumptions)?;
+        writeln!(f, "segments: {}", self.segments)?;
+        writeln!(f, "seal size: {} bytes", self.seal_size)?;
+        write!(f, "metadata: {:?}", self.metadata)
+    }
+}
+
+impl fmt::Display for ReceiptDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} != {}", self.field, self.left, self.right)
+    }
+}
+
+fn kind_name(inner: &InnerReceipt) -> &'static str {
+    match inner {
+        InnerReceipt::Composite(_) => "composite",
+        InnerReceipt::Succinct(_) => "succinct",
+        InnerReceipt::Compact(_) => "compact",
+        InnerReceipt::Fake { .. } => "fake",
+    }
+}
+
+fn seal_size(inner: &InnerReceipt) -> usize {
+    match inner {
+        InnerReceipt::Composite(receipt) => {
+            let segments: usize = receipt
+                .segments
+                .iter()
+                .map(|segment| segment.seal.len() * WORD_SIZE)
+                .sum();
+            let assumptions: usize = receipt.assumptions.iter().map(seal_size).sum();
+            segments + assumptions
+        }
+        InnerReceipt::Succinct(receipt) => receipt.seal.len() * WORD_SIZE,
+        InnerReceipt::Compact(receipt) => receipt.seal.len(),
+        InnerReceipt::Fake { .. } => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{host::testutils::fake_receipt, ExitCode, InnerReceipt};
+
+    #[test]
+    fn inspect() {
+        let summary = fake_receipt(ExitCode::Halted(0), b"journal")
+            .inspect()
+            .unwrap();
+        assert_eq!(summary.kind, "fake");
+        assert_eq!(summary.exit_code, ExitCode::Halted(0));
+        assert_eq!(summary.journal_len, 7);
+        assert_eq!(summary.assumptions, Some(vec![]));
+        assert_eq!(summary.seal_size, 0);
+    }
+
+    #[test]
+    fn diff() {
+        let left = fake_receipt(ExitCode::Halted(0), b"journal")
+            .inspect()
+            .unwrap();
+        assert!(left.diff(&left).is_empty());
+
+        let right = fake_receipt(ExitCode::Halted(1), b"journal")
+            .inspect()
+            .unwrap();
+        let fields: Vec<_> = left.diff(&right).iter().map(|diff| diff.field).collect();
+        assert_eq!(fields, vec!["claim_digest", "exit_code"]);
+    }
+
+    #[test]
+    fn pruned_assumptions() {
+        let mut receipt = fake_receipt(ExitCode::Halted(0), b"journal");
+        let InnerReceipt::Fake { claim } = &mut receipt.inner else {
+            unreachable!()
+        };
+        claim.output = claim.output.prune();
+        assert_eq!(receipt.inspect().unwrap().assumptions, None);
+    }
+}
@@ -17,10 +17,18 @@ pub(crate) mod api;
 #[cfg(feature = "client")]
 pub(crate) mod client;
 pub(crate) mod control_id;
+#[cfg(feature = "envelope")]
+pub(crate) mod envelope;
+pub(crate) mod inspect;
 pub(crate) mod receipt;
 pub(crate) mod recursion;
 #[cfg(feature = "prove")]
 pub(crate) mod server;
+#[cfg(feature = "signed")]
+pub(crate) mod signed;
+#[cfg(test)]
+pub(crate) mod testutils;
+pub(crate) mod versions;
 
 #[cfg(any(feature = "client", feature = "prove"))]
 mod protos {
@@ -14,15 +14,15 @@
 
 //! Manages the output and cryptographic data for a proven computation.
 
-use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
-use core::fmt::Debug;
+use alloc::{borrow::Cow, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::fmt::{self, Debug};
 
 use anyhow::Result;
 use risc0_binfmt::SystemState;
 use risc0_circuit_recursion::control_id::ALLOWED_IDS_ROOT;
 use risc0_circuit_rv32im::layout;
 use risc0_core::field::baby_bear::BabyBear;
-use risc0_groth16::{split_digest, verifier::prepared_verifying_key, Seal, Verifier};
+use risc0_groth16::{split_digest, verifier::prepared_verifying_key, Seal, Verifier, VerifyingKey};
 use risc0_zkp::{
     core::{
         digest::Digest,
@@ -39,9 +39,10 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::control_id::{BLAKE2B_CONTROL_ID, POSEIDON_CONTROL_ID, SHA256_CONTROL_ID};
 // Make succinct receipt available through this `receipt` module.
+use super::recursion::valid_control_ids;
 pub use super::recursion::SuccinctReceipt;
 use crate::{
-    serde::{from_slice, Error},
+    serde::{from_slice, to_vec, Error},
     sha::{Digestible, Sha256},
     Assumptions, ExitCode, MaybePruned, Output, ReceiptClaim,
 };
@@ -113,6 +114,12 @@ pub struct Receipt {
     /// This data is cryptographically authenticated in
     /// [Receipt::verify].
     pub journal: Journal,
+
+    /// Information about how the receipt was produced.
+    ///
+    /// The metadata is not part of the claim, and is not authenticated.
+    #[serde(default)]
+    metadata: ReceiptMetadata,
 }
 
 impl Receipt {
@@ -121,9 +128,24 @@ impl Receipt {
         Self {
             inner,
             journal: Journal::new(journal),
+            metadata: ReceiptMetadata::default(),
         }
     }
 
+    /// Attach the given [ReceiptMetadata] to this receipt.
+    pub fn with_metadata(mut self, metadata: ReceiptMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Information about how the receipt was produced.
+    ///
+    /// The metadata is not authenticated by verification, see
+    /// [ReceiptMetadata].
+    pub fn metadata(&self) -> &ReceiptMetadata {
+        &self.metadata
+    }
+
     /// Verify that this receipt proves a successful execution of the zkVM from
     /// the given `image_id`.
     ///
@@ -136,6 +158,16 @@ impl Receipt {
         self.verify_with_context(&VerifierContext::default(), image_id)
     }
 
+    /// Verify that this receipt proves a successful execution of the zkVM from
+    /// the given `image_id`, rejecting fake receipts.
+    ///
+    /// This is [Receipt::verify] with [VerifierContext::strict] set: receipts
+    /// produced in dev mode fail with [VerificationError::FakeReceipt], whether
+    /// or not `RISC0_DEV_MODE` is set in the verifying process.
+    pub fn verify_strict(&self, image_id: impl Into<Digest>) -> Result<(), VerificationError> {
+        self.verify_with_context(&VerifierContext::default().with_strict(true), image_id)
+    }
+
     /// Verify that this receipt proves a successful execution of the zkVM from
     /// the given `image_id`.
     ///
@@ -144,48 +176,23 @@ impl Receipt {
     /// guest exited with a successful status code (e.g. `Halted(0)` or
     /// `Paused(0)`), the image ID is as expected, and the journal
     /// has not been tampered with.
+    ///
+    /// The receipt is verified with the [VerifierParameters] of the release
+    /// that produced it, as selected by [VerifierContext::for_metadata].
     pub fn verify_with_context(
         &self,
         ctx: &VerifierContext,
         image_id: impl Into<Digest>,
     ) -> Result<(), VerificationError> {
-        self.inner.verify_integrity_with_context(ctx)?;
-
-        // NOTE: Post-state digest and input digest are unconstrained by this method.
-        let claim = self.inner.get_claim()?;
-        if claim.pre.digest() != image_id.into() {
-            return Err(VerificationError::ImageVerificationError);
-        }
-
-        // Check the exit code. This verification method requires execution to be
-        // successful.
-        if !claim.exit_code.is_ok() {
-            return Err(VerificationError::UnexpectedExitCode);
-        };
-
-        // Finally check the output hash in the decoded claim against the expected
-        // output.
-        let expected_output = Output {
-            journal: MaybePruned::Pruned(self.journal.digest()),
-            // It is expected that there are no (unresolved) assumptions.
-            assumptions: Assumptions(vec![]).into(),
-        };
-
-        if claim.output.digest() != expected_output.digest() {
-            let empty_output = claim.output.is_none() && self.journal.bytes.is_empty();
-            if !empty_output {
-                tracing::debug!(
-                    "journal: 0x{}, expected output digest: 0x{}, decoded output digest: 0x{}",
-                    hex::encode(&self.journal.bytes),
-                    hex::encode(expected_output.digest()),
-                    hex::encode(claim.output.digest()),
-                );
-                return Err(VerificationError::JournalDigestMismatch);
-            }
-            tracing::debug!("accepting zero digest for output of receipt with empty journal");
-        }
-
-        Ok(())
+        let ctx = ctx.for_metadata(&self.metadata);
+        self.inner
+            .verify_with_journal_digest_with_context(&ctx, image_id, self.journal.digest())
+            .map_err(|err| {
+                if err == VerificationError::JournalDigestMismatch {
+                    tracing::debug!("journal: 0x{}", hex::encode(&self.journal.bytes));
+                }
+                err
+            })
     }
 
     /// Verify the integrity of this receipt, ensuring the claim and jounral
@@ -203,7 +210,8 @@ impl Receipt {
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
-        self.inner.verify_integrity_with_context(ctx)?;
+        self.inner
+            .verify_integrity_with_context(&ctx.for_metadata(&self.metadata))?;
 
         // Check that self.journal is attested to by the inner receipt.
         let claim = self.inner.get_claim()?;
@@ -238,12 +246,244 @@ impl Receipt {
     pub fn get_claim(&self) -> Result<ReceiptClaim, VerificationError> {
         self.inner.get_claim()
     }
+
+    /// Encode the calldata of the call to `verify(bytes seal, bytes32 imageId, bytes32
+    /// postStateDigest, bytes32 journalDigest)` on the generated `RiscZeroGroth16Verifier`
+    /// contract, checking this receipt and its journal.
+    ///
+    /// Only a [CompactReceipt] can be verified on chain, and the contract rebuilds the claim of a
+    /// successful execution, so the receipt must have exited with `Halted(0)`, with no input and
+    /// no assumptions.
+    pub fn verify_calldata(&self) -> Result<Vec<u8>, VerificationError> {
+        let compact = self.inner.compact()?;
+        verify_calldata(&compact.seal, &compact.claim, self.journal.digest())
+    }
+
+    /// Magic bytes at the start of an encoded receipt.
+    pub const FORMAT_MAGIC: [u8; 4] = *b"R0RC";
+
+    /// Version of the encoding produced by [Receipt::to_bytes].
+    ///
+    /// The version changes whenever the layout of an encoded receipt changes,
+    /// so that a receipt encoded by another release is rejected with a
+    /// [ReceiptDecodeError::UnsupportedVersion] instead of being misread.
+    ///
+    /// Version 2 added the [ReceiptMetadata]. Receipts encoded with version 1
+    /// are still decoded, with default metadata.
+    pub const FORMAT_VERSION: u32 = 2;
+
+    /// Encode this receipt in the versioned wire format.
+    ///
+    /// The encoding is the [Receipt::FORMAT_MAGIC], the
+    /// [Receipt::FORMAT_VERSION] and the kind of the [InnerReceipt], each as a
+    /// little-endian `u32`, followed by the receipt serialized with the
+    /// [risc0 serializer](crate::serde). Unlike an encoding with a general
+    /// purpose format such as bincode, a receipt in this format can be
+    /// identified, and checked for compatibility before it is decoded.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let body = to_vec(self)?;
+        let mut bytes = Vec::with_capacity(FORMAT_HEADER_BYTES + body.len() * WORD_SIZE);
+        bytes.extend_from_slice(&Self::FORMAT_MAGIC);
+        bytes.extend_from_slice(&Self::FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.inner.kind().to_le_bytes());
+        for word in body {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        Ok(bytes)
+    }
+
+    /// Decode a receipt encoded with [Receipt::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReceiptDecodeError> {
+        if bytes.len() < FORMAT_HEADER_BYTES || bytes[..4] != Self::FORMAT_MAGIC {
+            return Err(ReceiptDecodeError::BadMagic);
+        }
+        let word = |idx: usize| u32::from_le_bytes(bytes[idx * 4..][..4].try_into().unwrap());
+        let version = word(1);
+        if !(1..=Self::FORMAT_VERSION).contains(&version) {
+            return Err(ReceiptDecodeError::UnsupportedVersion(version));
+        }
+        let kind = word(2);
+        if !(InnerReceipt::KIND_COMPOSITE..=InnerReceipt::KIND_FAKE).contains(&kind) {
+            return Err(ReceiptDecodeError::UnknownKind(kind));
+        }
+
+        let body = &bytes[FORMAT_HEADER_BYTES..];
+        if body.len() % WORD_SIZE != 0 {
+            return Err(Error::DeserializeUnexpectedEnd.into());
+        }
+        let body: Vec<u32> = body
+            .chunks_exact(WORD_SIZE)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        let receipt: Self = match version {
+            1 => from_slice::<ReceiptV1, _>(&body)?.into(),
+            _ => from_slice(&body)?,
+        };
+        if receipt.inner.kind() != kind {
+            return Err(ReceiptDecodeError::KindMismatch {
+                header: kind,
+                body: receipt.inner.kind(),
+            });
+        }
+        Ok(receipt)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Receipt {
+    /// Encode this receipt as JSON.
+    ///
+    /// Byte fields, such as the journal and the seal, are encoded as hex
+    /// strings, so that the receipt can be consumed by services written in
+    /// other languages, or stored in document databases.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Decode a receipt encoded with [Receipt::to_json].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl Receipt {
+    /// Encode this receipt as CBOR.
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Decode a receipt encoded with [Receipt::to_cbor].
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        Ok(ciborium::from_reader(bytes)?)
+    }
+}
+
+// Length of the magic, version and kind at the start of an encoded receipt.
+const FORMAT_HEADER_BYTES: usize = 12;
+
+// Layout of a receipt encoded with version 1 of the format, before the
+// metadata was added.
+#[derive(Deserialize)]
+struct ReceiptV1 {
+    inner: InnerReceipt,
+    journal: Journal,
+}
+
+impl From<ReceiptV1> for Receipt {
+    fn from(value: ReceiptV1) -> Self {
+        Self {
+            inner: value.inner,
+            journal: value.journal,
+            metadata: ReceiptMetadata::default(),
+        }
+    }
+}
+
+/// Error returned by [Receipt::from_bytes].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReceiptDecodeError {
+    /// The bytes do not start with [Receipt::FORMAT_MAGIC], so they are not
+    /// an encoded receipt.
+    BadMagic,
+    /// The receipt was encoded with another version of the format.
+    UnsupportedVersion(u32),
+    /// The kind of the inner receipt is not known to this release.
+    UnknownKind(u32),
+    /// The kind in the header does not match the decoded receipt.
+    KindMismatch {
+        /// The kind in the header.
+        header: u32,
+        /// The kind of the decoded receipt.
+        body: u32,
+    },
+    /// The receipt could not be deserialized.
+    Deserialize(Error),
+}
+
+impl From<Error> for ReceiptDecodeError {
+    fn from(err: Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+impl fmt::Display for ReceiptDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(
+                f,
+                "not an encoded receipt: the data does not start with the receipt format magic"
+            ),
+            Self::UnsupportedVersion(version) if *version > Receipt::FORMAT_VERSION => write!(
+                f,
+                "receipt format version {version} is newer than the supported version {}: \
+                upgrade risc0-zkvm to decode this receipt",
+                Receipt::FORMAT_VERSION
+            ),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "receipt format version {version} is older than the supported version {}: \
+                decode this receipt with the release that encoded it, and re-encode it",
+                Receipt::FORMAT_VERSION
+            ),
+            Self::UnknownKind(kind) => write!(
+                f,
+                "unknown inner receipt kind {kind}: upgrade risc0-zkvm to decode this receipt"
+            ),
+            Self::KindMismatch { header, body } => write!(
+                f,
+                "corrupted receipt: header has inner receipt kind {header}, but body has kind {body}"
+            ),
+            Self::Deserialize(err) => write!(f, "failed to deserialize receipt: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReceiptDecodeError {}
+
+/// Information about how a [Receipt] was produced, for auditing and debugging.
+///
+/// The metadata is provided by the prover alongside the receipt. It is not part
+/// of the [ReceiptClaim], and is not checked by verification, so it must not be
+/// trusted: it only describes the receipt when it comes from a trusted prover.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[non_exhaustive]
+pub struct ReceiptMetadata {
+    /// Version of the zkVM that produced the receipt.
+    ///
+    /// The circuits are released along with the zkVM, with the same version.
+    pub zkvm_version: Option<String>,
+
+    /// Name of the hash suite used for the seal, e.g. `poseidon`.
+    pub hash_suite: Option<String>,
+
+    /// Name of the prover, which identifies its hardware, e.g. `cpu` or `cuda`.
+    pub prover: Option<String>,
+
+    /// Time spent proving, in milliseconds.
+    pub proving_time_ms: Option<u64>,
+}
+
+impl ReceiptMetadata {
+    /// Metadata of a receipt produced by the given prover of this release.
+    pub fn new(prover: &str) -> Self {
+        Self {
+            zkvm_version: Some(crate::VERSION.into()),
+            prover: Some(prover.into()),
+            ..Default::default()
+        }
+    }
 }
 
 /// A journal is a record of all public commitments for a given proof session.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct Journal {
     /// The raw bytes of the journal.
+    #[serde(with = "crate::serde::hex_bytes")]
     pub bytes: Vec<u8>,
 }
 
@@ -302,6 +542,98 @@ pub enum InnerReceipt {
 }
 
 impl InnerReceipt {
+    /// Kind of an [InnerReceipt::Composite] in an encoded receipt.
+    pub const KIND_COMPOSITE: u32 = 1;
+    /// Kind of an [InnerReceipt::Succinct] in an encoded receipt.
+    pub const KIND_SUCCINCT: u32 = 2;
+    /// Kind of an [InnerReceipt::Compact] in an encoded receipt.
+    pub const KIND_COMPACT: u32 = 3;
+    /// Kind of an [InnerReceipt::Fake] in an encoded receipt.
+    pub const KIND_FAKE: u32 = 4;
+
+    /// Returns the kind of this receipt, as recorded by [Receipt::to_bytes].
+    pub fn kind(&self) -> u32 {
+        match self {
+            InnerReceipt::Composite(_) => Self::KIND_COMPOSITE,
+            InnerReceipt::Succinct(_) => Self::KIND_SUCCINCT,
+            InnerReceipt::Compact(_) => Self::KIND_COMPACT,
+            InnerReceipt::Fake { .. } => Self::KIND_FAKE,
+        }
+    }
+
+    /// Verify that this receipt proves a successful execution of the zkVM from
+    /// the given `image_id`, which committed a journal with the given digest.
+    ///
+    /// This supports receipts whose journal is stored separately, e.g. in a
+    /// content-addressed store. It checks the seal, the image ID, that the
+    /// guest exited successfully, and that the proven journal digest equals
+    /// `journal_digest`. It does not check any journal bytes: the caller must
+    /// obtain `journal_digest` by hashing the journal it will use, with
+    /// [crate::sha::Impl], rather than trusting a digest supplied along with
+    /// the receipt.
+    pub fn verify_with_journal_digest(
+        &self,
+        image_id: impl Into<Digest>,
+        journal_digest: impl Into<Digest>,
+    ) -> Result<(), VerificationError> {
+        self.verify_with_journal_digest_with_context(
+            &VerifierContext::default(),
+            image_id,
+            journal_digest,
+        )
+    }
+
+    /// Verify that this receipt proves a successful execution of the zkVM from
+    /// the given `image_id`, which committed a journal with the given digest.
+    ///
+    /// See [InnerReceipt::verify_with_journal_digest].
+    pub fn verify_with_journal_digest_with_context(
+        &self,
+        ctx: &VerifierContext,
+        image_id: impl Into<Digest>,
+        journal_digest: impl Into<Digest>,
+    ) -> Result<(), VerificationError> {
+        self.verify_integrity_with_context(ctx)?;
+
+        // NOTE: Post-state digest and input digest are unconstrained by this method.
+        let claim = self.get_claim()?;
+        if claim.pre.digest() != image_id.into() {
+            return Err(VerificationError::ImageVerificationError);
+        }
+
+        // Check the exit code. This verification method requires execution to be
+        // successful.
+        if !claim.exit_code.is_ok() {
+            return Err(VerificationError::UnexpectedExitCode);
+        };
+
+        // Finally check the output hash in the decoded claim against the expected
+        // output.
+        let journal_digest = journal_digest.into();
+        let expected_output = Output {
+            journal: MaybePruned::Pruned(journal_digest),
+            // It is expected that there are no (unresolved) assumptions.
+            assumptions: Assumptions(vec![]).into(),
+        };
+
+        if claim.output.digest() != expected_output.digest() {
+            let empty_output =
+                claim.output.is_none() && journal_digest == Vec::<u8>::new().digest();
+            if !empty_output {
+                tracing::debug!(
+                    "journal digest: 0x{}, expected output digest: 0x{}, decoded output digest: 0x{}",
+                    hex::encode(journal_digest),
+                    hex::encode(expected_output.digest()),
+                    hex::encode(claim.output.digest()),
+                );
+                return Err(VerificationError::JournalDigestMismatch);
+            }
+            tracing::debug!("accepting zero digest for output of receipt with empty journal");
+        }
+
+        Ok(())
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity_with_context(
@@ -310,9 +642,12 @@ impl InnerReceipt {
     ) -> Result<(), VerificationError> {
         match self {
             InnerReceipt::Composite(x) => x.verify_integrity_with_context(ctx),
-            InnerReceipt::Compact(x) => x.verify_integrity(),
+            InnerReceipt::Compact(x) => x.verify_integrity_with_context(ctx),
             InnerReceipt::Succinct(x) => x.verify_integrity_with_context(ctx),
             InnerReceipt::Fake { .. } => {
+                if ctx.strict {
+                    return Err(VerificationError::FakeReceipt);
+                }
                 #[cfg(feature = "std")]
                 if crate::is_dev_mode() {
                     return Ok(());
@@ -365,6 +700,7 @@ impl InnerReceipt {
 #[cfg_attr(test, derive(PartialEq))]
 pub struct CompactReceipt {
     /// A Groth16 proof of a zkVM execution with the associated claim.
+    #[serde(with = "crate::serde::hex_bytes")]
     pub seal: Vec<u8>,
 
     /// [ReceiptClaim] containing information about the execution that this
@@ -376,25 +712,121 @@ impl CompactReceipt {
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity(&self) -> Result<(), VerificationError> {
-        use hex::FromHex;
-        let (a0, a1) = split_digest(
-            Digest::from_hex(ALLOWED_IDS_ROOT).map_err(|_| VerificationError::InvalidProof)?,
-        )
-        .map_err(|_| VerificationError::InvalidProof)?;
+        self.verify_integrity_with_context(&VerifierContext::default())
+    }
+
+    /// Verify the integrity of this receipt, ensuring the claim is attested
+    /// to by the seal, using the Groth16 verifying key from the given context.
+    pub fn verify_integrity_with_context(
+        &self,
+        ctx: &VerifierContext,
+    ) -> Result<(), VerificationError> {
+        self.verifier(ctx)?
+            .verify()
+            .map_err(|_| VerificationError::InvalidProof)?;
+
+        // Everything passed
+        Ok(())
+    }
+
+    /// Verify the integrity of a batch of receipts, ensuring each claim is
+    /// attested to by its seal.
+    ///
+    /// The Groth16 proofs are checked together with a single randomized
+    /// pairing check, which is considerably cheaper than verifying each
+    /// receipt on its own. On failure, the receipts must be verified
+    /// individually to find out which of them are invalid.
+    pub fn verify_integrity_batch(
+        receipts: &[CompactReceipt],
+        ctx: &VerifierContext,
+    ) -> Result<(), VerificationError> {
+        let verifiers = receipts
+            .iter()
+            .map(|receipt| receipt.verifier(ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+        Verifier::verify_batch(&verifiers).map_err(|_| VerificationError::InvalidProof)?;
+
+        // Everything passed
+        Ok(())
+    }
+
+    /// Encode the calldata of the call to `verify(bytes seal, bytes32 imageId, bytes32
+    /// postStateDigest, bytes32 journalDigest)` on the generated `RiscZeroGroth16Verifier`
+    /// contract, checking this receipt.
+    ///
+    /// The journal digest is taken from the claim, which must not be pruned. See
+    /// [Receipt::verify_calldata] for the claims that can be verified on chain.
+    pub fn verify_calldata(&self) -> Result<Vec<u8>, VerificationError> {
+        let journal_digest = match &self.claim.output {
+            MaybePruned::Value(Some(output)) => output.journal.digest(),
+            _ => return Err(VerificationError::ReceiptFormatError),
+        };
+        verify_calldata(&self.seal, &self.claim, journal_digest)
+    }
+
+    fn verifier(&self, ctx: &VerifierContext) -> Result<Verifier, VerificationError> {
+        let (a0, a1) = split_digest(ctx.parameters.allowed_ids_root)
+            .map_err(|_| VerificationError::InvalidProof)?;
         let (c0, c1) =
             split_digest(self.claim.digest()).map_err(|_| VerificationError::InvalidProof)?;
+        let prepared_verifying_key = match ctx.parameters.groth16_verifying_key {
+            Some(ref verifying_key) => {
+                tracing::warn!(
+                    "verifying Groth16 seal with a key other than the RISC Zero release key"
+                );
+                verifying_key.prepared()
+            }
+            None => prepared_verifying_key().map_err(|_| VerificationError::InvalidProof)?,
+        };
         Verifier::new(
             &Seal::from_vec(&self.seal).map_err(|_| VerificationError::InvalidProof)?,
             vec![a0, a1, c0, c1],
-            prepared_verifying_key().map_err(|_| VerificationError::InvalidProof)?,
+            prepared_verifying_key,
         )
-        .map_err(|_| VerificationError::InvalidProof)?
-        .verify()
-        .map_err(|_| VerificationError::InvalidProof)?;
+        .map_err(|_| VerificationError::InvalidProof)
+    }
+}
 
-        // Everything passed
-        Ok(())
+// Selector of `verify(bytes,bytes32,bytes32,bytes32)`.
+const VERIFY_SELECTOR: [u8; 4] = [0x6e, 0xfe, 0xf0, 0x09];
+
+// ABI encodes the call to `verify`, after checking that the contract will rebuild the same claim
+// from the arguments.
+fn verify_calldata(
+    seal: &[u8],
+    claim: &ReceiptClaim,
+    journal_digest: Digest,
+) -> Result<Vec<u8>, VerificationError> {
+    if claim.exit_code != ExitCode::Halted(0) {
+        return Err(VerificationError::UnexpectedExitCode);
     }
+    if claim.input != Digest::ZERO {
+        return Err(VerificationError::ReceiptFormatError);
+    }
+    let expected_output = Output {
+        journal: MaybePruned::Pruned(journal_digest),
+        assumptions: Assumptions(vec![]).into(),
+    };
+    if claim.output.digest() != Some(expected_output).digest() {
+        return Err(VerificationError::JournalDigestMismatch);
+    }
+
+    let abi_word = |value: usize| {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+        word
+    };
+    let mut calldata = VERIFY_SELECTOR.to_vec();
+    // Head: the offset of the seal, followed by the static arguments.
+    calldata.extend_from_slice(&abi_word(4 * 32));
+    calldata.extend_from_slice(claim.pre.digest().as_bytes());
+    calldata.extend_from_slice(claim.post.digest().as_bytes());
+    calldata.extend_from_slice(journal_digest.as_bytes());
+    // Tail: the length of the seal, followed by the seal padded to a multiple of 32 bytes.
+    calldata.extend_from_slice(&abi_word(seal.len()));
+    calldata.extend_from_slice(seal);
+    calldata.resize(calldata.len() + (32 - seal.len() % 32) % 32, 0);
+    Ok(calldata)
 }
 
 /// A receipt composed of one or more [SegmentReceipt] structs proving a single
@@ -430,54 +862,86 @@ impl CompositeReceipt {
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
         // Verify the continuation, by verifying every segment receipt in order.
-        let (final_receipt, receipts) = self
+        let final_receipt = self
             .segments
-            .as_slice()
-            .split_last()
+            .last()
             .ok_or(VerificationError::ReceiptFormatError)?;
+        let mut chain = SegmentChain::default();
+        for (idx, receipt) in self.segments.iter().enumerate() {
+            chain.verify_segment(ctx, receipt, idx + 1 == self.segments.len())?;
+        }
 
-        // Verify each segment and its chaining to the next.
-        let mut expected_pre_state_digest = None;
-        for receipt in receipts {
-            receipt.verify_integrity_with_context(ctx)?;
-            tracing::debug!("claim: {:#?}", receipt.claim);
-            if let Some(id) = expected_pre_state_digest {
-                if id != receipt.claim.pre.digest() {
-                    return Err(VerificationError::ImageVerificationError);
-                }
-            }
-            if receipt.claim.exit_code != ExitCode::SystemSplit {
-                return Err(VerificationError::UnexpectedExitCode);
-            }
-            if !receipt.claim.output.is_none() {
-                return Err(VerificationError::ReceiptFormatError);
-            }
-            expected_pre_state_digest = Some({
-                // Post state PC is stored as the "actual" value plus 4. This matches the join
-                // predicate implementation. See [ReceiptClaim] for more detail.
-                let mut post = receipt
-                    .claim
-                    .post
-                    .as_value()
-                    .map_err(|_| VerificationError::ReceiptFormatError)?
-                    .clone();
-                post.pc = post
-                    .pc
-                    .checked_sub(WORD_SIZE as u32)
-                    .ok_or(VerificationError::ReceiptFormatError)?;
-                post.digest()
-            });
+        self.verify_assumptions(ctx, &final_receipt.claim)
+    }
+
+    /// Write this receipt in the format read by
+    /// [CompositeReceipt::verify_integrity_from_reader].
+    ///
+    /// The format is the number of segments, followed by each segment receipt,
+    /// followed by the assumptions and the journal digest. Each item is
+    /// encoded with the [risc0 serializer](crate::serde), prefixed with its
+    /// length in words, and all words are little-endian.
+    #[cfg(feature = "std")]
+    pub fn write_stream(&self, mut writer: impl std::io::Write) -> Result<()> {
+        let count: u32 = self.segments.len().try_into()?;
+        writer.write_all(&count.to_le_bytes())?;
+        for segment in self.segments.iter() {
+            write_stream_item(&mut writer, segment)?;
         }
+        write_stream_item(&mut writer, &(&self.assumptions, &self.journal_digest))
+    }
 
-        // Verify the last receipt in the continuation.
-        final_receipt.verify_integrity_with_context(ctx)?;
-        tracing::debug!("final: {:#?}", final_receipt.claim);
-        if let Some(id) = expected_pre_state_digest {
-            if id != final_receipt.claim.pre.digest() {
-                return Err(VerificationError::ImageVerificationError);
+    /// Verify the integrity of a composite receipt written with
+    /// [CompositeReceipt::write_stream], reading one segment receipt at a time.
+    ///
+    /// Only the first and the last segment receipts are kept in memory, so
+    /// that receipts for very long executions can be checked without loading
+    /// them entirely. Returns the [ReceiptClaim] of the receipt.
+    #[cfg(feature = "std")]
+    pub fn verify_integrity_from_reader(
+        ctx: &VerifierContext,
+        mut reader: impl std::io::Read,
+    ) -> Result<ReceiptClaim, VerificationError> {
+        let mut count = [0u8; 4];
+        reader
+            .read_exact(&mut count)
+            .map_err(|_| VerificationError::ReceiptFormatError)?;
+        let count = u32::from_le_bytes(count) as usize;
+        if count == 0 {
+            return Err(VerificationError::ReceiptFormatError);
+        }
+
+        let mut chain = SegmentChain::default();
+        let mut first = None;
+        let mut last = None;
+        for idx in 0..count {
+            let receipt: SegmentReceipt = read_stream_item(&mut reader)?;
+            chain.verify_segment(ctx, &receipt, idx + 1 == count)?;
+            if idx == 0 {
+                first = Some(receipt);
+            } else {
+                last = Some(receipt);
             }
         }
+        let (assumptions, journal_digest) = read_stream_item(&mut reader)?;
 
+        let receipt = Self {
+            segments: first.into_iter().chain(last).collect(),
+            assumptions,
+            journal_digest,
+        };
+        let final_receipt = receipt.segments.last().unwrap();
+        receipt.verify_assumptions(ctx, &final_receipt.claim)?;
+        receipt.get_claim()
+    }
+
+    // Verify the assumption receipts, and the consistency of the output of the
+    // final segment with them.
+    fn verify_assumptions(
+        &self,
+        ctx: &VerifierContext,
+        final_claim: &ReceiptClaim,
+    ) -> Result<(), VerificationError> {
         // Verify all assumption receipts attached to this composite receipt.
         for receipt in self.assumptions.iter() {
             tracing::debug!("verifying assumption: {:?}", receipt.get_claim()?.digest());
@@ -486,7 +950,7 @@ impl CompositeReceipt {
 
         // Verify decoded output digest is consistent with the journal_digest and
         // assumptions.
-        self.verify_output_consistency(&final_receipt.claim)?;
+        self.verify_output_consistency(final_claim)?;
 
         Ok(())
     }
@@ -599,6 +1063,91 @@ impl CompositeReceipt {
     }
 }
 
+/// Checks the segment receipts of a continuation, and their chaining, one at a
+/// time and in order.
+#[derive(Default)]
+struct SegmentChain {
+    expected_pre_state_digest: Option<Digest>,
+}
+
+impl SegmentChain {
+    fn verify_segment(
+        &mut self,
+        ctx: &VerifierContext,
+        receipt: &SegmentReceipt,
+        last: bool,
+    ) -> Result<(), VerificationError> {
+        receipt.verify_integrity_with_context(ctx)?;
+        tracing::debug!("claim: {:#?}", receipt.claim);
+        if let Some(id) = self.expected_pre_state_digest {
+            if id != receipt.claim.pre.digest() {
+                return Err(VerificationError::ImageVerificationError);
+            }
+        }
+        if last {
+            return Ok(());
+        }
+
+        if receipt.claim.exit_code != ExitCode::SystemSplit {
+            return Err(VerificationError::UnexpectedExitCode);
+        }
+        if !receipt.claim.output.is_none() {
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.expected_pre_state_digest = Some({
+            // Post state PC is stored as the "actual" value plus 4. This matches the join
+            // predicate implementation. See [ReceiptClaim] for more detail.
+            let mut post = receipt
+                .claim
+                .post
+                .as_value()
+                .map_err(|_| VerificationError::ReceiptFormatError)?
+                .clone();
+            post.pc = post
+                .pc
+                .checked_sub(WORD_SIZE as u32)
+                .ok_or(VerificationError::ReceiptFormatError)?;
+            post.digest()
+        });
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_stream_item<T: Serialize>(writer: &mut impl std::io::Write, item: &T) -> Result<()> {
+    let words = to_vec(item)?;
+    let len: u32 = words.len().try_into()?;
+    writer.write_all(&len.to_le_bytes())?;
+    for word in words {
+        writer.write_all(&word.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+fn read_stream_item<T: DeserializeOwned>(
+    reader: &mut impl std::io::Read,
+) -> Result<T, VerificationError> {
+    use std::io::Read;
+
+    let mut len = [0u8; 4];
+    reader
+        .read_exact(&mut len)
+        .map_err(|_| VerificationError::ReceiptFormatError)?;
+    let len = u32::from_le_bytes(len) as u64 * WORD_SIZE as u64;
+
+    // The length is untrusted, so the buffer grows as data is read instead of
+    // being allocated upfront.
+    let mut bytes = Vec::new();
+    Read::take(reader, len)
+        .read_to_end(&mut bytes)
+        .map_err(|_| VerificationError::ReceiptFormatError)?;
+    if bytes.len() as u64 != len {
+        return Err(VerificationError::ReceiptFormatError);
+    }
+    from_slice(&bytes).map_err(|_| VerificationError::ReceiptFormatError)
+}
+
 /// A receipt attesting to the execution of a Segment.
 ///
 /// A SegmentReceipt attests that a Segment was executed in a manner
@@ -613,6 +1162,7 @@ pub struct SegmentReceipt {
     /// Segment was faithfully executed. It is largely opaque cryptographic data, but contains a
     /// non-opaque claim component, which can be conveniently accessed with
     /// [SegmentReceipt::claim].
+    #[serde(with = "crate::serde::hex_bytes::words")]
     pub seal: Vec<u32>,
 
     /// Segment index within the [Receipt]
@@ -628,30 +1178,92 @@ pub struct SegmentReceipt {
 impl SegmentReceipt {
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
+    ///
+    /// See [SegmentReceipt::verify_integrity_with_context].
+    pub fn verify_integrity(&self) -> Result<(), VerificationError> {
+        self.verify_integrity_with_context(&VerifierContext::default())
+    }
+
+    /// Verify the integrity of this receipt, ensuring the claim is attested
+    /// to by the seal.
+    ///
+    /// This checks that the seal is a valid proof of the rv32im circuit, with
+    /// one of the control IDs in [VerifierContext::parameters] and the hash
+    /// suite named by [SegmentReceipt::hashfn], and that [SegmentReceipt::claim]
+    /// has the same digest as the claim proven by the seal. Segments can
+    /// therefore be checked one at a time, as they are produced, before being
+    /// aggregated.
+    ///
+    /// A valid segment receipt only attests to the execution of its own
+    /// segment. In particular, this does not check:
+    ///
+    /// * [SegmentReceipt::index], which is not bound by the seal.
+    /// * That the pre state of the segment is the post state of the previous
+    ///   one, which is checked when verifying a [CompositeReceipt].
+    /// * The image ID or the exit code, which should be checked by the caller,
+    ///   e.g. a segment other than the last one is expected to exit with
+    ///   [ExitCode::SystemSplit].
+    /// * The journal and assumptions, of which the seal only binds the digest.
     pub fn verify_integrity_with_context(
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
-        use hex::FromHex;
-        let check_code = |_, control_id: &Digest| -> Result<(), VerificationError> {
-            POSEIDON_CONTROL_ID
-                .into_iter()
-                .chain(SHA256_CONTROL_ID)
-                .chain(BLAKE2B_CONTROL_ID)
-                .find(|x| Digest::from_hex(x).unwrap() == *control_id)
-                .map(|_| ())
-                .ok_or(VerificationError::ControlVerificationError {
-                    control_id: *control_id,
-                })
-        };
         let suite = ctx
             .suites
             .get(&self.hashfn)
             .ok_or(VerificationError::InvalidHashSuite)?;
-        risc0_zkp::verify::verify(&super::CIRCUIT, suite, &self.seal, check_code)?;
+        risc0_zkp::verify::verify(&super::CIRCUIT, suite, &self.seal, Self::check_code(ctx))?;
+        self.verify_decoded_claim()
+    }
 
-        // Receipt is consistent with the claim encoded on the seal. Now check against the
-        // claim on the struct.
+    /// Verify the integrity of several receipts at once.
+    ///
+    /// This performs the same checks as calling
+    /// [SegmentReceipt::verify_integrity_with_context] on each receipt, but
+    /// the Merkle branches opened by the seals using the same hash suite are
+    /// checked together, which is faster for verifiers processing many
+    /// receipts. If verification fails, the failing receipt is not identified.
+    pub fn verify_integrity_batch(
+        receipts: &[SegmentReceipt],
+        ctx: &VerifierContext,
+    ) -> Result<(), VerificationError> {
+        let mut seals: BTreeMap<&str, Vec<&[u32]>> = BTreeMap::new();
+        for receipt in receipts {
+            seals
+                .entry(receipt.hashfn.as_str())
+                .or_default()
+                .push(&receipt.seal);
+        }
+        for (hashfn, seals) in seals {
+            let suite = ctx
+                .suites
+                .get(hashfn)
+                .ok_or(VerificationError::InvalidHashSuite)?;
+            risc0_zkp::verify::verify_batch(&super::CIRCUIT, suite, &seals, Self::check_code(ctx))?;
+        }
+        receipts
+            .iter()
+            .try_for_each(|receipt| receipt.verify_decoded_claim())
+    }
+
+    fn check_code(
+        ctx: &VerifierContext,
+    ) -> impl Fn(u32, &Digest) -> Result<(), VerificationError> + '_ {
+        move |_, control_id: &Digest| -> Result<(), VerificationError> {
+            ctx.parameters
+                .segment_control_ids
+                .iter()
+                .find(|x| *x == control_id)
+                .map(|_| ())
+                .ok_or(VerificationError::ControlVerificationError {
+                    control_id: *control_id,
+                })
+        }
+    }
+
+    // Receipt is consistent with the claim encoded on the seal. Now check against the
+    // claim on the struct.
+    fn verify_decoded_claim(&self) -> Result<(), VerificationError> {
         let decoded_claim = decode_receipt_claim_from_seal(&self.seal)?;
         if decoded_claim.digest() != self.claim.digest() {
             tracing::debug!(
@@ -732,10 +1344,185 @@ impl From<ReceiptClaim> for Assumption {
     }
 }
 
+/// Parameters of a circuit release, identifying the circuits whose proofs are
+/// accepted.
+///
+/// Every zkVM release comes with its own circuits. A verifier accepts the
+/// receipts produced by an older release only if it is given the parameters
+/// of that release, with [VerifierContext::with_release], and release
+/// selection is enabled with [VerifierContext::with_select_release].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifierParameters {
+    /// Control IDs of the rv32im circuit accepted in [SegmentReceipt] seals.
+    pub segment_control_ids: Vec<Digest>,
+
+    /// Control IDs of the recursion programs accepted in [SuccinctReceipt] seals.
+    pub recursion_control_ids: Vec<Digest>,
+
+    /// Root of the Merkle tree of the allowed recursion control IDs, checked by
+    /// [CompactReceipt] seals.
+    pub allowed_ids_root: Digest,
+
+    /// Groth16 verifying key used to verify [CompactReceipt] seals.
+    ///
+    /// When `None`, the key from the RISC Zero trusted setup ceremony is used.
+    /// Deployments with their own setup can supply their key here, with
+    /// [VerifierParameters::with_groth16_verifying_key].
+    pub groth16_verifying_key: Option<VerifyingKey>,
+}
+
+impl VerifierParameters {
+    /// Parameters of the circuits of this release.
+    pub fn current() -> Self {
+        use hex::FromHex;
+        let segment_control_ids = POSEIDON_CONTROL_ID
+            .into_iter()
+            .chain(SHA256_CONTROL_ID)
+            .chain(BLAKE2B_CONTROL_ID)
+            .map(|x| Digest::from_hex(x).unwrap())
+            .collect();
+        Self {
+            segment_control_ids,
+            recursion_control_ids: valid_control_ids(),
+            allowed_ids_root: Digest::from_hex(ALLOWED_IDS_ROOT).unwrap(),
+            groth16_verifying_key: None,
+        }
+    }
+
+    /// Verify [CompactReceipt] seals with the given Groth16 verifying key
+    /// instead of the key from the RISC Zero trusted setup ceremony.
+    ///
+    /// # Warning
+    ///
+    /// A verifying key from a setup that the verifier does not trust lets its
+    /// owner forge [CompactReceipt] seals for any claim. Only use keys from a
+    /// trusted setup.
+    pub fn with_groth16_verifying_key(mut self, verifying_key: VerifyingKey) -> Self {
+        self.groth16_verifying_key = Some(verifying_key);
+        self
+    }
+}
+
+impl Default for VerifierParameters {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
 /// Context available to the verification process.
+#[derive(Clone)]
 pub struct VerifierContext {
     /// A registry of hash functions to be used by the verification process.
     pub suites: BTreeMap<String, HashSuite<BabyBear>>,
+
+    /// Whether to reject [InnerReceipt::Fake] receipts.
+    ///
+    /// When `true`, fake receipts fail verification with [VerificationError::FakeReceipt], even
+    /// if `RISC0_DEV_MODE` is set. Services accepting receipts from untrusted parties should
+    /// enable it, so that a misconfigured environment cannot make them accept unproven claims.
+    pub strict: bool,
+
+    /// Parameters of the circuits whose proofs are accepted.
+    ///
+    /// Defaults to the parameters of this release.
+    pub parameters: VerifierParameters,
+
+    /// Parameters of other circuit releases, by zkVM version.
+    ///
+    /// When [VerifierContext::select_release] is enabled, a [Receipt] whose
+    /// [ReceiptMetadata::zkvm_version] is in this map is verified with the
+    /// parameters of that release instead of [VerifierContext::parameters].
+    /// This lets a service accept receipts from provers that have not been
+    /// upgraded yet.
+    pub releases: BTreeMap<String, VerifierParameters>,
+
+    /// Whether to select the parameters to verify a [Receipt] with from its
+    /// [ReceiptMetadata::zkvm_version].
+    ///
+    /// The metadata is not authenticated, so a receipt can select any of the
+    /// [VerifierContext::releases]. When `false`, the default, receipts are
+    /// only verified with [VerifierContext::parameters].
+    pub select_release: bool,
+}
+
+impl VerifierContext {
+    /// Set whether to reject [InnerReceipt::Fake] receipts. See [VerifierContext::strict].
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Add the hash suites of the [registry](risc0_zkp::core::hash::registry),
+    /// and accept the control IDs registered with them in
+    /// [VerifierContext::parameters].
+    ///
+    /// The hash suite used to verify a receipt is named by the receipt itself,
+    /// so only add the registered suites when every one of them is trusted.
+    #[cfg(feature = "std")]
+    pub fn with_registered_suites(mut self) -> Self {
+        use risc0_zkp::core::hash::registry;
+
+        for suite in registry::suites() {
+            if let Some(control_ids) = registry::control_ids(&suite.name) {
+                self.parameters.segment_control_ids.extend(control_ids);
+            }
+            self.suites.insert(suite.name.clone(), suite);
+        }
+        self
+    }
+
+    /// Set the parameters of the circuits whose proofs are accepted. See
+    /// [VerifierContext::parameters].
+    pub fn with_parameters(mut self, parameters: VerifierParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Set whether to select the parameters from the receipt metadata. See
+    /// [VerifierContext::select_release].
+    pub fn with_select_release(mut self, select_release: bool) -> Self {
+        self.select_release = select_release;
+        self
+    }
+
+    /// Accept receipts produced by the given zkVM version, verifying them with
+    /// the given parameters. See [VerifierContext::releases].
+    ///
+    /// The release is only selected if [VerifierContext::select_release] is
+    /// enabled.
+    pub fn with_release(
+        mut self,
+        version: impl Into<String>,
+        parameters: VerifierParameters,
+    ) -> Self {
+        self.releases.insert(version.into(), parameters);
+        self
+    }
+
+    /// Returns the context to verify a receipt with the given metadata.
+    ///
+    /// If [VerifierContext::select_release] is enabled, this is a context with
+    /// the parameters of the release named by [ReceiptMetadata::zkvm_version],
+    /// if it is one of [VerifierContext::releases]. Otherwise, it is this
+    /// context. As metadata is not authenticated, a receipt can select any of
+    /// the configured releases, so every release in the map must be trusted to
+    /// be sound.
+    pub fn for_metadata(&self, metadata: &ReceiptMetadata) -> Cow<'_, Self> {
+        if !self.select_release {
+            return Cow::Borrowed(self);
+        }
+        match metadata
+            .zkvm_version
+            .as_ref()
+            .and_then(|version| self.releases.get(version))
+        {
+            Some(parameters) => Cow::Owned(Self {
+                parameters: parameters.clone(),
+                ..self.clone()
+            }),
+            None => Cow::Borrowed(self),
+        }
+    }
 }
 
 fn decode_system_state_from_io(
@@ -790,6 +1577,8 @@ pub(crate) fn decode_receipt_claim_from_seal(
 }
 
 impl Default for VerifierContext {
+    /// Return a [VerifierContext] with the built-in hash suites and the
+    /// parameters of this release.
     fn default() -> Self {
         Self {
             suites: BTreeMap::from([
@@ -797,6 +1586,191 @@ impl Default for VerifierContext {
                 ("poseidon".into(), PoseidonHashSuite::new_suite()),
                 ("sha-256".into(), Sha256HashSuite::new_suite()),
             ]),
+            strict: false,
+            parameters: VerifierParameters::current(),
+            releases: BTreeMap::new(),
+            select_release: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+
+    use super::{
+        InnerReceipt, Receipt, ReceiptDecodeError, ReceiptMetadata, VerifierContext,
+        VerifierParameters,
+    };
+    use crate::{host::testutils::fake_receipt, serde::to_vec, ExitCode};
+
+    #[test]
+    fn round_trip() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        let bytes = receipt.to_bytes().unwrap();
+        assert_eq!(bytes[..4], Receipt::FORMAT_MAGIC);
+        assert_eq!(Receipt::from_bytes(&bytes).unwrap(), receipt);
+    }
+
+    #[test]
+    fn strict_rejects_fake() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        assert_eq!(
+            receipt.verify_strict(Digest::ZERO),
+            Err(VerificationError::FakeReceipt)
+        );
+        let ctx = VerifierContext::default().with_strict(true);
+        assert_eq!(
+            receipt.verify_integrity_with_context(&ctx),
+            Err(VerificationError::FakeReceipt)
+        );
+    }
+
+    #[test]
+    fn release_parameters() {
+        let old = VerifierParameters {
+            allowed_ids_root: Digest::from([1u32; 8]),
+            ..VerifierParameters::current()
+        };
+        let ctx = VerifierContext::default().with_release("0.20.0", old.clone());
+
+        let mut metadata = ReceiptMetadata::new("cpu");
+        metadata.zkvm_version = Some("0.20.0".into());
+        // Releases are only selected from the metadata when explicitly enabled.
+        assert_eq!(
+            ctx.for_metadata(&metadata).parameters,
+            VerifierParameters::current()
+        );
+
+        let ctx = ctx.with_select_release(true);
+        assert_eq!(ctx.for_metadata(&metadata).parameters, old);
+        metadata.zkvm_version = Some("0.19.0".into());
+        assert_eq!(
+            ctx.for_metadata(&metadata).parameters,
+            VerifierParameters::current()
+        );
+    }
+
+    #[test]
+    fn round_trip_metadata() {
+        let mut metadata = ReceiptMetadata::new("cpu");
+        metadata.proving_time_ms = Some(1234);
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]).with_metadata(metadata);
+        let decoded = Receipt::from_bytes(&receipt.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.metadata(), receipt.metadata());
+        assert_eq!(
+            decoded.metadata().zkvm_version.as_deref(),
+            Some(crate::VERSION)
+        );
+    }
+
+    #[test]
+    fn decodes_v1() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        let mut bytes = Receipt::FORMAT_MAGIC.to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&receipt.inner.kind().to_le_bytes());
+        for word in to_vec(&(&receipt.inner, &receipt.journal)).unwrap() {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        let decoded = Receipt::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, receipt);
+        assert_eq!(decoded.metadata(), &ReceiptMetadata::default());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut bytes = fake_receipt(ExitCode::Halted(0), &[1, 2, 3])
+            .to_bytes()
+            .unwrap();
+        bytes[4..8].copy_from_slice(&(Receipt::FORMAT_VERSION + 1).to_le_bytes());
+        let err = Receipt::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(err, ReceiptDecodeError::UnsupportedVersion(_)));
+        assert!(err.to_string().contains("upgrade"), "{err}");
+
+        bytes[..4].copy_from_slice(b"JUNK");
+        assert!(matches!(
+            Receipt::from_bytes(&bytes),
+            Err(ReceiptDecodeError::BadMagic)
+        ));
+    }
+
+    #[test]
+    fn rejects_kind_mismatch() {
+        let mut bytes = fake_receipt(ExitCode::Halted(0), &[1, 2, 3])
+            .to_bytes()
+            .unwrap();
+        bytes[8..12].copy_from_slice(&InnerReceipt::KIND_COMPOSITE.to_le_bytes());
+        assert!(matches!(
+            Receipt::from_bytes(&bytes),
+            Err(ReceiptDecodeError::KindMismatch { .. })
+        ));
+        bytes[8..12].copy_from_slice(&0u32.to_le_bytes());
+        assert!(matches!(
+            Receipt::from_bytes(&bytes),
+            Err(ReceiptDecodeError::UnknownKind(0))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_round_trip() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        let json = receipt.to_json().unwrap();
+        assert!(json.contains(r#""journal":{"bytes":"010203"}"#), "{json}");
+        assert_eq!(Receipt::from_json(&json).unwrap(), receipt);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_accepts_byte_lists() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        let json = receipt
+            .to_json()
+            .unwrap()
+            .replace(r#""bytes":"010203""#, r#""bytes":[1,2,3]"#);
+        assert_eq!(Receipt::from_json(&json).unwrap(), receipt);
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor_round_trip() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        let cbor = receipt.to_cbor().unwrap();
+        assert_eq!(Receipt::from_cbor(&cbor).unwrap(), receipt);
+    }
+
+    #[test]
+    fn hex_bytes_only_for_human_readable_formats() {
+        let receipt = fake_receipt(ExitCode::Halted(0), &[1, 2, 3]);
+        // Binary formats encode byte fields as derived implementations do.
+        assert_eq!(
+            to_vec(&receipt.journal).unwrap(),
+            to_vec(&vec![1u8, 2, 3]).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn registered_suites() {
+        use risc0_zkp::core::hash::{registry, sha::Sha256HashSuite, HashSuite};
+
+        fn suite() -> HashSuite<risc0_core::field::baby_bear::BabyBear> {
+            HashSuite {
+                name: "receipt-test".into(),
+                ..Sha256HashSuite::new_suite()
+            }
+        }
+
+        let control_id = Digest::from([1u32; 8]);
+        registry::register("receipt-test", suite, &[control_id]).unwrap();
+        let ctx = VerifierContext::default();
+        assert!(!ctx.suites.contains_key("receipt-test"));
+        assert!(!ctx.parameters.segment_control_ids.contains(&control_id));
+        let ctx = ctx.with_registered_suites();
+        assert!(ctx.suites.contains_key("receipt-test"));
+        assert!(ctx.parameters.segment_control_ids.contains(&control_id));
+    }
+}
@@ -52,6 +52,7 @@ pub fn valid_control_ids() -> Vec<Digest> {
 pub struct SuccinctReceipt {
     /// The cryptographic seal of this receipt. This seal is a STARK proving an execution of the
     /// recursion circuit.
+    #[serde(with = "crate::serde::hex_bytes::words")]
     pub seal: Vec<u32>,
 
     /// The control ID of this receipt, identifying the recursion program that was run (e.g. lift,
@@ -75,9 +76,8 @@ impl SuccinctReceipt {
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
-        // Assemble the list of control IDs, and therefore circuit variants, we will
-        // accept.
-        let valid_ids = valid_control_ids();
+        // The list of control IDs, and therefore circuit variants, we will accept.
+        let valid_ids = &ctx.parameters.recursion_control_ids;
         let check_code = |_, control_id: &Digest| -> Result<(), VerificationError> {
             valid_ids
                 .iter()
@@ -52,7 +52,7 @@ use super::{monitor::MemoryMonitor, profiler::Profiler, syscall::SyscallTable};
 use crate::{
     align_up,
     host::{
-        client::exec::TraceEvent,
+        client::exec::{GuestFault, SessionLimit, TraceEvent},
         receipt::Assumption,
         server::opcode::{MajorType, OpCode},
     },
@@ -123,6 +123,7 @@ pub struct ExecutorImpl<'a> {
     body_cycles: usize,
     segment_limit: usize,
     segment_cycle: usize,
+    prior_cycles: u64,
     segments: Vec<Box<dyn SegmentRef>>,
     insn_counter: u32,
     split_insn: Option<u32>,
@@ -177,6 +178,7 @@ impl<'a> ExecutorImpl<'a> {
             body_cycles: 0,
             segment_limit: 1 << segment_limit_po2,
             segment_cycle: init_cycles,
+            prior_cycles: 0,
             segments: Vec::new(),
             insn_counter: 0,
             split_insn: None,
@@ -239,6 +241,28 @@ impl<'a> ExecutorImpl<'a> {
         self.run_with_callback(|segment| Ok(Box::new(FileSegmentRef::new(&segment, &path)?)))
     }
 
+    /// Resume an execution paused by the guest with `env::pause`, replacing the [ExecutorEnv] to
+    /// provide new inputs to the guest.
+    ///
+    /// The guest continues from the state it paused in, reading from the I/O of the new
+    /// environment, until it pauses again or halts. Each [Session] can be proven on its own, and
+    /// the pre-state of the resumed [Session] is the post-state of the paused one, so receipts of
+    /// consecutive rounds can be chained by the verifier. The segment limit and the assumptions of
+    /// the original environment are kept.
+    pub fn resume(&mut self, env: ExecutorEnv<'a>) -> Result<Session> {
+        let Some(ExitCode::Paused(_)) = self.exit_code else {
+            bail!(
+                "cannot resume an execution which exited with {:?}",
+                self.exit_code
+            );
+        };
+        let cached = mem::take(&mut self.env.assumptions.borrow_mut().cached);
+        env.assumptions.borrow_mut().cached.extend(cached);
+        self.syscall_table = SyscallTable::new(&env);
+        self.env = env;
+        self.run()
+    }
+
     /// Run the executor until [ExitCode::Halted] or [ExitCode::Paused] is reached, producing a
     /// [Session] as a result.
     pub fn run_with_callback<F>(&mut self, mut callback: F) -> Result<Session>
@@ -261,6 +285,7 @@ impl<'a> ExecutorImpl<'a> {
             .ok_or_else(|| anyhow!("attempted to run the executor with no pre_image"))?
             .pc;
         self.monitor.clear_session()?;
+        self.prior_cycles = 0;
 
         let journal = Journal::default();
         self.env
@@ -303,6 +328,7 @@ impl<'a> ExecutorImpl<'a> {
                         ExitCode::SystemSplit => {
                             let segment_ref = callback(segment)?;
                             self.segments.push(segment_ref);
+                            self.prior_cycles = self.cycle_count();
                             self.split(Some(post_image.into()))?
                         }
                         ExitCode::Paused(inner) => {
@@ -378,13 +404,14 @@ impl<'a> ExecutorImpl<'a> {
             std::fs::write(self.env.pprof_out.as_ref().unwrap(), report)?;
         }
 
-        let session = Session::new(
+        let mut session = Session::new(
             mem::take(&mut self.segments),
             session_journal,
             exit_code,
             post_image,
             assumptions,
         );
+        session.heap_stats = self.syscall_table.heap_stats.take();
 
         tracing::info_span!("executor").in_scope(|| {
             tracing::info!("execution time: {}", elapsed.human_duration());
@@ -411,7 +438,7 @@ impl<'a> ExecutorImpl<'a> {
     pub fn step(&mut self) -> Result<Option<ExitCode>> {
         if let Some(limit) = self.env.session_limit {
             if self.session_cycle() >= (limit as usize) {
-                bail!("Session limit exceeded")
+                return Err(SessionLimit { limit }.into());
             }
         }
 
@@ -478,7 +505,11 @@ impl<'a> ExecutorImpl<'a> {
                     err
                 );
                 self.monitor.undo()?;
-                bail!("execution encountered a fault: {:?}", err);
+                return Err(GuestFault {
+                    pc: self.pc,
+                    cause: format!("{err:?}"),
+                }
+                .into());
             }
 
             if let Some(idx) = hart.last_register_write {
@@ -540,7 +571,11 @@ impl<'a> ExecutorImpl<'a> {
         let page_read_cycles = self.monitor.page_read_cycles;
         // tracing::debug!("page_read_cycles: {page_read_cycles}");
         self.segment_cycle = self.init_cycles + page_read_cycles + self.body_cycles;
-        self.monitor.commit(self.session_cycle());
+        self.monitor.commit(
+            self.session_cycle(),
+            self.cycle_count(),
+            self.segment_limit.saturating_sub(self.total_cycles()),
+        );
         if let Some(syscall) = self.pending_syscall.take() {
             self.syscalls.push(syscall);
         }
@@ -554,6 +589,14 @@ impl<'a> ExecutorImpl<'a> {
             + self.body_cycles
     }
 
+    // Cycles consumed by the guest so far in the session, including paging but excluding the
+    // fixed overhead of each segment, as reported by `env::cycle_count`.
+    fn cycle_count(&self) -> u64 {
+        self.prior_cycles
+            + (self.body_cycles + self.monitor.page_read_cycles + self.monitor.page_write_cycles)
+                as u64
+    }
+
     fn session_cycle(&self) -> usize {
         self.segments.len() * self.segment_limit + self.segment_cycle
     }
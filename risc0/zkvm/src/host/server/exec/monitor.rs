@@ -70,6 +70,8 @@ pub struct MemoryMonitor {
     num_pages: usize,
     pub faults: PageFaults,
     session_cycle: usize,
+    cycle_count: u64,
+    segment_cycles_remaining: usize,
     pub trace_events: BTreeSet<TraceEvent>,
     resident: Vec<bool>,
     dirty: Vec<bool>,
@@ -92,6 +94,8 @@ impl MemoryMonitor {
             num_pages,
             faults: PageFaults::default(),
             session_cycle: 0,
+            cycle_count: 0,
+            segment_cycles_remaining: 0,
             trace_events: BTreeSet::new(),
             resident,
             dirty,
@@ -437,9 +441,11 @@ impl MemoryMonitor {
     }
 
     // commit all pending activity
-    pub fn commit(&mut self, cycle: usize) {
+    pub fn commit(&mut self, cycle: usize, cycle_count: u64, segment_cycles_remaining: usize) {
         self.pending_actions.clear();
         self.session_cycle = cycle;
+        self.cycle_count = cycle_count;
+        self.segment_cycles_remaining = segment_cycles_remaining;
         if self.enable_trace {
             self.trace_events.clear();
         }
@@ -458,6 +464,7 @@ impl MemoryMonitor {
     pub fn clear_session(&mut self) -> Result<()> {
         self.clear_segment()?;
         self.session_cycle = 0;
+        self.cycle_count = 0;
         Ok(())
     }
 
@@ -523,6 +530,14 @@ impl SyscallContext for MemoryMonitor {
         self.session_cycle
     }
 
+    fn get_cycle_count(&self) -> u64 {
+        self.cycle_count
+    }
+
+    fn get_segment_cycles_remaining(&self) -> usize {
+        self.segment_cycles_remaining
+    }
+
     fn load_register(&mut self, idx: usize) -> u32 {
         self.registers[idx]
     }
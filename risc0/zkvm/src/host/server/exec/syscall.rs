@@ -22,8 +22,9 @@ use risc0_zkvm_platform::{
     fileno,
     syscall::{
         nr::{
-            SYS_ARGC, SYS_ARGV, SYS_CYCLE_COUNT, SYS_GETENV, SYS_LOG, SYS_PANIC, SYS_RANDOM,
-            SYS_READ, SYS_VERIFY, SYS_VERIFY_INTEGRITY, SYS_WRITE,
+            SYS_ARGC, SYS_ARGV, SYS_BIGINT_DIVREM, SYS_CYCLE_BUDGET, SYS_CYCLE_COUNT, SYS_GETENV,
+            SYS_HEAP_STATS, SYS_LOG, SYS_LOG_RECORD, SYS_PANIC, SYS_RANDOM, SYS_READ, SYS_VERIFY,
+            SYS_VERIFY_INTEGRITY, SYS_WRITE,
         },
         reg_abi::{REG_A3, REG_A4, REG_A5},
         SyscallName, DIGEST_BYTES, DIGEST_WORDS,
@@ -34,11 +35,12 @@ use risc0_zkvm_platform::{
 use crate::{
     host::client::{
         env::{Assumptions, ExecutorEnv},
+        exec::GuestPanic,
         posix_io::PosixIo,
         slice_io::SliceIo,
     },
     sha::{Digest, Digestible},
-    Assumption, MaybePruned, PrunedValueError, ReceiptClaim,
+    Assumption, HeapStats, MaybePruned, PrunedValueError, ReceiptClaim,
 };
 
 /// A host-side implementation of a system call.
@@ -57,6 +59,17 @@ pub trait SyscallContext {
     /// Returns the current cycle being executed.
     fn get_cycle(&self) -> usize;
 
+    /// Returns the number of cycles consumed by the guest so far in the session, including the
+    /// cycles spent paging memory in and out, but not the fixed overhead of each segment.
+    ///
+    /// Defaults to the current cycle of the segment, for contexts that don't track the session.
+    fn get_cycle_count(&self) -> u64 {
+        self.get_cycle() as u64
+    }
+
+    /// Returns the number of cycles remaining before the current segment is full.
+    fn get_segment_cycles_remaining(&self) -> usize;
+
     /// Loads the value of the given register, e.g. REG_A0.
     fn load_register(&mut self, idx: usize) -> u32;
 
@@ -93,19 +106,28 @@ pub trait SyscallContext {
 #[derive(Clone)]
 pub(crate) struct SyscallTable<'a> {
     pub(crate) inner: HashMap<String, Rc<RefCell<dyn Syscall + 'a>>>,
+    /// The heap usage last reported by the guest.
+    pub(crate) heap_stats: Rc<RefCell<Option<HeapStats>>>,
 }
 
 impl<'a> SyscallTable<'a> {
     pub fn new(env: &ExecutorEnv<'a>) -> Self {
+        let heap_stats = Rc::new(RefCell::new(None));
         let mut this = Self {
             inner: HashMap::new(),
+            heap_stats: heap_stats.clone(),
         };
 
         let sys_verify = SysVerify::new(env.assumptions.clone());
 
         let posix_io = env.posix_io.clone();
-        this.with_syscall(SYS_CYCLE_COUNT, SysCycleCount)
+        this.with_syscall(SYS_BIGINT_DIVREM, SysBigIntDivRem)
+            .with_syscall(SYS_CYCLE_BUDGET, SysCycleBudget(env.session_limit))
+            .with_syscall(SYS_CYCLE_COUNT, SysCycleCount)
+            .with_syscall(SYS_HEAP_STATS, SysHeapStats(heap_stats))
             .with_syscall(SYS_LOG, posix_io.clone())
+            .with_syscall(SYS_LOG_RECORD, SysLogRecord)
+            .with_syscall(SYS_OUTPUT, SysOutput(env.outputs.clone()))
             .with_syscall(SYS_PANIC, SysPanic)
             .with_syscall(SYS_RANDOM, SysRandom)
             .with_syscall(SYS_GETENV, SysGetenv(env.env_vars.clone()))
@@ -139,6 +161,102 @@ impl<'a> SyscallTable<'a> {
     }
 }
 
+pub(crate) struct SysBigIntDivRem;
+impl Syscall for SysBigIntDivRem {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ctx.load_register(REG_A3);
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest: Vec<u32> = ctx
+            .load_region(buf_ptr, buf_len)?
+            .chunks_exact(WORD_SIZE)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+
+        // The guest sends a product of 2 * width words and a modulus of width words, and
+        // expects a quotient of 2 * width words and a remainder of width words.
+        let width = to_guest.len() / 3;
+        if to_guest.len() != 3 * width || from_guest.len() != 3 * width {
+            bail!(
+                "sys_bigint_divrem call with input of {} words and output of {} words",
+                from_guest.len(),
+                to_guest.len()
+            );
+        }
+        let (product, modulus) = from_guest.split_at(2 * width);
+        if modulus.iter().all(|&word| word == 0) {
+            bail!("sys_bigint_divrem call with a zero modulus");
+        }
+        let (quotient, remainder) = to_guest.split_at_mut(2 * width);
+        bigint_divrem(product, modulus, quotient, remainder);
+        Ok((0, 0))
+    }
+}
+
+// Schoolbook binary long division of little-endian words.
+fn bigint_divrem(dividend: &[u32], divisor: &[u32], quotient: &mut [u32], remainder: &mut [u32]) {
+    // One extra word holds the bit shifted out of the remainder before it is reduced.
+    let mut rem = vec![0u32; divisor.len() + 1];
+    quotient.fill(0);
+    for bit in (0..dividend.len() * 32).rev() {
+        let mut carry = (dividend[bit / 32] >> (bit % 32)) & 1;
+        for word in rem.iter_mut() {
+            let next = *word >> 31;
+            *word = (*word << 1) | carry;
+            carry = next;
+        }
+        if !bigint_less_than(&rem, divisor) {
+            let mut borrow = 0u64;
+            for (i, word) in rem.iter_mut().enumerate() {
+                let diff = (*word as u64)
+                    .wrapping_sub(divisor.get(i).copied().unwrap_or(0) as u64)
+                    .wrapping_sub(borrow);
+                *word = diff as u32;
+                borrow = diff >> 63;
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    remainder.copy_from_slice(&rem[..divisor.len()]);
+}
+
+// Compares little-endian words, where `a` may be longer than `b`.
+fn bigint_less_than(a: &[u32], b: &[u32]) -> bool {
+    for (i, &a) in a.iter().enumerate().rev() {
+        let b = b.get(i).copied().unwrap_or(0);
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+pub(crate) struct SysCycleBudget(pub Option<u64>);
+impl Syscall for SysCycleBudget {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let segment = ctx.get_segment_cycles_remaining();
+        let Some(limit) = self.0 else {
+            return Ok((segment as u32, 0));
+        };
+        let session = limit.saturating_sub(ctx.get_cycle() as u64);
+        if to_guest.len() < 2 {
+            bail!("Buffer for the session budget is too small");
+        }
+        to_guest[0] = session as u32;
+        to_guest[1] = (session >> 32) as u32;
+        Ok((segment as u32, 1))
+    }
+}
+
 pub(crate) struct SysCycleCount;
 impl Syscall for SysCycleCount {
     fn syscall(
@@ -147,7 +265,8 @@ impl Syscall for SysCycleCount {
         ctx: &mut dyn SyscallContext,
         _to_guest: &mut [u32],
     ) -> Result<(u32, u32)> {
-        Ok((ctx.get_cycle() as u32, 0))
+        let cycle_count = ctx.get_cycle_count();
+        Ok((cycle_count as u32, (cycle_count >> 32) as u32))
     }
 }
 
@@ -176,6 +295,100 @@ impl Syscall for SysGetenv {
     }
 }
 
+pub(crate) struct SysHeapStats(Rc<RefCell<Option<HeapStats>>>);
+impl Syscall for SysHeapStats {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ctx.load_register(REG_A3);
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest = ctx.load_region(buf_ptr, buf_len)?;
+        if from_guest.len() != 3 * WORD_SIZE {
+            bail!(
+                "sys_heap_stats call with {} bytes of stats",
+                from_guest.len()
+            );
+        }
+        let stats: Vec<u32> = from_guest
+            .chunks_exact(WORD_SIZE)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        let stats = HeapStats {
+            used: stats[0],
+            peak: stats[1],
+            free: stats[2],
+        };
+        tracing::debug!("guest heap: {stats:?}");
+        *self.0.borrow_mut() = Some(stats);
+        Ok((0, 0))
+    }
+}
+
+pub(crate) struct SysLogRecord;
+impl Syscall for SysLogRecord {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ctx.load_register(REG_A3);
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest = ctx.load_region(buf_ptr, buf_len)?;
+
+        // The record is the level and the length of the target, followed by the target and the
+        // message.
+        if from_guest.len() < 8 {
+            bail!(
+                "sys_log_record call with a record of {} bytes",
+                from_guest.len()
+            );
+        }
+        let (header, body) = from_guest.split_at(8);
+        let level = u32::from_le_bytes(header[..4].try_into().unwrap());
+        let target_len = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
+        if target_len > body.len() {
+            bail!("sys_log_record call with a target of {target_len} bytes out of bounds");
+        }
+        let (target, msg) = body.split_at(target_len);
+        let guest_target = from_utf8(target)?;
+        let msg = String::from_utf8_lossy(msg);
+
+        const TARGET: &str = "risc0_zkvm::guest";
+        match level {
+            1 => tracing::error!(target: TARGET, guest_target, "{msg}"),
+            2 => tracing::warn!(target: TARGET, guest_target, "{msg}"),
+            3 => tracing::info!(target: TARGET, guest_target, "{msg}"),
+            4 => tracing::debug!(target: TARGET, guest_target, "{msg}"),
+            5 => tracing::trace!(target: TARGET, guest_target, "{msg}"),
+            _ => bail!("sys_log_record call with invalid level {level}"),
+        }
+        Ok((0, 0))
+    }
+}
+
+pub(crate) struct SysOutput(pub HashMap<String, u32>);
+impl Syscall for SysOutput {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ctx.load_register(REG_A3);
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest = ctx.load_region(buf_ptr, buf_len)?;
+        let name = from_utf8(&from_guest)?;
+        match self.0.get(name) {
+            Some(fd) => Ok((*fd, 0)),
+            None => bail!("Guest requested the output `{name}`, which is not registered"),
+        }
+    }
+}
+
 pub(crate) struct SysPanic;
 impl Syscall for SysPanic {
     fn syscall(
@@ -188,7 +401,7 @@ impl Syscall for SysPanic {
         let buf_len = ctx.load_register(REG_A4);
         let from_guest = ctx.load_region(buf_ptr, buf_len)?;
         let msg = from_utf8(&from_guest)?;
-        bail!("Guest panicked: {msg}");
+        Err(GuestPanic::parse(msg).into())
     }
 }
 
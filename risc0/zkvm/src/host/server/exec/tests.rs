@@ -16,7 +16,7 @@ use std::{
     collections::{BTreeMap, HashSet},
     io::Cursor,
     str::from_utf8,
-    sync::Mutex,
+    sync::{Arc, Mutex},
 };
 
 use anyhow::Result;
@@ -40,7 +40,7 @@ use crate::{
     },
     serde::to_vec,
     sha::{Digest, Digestible},
-    ExecutorEnv, ExecutorImpl, ExitCode,
+    ExecutorEnv, ExecutorImpl, ExitCode, GuestFault, GuestPanic, SessionLimit,
 };
 
 fn run_test(spec: MultiTestSpec) {
@@ -141,6 +141,58 @@ fn system_split() {
     assert_eq!(segments[1].index, 1);
 }
 
+#[test]
+fn pause_resume() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::PauseResume)
+        .unwrap()
+        .write(&1u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+    let paused = exec.run().unwrap();
+    assert_eq!(paused.exit_code, ExitCode::Paused(0));
+    assert_eq!(paused.journal.as_ref().unwrap().decode::<u32>().unwrap(), 1);
+
+    // A second round, with a new input.
+    let env = ExecutorEnv::builder()
+        .write(&2u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = exec.resume(env).unwrap();
+    assert_eq!(session.exit_code, ExitCode::Paused(0));
+    assert_eq!(session.journal.unwrap().decode::<u32>().unwrap(), 2);
+
+    // The final round, halting the guest.
+    let env = ExecutorEnv::builder()
+        .write(&0u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let halted = exec.resume(env).unwrap();
+    assert_eq!(halted.exit_code, ExitCode::Halted(0));
+    assert_eq!(halted.journal.as_ref().unwrap().decode::<u32>().unwrap(), 0);
+    let env = ExecutorEnv::builder().build().unwrap();
+    assert!(exec.resume(env).is_err());
+
+    // Resuming from the image of a paused session continues where the guest paused.
+    let env = ExecutorEnv::builder()
+        .write(&0u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::new(env, paused.resume_image().unwrap()).unwrap();
+    let session = exec.run().unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert_eq!(
+        session.get_claim().unwrap().pre.digest(),
+        paused.get_claim().unwrap().post.digest()
+    );
+    assert!(halted.resume_image().is_err());
+}
+
 #[test]
 fn libm_build() {
     run_test(MultiTestSpec::LibM);
@@ -207,6 +259,73 @@ fn sha_cycle_count() {
     run_test(MultiTestSpec::ShaCycleCount);
 }
 
+#[test]
+fn cycle_budget() {
+    let run = |session_limit| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::CycleBudget { margin: 1 << 18 })
+            .unwrap()
+            .segment_limit_po2(16)
+            .session_limit(session_limit)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session
+            .journal
+            .unwrap()
+            .decode::<(u64, Option<u64>, u32)>()
+            .unwrap()
+    };
+
+    // The guest stops before reaching the session limit, across several segments.
+    let limit = 1 << 21;
+    let (segment, session, rounds) = run(Some(limit));
+    assert!(segment > 0 && segment < 1 << 16);
+    assert!(session.unwrap() > 1 << 18 && session.unwrap() < limit);
+    assert!(rounds > 0);
+
+    // Without a session limit, only the segment budget is reported.
+    let (segment, session, rounds) = run(None);
+    assert!(segment > 0 && segment < 1 << 16);
+    assert_eq!(session, None);
+    assert_eq!(rounds, 0);
+}
+
+#[test]
+fn cycle_count() {
+    let run = || {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::CycleCount { cycles: 1 << 17 })
+            .unwrap()
+            .segment_limit_po2(16)
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+
+    let session = run();
+    assert!(session.resolve().unwrap().len() > 2);
+    let (start, end): (u64, u64) = session.journal.as_ref().unwrap().decode().unwrap();
+    assert!(end - start >= 1 << 17);
+
+    // The count only includes the cycles consumed by the guest, not the overhead of the
+    // segments, and is the same on every execution.
+    let (total_cycles, _) = session.get_cycles().unwrap();
+    assert!(end < total_cycles);
+    let session = run();
+    assert_eq!(
+        session.journal.unwrap().decode::<(u64, u64)>().unwrap(),
+        (start, end)
+    );
+}
+
 #[test]
 fn rsa_compat() {
     run_test(MultiTestSpec::RsaCompat);
@@ -238,6 +357,210 @@ fn bigint_accel() {
     }
 }
 
+#[test]
+fn bigint_wide_accel() {
+    use crypto_bigint::{Encoding, NonZero, RandomMod, U384, U768};
+
+    fn to_words(num: &U384) -> Vec<u32> {
+        bytemuck::pod_collect_to_vec(&num.to_le_bytes())
+    }
+
+    // Base field modulus of BLS12-381.
+    let modulus = NonZero::<U384>::from_uint(U384::from_be_hex(
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+    ));
+    let mut rng = rand::thread_rng();
+    for _ in 0..4 {
+        let x = U384::random_mod(&mut rng, &modulus);
+        let y = U384::random_mod(&mut rng, &modulus);
+        let (lo, hi) = x.mul_wide(&y);
+        let expected: U384 = hi
+            .concat(&lo)
+            .rem(&NonZero::<U768>::from_uint(modulus.resize()))
+            .resize();
+
+        let input = MultiTestSpec::BigIntWide {
+            x: to_words(&x),
+            y: to_words(&y),
+            modulus: to_words(&modulus),
+        };
+        let env = ExecutorEnv::builder()
+            .write(&input)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+        let session = exec.run().unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        assert_eq!(
+            session.journal.unwrap().bytes.as_slice(),
+            expected.to_le_bytes().as_slice()
+        );
+    }
+}
+
+#[test]
+fn bigint_modpow() {
+    use crypto_bigint::{Encoding, NonZero, Random, RandomMod, U2048, U4096};
+
+    fn to_words(num: &U2048) -> Vec<u32> {
+        bytemuck::pod_collect_to_vec(&num.to_le_bytes())
+    }
+
+    let mut rng = rand::thread_rng();
+    // An RSA-2048 sized modulus, with the top bit set, and the usual public exponent.
+    let modulus =
+        NonZero::<U2048>::from_uint(U2048::random(&mut rng) | U2048::ONE.shl_vartime(2047));
+    let wide_modulus = NonZero::<U4096>::from_uint(modulus.resize());
+    let exponent = U2048::from_u32(65537);
+    let base = U2048::random_mod(&mut rng, &modulus);
+
+    let mut expected = U2048::ONE;
+    for bit in (0..exponent.bits_vartime()).rev() {
+        let (lo, hi) = expected.mul_wide(&expected);
+        expected = hi.concat(&lo).rem(&wide_modulus).resize();
+        if exponent.bit_vartime(bit) {
+            let (lo, hi) = expected.mul_wide(&base);
+            expected = hi.concat(&lo).rem(&wide_modulus).resize();
+        }
+    }
+
+    let input = MultiTestSpec::BigIntModPow {
+        base: to_words(&base),
+        exponent: to_words(&exponent),
+        modulus: to_words(&modulus),
+    };
+    let env = ExecutorEnv::builder()
+        .write(&input)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+    let session = exec.run().unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert_eq!(
+        session.journal.unwrap().bytes.as_slice(),
+        expected.to_le_bytes().as_slice()
+    );
+}
+
+#[test]
+fn log_record() {
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            std::io::Write::write(&mut *self.0.lock().unwrap(), buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buf = SharedBuf::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_max_level(tracing::Level::INFO)
+        .with_writer({
+            let buf = buf.clone();
+            move || buf.clone()
+        })
+        .finish();
+    tracing::subscriber::with_default(subscriber, || run_test(MultiTestSpec::LogRecord));
+
+    let output = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output
+        .lines()
+        .filter(|line| line.contains("risc0_zkvm::guest"))
+        .collect();
+    assert_eq!(lines.len(), 2, "{output}");
+    assert!(lines[0].contains("INFO"), "{output}");
+    assert!(lines[0].contains("hello from the guest: 42"), "{output}");
+    assert!(lines[0].contains("guest_target=\"multi_test\""), "{output}");
+    assert!(lines[1].contains("WARN"), "{output}");
+    assert!(lines[1].contains("careful"), "{output}");
+    assert!(lines[1].contains("guest_target=\"custom\""), "{output}");
+}
+
+#[test]
+fn guest_panic() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::Panic)
+        .unwrap()
+        .build()
+        .unwrap();
+    let err = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap_err();
+    let panic = err.downcast_ref::<GuestPanic>().unwrap();
+    assert_eq!(panic.message, "MultiTestSpec::Panic invoked");
+    let (file, line, _) = panic.location.as_ref().unwrap();
+    assert!(file.ends_with("multi_test.rs"), "{file}");
+    assert!(*line > 0);
+    assert!(err.to_string().contains("MultiTestSpec::Panic invoked"));
+}
+
+#[test]
+fn read_frame() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ReadFrame)
+        .unwrap()
+        .write_frame(1, 1, &(1u32, 2u32))
+        .unwrap()
+        .write_frame(1, 2, &42u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert_eq!(session.journal.unwrap().decode::<u32>().unwrap(), 42);
+}
+
+#[test]
+fn named_outputs() {
+    let mut alpha = Vec::new();
+    let mut beta = Vec::new();
+    {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::NamedOutputs)
+            .unwrap()
+            .output("alpha", &mut alpha)
+            .output("beta", &mut beta)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        assert_eq!(session.journal.unwrap().decode::<u32>().unwrap(), 1);
+    }
+    assert_eq!(alpha, b"hello world");
+    assert_eq!(
+        beta,
+        bytemuck::cast_slice::<u32, u8>(&to_vec(&42u32).unwrap())
+    );
+
+    // Outputs that are not registered by the host are rejected.
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::NamedOutputs)
+        .unwrap()
+        .output("alpha", std::io::sink())
+        .build()
+        .unwrap();
+    let err = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap_err();
+    assert!(err.to_string().contains("beta"), "{err}");
+}
+
 #[test]
 fn env_stdio() {
     const MSG: &str = "Hello world!  This is a test of standard input and output.";
@@ -474,6 +797,31 @@ mod sys_verify {
             .is_err());
     }
 
+    #[test]
+    fn sys_verify_after_resume() {
+        let hello_commit_session = exec_hello_commit();
+
+        let spec = &MultiTestSpec::PauseSysVerify(vec![(
+            HELLO_COMMIT_ID.into(),
+            hello_commit_session.journal.clone().unwrap().bytes,
+        )]);
+        let env = ExecutorEnv::builder()
+            .write(&spec)
+            .unwrap()
+            .add_assumption(hello_commit_session.get_claim().unwrap())
+            .build()
+            .unwrap();
+        let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+        let paused = exec.run().unwrap();
+        assert_eq!(paused.exit_code, ExitCode::Paused(0));
+
+        // The assumption added before the pause is still available after resuming.
+        let env = ExecutorEnv::builder().build().unwrap();
+        let session = exec.resume(env).unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        assert_eq!(session.assumptions.len(), 1);
+    }
+
     #[test]
     fn sys_verify_halt_codes() {
         for code in [0u8, 1, 2, 255] {
@@ -798,6 +1146,8 @@ fn fault() {
     let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
     let err = exec.run().err().unwrap();
     assert!(err.to_string().contains("fault"));
+    let fault = err.downcast_ref::<GuestFault>().unwrap();
+    assert_ne!(fault.pc, 0);
 }
 
 #[test]
@@ -897,6 +1247,55 @@ fn oom() {
     let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
     let err = exec.run().err().unwrap();
     assert!(err.to_string().contains("Out of memory"), "{err:?}");
+    assert!(err.to_string().contains("bytes in use"), "{err:?}");
+}
+
+#[test]
+fn heap_stats() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HeapStats)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    let heap_stats = session.heap_stats.unwrap();
+    let (used, free): (u32, u32) = session.journal.unwrap().decode().unwrap();
+    assert!(used as usize >= PAGE_SIZE);
+    assert!(free > 0);
+    // The guest reports its usage after freeing the page.
+    assert_eq!(heap_stats.used as usize, used as usize - PAGE_SIZE);
+    assert!(heap_stats.peak >= used);
+    assert!(heap_stats.free >= free);
+}
+
+#[test]
+fn threads() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::Threads)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    // Spawned threads run to completion in the order they are spawned.
+    assert_eq!(
+        session
+            .journal
+            .unwrap()
+            .decode::<(u32, Vec<u32>)>()
+            .unwrap(),
+        (30, vec![0, 1, 2, 3])
+    );
+}
+
+#[test]
+fn try_alloc() {
+    run_test(MultiTestSpec::TryAlloc);
 }
 
 #[test]
@@ -1105,6 +1504,10 @@ mod docker {
         // This test should always fail if the last parameter is zero
         let err = run_session(0, 16, 0).err().unwrap();
         assert!(err.to_string().contains("Session limit exceeded"));
+        assert_eq!(
+            err.downcast_ref::<SessionLimit>(),
+            Some(&SessionLimit { limit: 0 })
+        );
 
         assert!(run_session(0, 16, 2).is_ok());
 
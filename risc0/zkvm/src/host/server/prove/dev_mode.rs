@@ -15,7 +15,7 @@
 use anyhow::{bail, Result};
 
 use crate::{
-    host::receipt::{InnerReceipt, SegmentReceipt, SuccinctReceipt},
+    host::receipt::{InnerReceipt, ReceiptMetadata, SegmentReceipt, SuccinctReceipt},
     ProverServer, Receipt, Segment, Session, VerifierContext,
 };
 
@@ -57,7 +57,8 @@ impl ProverServer for DevModeProver {
         Ok(Receipt::new(
             InnerReceipt::Fake { claim },
             session.journal.clone().unwrap_or_default().bytes,
-        ))
+        )
+        .with_metadata(ReceiptMetadata::new("dev-mode")))
     }
 
     fn prove_segment(&self, _ctx: &VerifierContext, _segment: &Segment) -> Result<SegmentReceipt> {
@@ -19,6 +19,8 @@ mod exec;
 pub(crate) mod loader;
 mod plonk;
 mod prover_impl;
+#[cfg(feature = "remote-snark")]
+mod remote_snark;
 #[cfg(test)]
 mod tests;
 
@@ -35,13 +37,19 @@ use risc0_zkp::{
     adapter::CircuitInfo,
     core::digest::DIGEST_WORDS,
     hal::{CircuitHal, Hal},
+    security::{FriParams, ZkParams},
 };
 use risc0_zkvm_platform::WORD_SIZE;
 
+#[cfg(feature = "remote-snark")]
+pub use self::remote_snark::RemoteSnarkProver;
 use self::{dev_mode::DevModeProver, prover_impl::ProverImpl};
 use crate::{
-    host::receipt::{CompositeReceipt, InnerReceipt, SegmentReceipt, SuccinctReceipt},
-    is_dev_mode, ExecutorEnv, ExecutorImpl, ProverOpts, Receipt, Segment, Session, VerifierContext,
+    host::receipt::{
+        CompactReceipt, CompositeReceipt, InnerReceipt, SegmentReceipt, SuccinctReceipt,
+    },
+    is_dev_mode, ExecutorEnv, ExecutorImpl, ProverOpts, Receipt, ReceiptKind, Segment, Session,
+    VerifierContext,
 };
 
 /// A ProverServer can execute a given ELF binary and produce a [Receipt]
@@ -90,6 +98,28 @@ pub trait ProverServer {
     /// Convert a [SuccinctReceipt] with a Poseidon hash function that uses a 254-bit field
     fn identity_p254(&self, a: &SuccinctReceipt) -> Result<SuccinctReceipt>;
 
+    /// Compact a [SuccinctReceipt] into a [CompactReceipt] by wrapping its identity_p254 seal
+    /// in a Groth16 proof.
+    fn compact(&self, _a: &SuccinctReceipt) -> Result<CompactReceipt> {
+        bail!("compact is not supported by this prover")
+    }
+
+    /// Convert a [Receipt] produced by this prover into the given [ReceiptKind].
+    ///
+    /// Fake receipts, produced in dev mode, are returned unchanged.
+    fn compress_to_kind(&self, receipt: Receipt, kind: ReceiptKind) -> Result<Receipt> {
+        let inner = match (kind, receipt.inner) {
+            (ReceiptKind::Succinct, InnerReceipt::Composite(composite)) => {
+                InnerReceipt::Succinct(self.compress(&composite)?)
+            }
+            (ReceiptKind::Composite, inner @ InnerReceipt::Composite(_))
+            | (ReceiptKind::Succinct, inner @ InnerReceipt::Succinct(_))
+            | (_, inner @ InnerReceipt::Fake { .. }) => inner,
+            (kind, _) => bail!("Cannot convert the receipt into a {kind:?} receipt"),
+        };
+        Ok(Receipt { inner, ..receipt })
+    }
+
     /// Compress a [CompositeReceipt] into a single [SuccinctReceipt].
     ///
     /// A [CompositeReceipt] may contain an arbitrary number of receipts assembled into
@@ -273,7 +303,7 @@ mod cpu {
     use anyhow::{bail, Result};
     use risc0_circuit_rv32im::cpu::CpuCircuitHal;
     use risc0_zkp::{
-        core::hash::{poseidon::PoseidonHashSuite, sha::Sha256HashSuite},
+        core::hash::{poseidon::PoseidonHashSuite, registry, sha::Sha256HashSuite},
         hal::cpu::CpuHal,
     };
 
@@ -284,7 +314,10 @@ mod cpu {
         let suite = match opts.hashfn.as_str() {
             "sha-256" => Sha256HashSuite::new_suite(),
             "poseidon" => PoseidonHashSuite::new_suite(),
-            _ => bail!("Unsupported hashfn: {}", opts.hashfn),
+            name => match registry::get(name) {
+                Some(suite) => suite,
+                None => bail!("Unsupported hashfn: {}", opts.hashfn),
+            },
         };
         let hal = Rc::new(CpuHal::new(suite));
         let circuit_hal = Rc::new(CpuCircuitHal::new(&CIRCUIT));
@@ -301,6 +334,15 @@ pub fn get_prover_server(opts: &ProverOpts) -> Result<Rc<dyn ProverServer>> {
         return Ok(Rc::new(DevModeProver));
     }
 
+    opts.zk.validate(&FriParams::default())?;
+    if opts.zk != ZkParams::default() {
+        bail!(
+            "Unsupported zero-knowledge parameters: {:?}, only {:?} are supported",
+            opts.zk,
+            ZkParams::default()
+        );
+    }
+
     cfg_if! {
         if #[cfg(feature = "cuda")] {
             cuda::get_prover_server(opts)
@@ -12,23 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::{bail, Result};
+use std::{thread, time::Instant};
+
+use anyhow::{anyhow, bail, Result};
 use risc0_circuit_rv32im::{
     layout::{OutBuffer, LAYOUT},
-    REGISTER_GROUP_ACCUM, REGISTER_GROUP_CODE, REGISTER_GROUP_DATA,
+    CircuitImpl, REGISTER_GROUP_ACCUM, REGISTER_GROUP_CODE, REGISTER_GROUP_DATA,
 };
 use risc0_core::field::baby_bear::{BabyBear, Elem, ExtElem};
 use risc0_zkp::{
     adapter::TapsProvider,
     hal::{CircuitHal, Hal},
     layout::Buffer,
-    prove::adapter::ProveAdapter,
+    prove::{adapter::ProveAdapter, executor::Executor},
 };
 
 use super::{exec::MachineContext, HalPair, ProverServer};
 use crate::{
     host::{
-        receipt::{CompositeReceipt, InnerReceipt, SegmentReceipt, SuccinctReceipt},
+        receipt::{
+            CompositeReceipt, InnerReceipt, ReceiptMetadata, SegmentReceipt, SuccinctReceipt,
+        },
         recursion::{identity_p254, join, lift, resolve},
         CIRCUIT,
     },
@@ -58,6 +62,57 @@ where
             hal_pair,
         }
     }
+
+    /// Commits to the trace of the segment generated by `executor`, and proves
+    /// it.
+    fn prove_trace(
+        &self,
+        ctx: &VerifierContext,
+        segment: &Segment,
+        mut executor: Executor<BabyBear, CircuitImpl, MachineContext>,
+    ) -> Result<SegmentReceipt> {
+        let (hal, circuit_hal) = (self.hal_pair.hal.as_ref(), &self.hal_pair.circuit_hal);
+        let hashfn = &hal.get_hash_suite().name;
+
+        let mut adapter = ProveAdapter::new(&mut executor);
+        let mut prover = risc0_zkp::prove::Prover::new(hal, CIRCUIT.get_taps());
+
+        adapter.execute(prover.iop());
+
+        prover.set_po2(adapter.po2() as usize);
+
+        prover.commit_group(
+            REGISTER_GROUP_CODE,
+            hal.copy_from_elem("code", &adapter.get_code().as_slice()),
+        );
+        prover.commit_group(
+            REGISTER_GROUP_DATA,
+            hal.copy_from_elem("data", &adapter.get_data().as_slice()),
+        );
+        adapter.accumulate(prover.iop());
+        prover.commit_group(
+            REGISTER_GROUP_ACCUM,
+            hal.copy_from_elem("accum", &adapter.get_accum().as_slice()),
+        );
+
+        let mix = hal.copy_from_elem("mix", &adapter.get_mix().as_slice());
+        let out_slice = &adapter.get_io().as_slice();
+
+        tracing::debug!("Globals: {:?}", OutBuffer(out_slice).tree(&LAYOUT));
+        let out = hal.copy_from_elem("out", &adapter.get_io().as_slice());
+
+        let seal = prover.finalize(&[&mix, &out], circuit_hal.as_ref());
+
+        let receipt = SegmentReceipt {
+            seal,
+            index: segment.index,
+            hashfn: hashfn.clone(),
+            claim: segment.get_claim()?,
+        };
+        receipt.verify_integrity_with_context(ctx)?;
+
+        Ok(receipt)
+    }
 }
 
 impl<H, C> ProverServer for ProverImpl<H, C>
@@ -66,23 +121,52 @@ where
     C: CircuitHal<H>,
 {
     fn prove_session(&self, ctx: &VerifierContext, session: &Session) -> Result<Receipt> {
+        let start = Instant::now();
         tracing::info!(
             "prove_session: {}, exit_code = {:?}, journal = {:?}",
             self.name,
             session.exit_code,
             session.journal.as_ref().map(|x| hex::encode(x))
         );
-        let mut segments = Vec::new();
-        for segment_ref in session.segments.iter() {
-            let segment = segment_ref.resolve()?;
-            for hook in &session.hooks {
-                hook.on_pre_prove_segment(&segment);
-            }
-            segments.push(self.prove_segment(ctx, &segment)?);
-            for hook in &session.hooks {
-                hook.on_post_prove_segment(&segment);
+        // Generate the trace of the next segment on another thread while the
+        // current one is committed and proven, so that the CPU is not idle while
+        // the HAL hashes the trace, e.g. on a GPU. This holds the traces of two
+        // segments in memory at once.
+        let segments = thread::scope(|scope| {
+            let spawn_preflight = |segment: Segment| {
+                scope.spawn(move || {
+                    let executor = preflight(&segment)?;
+                    Ok::<_, anyhow::Error>((segment, executor.detach()))
+                })
+            };
+
+            let mut segment_refs = session.segments.iter();
+            let mut next = segment_refs
+                .next()
+                .map(|segment_ref| segment_ref.resolve())
+                .transpose()?
+                .map(spawn_preflight);
+            let mut segments = Vec::new();
+            while let Some(handle) = next {
+                let (segment, executor) = handle
+                    .join()
+                    .map_err(|_| anyhow!("segment preflight panicked"))??;
+                next = segment_refs
+                    .next()
+                    .map(|segment_ref| segment_ref.resolve())
+                    .transpose()?
+                    .map(spawn_preflight);
+
+                for hook in &session.hooks {
+                    hook.on_pre_prove_segment(&segment);
+                }
+                segments.push(self.prove_trace(ctx, &segment, executor.attach())?);
+                for hook in &session.hooks {
+                    hook.on_post_prove_segment(&segment);
+                }
             }
-        }
+            Ok::<_, anyhow::Error>(segments)
+        })?;
         // TODO(#982): Support unresolved assumptions here.
         let composite_receipt = CompositeReceipt {
             segments,
@@ -120,7 +204,12 @@ where
         let receipt = Receipt::new(
             inner_receipt,
             session.journal.clone().unwrap_or_default().bytes,
-        );
+        )
+        .with_metadata(ReceiptMetadata {
+            hash_suite: Some(self.hal_pair.hal.get_hash_suite().name.clone()),
+            proving_time_ms: Some(start.elapsed().as_millis() as u64),
+            ..ReceiptMetadata::new(&self.name)
+        });
 
         // Verify the receipt to catch if something is broken in the proving process.
         receipt.verify_integrity_with_context(ctx)?;
@@ -139,64 +228,7 @@ where
     }
 
     fn prove_segment(&self, ctx: &VerifierContext, segment: &Segment) -> Result<SegmentReceipt> {
-        use risc0_zkp::prove::executor::Executor;
-
-        tracing::debug!(
-            "prove_segment[{}]: po2: {}, cycles: {}",
-            segment.index,
-            segment.po2,
-            segment.cycles,
-        );
-        let (hal, circuit_hal) = (self.hal_pair.hal.as_ref(), &self.hal_pair.circuit_hal);
-        let hashfn = &hal.get_hash_suite().name;
-
-        let io = segment.prepare_globals()?;
-        let machine = MachineContext::new(segment);
-        let po2 = segment.po2 as usize;
-        let mut executor = Executor::new(&CIRCUIT, machine, po2, po2, &io);
-
-        let loader = Loader::new();
-        loader.load(|chunk, fini| executor.step(chunk, fini))?;
-        executor.finalize();
-
-        let mut adapter = ProveAdapter::new(&mut executor);
-        let mut prover = risc0_zkp::prove::Prover::new(hal, CIRCUIT.get_taps());
-
-        adapter.execute(prover.iop());
-
-        prover.set_po2(adapter.po2() as usize);
-
-        prover.commit_group(
-            REGISTER_GROUP_CODE,
-            hal.copy_from_elem("code", &adapter.get_code().as_slice()),
-        );
-        prover.commit_group(
-            REGISTER_GROUP_DATA,
-            hal.copy_from_elem("data", &adapter.get_data().as_slice()),
-        );
-        adapter.accumulate(prover.iop());
-        prover.commit_group(
-            REGISTER_GROUP_ACCUM,
-            hal.copy_from_elem("accum", &adapter.get_accum().as_slice()),
-        );
-
-        let mix = hal.copy_from_elem("mix", &adapter.get_mix().as_slice());
-        let out_slice = &adapter.get_io().as_slice();
-
-        tracing::debug!("Globals: {:?}", OutBuffer(out_slice).tree(&LAYOUT));
-        let out = hal.copy_from_elem("out", &adapter.get_io().as_slice());
-
-        let seal = prover.finalize(&[&mix, &out], circuit_hal.as_ref());
-
-        let receipt = SegmentReceipt {
-            seal,
-            index: segment.index,
-            hashfn: hashfn.clone(),
-            claim: segment.get_claim()?,
-        };
-        receipt.verify_integrity_with_context(ctx)?;
-
-        Ok(receipt)
+        self.prove_trace(ctx, segment, preflight(segment)?)
     }
 
     fn get_peak_memory_usage(&self) -> usize {
@@ -223,3 +255,22 @@ where
         identity_p254(a)
     }
 }
+
+/// Executes the segment in the circuit, generating its trace.
+fn preflight(segment: &Segment) -> Result<Executor<BabyBear, CircuitImpl, MachineContext>> {
+    tracing::debug!(
+        "prove_segment[{}]: po2: {}, cycles: {}",
+        segment.index,
+        segment.po2,
+        segment.cycles,
+    );
+    let io = segment.prepare_globals()?;
+    let machine = MachineContext::new(segment);
+    let po2 = segment.po2 as usize;
+    let mut executor = Executor::new(&CIRCUIT, machine, po2, po2, &io);
+
+    let loader = Loader::new();
+    loader.load(|chunk, fini| executor.step(chunk, fini))?;
+    executor.finalize();
+    Ok(executor)
+}
@@ -0,0 +1,144 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{rc::Rc, time::Duration};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    host::receipt::{CompactReceipt, SegmentReceipt, SuccinctReceipt},
+    ProverServer, Receipt, Segment, Session, VerifierContext,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// An implementation of a [ProverServer] that compacts receipts using a remote
+/// SNARK proving endpoint.
+///
+/// All STARK proving, including the identity_p254 recursion program, is
+/// delegated to the wrapped [ProverServer]. Only the final Groth16 proof is
+/// produced remotely, allowing hosts without the resources to run the SNARK
+/// prover to still emit receipts that can be verified on-chain.
+///
+/// ```no_run
+/// use std::rc::Rc;
+///
+/// use risc0_zkvm::{get_prover_server, ProverOpts, RemoteSnarkProver};
+///
+/// let inner = get_prover_server(&ProverOpts::default()).unwrap();
+/// let prover = Rc::new(RemoteSnarkProver::new(inner, "https://snark.example.com/prove"));
+/// ```
+pub struct RemoteSnarkProver {
+    inner: Rc<dyn ProverServer>,
+    url: String,
+    timeout: Duration,
+    ctx: VerifierContext,
+}
+
+impl RemoteSnarkProver {
+    /// Construct a [RemoteSnarkProver] that delegates to `inner` and sends
+    /// identity_p254 seals to the SNARK proving endpoint at `url`.
+    pub fn new(inner: Rc<dyn ProverServer>, url: &str) -> Self {
+        Self {
+            inner,
+            url: url.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            ctx: VerifierContext::default(),
+        }
+    }
+
+    /// Set how long to wait for the SNARK proving endpoint to reply.
+    ///
+    /// Defaults to 10 minutes.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the [VerifierContext] used to check the proofs returned by the SNARK
+    /// proving endpoint.
+    ///
+    /// This must match the context the compacted receipts will be verified
+    /// with, e.g. when using a Groth16 verifying key other than the release key.
+    pub fn with_verifier_context(mut self, ctx: VerifierContext) -> Self {
+        self.ctx = ctx;
+        self
+    }
+}
+
+impl ProverServer for RemoteSnarkProver {
+    fn prove_session(&self, ctx: &VerifierContext, session: &Session) -> Result<Receipt> {
+        self.inner.prove_session(ctx, session)
+    }
+
+    fn prove_segment(&self, ctx: &VerifierContext, segment: &Segment) -> Result<SegmentReceipt> {
+        self.inner.prove_segment(ctx, segment)
+    }
+
+    fn get_peak_memory_usage(&self) -> usize {
+        self.inner.get_peak_memory_usage()
+    }
+
+    fn lift(&self, receipt: &SegmentReceipt) -> Result<SuccinctReceipt> {
+        self.inner.lift(receipt)
+    }
+
+    fn join(&self, a: &SuccinctReceipt, b: &SuccinctReceipt) -> Result<SuccinctReceipt> {
+        self.inner.join(a, b)
+    }
+
+    fn resolve(
+        &self,
+        conditional: &SuccinctReceipt,
+        assumption: &SuccinctReceipt,
+    ) -> Result<SuccinctReceipt> {
+        self.inner.resolve(conditional, assumption)
+    }
+
+    fn identity_p254(&self, a: &SuccinctReceipt) -> Result<SuccinctReceipt> {
+        self.inner.identity_p254(a)
+    }
+
+    fn compact(&self, a: &SuccinctReceipt) -> Result<CompactReceipt> {
+        let ident_receipt = self.identity_p254(a)?;
+        let seal_bytes = ident_receipt.get_seal_bytes();
+
+        // Run the request on its own thread and runtime, so that it neither
+        // blocks nor nests within a runtime the caller may be running on.
+        let seal = std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()?
+                        .block_on(risc0_groth16::remote::stark_to_snark(
+                            &self.url,
+                            self.timeout,
+                            &seal_bytes,
+                        ))
+                })
+                .join()
+                .map_err(|_| anyhow!("remote SNARK request panicked"))?
+        })?;
+        let receipt = CompactReceipt {
+            seal: seal.to_vec(),
+            claim: a.claim.clone(),
+        };
+
+        // Verify the receipt to catch a misbehaving remote prover.
+        receipt.verify_integrity_with_context(&self.ctx)?;
+
+        Ok(receipt)
+    }
+}
@@ -20,6 +20,7 @@ use risc0_zkp::{
     core::{digest::Digest, hash::blake2b::Blake2bCpuHashSuite},
     hal::cpu::CpuHal,
     verify::VerificationError,
+    ZK_CYCLES,
 };
 use risc0_zkvm_methods::{multi_test::MultiTestSpec, MULTI_TEST_ELF, MULTI_TEST_ID};
 use risc0_zkvm_platform::{memory, WORD_SIZE};
@@ -28,16 +29,18 @@ use test_log::test;
 
 use super::{get_prover_server, HalPair, ProverImpl};
 use crate::{
-    host::{server::testutils, CIRCUIT},
+    host::{client::prove::local::LocalProver, server::testutils, CIRCUIT},
     serde::{from_slice, to_vec},
-    ExecutorEnv, ExecutorImpl, ExitCode, ProverOpts, ProverServer, Receipt, Session,
-    VerifierContext,
+    sha::Digestible,
+    CompositeReceipt, ExecutorEnv, ExecutorImpl, ExitCode, InnerReceipt, Prover, ProverOpts,
+    ProverServer, Receipt, SegmentReceipt, Session, VerifierContext, ZkParams,
 };
 
 fn prover_opts_fast() -> ProverOpts {
     ProverOpts {
         hashfn: "sha-256".to_string(),
         prove_guest_errors: false,
+        ..Default::default()
     }
 }
 
@@ -57,6 +60,7 @@ fn prove_nothing(hashfn: &str) -> Result<Receipt> {
     let opts = ProverOpts {
         hashfn: hashfn.to_string(),
         prove_guest_errors: false,
+        ..Default::default()
     };
     get_prover_server(&opts).unwrap().prove(env, MULTI_TEST_ELF)
 }
@@ -82,6 +86,89 @@ fn hashfn_blake2b() {
     prover.prove(env, MULTI_TEST_ELF).unwrap();
 }
 
+#[test]
+#[cfg_attr(feature = "cuda", serial)]
+fn hashfn_registered() {
+    use hex::FromHex;
+    use risc0_zkp::core::hash::{registry, sha::Sha256HashSuite, HashSuite};
+
+    use crate::host::control_id::SHA256_CONTROL_ID;
+
+    // A suite computing the same hash as SHA-256 under another name, so that
+    // its control IDs are the ones of SHA-256.
+    fn suite() -> HashSuite<risc0_core::field::baby_bear::BabyBear> {
+        HashSuite {
+            name: "prove-test".into(),
+            ..Sha256HashSuite::new_suite()
+        }
+    }
+
+    let control_ids: Vec<Digest> = SHA256_CONTROL_ID
+        .into_iter()
+        .map(|id| Digest::from_hex(id).unwrap())
+        .collect();
+    registry::register("prove-test", suite, &control_ids).unwrap();
+
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .build()
+        .unwrap();
+    let opts = ProverOpts {
+        hashfn: "prove-test".to_string(),
+        ..prover_opts_fast()
+    };
+    let ctx = VerifierContext::default().with_registered_suites();
+    let receipt = get_prover_server(&opts)
+        .unwrap()
+        .prove_with_ctx(env, &ctx, MULTI_TEST_ELF)
+        .unwrap();
+    let segment = &receipt.inner.composite().unwrap().segments[0];
+    assert_eq!(segment.hashfn, "prove-test");
+    receipt.verify_with_context(&ctx, MULTI_TEST_ID).unwrap();
+
+    // The suite is only accepted by the verifiers that add the registered suites.
+    assert_eq!(
+        receipt.verify(MULTI_TEST_ID).unwrap_err(),
+        VerificationError::InvalidHashSuite
+    );
+}
+
+#[test]
+fn unsupported_zk_params() {
+    let opts = ProverOpts {
+        zk: ZkParams::new(2 * ZK_CYCLES),
+        ..prover_opts_fast()
+    };
+    assert!(get_prover_server(&opts).is_err());
+
+    let opts = ProverOpts {
+        zk: ZkParams::new(10),
+        ..prover_opts_fast()
+    };
+    assert!(get_prover_server(&opts).is_err());
+}
+
+#[test]
+#[cfg_attr(feature = "cuda", serial)]
+fn max_privacy() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .build()
+        .unwrap();
+    let receipt = LocalProver::new("local")
+        .prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            MULTI_TEST_ELF,
+            &ProverOpts::max_privacy(),
+        )
+        .unwrap();
+    assert!(matches!(receipt.inner, InnerReceipt::Succinct(_)));
+    receipt.verify(MULTI_TEST_ID).unwrap();
+}
+
 #[test]
 #[cfg_attr(feature = "cuda", serial)]
 fn receipt_serde() {
@@ -92,6 +179,79 @@ fn receipt_serde() {
     decoded.verify(MULTI_TEST_ID).unwrap();
 }
 
+#[test]
+#[cfg_attr(feature = "cuda", serial)]
+fn composite_stream() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::BusyLoop { cycles: 1 << 16 })
+        .unwrap()
+        .segment_limit_po2(16)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    let receipt = prove_session_fast(&session);
+    let composite = receipt.inner.composite().unwrap();
+    assert!(composite.segments.len() > 1);
+
+    let mut stream = Vec::new();
+    composite.write_stream(&mut stream).unwrap();
+    let ctx = VerifierContext::default();
+    let claim = CompositeReceipt::verify_integrity_from_reader(&ctx, stream.as_slice()).unwrap();
+    assert_eq!(claim.digest(), receipt.inner.get_claim().unwrap().digest());
+
+    let truncated = &stream[..stream.len() - WORD_SIZE];
+    assert_eq!(
+        CompositeReceipt::verify_integrity_from_reader(&ctx, truncated).unwrap_err(),
+        VerificationError::ReceiptFormatError
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "cuda", serial)]
+fn segment_verify_integrity() {
+    let receipt = prove_nothing("sha-256").unwrap();
+    let mut segment = receipt.inner.composite().unwrap().segments[0].clone();
+    segment.verify_integrity().unwrap();
+
+    // The index is not bound by the seal.
+    segment.index += 1;
+    segment.verify_integrity().unwrap();
+
+    segment.claim.exit_code = ExitCode::Halted(1);
+    assert_eq!(
+        segment.verify_integrity().unwrap_err(),
+        VerificationError::ReceiptFormatError
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "cuda", serial)]
+fn segment_verify_integrity_batch() {
+    let ctx = VerifierContext::default();
+    let mut segments: Vec<SegmentReceipt> = ["sha-256", "poseidon", "sha-256"]
+        .into_iter()
+        .map(|hashfn| {
+            let receipt = prove_nothing(hashfn).unwrap();
+            receipt.inner.composite().unwrap().segments[0].clone()
+        })
+        .collect();
+    SegmentReceipt::verify_integrity_batch(&segments, &ctx).unwrap();
+
+    segments[2].claim.exit_code = ExitCode::Halted(1);
+    assert_eq!(
+        SegmentReceipt::verify_integrity_batch(&segments, &ctx).unwrap_err(),
+        VerificationError::ReceiptFormatError
+    );
+
+    segments[2] = segments[0].clone();
+    let last = segments[1].seal.len() - 1;
+    segments[1].seal[last] ^= 1;
+    assert!(SegmentReceipt::verify_integrity_batch(&segments, &ctx).is_err());
+}
+
 #[test]
 #[cfg_attr(feature = "cuda", serial)]
 fn check_image_id() {
@@ -453,6 +613,7 @@ mod docker {
 }
 
 mod sys_verify {
+    use risc0_zkp::{core::digest::Digest, verify::VerificationError};
     use risc0_zkvm_methods::{
         multi_test::MultiTestSpec, HELLO_COMMIT_ELF, HELLO_COMMIT_ID, MULTI_TEST_ELF, MULTI_TEST_ID,
     };
@@ -479,6 +640,7 @@ mod sys_verify {
         let opts = ProverOpts {
             hashfn: "sha-256".to_string(),
             prove_guest_errors: true,
+            ..Default::default()
         };
 
         let env = ExecutorEnvBuilder::default()
@@ -505,6 +667,22 @@ mod sys_verify {
         static ref HELLO_COMMIT_RECEIPT: Receipt = prove_hello_commit();
     }
 
+    #[test]
+    fn detached_journal() {
+        let receipt = HELLO_COMMIT_RECEIPT.clone();
+        receipt
+            .inner
+            .verify_with_journal_digest(HELLO_COMMIT_ID, receipt.journal.digest())
+            .unwrap();
+        assert_eq!(
+            receipt
+                .inner
+                .verify_with_journal_digest(HELLO_COMMIT_ID, Digest::ZERO)
+                .unwrap_err(),
+            VerificationError::JournalDigestMismatch
+        );
+    }
+
     #[test]
     fn sys_verify() {
         let spec = &MultiTestSpec::SysVerify(vec![(
@@ -23,7 +23,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use human_repr::HumanCount;
 use risc0_binfmt::{MemoryImage, SystemState};
 use risc0_zkvm_platform::WORD_SIZE;
@@ -31,7 +31,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     host::server::exec::executor::SyscallRecord, sha::Digest, Assumption, Assumptions, ExitCode,
-    Journal, Output, ReceiptClaim,
+    HeapStats, Journal, Output, ReceiptClaim,
 };
 
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
@@ -66,11 +66,47 @@ pub struct Session {
     /// The list of assumptions made by the guest and resolved by the host.
     pub assumptions: Vec<Assumption>,
 
+    /// The usage of the guest heap, as last reported by the guest with
+    /// `risc0_zkvm::guest::env::report_heap_stats`, if it did.
+    #[serde(default, with = "heap_stats_serde")]
+    pub heap_stats: Option<HeapStats>,
+
     /// The hooks to be called during the proving phase.
     #[serde(skip)]
     pub hooks: Vec<Box<dyn SessionEvents>>,
 }
 
+/// Serializes the [HeapStats] of a [Session], which are defined by
+/// `risc0-zkvm-platform` without serde.
+mod heap_stats_serde {
+    use risc0_zkvm_platform::heap::HeapStats;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "HeapStats")]
+    struct HeapStatsDef {
+        used: u32,
+        peak: u32,
+        free: u32,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "HeapStatsDef")] HeapStats);
+
+    pub fn serialize<S: Serializer>(
+        stats: &Option<HeapStats>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        stats.map(Wrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<HeapStats>, D::Error> {
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(stats)| stats))
+    }
+}
+
 /// A reference to a [Segment].
 ///
 /// This allows implementors to determine the best way to represent this in an
@@ -139,6 +175,7 @@ impl Session {
             exit_code,
             post_image,
             assumptions,
+            heap_stats: None,
             hooks: Vec::new(),
         }
     }
@@ -233,6 +270,24 @@ impl Session {
         })
     }
 
+    /// Returns the [MemoryImage] to resume this [Session] from, if the guest paused.
+    ///
+    /// An [ExecutorImpl](crate::ExecutorImpl) constructed from this image continues the execution
+    /// after the call to `env::pause`, e.g. in another process, with new inputs. Within the same
+    /// process, [ExecutorImpl::resume](crate::ExecutorImpl::resume) can be used instead.
+    pub fn resume_image(&self) -> Result<MemoryImage> {
+        let ExitCode::Paused(_) = self.exit_code else {
+            bail!(
+                "cannot resume a session which exited with {:?}",
+                self.exit_code
+            );
+        };
+        let mut image = self.post_image.clone();
+        // Move the pc forward by WORD_SIZE because pause does not.
+        image.pc += WORD_SIZE as u32;
+        Ok(image)
+    }
+
     /// Report cycle information for this [Session].
     ///
     /// Returns a tuple `(x, y)` where:
@@ -277,6 +332,14 @@ impl Session {
             "cycle efficiency: {}%",
             cycles_used_ratio.human_count_bare()
         );
+        if let Some(heap_stats) = &self.heap_stats {
+            tracing::info!(
+                "guest heap: {} bytes used, {} bytes peak, {} bytes free",
+                heap_stats.used.human_count_bare(),
+                heap_stats.peak.human_count_bare(),
+                heap_stats.free.human_count_bare()
+            );
+        }
 
         Ok(())
     }
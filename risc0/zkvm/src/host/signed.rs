@@ -0,0 +1,269 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receipts signed by their prover.
+//!
+//! A [SignedReceipt] attaches to a receipt an ed25519 signature of its claim
+//! digest, made by the proving service with a key it is configured with. The
+//! signature does not add to the soundness of the proof, but lets verifiers
+//! check which service produced a receipt, e.g. to keep a chain of custody.
+//! [SignedReceipt::verify] checks both the proof and the signature.
+//!
+//! A proving service configured with a key can wrap its [Prover] in a
+//! [SigningProver], which signs every receipt it proves.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "client")]
+use alloc::rc::Rc;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+use serde::{Deserialize, Serialize};
+
+use super::receipt::{Receipt, VerifierContext};
+use crate::sha::Digestible;
+#[cfg(feature = "client")]
+use crate::{ExecutorEnv, Prover, ProverOpts};
+
+// Domain separation tag of the signed message.
+const SIGNATURE_DOMAIN: &[u8] = b"risc0.SignedReceipt";
+
+/// A [Receipt] along with a signature of its claim digest by the prover.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct SignedReceipt {
+    /// The signed receipt.
+    pub receipt: Receipt,
+
+    /// Public key of the signer, as an encoded ed25519 verifying key.
+    pub signer: [u8; 32],
+
+    /// Signature of the claim digest, as an encoded ed25519 signature.
+    pub signature: Vec<u8>,
+}
+
+impl SignedReceipt {
+    /// Sign the claim digest of a receipt with the given key.
+    pub fn sign(receipt: Receipt, key: &SigningKey) -> Result<Self, VerificationError> {
+        let message = signed_message(&receipt.inner.get_claim()?.digest());
+        Ok(Self {
+            signer: key.verifying_key().to_bytes(),
+            signature: key.sign(&message).to_bytes().to_vec(),
+            receipt,
+        })
+    }
+
+    /// Check that the receipt was signed by one of the trusted keys.
+    ///
+    /// This only checks the signature. Use [SignedReceipt::verify] to also
+    /// verify the receipt.
+    pub fn verify_signature(&self, trusted: &[VerifyingKey]) -> Result<(), SignedReceiptError> {
+        let signer = trusted
+            .iter()
+            .find(|key| key.as_bytes() == &self.signer)
+            .ok_or(SignedReceiptError::UntrustedSigner)?;
+        let signature =
+            Signature::from_slice(&self.signature).map_err(|_| SignedReceiptError::BadSignature)?;
+        let claim_digest = self
+            .receipt
+            .inner
+            .get_claim()
+            .map_err(SignedReceiptError::Verify)?
+            .digest();
+        signer
+            .verify_strict(&signed_message(&claim_digest), &signature)
+            .map_err(|_| SignedReceiptError::BadSignature)
+    }
+
+    /// Verify the receipt with [Receipt::verify], and check that it was signed
+    /// by one of the trusted keys.
+    pub fn verify(
+        &self,
+        image_id: impl Into<Digest>,
+        trusted: &[VerifyingKey],
+    ) -> Result<(), SignedReceiptError> {
+        self.verify_with_context(&VerifierContext::default(), image_id, trusted)
+    }
+
+    /// Verify the receipt with [Receipt::verify_with_context], and check that
+    /// it was signed by one of the trusted keys.
+    pub fn verify_with_context(
+        &self,
+        ctx: &VerifierContext,
+        image_id: impl Into<Digest>,
+        trusted: &[VerifyingKey],
+    ) -> Result<(), SignedReceiptError> {
+        self.verify_signature(trusted)?;
+        self.receipt
+            .verify_with_context(ctx, image_id)
+            .map_err(SignedReceiptError::Verify)
+    }
+}
+
+/// A [Prover] wrapper which signs the receipts it proves with a configured key.
+///
+/// ```no_run
+/// use risc0_zkvm::{default_prover, ed25519_dalek::SigningKey, SigningProver};
+///
+/// let key = SigningKey::from_bytes(&[1; 32]);
+/// let prover = SigningProver::new(default_prover(), key);
+/// ```
+#[cfg(feature = "client")]
+pub struct SigningProver {
+    inner: Rc<dyn Prover>,
+    key: SigningKey,
+}
+
+#[cfg(feature = "client")]
+impl SigningProver {
+    /// Construct a [SigningProver] that proves with `inner` and signs the
+    /// resulting receipts with `key`.
+    pub fn new(inner: Rc<dyn Prover>, key: SigningKey) -> Self {
+        Self { inner, key }
+    }
+
+    /// Return the key verifiers should trust to check the signed receipts.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// Prove zkVM execution starting from the specified ELF binary, and sign
+    /// the receipt.
+    pub fn prove(&self, env: ExecutorEnv<'_>, elf: &[u8]) -> anyhow::Result<SignedReceipt> {
+        self.prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            elf,
+            &ProverOpts::default(),
+        )
+    }
+
+    /// Prove zkVM execution starting from the specified ELF binary with the
+    /// specified [VerifierContext] and [ProverOpts], and sign the receipt.
+    pub fn prove_with_ctx(
+        &self,
+        env: ExecutorEnv<'_>,
+        ctx: &VerifierContext,
+        elf: &[u8],
+        opts: &ProverOpts,
+    ) -> anyhow::Result<SignedReceipt> {
+        let receipt = self.inner.prove_with_ctx(env, ctx, elf, opts)?;
+        Ok(SignedReceipt::sign(receipt, &self.key)?)
+    }
+}
+
+fn signed_message(claim_digest: &Digest) -> Vec<u8> {
+    [SIGNATURE_DOMAIN, claim_digest.as_bytes()].concat()
+}
+
+/// Error returned when verifying a [SignedReceipt].
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SignedReceiptError {
+    /// The receipt was signed by a key which is not trusted.
+    UntrustedSigner,
+    /// The signature is malformed, or does not match the claim of the receipt.
+    BadSignature,
+    /// The receipt failed verification.
+    Verify(VerificationError),
+}
+
+impl fmt::Display for SignedReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UntrustedSigner => write!(f, "receipt signed by an untrusted key"),
+            Self::BadSignature => write!(f, "invalid receipt signature"),
+            Self::Verify(err) => write!(f, "signed receipt failed verification: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SignedReceiptError {}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::SigningKey;
+    use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+
+    use super::{SignedReceipt, SignedReceiptError};
+    use crate::{host::testutils::fake_receipt, ExitCode, VerifierContext};
+
+    #[test]
+    fn signature() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let other = SigningKey::from_bytes(&[2; 32]);
+        let signed =
+            SignedReceipt::sign(fake_receipt(ExitCode::Halted(0), b"journal"), &key).unwrap();
+
+        signed.verify_signature(&[key.verifying_key()]).unwrap();
+        assert_eq!(
+            signed.verify_signature(&[other.verifying_key()]),
+            Err(SignedReceiptError::UntrustedSigner)
+        );
+
+        let ctx = VerifierContext::default().with_strict(true);
+        assert_eq!(
+            signed.verify_with_context(&ctx, Digest::ZERO, &[key.verifying_key()]),
+            Err(SignedReceiptError::Verify(VerificationError::FakeReceipt))
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn signing_prover() {
+        use std::rc::Rc;
+
+        use anyhow::Result;
+
+        use super::SigningProver;
+        use crate::{ExecutorEnv, Prover, ProverOpts, Receipt};
+
+        struct FakeProver;
+
+        impl Prover for FakeProver {
+            fn get_name(&self) -> String {
+                "fake".to_string()
+            }
+
+            fn prove_with_ctx(
+                &self,
+                _env: ExecutorEnv<'_>,
+                _ctx: &VerifierContext,
+                _elf: &[u8],
+                _opts: &ProverOpts,
+            ) -> Result<Receipt> {
+                Ok(fake_receipt(ExitCode::Halted(0), b"journal"))
+            }
+        }
+
+        let prover = SigningProver::new(Rc::new(FakeProver), SigningKey::from_bytes(&[1; 32]));
+        let signed = prover.prove(ExecutorEnv::default(), &[]).unwrap();
+        assert_eq!(signed.receipt.journal.bytes, b"journal");
+        signed.verify_signature(&[prover.verifying_key()]).unwrap();
+    }
+
+    #[test]
+    fn swapped_receipt() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let mut signed =
+            SignedReceipt::sign(fake_receipt(ExitCode::Halted(0), b"journal"), &key).unwrap();
+        signed.receipt = fake_receipt(ExitCode::Halted(0), b"another journal");
+        assert_eq!(
+            signed.verify_signature(&[key.verifying_key()]),
+            Err(SignedReceiptError::BadSignature)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkp::core::digest::Digest;
+
+use crate::{ExitCode, InnerReceipt, Receipt, ReceiptClaim};
+
+/// Returns a fake receipt of an execution of the zero image ID with the given
+/// exit code and journal.
+pub(crate) fn fake_receipt(exit_code: ExitCode, journal: &[u8]) -> Receipt {
+    let claim = ReceiptClaim::new(Digest::ZERO, Digest::ZERO, exit_code, journal.to_vec());
+    Receipt::new(InnerReceipt::Fake { claim }, journal.to_vec())
+}
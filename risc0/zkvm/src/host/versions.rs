@@ -0,0 +1,69 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versions of the circuits and keys accepted by this release.
+
+use alloc::string::String;
+
+use hex::FromHex;
+use risc0_circuit_recursion::control_id::ALLOWED_IDS_ROOT;
+use risc0_zkp::core::digest::Digest;
+use serde::{Deserialize, Serialize};
+
+use crate::sha;
+
+/// Versions of the circuits and keys of this release, returned by [versions].
+///
+/// Deployment tooling can compare these with the values of a deployed
+/// verifier, e.g. an on-chain Groth16 verifier, to check that it accepts the
+/// receipts produced by this release.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Versions {
+    /// Version of the zkVM crate.
+    pub zkvm: String,
+    /// Digest of the taps and constraints of the rv32im circuit, which proves
+    /// the segments, see [risc0_circuit_rv32im::CircuitImpl::digest].
+    pub rv32im_circuit: Digest,
+    /// Root of the Merkle tree of the allowed recursion control IDs.
+    pub recursion_control_root: Digest,
+    /// Digest of the default Groth16 verifying key, see
+    /// [risc0_groth16::VerifyingKey::digest].
+    pub groth16_verifying_key: Digest,
+}
+
+/// Returns the versions of the circuits and keys of this release.
+pub fn versions() -> Versions {
+    let groth16_verifying_key = risc0_groth16::verifier::verifying_key()
+        .and_then(|vk| vk.digest())
+        .expect("the default Groth16 verifying key is valid");
+    Versions {
+        zkvm: crate::VERSION.into(),
+        rv32im_circuit: risc0_circuit_rv32im::CircuitImpl::new().digest::<sha::Impl>(),
+        recursion_control_root: Digest::from_hex(ALLOWED_IDS_ROOT).unwrap(),
+        groth16_verifying_key,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{versions, Versions};
+
+    #[test]
+    fn serde() {
+        let versions = versions();
+        let json = serde_json::to_string(&versions).unwrap();
+        assert_eq!(serde_json::from_str::<Versions>(&json).unwrap(), versions);
+    }
+}
@@ -36,9 +36,19 @@ pub use anyhow::Result;
 #[cfg(not(target_os = "zkvm"))]
 #[cfg(any(feature = "client", feature = "prove"))]
 pub use bytes::Bytes;
+#[cfg(all(not(target_os = "zkvm"), feature = "signed"))]
+pub use ed25519_dalek;
 pub use risc0_binfmt::SystemState;
-pub use risc0_zkvm_platform::{declare_syscall, memory::GUEST_MAX_MEM, PAGE_SIZE};
+pub use risc0_zkvm_platform::{declare_syscall, heap::HeapStats, memory::GUEST_MAX_MEM, PAGE_SIZE};
 
+#[cfg(all(not(target_os = "zkvm"), feature = "envelope"))]
+pub use self::host::envelope::{EncryptedReceipt, EnvelopeError};
+#[cfg(all(not(target_os = "zkvm"), feature = "remote-snark"))]
+pub use self::host::server::prove::RemoteSnarkProver;
+#[cfg(all(not(target_os = "zkvm"), feature = "signed", feature = "client"))]
+pub use self::host::signed::SigningProver;
+#[cfg(all(not(target_os = "zkvm"), feature = "signed"))]
+pub use self::host::signed::{SignedReceipt, SignedReceiptError};
 #[cfg(all(not(target_os = "zkvm"), feature = "prove"))]
 pub use self::host::{
     api::server::Server as ApiServer,
@@ -54,10 +64,10 @@ pub use self::host::{
     api::{client::Client as ApiClient, Asset, AssetRequest, Connector, SegmentInfo, SessionInfo},
     client::{
         env::{ExecutorEnv, ExecutorEnvBuilder},
-        exec::TraceEvent,
+        exec::{GuestFault, GuestPanic, SessionLimit, TraceEvent},
         prove::{
             bonsai::BonsaiProver, default_executor, default_prover, external::ExternalProver,
-            Executor, Prover, ProverOpts,
+            Executor, Prover, ProverOpts, ReceiptKind,
         },
     },
 };
@@ -69,13 +79,17 @@ pub use self::receipt_claim::{
 pub use {
     self::host::{
         control_id::POSEIDON_CONTROL_ID,
+        inspect::{ReceiptDiff, ReceiptSummary},
         receipt::{
             Assumption, CompactReceipt, CompositeReceipt, InnerReceipt, Journal, Receipt,
-            SegmentReceipt, SuccinctReceipt, VerifierContext,
+            ReceiptDecodeError, ReceiptMetadata, SegmentReceipt, SuccinctReceipt, VerifierContext,
+            VerifierParameters,
         },
         recursion::ALLOWED_IDS_ROOT,
+        versions::{versions, Versions},
     },
     risc0_binfmt::compute_image_id,
+    risc0_zkp::security::{FriParams, ZkParams},
 };
 
 use semver::Version;
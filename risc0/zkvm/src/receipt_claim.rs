@@ -74,6 +74,48 @@ pub struct ReceiptClaim {
 }
 
 impl ReceiptClaim {
+    /// Construct a [ReceiptClaim] for an execution of the given image, with no assumptions.
+    ///
+    /// This is the claim checked by `env::verify`, and by verifiers outside the zkVM, such as
+    /// on-chain contracts, which only know the image ID, the digest of the post state and the
+    /// journal. The claim digest of a [crate::Receipt] produced for this execution is equal to
+    /// the digest of the returned claim.
+    pub fn new(
+        image_id: impl Into<Digest>,
+        post_state_digest: impl Into<Digest>,
+        exit_code: ExitCode,
+        journal: impl Into<MaybePruned<Vec<u8>>>,
+    ) -> Self {
+        Self {
+            pre: MaybePruned::Pruned(image_id.into()),
+            post: MaybePruned::Pruned(post_state_digest.into()),
+            exit_code,
+            input: Digest::ZERO,
+            output: Some(Output::new(journal)).into(),
+        }
+    }
+
+    /// Returns the image ID, which is the digest of the pre state.
+    pub fn image_id(&self) -> Digest {
+        self.pre.digest::<sha::Impl>()
+    }
+
+    /// Returns the digest of the post state.
+    pub fn post_state_digest(&self) -> Digest {
+        self.post.digest::<sha::Impl>()
+    }
+
+    /// Returns the digest of the journal, or `None` if the execution has no [Output].
+    ///
+    /// Returns an error if the output is pruned, as the journal digest cannot be recovered.
+    pub fn journal_digest(&self) -> Result<Option<Digest>, PrunedValueError> {
+        Ok(self
+            .output
+            .as_value()?
+            .as_ref()
+            .map(|output| output.journal.digest::<sha::Impl>()))
+    }
+
     /// Decode a [ReceiptClaim] from a list of [u32]'s
     pub fn decode(flat: &mut VecDeque<u32>) -> Result<Self, DecodeError> {
         let input = read_sha_halfs(flat)?;
@@ -254,6 +296,7 @@ impl std::error::Error for InvalidExitCodeError {}
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Output {
     /// The journal committed to by the guest execution.
+    #[serde(with = "crate::serde::hex_bytes::maybe_pruned")]
     pub journal: MaybePruned<Vec<u8>>,
 
     /// An ordered list of [ReceiptClaim] digests corresponding to the
@@ -267,6 +310,16 @@ pub struct Output {
     pub assumptions: MaybePruned<Assumptions>,
 }
 
+impl Output {
+    /// Construct an [Output] with the given journal and no assumptions.
+    pub fn new(journal: impl Into<MaybePruned<Vec<u8>>>) -> Self {
+        Self {
+            journal: journal.into(),
+            assumptions: MaybePruned::Value(Assumptions::default()),
+        }
+    }
+}
+
 impl Digestible for Output {
     /// Hash the [Output] to get a digest of the struct.
     fn digest<S: Sha256>(&self) -> Digest {
@@ -429,6 +482,18 @@ where
     }
 }
 
+impl<T> MaybePruned<T>
+where
+    T: Digestible + Clone + Serialize,
+{
+    /// Prune the value, replacing it with its digest.
+    ///
+    /// The digest of the result is the same as the digest of `self`.
+    pub fn prune(&self) -> Self {
+        MaybePruned::Pruned(self.digest::<sha::Impl>())
+    }
+}
+
 impl<T> From<T> for MaybePruned<T>
 where
     T: Clone + Serialize,
@@ -745,6 +810,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn new_receipt_claim() {
+        let image_id = Digest::from([1u32; 8]);
+        let post = SystemState {
+            pc: 4,
+            merkle_root: Digest::from([2u32; 8]),
+        };
+        let journal = b"hello world".to_vec();
+
+        let claim = ReceiptClaim {
+            pre: MaybePruned::Pruned(image_id),
+            post: post.clone().into(),
+            exit_code: ExitCode::Halted(0),
+            input: Digest::ZERO,
+            output: MaybePruned::Value(Some(Output {
+                journal: MaybePruned::Value(journal.clone()),
+                assumptions: MaybePruned::Pruned(Digest::ZERO),
+            })),
+        };
+        let built = ReceiptClaim::new(
+            image_id,
+            post.digest(),
+            ExitCode::Halted(0),
+            journal.clone(),
+        );
+        assert_eq!(built.digest(), claim.digest());
+        assert_eq!(built.image_id(), image_id);
+        assert_eq!(built.post_state_digest(), post.digest());
+        assert_eq!(built.journal_digest().unwrap(), Some(journal.digest()));
+
+        let pruned = MaybePruned::Value(built).prune();
+        assert_eq!(pruned.digest(), claim.digest());
+        assert!(pruned.as_value().is_err());
+    }
+
     #[test]
     fn merge_receipt_claim() {
         let claim = MaybePruned::Value(ReceiptClaim {
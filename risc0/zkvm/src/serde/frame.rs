@@ -0,0 +1,146 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{from_slice, to_vec, Error, Result};
+
+/// Header of a framed value.
+///
+/// A frame is the header, encoded as [FrameHeader::WORDS] words, followed by
+/// the value serialized with [to_vec]. The tag identifies the type of the
+/// value, and the version its schema, so that a guest can reject inputs it
+/// does not understand instead of misdeserializing them. The length allows
+/// skipping the body of a rejected frame, keeping the input stream in sync.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FrameHeader {
+    /// Identifier of the type of the value.
+    pub tag: u32,
+    /// Version of the schema of the value.
+    pub version: u32,
+    /// Length of the body, in words.
+    pub len: u32,
+}
+
+impl FrameHeader {
+    /// Length of an encoded header, in words.
+    pub const WORDS: usize = 3;
+
+    /// Encodes the header as words.
+    pub fn to_words(&self) -> [u32; Self::WORDS] {
+        [self.tag, self.version, self.len]
+    }
+
+    /// Decodes a header from words.
+    pub fn from_words(words: [u32; Self::WORDS]) -> Self {
+        let [tag, version, len] = words;
+        Self { tag, version, len }
+    }
+
+    /// Checks that the header has the expected tag and version.
+    pub fn check(&self, tag: u32, version: u32) -> core::result::Result<(), FrameError> {
+        if self.tag != tag {
+            return Err(FrameError::TagMismatch {
+                expected: tag,
+                found: self.tag,
+            });
+        }
+        if self.version != version {
+            return Err(FrameError::VersionMismatch {
+                expected: version,
+                found: self.version,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Serializes a value into a frame with the given tag and version.
+pub fn to_frame<T: Serialize + ?Sized>(tag: u32, version: u32, value: &T) -> Result<Vec<u32>> {
+    let body = to_vec(value)?;
+    let len = body.len().try_into().map_err(|_| Error::NotSupported)?;
+    let header = FrameHeader { tag, version, len };
+    let mut frame = Vec::with_capacity(FrameHeader::WORDS + body.len());
+    frame.extend_from_slice(&header.to_words());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
+/// Deserializes a value from a frame, checking its tag and version.
+pub fn from_frame<T: DeserializeOwned>(
+    tag: u32,
+    version: u32,
+    frame: &[u32],
+) -> core::result::Result<T, FrameError> {
+    if frame.len() < FrameHeader::WORDS {
+        return Err(Error::DeserializeUnexpectedEnd.into());
+    }
+    let (header, body) = frame.split_at(FrameHeader::WORDS);
+    let header = FrameHeader::from_words(header.try_into().unwrap());
+    header.check(tag, version)?;
+    if body.len() != header.len as usize {
+        return Err(Error::DeserializeUnexpectedEnd.into());
+    }
+    Ok(from_slice(body)?)
+}
+
+/// Error returned when reading a frame.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FrameError {
+    /// The frame holds a value of another type.
+    TagMismatch {
+        /// The tag of the requested type.
+        expected: u32,
+        /// The tag of the frame.
+        found: u32,
+    },
+    /// The frame holds a value of another version.
+    VersionMismatch {
+        /// The requested version.
+        expected: u32,
+        /// The version of the frame.
+        found: u32,
+    },
+    /// The body of the frame could not be deserialized.
+    Deserialize(Error),
+}
+
+impl From<Error> for FrameError {
+    fn from(err: Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::TagMismatch { expected, found } => {
+                write!(f, "Frame tag mismatch: expected {expected}, found {found}")
+            }
+            Self::VersionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Frame version mismatch: expected {expected}, found {found}"
+                )
+            }
+            Self::Deserialize(err) => write!(f, "Failed to deserialize frame: {err}"),
+        }
+    }
+}
+
+// This is an alias for either std::Error, or serde's no_std error replacement.
+impl serde::ser::StdError for FrameError {}
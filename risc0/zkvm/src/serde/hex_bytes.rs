@@ -0,0 +1,165 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serde helpers encoding byte fields as hex strings in human-readable formats.
+//!
+//! Derived implementations encode a `Vec<u8>` as a list of numbers, which is
+//! large and awkward to consume in formats such as JSON. With these helpers,
+//! byte fields are encoded as hex strings when the format is human-readable,
+//! and as before otherwise, so that binary formats such as bincode and the
+//! [risc0 serializer](crate::serde) are unaffected.
+//!
+//! Human-readable documents written before the switch to hex strings encode
+//! byte fields as lists of numbers; these are still accepted when decoding.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Visitor accepting either a hex string or a list of elements.
+struct HexOrSeq<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for HexOrSeq<T>
+where
+    T: Deserialize<'de> + FromLeBytes,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string or a list of integers")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        let bytes = hex::decode(value).map_err(E::custom)?;
+        if bytes.len() % T::SIZE != 0 {
+            return Err(E::custom("hex string is not a whole number of words"));
+        }
+        Ok(bytes.chunks_exact(T::SIZE).map(T::from_le_bytes).collect())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+/// Element types that can be decoded from their little-endian bytes.
+trait FromLeBytes: Sized {
+    const SIZE: usize;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self;
+}
+
+impl FromLeBytes for u8 {
+    const SIZE: usize = 1;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        bytes[0]
+    }
+}
+
+impl FromLeBytes for u32 {
+    const SIZE: usize = 4;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        u32::from_le_bytes(bytes.try_into().unwrap())
+    }
+}
+
+pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        bytes.serialize(serializer)
+    }
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(HexOrSeq(PhantomData))
+    } else {
+        Vec::deserialize(deserializer)
+    }
+}
+
+/// Encodes words as the hex string of their little-endian bytes.
+pub(crate) mod words {
+    use alloc::vec::Vec;
+    use core::marker::PhantomData;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::HexOrSeq;
+
+    pub(crate) fn serialize<S: Serializer>(
+        words: &[u32],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+            super::serialize(&bytes, serializer)
+        } else {
+            words.serialize(serializer)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u32>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HexOrSeq(PhantomData))
+        } else {
+            Vec::deserialize(deserializer)
+        }
+    }
+}
+
+/// Encodes the value of a [MaybePruned] byte field as a hex string.
+pub(crate) mod maybe_pruned {
+    use alloc::vec::Vec;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::MaybePruned;
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct HexBytes(#[serde(with = "super")] Vec<u8>);
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &MaybePruned<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            MaybePruned::Value(bytes) => MaybePruned::Value(HexBytes(bytes.clone())),
+            MaybePruned::Pruned(digest) => MaybePruned::Pruned(*digest),
+        }
+        .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<MaybePruned<Vec<u8>>, D::Error> {
+        Ok(match MaybePruned::<HexBytes>::deserialize(deserializer)? {
+            MaybePruned::Value(HexBytes(bytes)) => MaybePruned::Value(bytes),
+            MaybePruned::Pruned(digest) => MaybePruned::Pruned(digest),
+        })
+    }
+}
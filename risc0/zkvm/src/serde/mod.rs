@@ -34,23 +34,39 @@
 //! [`env::commit`], so this crate rarely needs to be directly used in the
 //! guest.
 //!
+//! With the `postcard` feature, the `postcard` module provides a more compact
+//! encoding for byte-oriented data, which can be selected per channel.
+//!
+//! Types implementing [JournalSchema] are committed along with the digest of
+//! their schema, so that the host can detect a journal written by a guest
+//! built against another version of the type.
+//!
 //! [`env`]: ../guest/env/index.html
 //! [`env::commit`]: ../guest/env/fn.commit.html
 //! [`env::read`]: ../guest/env/fn.read.html
 
 mod deserializer;
 mod err;
+mod frame;
+pub(crate) mod hex_bytes;
+#[cfg(feature = "postcard")]
+pub mod postcard;
+mod schema;
 mod serializer;
 
 pub use deserializer::{from_slice, Deserializer, WordRead};
 pub use err::{Error, Result};
+pub use frame::{from_frame, to_frame, FrameError, FrameHeader};
+#[cfg(feature = "derive")]
+pub use risc0_zkvm_derive::JournalSchema;
+pub use schema::{schema_digest, JournalSchema, SchemaError};
 pub use serializer::{to_vec, to_vec_with_capacity, Serializer, WordWrite};
 
 #[cfg(test)]
 mod tests {
     use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
 
-    use crate::serde::{from_slice, to_vec};
+    use crate::serde::{from_frame, from_slice, to_frame, to_vec, FrameError};
 
     #[test]
     fn test_vec_round_trip() {
@@ -76,4 +92,27 @@ mod tests {
         let output: (u32, u64) = from_slice(data.as_slice()).unwrap();
         assert_eq!(input, output);
     }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let input: (u32, String) = (7, "frame".into());
+        let frame = to_frame(1, 2, &input).unwrap();
+        let output: (u32, String) = from_frame(1, 2, &frame).unwrap();
+        assert_eq!(input, output);
+
+        assert_eq!(
+            from_frame::<(u32, String)>(3, 2, &frame),
+            Err(FrameError::TagMismatch {
+                expected: 3,
+                found: 1
+            })
+        );
+        assert_eq!(
+            from_frame::<(u32, String)>(1, 3, &frame),
+            Err(FrameError::VersionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
 }
@@ -0,0 +1,83 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [postcard](::postcard) encoding of the data exchanged between the host and the guest.
+//!
+//! The default [crate::serde] format encodes every primitive in at least one
+//! word, which makes byte-oriented data, such as byte strings, `u8`
+//! fields or small integers, several times larger than needed. Postcard encodes
+//! such data compactly, which reduces the number of words read and hashed by
+//! the guest for large inputs.
+//!
+//! Values are encoded as their length in bytes, in one word, followed by the
+//! postcard encoding padded with zeroes to a whole number of words. In the
+//! guest, the codec is selected per channel with the `read_postcard` and
+//! `write_postcard` methods of the `env::Read` and `env::Write` traits. On the
+//! host, inputs are written with `ExecutorEnvBuilder::write_postcard`, and
+//! outputs decoded with [from_words].
+
+use alloc::{string::ToString, vec, vec::Vec};
+
+use risc0_zkvm_platform::WORD_SIZE;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{Error, Result};
+use crate::align_up;
+
+/// Serializes a value with postcard, prefixed with its length.
+pub fn to_words<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u32>> {
+    let bytes = ::postcard::to_allocvec(value).map_err(|err| Error::Custom(err.to_string()))?;
+    let len = bytes.len().try_into().map_err(|_| Error::NotSupported)?;
+    let mut words = vec![0u32; 1 + align_up(bytes.len(), WORD_SIZE) / WORD_SIZE];
+    words[0] = len;
+    bytemuck::cast_slice_mut::<u32, u8>(&mut words[1..])[..bytes.len()].copy_from_slice(&bytes);
+    Ok(words)
+}
+
+/// Deserializes a value encoded by [to_words].
+pub fn from_words<T: DeserializeOwned>(words: &[u32]) -> Result<T> {
+    let (len, body) = words.split_first().ok_or(Error::DeserializeUnexpectedEnd)?;
+    let bytes: &[u8] = bytemuck::cast_slice(body);
+    let bytes = bytes
+        .get(..*len as usize)
+        .ok_or(Error::DeserializeUnexpectedEnd)?;
+    ::postcard::from_bytes(bytes).map_err(|err| Error::Custom(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use super::{from_words, to_words};
+
+    #[test]
+    fn round_trip() {
+        let input: (Vec<u8>, String, u64) = (vec![1, 2, 3, 4, 5], "postcard".into(), 300);
+        let words = to_words(&input).unwrap();
+        assert_eq!(from_words::<(Vec<u8>, String, u64)>(&words).unwrap(), input);
+    }
+
+    #[test]
+    fn smaller_than_word_encoding() {
+        let input = vec![0xffu8; 1024];
+        assert!(to_words(&input).unwrap().len() < crate::serde::to_vec(&input).unwrap().len());
+    }
+
+    #[test]
+    fn truncated() {
+        let words = to_words(&"postcard").unwrap();
+        assert!(from_words::<String>(&words[..words.len() - 1]).is_err());
+        assert!(from_words::<String>(&[]).is_err());
+    }
+}
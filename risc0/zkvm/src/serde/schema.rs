@@ -0,0 +1,292 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Journals with a typed schema.
+//!
+//! A type implementing [JournalSchema] is committed by the guest with
+//! [JournalSchema::commit], which writes the digest of its schema before the
+//! value, and decoded by the host with [JournalSchema::decode], which checks
+//! that digest first. A host built against a different version of the type
+//! then gets a [SchemaError::DigestMismatch] instead of misdeserializing the
+//! journal.
+//!
+//! With the `derive` feature, the trait can be derived, in which case the
+//! schema digest covers the names, types and serde attributes of the fields and
+//! variants of the type, and the schemas of the types of its fields:
+//!
+//! ```ignore
+//! use risc0_zkvm::serde::JournalSchema;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(JournalSchema, Serialize, Deserialize)]
+//! struct Output {
+//!     root: [u8; 32],
+//!     count: u32,
+//! }
+//!
+//! // In the guest.
+//! Output { root, count }.commit();
+//!
+//! // On the host.
+//! let output = Output::decode(&receipt.journal.bytes)?;
+//! ```
+//!
+//! The types of the fields must implement [JournalSchema] too. It is
+//! implemented for the primitive types, [String], [Vec], [Option], [Box],
+//! arrays, tuples and [Digest].
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+use risc0_binfmt::tagged_struct;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{from_slice, to_vec, Error, Result};
+use crate::sha::{Digest, Impl, DIGEST_WORDS};
+
+/// A type committed to the journal along with the digest of its schema.
+pub trait JournalSchema: Serialize + DeserializeOwned {
+    /// Digest identifying the schema of the type.
+    fn schema_digest() -> Digest;
+
+    /// Serializes the value, prefixed with [JournalSchema::schema_digest].
+    fn to_journal(&self) -> Result<Vec<u32>> {
+        let mut words = Vec::from(Self::schema_digest().as_words());
+        words.extend(to_vec(self)?);
+        Ok(words)
+    }
+
+    /// Commits the value to the journal, prefixed with its schema digest.
+    fn commit(&self) {
+        crate::guest::env::commit_slice(&self.to_journal().unwrap());
+    }
+
+    /// Decodes a value committed with [JournalSchema::commit], checking its
+    /// schema digest.
+    fn decode(journal: &[u8]) -> core::result::Result<Self, SchemaError> {
+        if journal.len() % 4 != 0 || journal.len() < DIGEST_WORDS * 4 {
+            return Err(Error::DeserializeUnexpectedEnd.into());
+        }
+        let (digest, body) = journal.split_at(DIGEST_WORDS * 4);
+        let found = Digest::try_from(digest).unwrap();
+        let expected = Self::schema_digest();
+        if found != expected {
+            return Err(SchemaError::DigestMismatch { expected, found });
+        }
+        Ok(from_slice(body)?)
+    }
+}
+
+/// Hashes the description of a schema along with the digests of the schemas
+/// nested in it, e.g. those of the types of the fields of a struct.
+pub fn schema_digest(description: &str, nested: &[Digest]) -> Digest {
+    tagged_struct::<Impl>(&format!("risc0.JournalSchema:{description}"), nested, &[])
+}
+
+macro_rules! impl_primitive_schema {
+    ($($ty:ty),*) => {
+        $(
+            impl JournalSchema for $ty {
+                fn schema_digest() -> Digest {
+                    schema_digest(stringify!($ty), &[])
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_schema!(
+    (),
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    char,
+    String,
+    Digest
+);
+
+impl<T: JournalSchema> JournalSchema for Vec<T> {
+    fn schema_digest() -> Digest {
+        schema_digest("Vec", &[T::schema_digest()])
+    }
+}
+
+impl<T: JournalSchema> JournalSchema for Option<T> {
+    fn schema_digest() -> Digest {
+        schema_digest("Option", &[T::schema_digest()])
+    }
+}
+
+impl<T: JournalSchema> JournalSchema for Box<T> {
+    // Boxing does not change the encoding.
+    fn schema_digest() -> Digest {
+        T::schema_digest()
+    }
+}
+
+impl<T: JournalSchema, const N: usize> JournalSchema for [T; N]
+where
+    [T; N]: Serialize + DeserializeOwned,
+{
+    fn schema_digest() -> Digest {
+        schema_digest(&format!("[_; {N}]"), &[T::schema_digest()])
+    }
+}
+
+macro_rules! impl_tuple_schema {
+    ($($name:ident),+) => {
+        impl<$($name: JournalSchema),+> JournalSchema for ($($name,)+) {
+            fn schema_digest() -> Digest {
+                schema_digest("tuple", &[$($name::schema_digest()),+])
+            }
+        }
+    };
+}
+
+impl_tuple_schema!(A);
+impl_tuple_schema!(A, B);
+impl_tuple_schema!(A, B, C);
+impl_tuple_schema!(A, B, C, D);
+
+/// Error returned when decoding a journal with a schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SchemaError {
+    /// The journal was committed with another schema.
+    DigestMismatch {
+        /// The schema digest of the requested type.
+        expected: Digest,
+        /// The schema digest in the journal.
+        found: Digest,
+    },
+    /// The value could not be deserialized.
+    Deserialize(Error),
+}
+
+impl From<Error> for SchemaError {
+    fn from(err: Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::DigestMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Journal schema mismatch: expected {expected}, found {found}"
+                )
+            }
+            Self::Deserialize(err) => write!(f, "Failed to deserialize journal: {err}"),
+        }
+    }
+}
+
+// This is an alias for either std::Error, or serde's no_std error replacement.
+impl serde::ser::StdError for SchemaError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{schema_digest, JournalSchema, SchemaError};
+    use crate::sha::Digest;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct V1 {
+        name: String,
+        count: u32,
+    }
+
+    impl JournalSchema for V1 {
+        fn schema_digest() -> Digest {
+            Digest::new([1; 8])
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct V2 {
+        name: String,
+        count: u64,
+    }
+
+    impl JournalSchema for V2 {
+        fn schema_digest() -> Digest {
+            Digest::new([2; 8])
+        }
+    }
+
+    fn journal<T: JournalSchema>(value: &T) -> Vec<u8> {
+        bytemuck::cast_slice(&value.to_journal().unwrap()).to_vec()
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = V1 {
+            name: "schema".into(),
+            count: 3,
+        };
+        assert_eq!(V1::decode(&journal(&value)).unwrap(), value);
+    }
+
+    #[test]
+    fn mismatch() {
+        let value = V1 {
+            name: "schema".into(),
+            count: 3,
+        };
+        assert_eq!(
+            V2::decode(&journal(&value)),
+            Err(SchemaError::DigestMismatch {
+                expected: V2::schema_digest(),
+                found: V1::schema_digest(),
+            })
+        );
+    }
+
+    #[test]
+    fn nested_schemas() {
+        assert_ne!(Vec::<u32>::schema_digest(), Vec::<u64>::schema_digest());
+        assert_ne!(Vec::<u8>::schema_digest(), Option::<u8>::schema_digest());
+        assert_ne!(<[u8; 4]>::schema_digest(), <[u8; 8]>::schema_digest());
+        assert_eq!(Box::<u32>::schema_digest(), u32::schema_digest());
+        assert_eq!(
+            <(u32, String)>::schema_digest(),
+            schema_digest("tuple", &[u32::schema_digest(), String::schema_digest()])
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let value = V1 {
+            name: "schema".into(),
+            count: 3,
+        };
+        let journal = journal(&value);
+        assert!(V1::decode(&journal[..16]).is_err());
+        assert!(V1::decode(&journal[..journal.len() - 4]).is_err());
+    }
+}
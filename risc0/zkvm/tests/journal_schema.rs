@@ -0,0 +1,209 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::serde::JournalSchema;
+use risc0_zkvm_derive::JournalSchema;
+use serde::{Deserialize, Serialize};
+
+mod v1 {
+    use super::*;
+
+    #[derive(Debug, PartialEq, JournalSchema, Serialize, Deserialize)]
+    pub struct Inner {
+        pub count: u32,
+    }
+
+    #[derive(Debug, PartialEq, JournalSchema, Serialize, Deserialize)]
+    pub struct Output {
+        pub inner: Inner,
+        pub bytes: Vec<u8>,
+    }
+}
+
+mod v2 {
+    use super::*;
+
+    // Only the definition of the nested type changes.
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Inner {
+        pub count: u64,
+    }
+
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Output {
+        pub inner: Inner,
+        pub bytes: Vec<u8>,
+    }
+}
+
+mod qualified {
+    use super::*;
+
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Inner {
+        pub count: core::primitive::u32,
+    }
+
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Output {
+        pub inner: self::Inner,
+        pub bytes: std::vec::Vec<u8>,
+    }
+}
+
+mod renamed {
+    use super::*;
+
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Inner {
+        #[serde(rename = "total")]
+        pub count: u32,
+    }
+}
+
+mod raw {
+    use super::*;
+
+    // The same as `renamed::Inner`, with other spellings of the same tokens.
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Inner {
+        #[serde(rename = r"total")]
+        pub r#count: u32,
+    }
+}
+
+#[derive(JournalSchema, Serialize, Deserialize)]
+struct Bytes32 {
+    bytes: [u8; 32],
+}
+
+mod hex_len {
+    use super::*;
+
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Bytes32 {
+        pub bytes: [u8; 0x20],
+    }
+}
+
+#[derive(Debug, PartialEq, JournalSchema, Serialize, Deserialize)]
+enum List {
+    Nil,
+    Cons(u32, Box<List>),
+}
+
+#[derive(Debug, PartialEq, JournalSchema, Serialize, Deserialize)]
+struct Tree {
+    inner: v1::Inner,
+    children: Vec<(Self, v1::Inner)>,
+}
+
+mod tree_v2 {
+    use super::*;
+
+    #[derive(JournalSchema, Serialize, Deserialize)]
+    pub struct Tree {
+        pub inner: v1::Inner,
+        pub children: Vec<(Self, v2::Inner)>,
+    }
+}
+
+#[derive(Debug, PartialEq, JournalSchema, Serialize, Deserialize)]
+struct Skipped {
+    count: u32,
+    #[serde(skip)]
+    cache: std::collections::HashMap<u32, u32>,
+}
+
+#[derive(Debug, PartialEq, JournalSchema, Serialize, Deserialize)]
+enum Event<T> {
+    Empty,
+    Value(T),
+}
+
+#[test]
+fn round_trip() {
+    let output = v1::Output {
+        inner: v1::Inner { count: 3 },
+        bytes: vec![1, 2, 3],
+    };
+    let journal = output.to_journal().unwrap();
+    let journal: &[u8] = bytemuck::cast_slice(&journal);
+    assert_eq!(v1::Output::decode(journal).unwrap(), output);
+    assert!(v2::Output::decode(journal).is_err());
+}
+
+#[test]
+fn nested_types() {
+    assert_ne!(v1::Inner::schema_digest(), v2::Inner::schema_digest());
+    assert_ne!(v1::Output::schema_digest(), v2::Output::schema_digest());
+}
+
+#[test]
+fn normalized_paths() {
+    assert_eq!(
+        v1::Inner::schema_digest(),
+        qualified::Inner::schema_digest()
+    );
+    assert_eq!(
+        v1::Output::schema_digest(),
+        qualified::Output::schema_digest()
+    );
+}
+
+#[test]
+fn serde_attributes() {
+    assert_ne!(v1::Inner::schema_digest(), renamed::Inner::schema_digest());
+}
+
+#[test]
+fn skipped_fields() {
+    // The type of a skipped field does not need a schema.
+    let skipped = Skipped {
+        count: 3,
+        cache: Default::default(),
+    };
+    let journal = skipped.to_journal().unwrap();
+    assert_eq!(
+        Skipped::decode(bytemuck::cast_slice(&journal)).unwrap(),
+        skipped
+    );
+}
+
+#[test]
+fn generics() {
+    assert_ne!(Event::<u32>::schema_digest(), Event::<u64>::schema_digest());
+    let event = Event::Value(7u32);
+    let journal = event.to_journal().unwrap();
+    assert_eq!(
+        Event::<u32>::decode(bytemuck::cast_slice(&journal)).unwrap(),
+        event
+    );
+}
+
+#[test]
+fn canonical_tokens() {
+    assert_eq!(renamed::Inner::schema_digest(), raw::Inner::schema_digest());
+    assert_eq!(Bytes32::schema_digest(), hex_len::Bytes32::schema_digest());
+}
+
+#[test]
+fn recursive_types() {
+    let list = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Nil))));
+    let journal = list.to_journal().unwrap();
+    assert_eq!(List::decode(bytemuck::cast_slice(&journal)).unwrap(), list);
+
+    // The schemas of the types nested next to the recursive ones are covered.
+    assert_ne!(Tree::schema_digest(), tree_v2::Tree::schema_digest());
+}
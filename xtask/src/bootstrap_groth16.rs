@@ -12,122 +12,190 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{fs, fs::read_to_string, process::Command};
+use std::{
+    collections::HashMap,
+    fs,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
+use ark_bn254::{Fq, Fr};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
 use clap::Parser;
 use hex::FromHex;
 use regex::Regex;
-use risc0_groth16::docker::stark_to_snark;
+use risc0_groth16::{docker::stark_to_snark, VerifyingKey};
 use risc0_zkvm::{
     get_prover_server,
     recursion::identity_p254,
-    sha::{Digest, Digestible},
+    sha::{Digest, Digestible, Impl, Sha256},
     CompactReceipt, ExecutorEnv, ExecutorImpl, InnerReceipt, ProverOpts, Receipt, VerifierContext,
     ALLOWED_IDS_ROOT,
 };
 use risc0_zkvm_methods::{multi_test::MultiTestSpec, MULTI_TEST_ELF, MULTI_TEST_ID};
+use serde_json::json;
 
 #[derive(Parser)]
-pub struct BootstrapGroth16;
-
-const SOL_HEADER: &str = r#"// Copyright 2024 RISC Zero, Inc.
-//
-// Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License.
-// You may obtain a copy of the License at
-//
-//     http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS,
-// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// See the License for the specific language governing permissions and
-// limitations under the License.
-//
-// SPDX-License-Identifier: Apache-2.0
-
-// This file is automatically generated by:
-// cargo xtask bootstrap-groth16
-
-"#;
+pub struct BootstrapGroth16 {
+    /// Directory to export the verifying key to, as snarkjs JSON, arkworks-serialized bytes, and
+    /// a C header.
+    #[arg(long, default_value = "target/groth16")]
+    vk_out_dir: PathBuf,
+}
 
 const SOLIDITY_GROTH16_VERIFIER_PATH: &str =
     "bonsai/ethereum/contracts/groth16/Groth16Verifier.sol";
 const SOLIDITY_CONTROL_ID_PATH: &str = "bonsai/ethereum/contracts/groth16/ControlID.sol";
 const SOLIDITY_TEST_RECEIPT_PATH: &str = "bonsai/ethereum/test/TestReceipt.sol";
-const RUST_GROTH16_VERIFIER_PATH: &str = "risc0/groth16/src/verifier.rs";
+const RUST_GROTH16_CONSTANTS_PATH: &str = "risc0/groth16/src/constants.rs";
+const SOLANA_VERIFYING_KEY_PATH: &str = "bonsai/solana/src/vk.rs";
+const COSMWASM_CONTROL_ID_PATH: &str = "bonsai/cosmwasm/src/control_id.rs";
+const NEAR_VERIFYING_KEY_PATH: &str = "bonsai/near/src/vk.rs";
+const SUI_VERIFIER_PATH: &str = "bonsai/sui/sources/groth16_verifier.move";
+const SOLIDITY_L2_VERIFIER_DIR: &str = "bonsai/ethereum/contracts/groth16";
+const SOLIDITY_ZKSYNC_VERIFIER_PATH: &str =
+    "bonsai/ethereum/contracts/groth16/RiscZeroGroth16VerifierZkSync.sol";
+const SOLIDITY_DEPLOY_SCRIPT_PATH: &str = "bonsai/ethereum/script/DeployVerifier.s.sol";
+const DEPLOYMENT_MANIFEST_PATH: &str = "bonsai/ethereum/script/deployment.json";
 
 impl BootstrapGroth16 {
     pub fn run(&self) {
-        bootstrap_verifying_key();
+        let constants = parse_solidity_constants();
+        bootstrap_verifying_key(&constants);
+        export_verifying_key(&self.vk_out_dir);
         bootstrap_control_id();
+        bootstrap_solana_verifying_key(&constants);
+        bootstrap_cosmwasm_control_id();
+        bootstrap_near_verifying_key(&constants);
+        bootstrap_sui_verifier();
+        bootstrap_l2_verifiers(&constants);
+        bootstrap_zksync_verifier(&constants);
+        bootstrap_deployment();
         bootstrap_test_receipt();
     }
 }
 
-fn bootstrap_verifying_key() {
+// Parses the verifying key constants from the snarkjs generated Solidity verifier.
+fn parse_solidity_constants() -> HashMap<String, String> {
     let solidity_code = read_to_string(SOLIDITY_GROTH16_VERIFIER_PATH).expect(&format!(
         "failed to read the Solidity verifier from {}",
         SOLIDITY_GROTH16_VERIFIER_PATH
     ));
-    let mut rust_code = read_to_string(RUST_GROTH16_VERIFIER_PATH).expect(&format!(
-        "failed to read groth16.rs from {}",
-        RUST_GROTH16_VERIFIER_PATH
-    ));
 
-    let solidity_constants = [
-        "alphax", "alphay", "betax1", "betax2", "betay1", "betay2", "gammax1", "gammax2",
-        "gammay1", "gammay2", "deltax1", "deltax2", "deltay1", "deltay2", "IC0x", "IC0y", "IC1x",
-        "IC1y", "IC2x", "IC2y", "IC3x", "IC3y", "IC4x", "IC4y",
-    ];
-
-    let rust_constants = [
-        "ALPHA_X", "ALPHA_Y", "BETA_X1", "BETA_X2", "BETA_Y1", "BETA_Y2", "GAMMA_X1", "GAMMA_X2",
-        "GAMMA_Y1", "GAMMA_Y2", "DELTA_X1", "DELTA_X2", "DELTA_Y1", "DELTA_Y2", "IC0_X", "IC0_Y",
-        "IC1_X", "IC1_Y", "IC2_X", "IC2_Y", "IC3_X", "IC3_Y", "IC4_X", "IC4_Y",
-    ];
-
-    for (i, constant) in solidity_constants.into_iter().enumerate() {
-        let re = Regex::new(&format!(r"uint256 constant\s+{}\s*=\s*(\d+);", constant)).unwrap();
-        if let Some(caps) = re.captures(&solidity_code) {
-            let rust_re = Regex::new(&format!(
-                "const {}: &str =[\\r\\n\\s]*\"\\d+\";",
-                rust_constants[i]
-            ))
-            .unwrap();
-            rust_code = rust_re
-                .replace(
-                    &rust_code,
-                    &format!("const {}: &str = \"{}\";", rust_constants[i], &caps[1]),
-                )
-                .to_string();
-        } else {
-            println!("{} not found", constant);
-        }
-    }
+    let re = Regex::new(r"uint256 constant\s+(\w+)\s*=\s*(\d+);").unwrap();
+    re.captures_iter(&solidity_code)
+        .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+        .collect()
+}
+
+fn solidity_constant<'a>(constants: &'a HashMap<String, String>, name: &str) -> &'a str {
+    constants
+        .get(name)
+        .unwrap_or_else(|| panic!("{name} not found in {SOLIDITY_GROTH16_VERIFIER_PATH}"))
+}
+
+fn bootstrap_verifying_key(constants: &HashMap<String, String>) {
+    let constant = |name: &str| solidity_constant(constants, name);
+
+    let contents = format!(
+        include_str!("templates/groth16_constants.rs"),
+        alpha_x = constant("alphax"),
+        alpha_y = constant("alphay"),
+        beta_x1 = constant("betax1"),
+        beta_x2 = constant("betax2"),
+        beta_y1 = constant("betay1"),
+        beta_y2 = constant("betay2"),
+        gamma_x1 = constant("gammax1"),
+        gamma_x2 = constant("gammax2"),
+        gamma_y1 = constant("gammay1"),
+        gamma_y2 = constant("gammay2"),
+        delta_x1 = constant("deltax1"),
+        delta_x2 = constant("deltax2"),
+        delta_y1 = constant("deltay1"),
+        delta_y2 = constant("deltay2"),
+        ic0_x = constant("IC0x"),
+        ic0_y = constant("IC0y"),
+        ic1_x = constant("IC1x"),
+        ic1_y = constant("IC1y"),
+        ic2_x = constant("IC2x"),
+        ic2_y = constant("IC2y"),
+        ic3_x = constant("IC3x"),
+        ic3_y = constant("IC3y"),
+        ic4_x = constant("IC4x"),
+        ic4_y = constant("IC4y"),
+    );
 
-    fs::write(RUST_GROTH16_VERIFIER_PATH, rust_code).expect(&format!(
+    fs::write(RUST_GROTH16_CONSTANTS_PATH, contents).expect(&format!(
         "failed to save changes to {}",
-        RUST_GROTH16_VERIFIER_PATH
+        RUST_GROTH16_CONSTANTS_PATH
     ));
 
     // Use rustfmt to format the file.
     Command::new("rustfmt")
-        .arg(RUST_GROTH16_VERIFIER_PATH)
+        .arg(RUST_GROTH16_CONSTANTS_PATH)
         .status()
-        .expect("failed to format {RUST_GROTH16_VERIFIER_PATH}");
+        .expect("failed to format {RUST_GROTH16_CONSTANTS_PATH}");
 }
 
-fn bootstrap_control_id() {
-    const LIB_HEADER: &str = r#"pragma solidity ^0.8.9;
+// Builds the verifying key from the constants of the Solidity verifier.
+fn solidity_verifying_key() -> VerifyingKey {
+    let solidity_code = read_to_string(SOLIDITY_GROTH16_VERIFIER_PATH).unwrap_or_else(|_| {
+        panic!("failed to read the Solidity verifier from {SOLIDITY_GROTH16_VERIFIER_PATH}")
+    });
+    VerifyingKey::from_solidity(&solidity_code).unwrap()
+}
+
+fn export_verifying_key(out_dir: &Path) {
+    let verifying_key = solidity_verifying_key();
+
+    fs::create_dir_all(out_dir).unwrap();
 
- library ControlID {
-"#;
+    let json_path = out_dir.join("verification_key.json");
+    let json = serde_json::to_string_pretty(&verifying_key.to_json().unwrap()).unwrap();
+    fs::write(&json_path, json).unwrap();
+    tracing::info!(
+        "wrote verifying key as snarkjs JSON to {}",
+        json_path.display()
+    );
+
+    let bin_path = out_dir.join("verification_key.bin");
+    let bytes = verifying_key.to_bytes().unwrap();
+    fs::write(&bin_path, &bytes).unwrap();
+    tracing::info!(
+        "wrote verifying key as arkworks bytes to {}",
+        bin_path.display()
+    );
+
+    let header_path = out_dir.join("verification_key.h");
+    let contents = format!(
+        include_str!("templates/groth16_verifying_key.h"),
+        len = bytes.len(),
+        bytes = bytes
+            .chunks(12)
+            .map(|line| {
+                let line: Vec<_> = line.iter().map(|byte| format!("0x{byte:02x},")).collect();
+                format!("    {}", line.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    fs::write(&header_path, contents).unwrap();
+    tracing::info!(
+        "wrote verifying key as a C array to {}",
+        header_path.display()
+    );
+}
+
+fn bootstrap_control_id() {
     let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
-    let control_id_0 = format!("uint256 public constant CONTROL_ID_0 = {control_id_0};");
-    let control_id_1 = format!("uint256 public constant CONTROL_ID_1 = {control_id_1};");
-    let content = &format!("{SOL_HEADER}{LIB_HEADER}\n{control_id_0}\n{control_id_1}\n}}");
-    fs::write(SOLIDITY_CONTROL_ID_PATH, content).expect(&format!(
+    let contents = format!(
+        include_str!("templates/ControlID.sol"),
+        control_id_0 = control_id_0,
+        control_id_1 = control_id_1,
+    );
+    fs::write(SOLIDITY_CONTROL_ID_PATH, contents).expect(&format!(
         "failed to save changes to {}",
         SOLIDITY_CONTROL_ID_PATH
     ));
@@ -140,42 +208,388 @@ fn bootstrap_control_id() {
         .expect("failed to format {SOLIDITY_CONTROL_ID_PATH}");
 }
 
-fn bootstrap_test_receipt() {
-    const LIB_HEADER: &str = r#"pragma solidity ^0.8.13;
+// Converts a decimal field element into its 32 bytes big-endian hex encoding.
+fn decimal_to_hex(value: &str) -> String {
+    let mut bytes = [0u8; 32];
+    for digit in value.bytes() {
+        let mut carry = (digit - b'0') as u32;
+        for byte in bytes.iter_mut().rev() {
+            let product = *byte as u32 * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+        assert_eq!(carry, 0, "{value} does not fit in 32 bytes");
+    }
+    hex::encode(bytes)
+}
 
- library TestReceipt {
-"#;
-    let (receipt, image_id) = generate_receipt();
-    let seal = hex::encode(receipt.inner.compact().unwrap().seal.clone());
-    let post_digest = format!(
+fn bootstrap_solana_verifying_key(constants: &HashMap<String, String>) {
+    let constant = |name: &str| decimal_to_hex(solidity_constant(constants, name));
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+    // The control IDs are 16 bytes long, pad them to the size of a field element.
+    let pad = |control_id: String| format!("{:0>64}", control_id.trim_start_matches("0x"));
+
+    let contents = format!(
+        include_str!("templates/solana_verifying_key.rs"),
+        control_id_0 = pad(control_id_0),
+        control_id_1 = pad(control_id_1),
+        alpha_x = constant("alphax"),
+        alpha_y = constant("alphay"),
+        beta_x1 = constant("betax1"),
+        beta_x2 = constant("betax2"),
+        beta_y1 = constant("betay1"),
+        beta_y2 = constant("betay2"),
+        gamma_x1 = constant("gammax1"),
+        gamma_x2 = constant("gammax2"),
+        gamma_y1 = constant("gammay1"),
+        gamma_y2 = constant("gammay2"),
+        delta_x1 = constant("deltax1"),
+        delta_x2 = constant("deltax2"),
+        delta_y1 = constant("deltay1"),
+        delta_y2 = constant("deltay2"),
+        ic0_x = constant("IC0x"),
+        ic0_y = constant("IC0y"),
+        ic1_x = constant("IC1x"),
+        ic1_y = constant("IC1y"),
+        ic2_x = constant("IC2x"),
+        ic2_y = constant("IC2y"),
+        ic3_x = constant("IC3x"),
+        ic3_y = constant("IC3y"),
+        ic4_x = constant("IC4x"),
+        ic4_y = constant("IC4y"),
+    );
+
+    fs::write(SOLANA_VERIFYING_KEY_PATH, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {SOLANA_VERIFYING_KEY_PATH}"));
+}
+
+// Converts a big-endian hex encoding into little-endian.
+fn reverse_hex(value: &str) -> String {
+    let mut bytes = hex::decode(value.trim_start_matches("0x")).unwrap();
+    bytes.reverse();
+    hex::encode(bytes)
+}
+
+fn bootstrap_near_verifying_key(constants: &HashMap<String, String>) {
+    let constant = |name: &str| reverse_hex(&decimal_to_hex(solidity_constant(constants, name)));
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+    // The control IDs are 16 bytes long, pad them to the size of a field element.
+    let pad =
+        |control_id: String| reverse_hex(&format!("{:0>64}", control_id.trim_start_matches("0x")));
+
+    let contents = format!(
+        include_str!("templates/near_verifying_key.rs"),
+        control_id_0 = pad(control_id_0),
+        control_id_1 = pad(control_id_1),
+        alpha_x = constant("alphax"),
+        alpha_y = constant("alphay"),
+        beta_x1 = constant("betax1"),
+        beta_x2 = constant("betax2"),
+        beta_y1 = constant("betay1"),
+        beta_y2 = constant("betay2"),
+        gamma_x1 = constant("gammax1"),
+        gamma_x2 = constant("gammax2"),
+        gamma_y1 = constant("gammay1"),
+        gamma_y2 = constant("gammay2"),
+        delta_x1 = constant("deltax1"),
+        delta_x2 = constant("deltax2"),
+        delta_y1 = constant("deltay1"),
+        delta_y2 = constant("deltay2"),
+        ic0_x = constant("IC0x"),
+        ic0_y = constant("IC0y"),
+        ic1_x = constant("IC1x"),
+        ic1_y = constant("IC1y"),
+        ic2_x = constant("IC2x"),
+        ic2_y = constant("IC2y"),
+        ic3_x = constant("IC3x"),
+        ic3_y = constant("IC3y"),
+        ic4_x = constant("IC4x"),
+        ic4_y = constant("IC4y"),
+    );
+
+    fs::write(NEAR_VERIFYING_KEY_PATH, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {NEAR_VERIFYING_KEY_PATH}"));
+}
+
+fn bootstrap_sui_verifier() {
+    let verifying_key = solidity_verifying_key();
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+    // The control IDs are 16 bytes long, pad them to the size of a field element.
+    let pad =
+        |control_id: String| reverse_hex(&format!("{:0>64}", control_id.trim_start_matches("0x")));
+
+    let contents = format!(
+        include_str!("templates/groth16_verifier.move"),
+        control_id_0 = pad(control_id_0),
+        control_id_1 = pad(control_id_1),
+        verifying_key = hex::encode(verifying_key.to_compressed_bytes().unwrap()),
+    );
+
+    fs::write(SUI_VERIFIER_PATH, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {SUI_VERIFIER_PATH}"));
+}
+
+// Configuration of a verifier variant tuned for an L2 chain.
+struct L2Verifier {
+    name: &'static str,
+    chain: &'static str,
+    compress_g2: bool,
+}
+
+const L2_VERIFIERS: &[L2Verifier] = &[
+    L2Verifier {
+        name: "Optimism",
+        chain: "OP Stack chains",
+        compress_g2: true,
+    },
+    L2Verifier {
+        name: "Arbitrum",
+        chain: "Arbitrum",
+        compress_g2: false,
+    },
+];
+
+// Precomputes the contribution of the control IDs to the public input, IC0 + CONTROL_ID_0 * IC1 +
+// CONTROL_ID_1 * IC2, for the verifiers pinning the control IDs.
+fn control_ic() -> (String, String) {
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+    let scalar = |control_id: &str| {
+        Fr::from_be_bytes_mod_order(&hex::decode(control_id.trim_start_matches("0x")).unwrap())
+    };
+    let ic = solidity_verifying_key().0.gamma_abc_g1;
+    let control_ic =
+        (ic[0].into_group() + ic[1] * scalar(&control_id_0) + ic[2] * scalar(&control_id_1))
+            .into_affine();
+    let (x, y) = control_ic.xy().unwrap();
+    (x.to_string(), y.to_string())
+}
+
+fn bootstrap_l2_verifiers(constants: &HashMap<String, String>) {
+    let constant = |name: &str| solidity_constant(constants, name);
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+    let (control_ic_x, control_ic_y) = control_ic();
+
+    let p = Fq::MODULUS;
+    let fraction = |n: u64, d: u64| (Fq::from(n) * Fq::from(d).inverse().unwrap()).to_string();
+    let mut exp_sqrt = p;
+    exp_sqrt.add_with_carry(&1u64.into());
+    exp_sqrt.div2();
+    exp_sqrt.div2();
+
+    for verifier in L2_VERIFIERS {
+        let (seal_length, seal_doc) = if verifier.compress_g2 {
+            (
+                128,
+                "Seals are 128 bytes long, encoding the compressed A, B and C points.",
+            )
+        } else {
+            (
+                192,
+                "B is left uncompressed, in the precompile order, trading 64 bytes of calldata\n\
+                 /// for the square root in Fp2. Seals are 192 bytes long.",
+            )
+        };
+        let contents = format!(
+            include_str!("templates/RiscZeroGroth16VerifierL2.sol"),
+            name = verifier.name,
+            chain = verifier.chain,
+            seal_doc = seal_doc,
+            compress_g2 = verifier.compress_g2,
+            seal_length = seal_length,
+            control_id_0 = control_id_0,
+            control_id_1 = control_id_1,
+            p = p,
+            exp_sqrt = exp_sqrt,
+            fraction_1_2 = fraction(1, 2),
+            fraction_27_82 = fraction(27, 82),
+            fraction_3_82 = fraction(3, 82),
+            alpha_x = constant("alphax"),
+            alpha_y = constant("alphay"),
+            beta_x1 = constant("betax1"),
+            beta_x2 = constant("betax2"),
+            beta_y1 = constant("betay1"),
+            beta_y2 = constant("betay2"),
+            gamma_x1 = constant("gammax1"),
+            gamma_x2 = constant("gammax2"),
+            gamma_y1 = constant("gammay1"),
+            gamma_y2 = constant("gammay2"),
+            delta_x1 = constant("deltax1"),
+            delta_x2 = constant("deltax2"),
+            delta_y1 = constant("deltay1"),
+            delta_y2 = constant("deltay2"),
+            ic3_x = constant("IC3x"),
+            ic3_y = constant("IC3y"),
+            ic4_x = constant("IC4x"),
+            ic4_y = constant("IC4y"),
+            control_ic_x = control_ic_x,
+            control_ic_y = control_ic_y,
+        );
+
+        let path = format!(
+            "{SOLIDITY_L2_VERIFIER_DIR}/RiscZeroGroth16Verifier{}.sol",
+            verifier.name
+        );
+        fs::write(&path, contents).unwrap_or_else(|_| panic!("failed to save changes to {path}"));
+
+        // Use forge fmt to format the file.
+        Command::new("forge")
+            .arg("fmt")
+            .arg(&path)
+            .status()
+            .unwrap_or_else(|_| panic!("failed to format {path}"));
+    }
+}
+
+fn bootstrap_zksync_verifier(constants: &HashMap<String, String>) {
+    let constant = |name: &str| solidity_constant(constants, name);
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+    let (control_ic_x, control_ic_y) = control_ic();
+
+    let contents = format!(
+        include_str!("templates/RiscZeroGroth16VerifierZkSync.sol"),
+        control_id_0 = control_id_0,
+        control_id_1 = control_id_1,
+        p = Fq::MODULUS,
+        alpha_x = constant("alphax"),
+        alpha_y = constant("alphay"),
+        beta_x1 = constant("betax1"),
+        beta_x2 = constant("betax2"),
+        beta_y1 = constant("betay1"),
+        beta_y2 = constant("betay2"),
+        gamma_x1 = constant("gammax1"),
+        gamma_x2 = constant("gammax2"),
+        gamma_y1 = constant("gammay1"),
+        gamma_y2 = constant("gammay2"),
+        delta_x1 = constant("deltax1"),
+        delta_x2 = constant("deltax2"),
+        delta_y1 = constant("deltay1"),
+        delta_y2 = constant("deltay2"),
+        ic3_x = constant("IC3x"),
+        ic3_y = constant("IC3y"),
+        ic4_x = constant("IC4x"),
+        ic4_y = constant("IC4y"),
+        control_ic_x = control_ic_x,
+        control_ic_y = control_ic_y,
+    );
+    fs::write(SOLIDITY_ZKSYNC_VERIFIER_PATH, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {SOLIDITY_ZKSYNC_VERIFIER_PATH}"));
+
+    // Use forge fmt to format the file.
+    Command::new("forge")
+        .arg("fmt")
+        .arg(SOLIDITY_ZKSYNC_VERIFIER_PATH)
+        .status()
+        .unwrap_or_else(|_| panic!("failed to format {SOLIDITY_ZKSYNC_VERIFIER_PATH}"));
+}
+
+// Writes the Foundry deployment script of the verifier and its JSON manifest, pinning the control
+// IDs and the verifying key of the Rust constants.
+fn bootstrap_deployment() {
+    let (control_id_0, control_id_1) = split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+
+    // The router selector is the first 4 bytes of
+    // sha256(abi.encodePacked(CONTROL_ID_0, CONTROL_ID_1)).
+    let pad = |control_id: &str| format!("{:0>64}", control_id.trim_start_matches("0x"));
+    let packed = hex::decode(pad(&control_id_0) + &pad(&control_id_1)).unwrap();
+    let selector = format!(
         "0x{}",
-        hex::encode(receipt.get_claim().unwrap().post.digest().as_bytes())
+        hex::encode(&Impl::hash_bytes(&packed).as_bytes()[..4])
     );
-    let image_id = format!("0x{}", hex::encode(image_id.as_bytes()));
-    let journal = hex::encode(receipt.journal.bytes);
 
-    let seal = format!("bytes public constant SEAL = hex\"{seal}\";");
-    let post_digest = format!("bytes32 public constant POST_DIGEST = bytes32({post_digest});");
-    let journal = format!("bytes public constant JOURNAL = hex\"{journal}\";");
-    let image_id = format!("bytes32 public constant IMAGE_ID = bytes32({image_id});");
+    let verifying_key = solidity_verifying_key().to_bytes().unwrap();
+    let verifying_key_digest = format!(
+        "0x{}",
+        hex::encode(Impl::hash_bytes(&verifying_key).as_bytes())
+    );
 
-    let content =
-        &format!("{SOL_HEADER}{LIB_HEADER}\n{seal}\n{post_digest}\n{journal}\n{image_id}\n}}");
-    fs::write(SOLIDITY_TEST_RECEIPT_PATH, content).expect(&format!(
-        "failed to save changes to {}",
-        SOLIDITY_TEST_RECEIPT_PATH
-    ));
+    let contents = format!(
+        include_str!("templates/DeployVerifier.s.sol"),
+        control_id_0 = control_id_0,
+        control_id_1 = control_id_1,
+        selector = selector,
+        verifying_key_digest = verifying_key_digest,
+    );
+    fs::create_dir_all(Path::new(SOLIDITY_DEPLOY_SCRIPT_PATH).parent().unwrap()).unwrap();
+    fs::write(SOLIDITY_DEPLOY_SCRIPT_PATH, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {SOLIDITY_DEPLOY_SCRIPT_PATH}"));
 
     // Use forge fmt to format the file.
     Command::new("forge")
         .arg("fmt")
-        .arg(SOLIDITY_TEST_RECEIPT_PATH)
+        .arg(SOLIDITY_DEPLOY_SCRIPT_PATH)
         .status()
-        .expect("failed to format {SOLIDITY_TEST_RECEIPT_PATH}");
+        .unwrap_or_else(|_| panic!("failed to format {SOLIDITY_DEPLOY_SCRIPT_PATH}"));
+
+    let manifest = json!({
+        "risc0_zkvm_version": risc0_zkvm::VERSION,
+        "allowed_ids_root": ALLOWED_IDS_ROOT,
+        "control_id_0": control_id_0,
+        "control_id_1": control_id_1,
+        "router_selector": selector,
+        "verifying_key_digest": verifying_key_digest,
+        "verifying_key": format!("0x{}", hex::encode(&verifying_key)),
+    });
+    fs::write(
+        DEPLOYMENT_MANIFEST_PATH,
+        serde_json::to_string_pretty(&manifest).unwrap() + "\n",
+    )
+    .unwrap_or_else(|_| panic!("failed to save changes to {DEPLOYMENT_MANIFEST_PATH}"));
+}
+
+fn bootstrap_cosmwasm_control_id() {
+    let contents = format!(
+        include_str!("templates/cosmwasm_control_id.rs"),
+        allowed_ids_root = ALLOWED_IDS_ROOT,
+    );
+    fs::write(COSMWASM_CONTROL_ID_PATH, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {COSMWASM_CONTROL_ID_PATH}"));
+}
+
+fn bootstrap_test_receipt() {
+    let (receipt, image_id) = generate_receipt();
+    write_test_receipt(
+        &receipt,
+        image_id,
+        "TestReceipt",
+        "cargo xtask bootstrap-groth16",
+        Path::new(SOLIDITY_TEST_RECEIPT_PATH),
+    );
+}
+
+// Writes a Solidity library exposing the seal, post state digest, journal and image ID of a
+// Compact `Receipt`, for use as a fixture in Foundry tests.
+pub(crate) fn write_test_receipt(
+    receipt: &Receipt,
+    image_id: Digest,
+    library_name: &str,
+    command: &str,
+    path: &Path,
+) {
+    let contents = format!(
+        include_str!("templates/TestReceipt.sol"),
+        command = command,
+        library_name = library_name,
+        seal = hex::encode(receipt.inner.compact().unwrap().seal.clone()),
+        post_digest = format!(
+            "0x{}",
+            hex::encode(receipt.get_claim().unwrap().post.digest().as_bytes())
+        ),
+        journal = hex::encode(&receipt.journal.bytes),
+        image_id = format!("0x{}", hex::encode(image_id.as_bytes())),
+    );
+    fs::write(path, contents)
+        .unwrap_or_else(|_| panic!("failed to save changes to {}", path.display()));
+
+    // Use forge fmt to format the file.
+    Command::new("forge")
+        .arg("fmt")
+        .arg(path)
+        .status()
+        .unwrap_or_else(|_| panic!("failed to format {}", path.display()));
 }
 
 // Splits the digest in half returning the two halves as big endian
-fn split_digest(d: Digest) -> (String, String) {
+pub(crate) fn split_digest(d: Digest) -> (String, String) {
     let big_endian: Vec<u8> = d.as_bytes().to_vec().iter().rev().cloned().collect();
     let middle = big_endian.len() / 2;
     let (control_id_1, control_id_0) = big_endian.split_at(middle);
@@ -187,16 +601,24 @@ fn split_digest(d: Digest) -> (String, String) {
 
 // Return a Compact `Receipt` and the imageID used to generate the proof.
 // Requires running Docker on an x86 architecture.
-fn generate_receipt() -> (Receipt, Digest) {
+pub(crate) fn generate_receipt() -> (Receipt, Digest) {
     let env = ExecutorEnv::builder()
         .write(&MultiTestSpec::BusyLoop { cycles: 0 })
         .unwrap()
         .build()
         .unwrap();
 
+    let compact_receipt = prove_compact(env, MULTI_TEST_ELF);
+    let image_id = Digest::from(MULTI_TEST_ID);
+    (compact_receipt, image_id)
+}
+
+// Execute `elf` in `env`, and prove it down to a Compact `Receipt`.
+// Requires running Docker on an x86 architecture.
+pub(crate) fn prove_compact(env: ExecutorEnv, elf: &[u8]) -> Receipt {
     tracing::info!("execute");
 
-    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, elf).unwrap();
     let session = exec.run().unwrap();
 
     tracing::info!("prove");
@@ -216,10 +638,8 @@ fn generate_receipt() -> (Receipt, Digest) {
     let seal = stark_to_snark(&seal_bytes).unwrap().to_vec();
 
     tracing::info!("Receipt");
-    let compact_receipt = Receipt::new(
+    Receipt::new(
         InnerReceipt::Compact(CompactReceipt { seal, claim }),
         journal,
-    );
-    let image_id = Digest::from(MULTI_TEST_ID);
-    (compact_receipt, image_id)
+    )
 }
@@ -0,0 +1,72 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use hex::FromHex;
+use risc0_groth16::verifier::verifying_key;
+use risc0_zkvm::{
+    sha::{Digest, Digestible},
+    ALLOWED_IDS_ROOT, VERSION,
+};
+use serde_json::json;
+
+use crate::bootstrap_groth16::{generate_receipt, split_digest};
+
+/// Generate a Groth16 test vector for the current circuit and control ID.
+///
+/// Vectors are archived under one file per zkVM version and control ID root,
+/// so running this for each release accumulates a conformance suite for
+/// verifier implementations in other languages.
+#[derive(Parser)]
+pub struct GenGroth16Vectors {
+    /// Directory containing the archived test vectors.
+    #[arg(long, default_value = "risc0/groth16/tests/vectors")]
+    out_dir: PathBuf,
+}
+
+impl GenGroth16Vectors {
+    pub fn run(&self) {
+        let path = self
+            .out_dir
+            .join(format!("v{VERSION}-{}.json", &ALLOWED_IDS_ROOT[..8]));
+        if path.exists() {
+            tracing::info!("test vector already archived at {}", path.display());
+            return;
+        }
+
+        let (receipt, image_id) = generate_receipt();
+        let claim = receipt.get_claim().unwrap();
+        let (control_id_0, control_id_1) =
+            split_digest(Digest::from_hex(ALLOWED_IDS_ROOT).unwrap());
+        let vector = json!({
+            "version": VERSION,
+            "allowed_ids_root": ALLOWED_IDS_ROOT,
+            "control_id_0": control_id_0,
+            "control_id_1": control_id_1,
+            "verifying_key": verifying_key().unwrap().to_json().unwrap(),
+            "image_id": hex::encode(image_id),
+            "claim": claim,
+            "claim_digest": hex::encode(claim.digest()),
+            "post_digest": hex::encode(claim.post.digest()),
+            "journal": hex::encode(&receipt.journal.bytes),
+            "seal": hex::encode(&receipt.inner.compact().unwrap().seal),
+        });
+
+        fs::create_dir_all(&self.out_dir).unwrap();
+        fs::write(&path, serde_json::to_string_pretty(&vector).unwrap()).unwrap();
+        tracing::info!("wrote test vector to {}", path.display());
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use risc0_zkvm::{compute_image_id, ExecutorEnv};
+
+use crate::bootstrap_groth16::{prove_compact, write_test_receipt};
+
+/// Generate a Solidity `TestReceipt`-style library for a guest, to be used as a
+/// fixture in Foundry tests.
+///
+/// Requires running Docker on an x86 architecture.
+#[derive(Parser)]
+pub struct GenTestReceipt {
+    /// Path to the guest ELF.
+    #[arg(long)]
+    elf: PathBuf,
+
+    /// Path to the input of the guest.
+    ///
+    /// The file is passed to the guest as is, so values read with `env::read`
+    /// must be encoded with `risc0_zkvm::serde::to_vec` beforehand.
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Name of the generated Solidity library.
+    #[arg(long, default_value = "TestReceipt")]
+    library_name: String,
+
+    /// Path of the generated Solidity file.
+    #[arg(long)]
+    out: PathBuf,
+}
+
+impl GenTestReceipt {
+    pub fn run(&self) {
+        let elf =
+            fs::read(&self.elf).unwrap_or_else(|_| panic!("failed to read {}", self.elf.display()));
+        let input = match &self.input {
+            Some(path) => {
+                fs::read(path).unwrap_or_else(|_| panic!("failed to read {}", path.display()))
+            }
+            None => vec![],
+        };
+        let image_id = compute_image_id(&elf).unwrap();
+
+        let env = ExecutorEnv::builder().write_slice(&input).build().unwrap();
+        let receipt = prove_compact(env, &elf);
+        receipt.verify(image_id).unwrap();
+
+        write_test_receipt(
+            &receipt,
+            image_id,
+            &self.library_name,
+            "cargo xtask gen-test-receipt",
+            &self.out,
+        );
+    }
+}
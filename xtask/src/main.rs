@@ -15,7 +15,9 @@
 mod bootstrap;
 mod bootstrap_groth16;
 mod bootstrap_poseidon;
+mod gen_groth16_vectors;
 mod gen_receipt;
+mod gen_test_receipt;
 mod install;
 
 use clap::{Parser, Subcommand};
@@ -23,7 +25,8 @@ use tracing_subscriber::{prelude::*, EnvFilter};
 
 use self::{
     bootstrap::Bootstrap, bootstrap_groth16::BootstrapGroth16,
-    bootstrap_poseidon::BootstrapPoseidon, gen_receipt::GenReceipt, install::Install,
+    bootstrap_poseidon::BootstrapPoseidon, gen_groth16_vectors::GenGroth16Vectors,
+    gen_receipt::GenReceipt, gen_test_receipt::GenTestReceipt, install::Install,
 };
 
 #[derive(Parser)]
@@ -37,7 +40,9 @@ enum Commands {
     Bootstrap(Bootstrap),
     BootstrapGroth16(BootstrapGroth16),
     BootstrapPoseidon(BootstrapPoseidon),
+    GenGroth16Vectors(GenGroth16Vectors),
     GenReceipt(GenReceipt),
+    GenTestReceipt(GenTestReceipt),
     Install(Install),
 }
 
@@ -48,7 +53,9 @@ impl Commands {
             Commands::BootstrapGroth16(cmd) => cmd.run(),
             Commands::BootstrapPoseidon(cmd) => cmd.run(),
             Commands::Install(cmd) => cmd.run(),
+            Commands::GenGroth16Vectors(cmd) => cmd.run(),
             Commands::GenReceipt(cmd) => cmd.run(),
+            Commands::GenTestReceipt(cmd) => cmd.run(),
         }
     }
 }
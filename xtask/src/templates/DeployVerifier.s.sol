@@ -0,0 +1,101 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+pragma solidity ^0.8.17;
+
+import {{Script}} from "forge-std/Script.sol";
+import {{console2}} from "forge-std/console2.sol";
+
+import {{RiscZeroGroth16Verifier}} from "../contracts/groth16/RiscZeroGroth16Verifier.sol";
+import {{ControlID}} from "../contracts/groth16/ControlID.sol";
+import {{RiscZeroVerifierRouter}} from "../contracts/RiscZeroVerifierRouter.sol";
+import {{TestReceipt}} from "../test/TestReceipt.sol";
+
+/// @notice Deployment script for the RiscZeroGroth16Verifier, with the control IDs pinned.
+/// @dev The control IDs and the digest of the verifying key are the ones of the Rust constants at
+/// the time this script was generated, see `deployment.json`. The deployed verifier is checked
+/// against the pinned control IDs, and by verifying the test receipt, so every chain gets the same
+/// verifier. Use the following environment variables to control the deployment:
+///     * DEPLOYER_ADDRESS address of the wallet to be used for sending deploy transactions.
+///         Must be unlocked on the RPC provider node.
+///     * DEPLOYER_PRIVATE_KEY private key of the wallet to be used for deployment.
+///         Alternative to using DEPLOYER_ADDRESS.
+///     * DEPLOY_ROUTER_ADDRESS address of a RiscZeroVerifierRouter owned by the deployer.
+///         If specified, the verifier is registered in the router under `SELECTOR`.
+contract DeployVerifier is Script {{
+    /// @notice Control IDs accepted by the verifier.
+    uint256 public constant CONTROL_ID_0 = {control_id_0};
+    uint256 public constant CONTROL_ID_1 = {control_id_1};
+
+    /// @notice Selector of the verifier in a RiscZeroVerifierRouter.
+    bytes4 public constant SELECTOR = {selector};
+
+    /// @notice SHA-256 digest of the arkworks-serialized verifying key.
+    bytes32 public constant VERIFYING_KEY_DIGEST = {verifying_key_digest};
+
+    /// @notice use vm.startBroadcast to begin recording deploy transactions.
+    function startBroadcast() internal {{
+        address deployerAddr = vm.envOr("DEPLOYER_ADDRESS", address(0));
+        uint256 deployerKey = vm.envOr("DEPLOYER_PRIVATE_KEY", uint256(0));
+
+        if (deployerAddr != address(0) && deployerKey != uint256(0)) {{
+            revert("only one of DEPLOYER_ADDRESS or DEPLOYER_PRIVATE_KEY should be set");
+        }}
+        if (deployerAddr != address(0)) {{
+            vm.startBroadcast(deployerAddr);
+        }} else if (deployerKey != uint256(0)) {{
+            vm.startBroadcast(deployerKey);
+        }} else if (block.chainid == 31337) {{
+            // On an Anvil local testnet, use the first private key by default.
+            deployerKey = uint256(0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80);
+            vm.startBroadcast(deployerKey);
+        }} else {{
+            revert("specify a deployer with either DEPLOYER_ADDRESS or DEPLOYER_PRIVATE_KEY");
+        }}
+    }}
+
+    function run() external {{
+        require(
+            ControlID.CONTROL_ID_0 == CONTROL_ID_0 && ControlID.CONTROL_ID_1 == CONTROL_ID_1,
+            "ControlID.sol does not match the pinned control IDs, run cargo xtask bootstrap-groth16"
+        );
+
+        startBroadcast();
+
+        RiscZeroGroth16Verifier verifier = new RiscZeroGroth16Verifier(CONTROL_ID_0, CONTROL_ID_1);
+        console2.log("Deployed RiscZeroGroth16Verifier to ", address(verifier));
+
+        address routerAddr = vm.envOr("DEPLOY_ROUTER_ADDRESS", address(0));
+        if (routerAddr != address(0)) {{
+            RiscZeroVerifierRouter(routerAddr).addVerifier(SELECTOR, verifier);
+            console2.log("Registered RiscZeroGroth16Verifier in RiscZeroVerifierRouter at ", routerAddr);
+        }}
+
+        vm.stopBroadcast();
+
+        require(
+            verifier.CONTROL_ID_0() == CONTROL_ID_0 && verifier.CONTROL_ID_1() == CONTROL_ID_1,
+            "deployed verifier does not accept the pinned control IDs"
+        );
+        require(
+            verifier.verify(TestReceipt.SEAL, TestReceipt.IMAGE_ID, TestReceipt.POST_DIGEST, sha256(TestReceipt.JOURNAL)),
+            "deployed verifier rejects the test receipt"
+        );
+    }}
+}}
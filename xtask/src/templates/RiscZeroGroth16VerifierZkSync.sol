@@ -0,0 +1,226 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+pragma solidity ^0.8.9;
+
+import {{
+    ExitCode,
+    IRiscZeroVerifier,
+    Output,
+    OutputLib,
+    Receipt,
+    ReceiptClaim,
+    ReceiptClaimLib,
+    SystemExitCode
+}} from "../IRiscZeroVerifier.sol";
+import {{reverseByteOrderUint256, Seal}} from "./RiscZeroGroth16Verifier.sol";
+
+/// @notice Groth16 verifier contract for RISC Zero receipts of execution, compatible with zkSync Era.
+/// @dev The snarkjs verifier makes assumptions about the precompiles that do not hold on EraVM:
+/// - Calls to an address without code succeed and return no data, so a missing precompile would
+///   leave the input buffer in place of the output. Every precompile call checks the size of the
+///   returned data.
+/// - Gas is metered differently, so all the remaining gas is forwarded to the precompiles instead
+///   of a fixed margin being kept.
+/// - The modexp precompile is not available, so seals are not compressed: they are the same 256
+///   bytes as for `RiscZeroGroth16Verifier`, and only ecAdd, ecMul and ecPairing are used.
+/// The control IDs are fixed when this contract is generated, as immutables are not stored in the
+/// bytecode on EraVM, and their contribution to the public input is precomputed.
+contract RiscZeroGroth16VerifierZkSync is IRiscZeroVerifier {{
+    using ReceiptClaimLib for ReceiptClaim;
+    using OutputLib for Output;
+
+    /// @notice Length of the seals accepted by this verifier.
+    uint256 public constant SEAL_LENGTH = 256;
+
+    /// @notice Control ID hash for the identity_p254 predicate decomposed by `splitDigest`.
+    uint256 public constant CONTROL_ID_0 = {control_id_0};
+    uint256 public constant CONTROL_ID_1 = {control_id_1};
+
+    // Base field size
+    uint256 internal constant P = {p};
+
+    // Verification Key data
+    uint256 internal constant ALPHA_X = {alpha_x};
+    uint256 internal constant ALPHA_Y = {alpha_y};
+    uint256 internal constant BETA_X1 = {beta_x1};
+    uint256 internal constant BETA_X2 = {beta_x2};
+    uint256 internal constant BETA_Y1 = {beta_y1};
+    uint256 internal constant BETA_Y2 = {beta_y2};
+    uint256 internal constant GAMMA_X1 = {gamma_x1};
+    uint256 internal constant GAMMA_X2 = {gamma_x2};
+    uint256 internal constant GAMMA_Y1 = {gamma_y1};
+    uint256 internal constant GAMMA_Y2 = {gamma_y2};
+    uint256 internal constant DELTA_X1 = {delta_x1};
+    uint256 internal constant DELTA_X2 = {delta_x2};
+    uint256 internal constant DELTA_Y1 = {delta_y1};
+    uint256 internal constant DELTA_Y2 = {delta_y2};
+    uint256 internal constant IC3_X = {ic3_x};
+    uint256 internal constant IC3_Y = {ic3_y};
+    uint256 internal constant IC4_X = {ic4_x};
+    uint256 internal constant IC4_Y = {ic4_y};
+
+    // IC0 + CONTROL_ID_0 * IC1 + CONTROL_ID_1 * IC2
+    uint256 internal constant CONTROL_IC_X = {control_ic_x};
+    uint256 internal constant CONTROL_IC_Y = {control_ic_y};
+
+    /// @notice splits a digest into two 128-bit words to use as public signal inputs.
+    /// @dev See `RiscZeroGroth16Verifier.splitDigest`.
+    function splitDigest(bytes32 digest) internal pure returns (uint256, uint256) {{
+        uint256 reversed = reverseByteOrderUint256(uint256(digest));
+        return (uint256(uint128(uint256(reversed))), uint256(reversed >> 128));
+    }}
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify(bytes calldata seal, bytes32 imageId, bytes32 postStateDigest, bytes32 journalDigest)
+        public
+        view
+        returns (bool)
+    {{
+        Receipt memory receipt = Receipt(
+            seal,
+            ReceiptClaim(
+                imageId,
+                postStateDigest,
+                ExitCode(SystemExitCode.Halted, 0),
+                bytes32(0),
+                Output(journalDigest, bytes32(0)).digest()
+            )
+        );
+        return verify_integrity(receipt);
+    }}
+
+    /// @inheritdoc IRiscZeroVerifier
+    function verify_integrity(Receipt memory receipt) public view returns (bool) {{
+        if (receipt.seal.length != SEAL_LENGTH) {{
+            return false;
+        }}
+        Seal memory seal = abi.decode(receipt.seal, (Seal));
+        if (!checkFields(seal)) {{
+            return false;
+        }}
+        (uint256 claim0, uint256 claim1) = splitDigest(receipt.claim.digest());
+        return verifyProof(seal, claim0, claim1);
+    }}
+
+    /// @notice Checks that all the coordinates of the seal are in the base field.
+    function checkFields(Seal memory seal) internal pure returns (bool) {{
+        return seal.a[0] < P && seal.a[1] < P && seal.b[0][0] < P && seal.b[0][1] < P && seal.b[1][0] < P
+            && seal.b[1][1] < P && seal.c[0] < P && seal.c[1] < P;
+    }}
+
+    function negate(uint256 a) internal pure returns (uint256) {{
+        return (P - a) % P;
+    }}
+
+    /// @notice Calls a precompile, checking that it exists by the size of the returned data.
+    function callPrecompile(address precompile, uint256[] memory input, uint256 outputLength)
+        internal
+        view
+        returns (uint256[] memory output, bool success)
+    {{
+        output = new uint256[](outputLength);
+        uint256 inputSize = input.length * 0x20;
+        uint256 outputSize = outputLength * 0x20;
+        uint256 returned;
+        assembly {{
+            success := staticcall(gas(), precompile, add(input, 0x20), inputSize, add(output, 0x20), outputSize)
+            returned := returndatasize()
+        }}
+        success = success && returned == outputSize;
+    }}
+
+    /// @notice Adds `s * (x, y)` to `acc`.
+    function mulAccumulate(uint256[2] memory acc, uint256 x, uint256 y, uint256 s) internal view returns (bool) {{
+        uint256[] memory input = new uint256[](3);
+        input[0] = x;
+        input[1] = y;
+        input[2] = s;
+        (uint256[] memory product, bool success) = callPrecompile(address(0x07), input, 2);
+        if (!success) {{
+            return false;
+        }}
+
+        input = new uint256[](4);
+        input[0] = product[0];
+        input[1] = product[1];
+        input[2] = acc[0];
+        input[3] = acc[1];
+        uint256[] memory sum;
+        (sum, success) = callPrecompile(address(0x06), input, 2);
+        if (!success) {{
+            return false;
+        }}
+        acc[0] = sum[0];
+        acc[1] = sum[1];
+        return true;
+    }}
+
+    function verifyProof(Seal memory seal, uint256 claim0, uint256 claim1) internal view returns (bool) {{
+        uint256[2] memory vk = [CONTROL_IC_X, CONTROL_IC_Y];
+        if (!mulAccumulate(vk, IC3_X, IC3_Y, claim0) || !mulAccumulate(vk, IC4_X, IC4_Y, claim1)) {{
+            return false;
+        }}
+
+        uint256[] memory input = new uint256[](24);
+
+        // -A
+        input[0] = seal.a[0];
+        input[1] = negate(seal.a[1]);
+
+        // B
+        input[2] = seal.b[0][0];
+        input[3] = seal.b[0][1];
+        input[4] = seal.b[1][0];
+        input[5] = seal.b[1][1];
+
+        // alpha
+        input[6] = ALPHA_X;
+        input[7] = ALPHA_Y;
+
+        // beta
+        input[8] = BETA_X1;
+        input[9] = BETA_X2;
+        input[10] = BETA_Y1;
+        input[11] = BETA_Y2;
+
+        // vk_x
+        input[12] = vk[0];
+        input[13] = vk[1];
+
+        // gamma
+        input[14] = GAMMA_X1;
+        input[15] = GAMMA_X2;
+        input[16] = GAMMA_Y1;
+        input[17] = GAMMA_Y2;
+
+        // C
+        input[18] = seal.c[0];
+        input[19] = seal.c[1];
+
+        // delta
+        input[20] = DELTA_X1;
+        input[21] = DELTA_X2;
+        input[22] = DELTA_Y1;
+        input[23] = DELTA_Y2;
+
+        (uint256[] memory out, bool success) = callPrecompile(address(0x08), input, 1);
+        return success && out[0] == 1;
+    }}
+}}
@@ -0,0 +1,27 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is automatically generated by:
+// {command}
+
+pragma solidity ^0.8.13;
+
+library {library_name} {{
+    bytes public constant SEAL = hex"{seal}";
+    bytes32 public constant POST_DIGEST = bytes32({post_digest});
+    bytes public constant JOURNAL = hex"{journal}";
+    bytes32 public constant IMAGE_ID = bytes32({image_id});
+}}
@@ -0,0 +1,42 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+// Constants from: bonsai/ethereum/contracts/groth16/Groth16Verifier.sol
+pub(crate) const ALPHA_X: &str = "{alpha_x}";
+pub(crate) const ALPHA_Y: &str = "{alpha_y}";
+pub(crate) const BETA_X1: &str = "{beta_x1}";
+pub(crate) const BETA_X2: &str = "{beta_x2}";
+pub(crate) const BETA_Y1: &str = "{beta_y1}";
+pub(crate) const BETA_Y2: &str = "{beta_y2}";
+pub(crate) const GAMMA_X1: &str = "{gamma_x1}";
+pub(crate) const GAMMA_X2: &str = "{gamma_x2}";
+pub(crate) const GAMMA_Y1: &str = "{gamma_y1}";
+pub(crate) const GAMMA_Y2: &str = "{gamma_y2}";
+pub(crate) const DELTA_X1: &str = "{delta_x1}";
+pub(crate) const DELTA_X2: &str = "{delta_x2}";
+pub(crate) const DELTA_Y1: &str = "{delta_y1}";
+pub(crate) const DELTA_Y2: &str = "{delta_y2}";
+pub(crate) const IC0_X: &str = "{ic0_x}";
+pub(crate) const IC0_Y: &str = "{ic0_y}";
+pub(crate) const IC1_X: &str = "{ic1_x}";
+pub(crate) const IC1_Y: &str = "{ic1_y}";
+pub(crate) const IC2_X: &str = "{ic2_x}";
+pub(crate) const IC2_Y: &str = "{ic2_y}";
+pub(crate) const IC3_X: &str = "{ic3_x}";
+pub(crate) const IC3_Y: &str = "{ic3_y}";
+pub(crate) const IC4_X: &str = "{ic4_x}";
+pub(crate) const IC4_Y: &str = "{ic4_y}";
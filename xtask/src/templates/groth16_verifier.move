@@ -0,0 +1,119 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+/// Verifier of RISC Zero Groth16 receipts for Sui.
+///
+/// Accepts the receipts of the zkVM release matching `CONTROL_ID_0` and
+/// `CONTROL_ID_1`. Proof points are the arkworks compressed encoding of the
+/// seal, as produced by `risc0_sui_verifier::proof_points`.
+module risc0::groth16_verifier {{
+    use std::hash::sha2_256;
+    use std::vector;
+    use sui::groth16;
+
+    /// A digest is not 32 bytes long.
+    const EInvalidDigest: u64 = 0;
+
+    /// Control ID hash for the identity_p254 predicate decomposed by
+    /// `split_digest`, as little endian scalars.
+    const CONTROL_ID_0: vector<u8> = x"{control_id_0}";
+    const CONTROL_ID_1: vector<u8> = x"{control_id_1}";
+
+    /// Verifying key of the RISC Zero Groth16 circuit, in the arkworks
+    /// compressed encoding.
+    const VERIFYING_KEY: vector<u8> = x"{verifying_key}";
+
+    const ZERO_DIGEST: vector<u8> = x"0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// Verify that `proof_points` is a valid proof of an execution of
+    /// `image_id` with the given post state and journal digests.
+    ///
+    /// Like the `verify` method of the Solidity verifier, this checks that the
+    /// execution halted with exit code 0, with no input and no assumptions.
+    public fun verify(
+        proof_points: vector<u8>,
+        image_id: vector<u8>,
+        post_state_digest: vector<u8>,
+        journal_digest: vector<u8>,
+    ): bool {{
+        verify_integrity(proof_points, claim_digest(image_id, post_state_digest, journal_digest))
+    }}
+
+    /// Verify that `proof_points` is a valid proof of the claim with the given
+    /// digest.
+    public fun verify_integrity(proof_points: vector<u8>, claim_digest: vector<u8>): bool {{
+        assert!(vector::length(&claim_digest) == 32, EInvalidDigest);
+        let curve = groth16::bn254();
+        let pvk = groth16::prepare_verifying_key(&curve, &VERIFYING_KEY);
+
+        let public_inputs = CONTROL_ID_0;
+        vector::append(&mut public_inputs, CONTROL_ID_1);
+        vector::append(&mut public_inputs, split_digest(&claim_digest, 0));
+        vector::append(&mut public_inputs, split_digest(&claim_digest, 16));
+
+        groth16::verify_groth16_proof(
+            &curve,
+            &pvk,
+            &groth16::public_proof_inputs_from_bytes(public_inputs),
+            &groth16::proof_points_from_bytes(proof_points),
+        )
+    }}
+
+    /// Compute the digest of the claim of a halted execution with exit code 0,
+    /// no input and no assumptions, matching `ReceiptClaimLib.digest` in
+    /// Solidity.
+    public fun claim_digest(
+        image_id: vector<u8>,
+        post_state_digest: vector<u8>,
+        journal_digest: vector<u8>,
+    ): vector<u8> {{
+        assert!(vector::length(&image_id) == 32, EInvalidDigest);
+        assert!(vector::length(&post_state_digest) == 32, EInvalidDigest);
+        assert!(vector::length(&journal_digest) == 32, EInvalidDigest);
+
+        let output = sha2_256(b"risc0.Output");
+        vector::append(&mut output, journal_digest);
+        vector::append(&mut output, ZERO_DIGEST);
+        vector::append(&mut output, x"0200");
+
+        let claim = sha2_256(b"risc0.ReceiptClaim");
+        vector::append(&mut claim, ZERO_DIGEST);
+        vector::append(&mut claim, image_id);
+        vector::append(&mut claim, post_state_digest);
+        vector::append(&mut claim, sha2_256(output));
+        vector::append(&mut claim, x"00000000");
+        vector::append(&mut claim, x"00000000");
+        vector::append(&mut claim, x"0400");
+        sha2_256(claim)
+    }}
+
+    /// Returns the half of `digest` starting at `offset` as a little endian
+    /// scalar, matching `splitDigest` in Solidity.
+    fun split_digest(digest: &vector<u8>, offset: u64): vector<u8> {{
+        let scalar = vector::empty<u8>();
+        let i = 0;
+        while (i < 16) {{
+            vector::push_back(&mut scalar, *vector::borrow(digest, offset + i));
+            i = i + 1;
+        }};
+        while (i < 32) {{
+            vector::push_back(&mut scalar, 0);
+            i = i + 1;
+        }};
+        scalar
+    }}
+}}
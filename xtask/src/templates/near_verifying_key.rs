@@ -0,0 +1,76 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This file is automatically generated by:
+// cargo xtask bootstrap-groth16
+
+// Constants from: bonsai/ethereum/contracts/groth16/Groth16Verifier.sol
+// and bonsai/ethereum/contracts/groth16/ControlID.sol
+
+use hex_literal::hex;
+
+use crate::verifier::VerifyingKey;
+
+/// Control ID hash for the identity_p254 predicate decomposed by `split_digest`.
+pub const CONTROL_ID_0: [u8; 32] = hex!("{control_id_0}");
+pub const CONTROL_ID_1: [u8; 32] = hex!("{control_id_1}");
+
+/// Verifying key of the RISC Zero Groth16 circuit, in the little endian
+/// encoding expected by the NEAR alt_bn128 host functions.
+pub const VERIFYING_KEY: VerifyingKey = VerifyingKey {{
+    alpha_g1: hex!(
+        "{alpha_x}"
+        "{alpha_y}"
+    ),
+    beta_g2: hex!(
+        "{beta_x2}"
+        "{beta_x1}"
+        "{beta_y2}"
+        "{beta_y1}"
+    ),
+    gamma_g2: hex!(
+        "{gamma_x2}"
+        "{gamma_x1}"
+        "{gamma_y2}"
+        "{gamma_y1}"
+    ),
+    delta_g2: hex!(
+        "{delta_x2}"
+        "{delta_x1}"
+        "{delta_y2}"
+        "{delta_y1}"
+    ),
+    ic: [
+        hex!(
+            "{ic0_x}"
+            "{ic0_y}"
+        ),
+        hex!(
+            "{ic1_x}"
+            "{ic1_y}"
+        ),
+        hex!(
+            "{ic2_x}"
+            "{ic2_y}"
+        ),
+        hex!(
+            "{ic3_x}"
+            "{ic3_y}"
+        ),
+        hex!(
+            "{ic4_x}"
+            "{ic4_y}"
+        ),
+    ],
+}};